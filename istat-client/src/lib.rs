@@ -0,0 +1,498 @@
+//! # istat-client
+//!
+//! Typed Rust client for the vg.nat.istat XRPC endpoints, built on the
+//! generated lexicon types. Wraps every query and procedure with typed
+//! requests/outputs, provides cursor iteration helpers for the list
+//! endpoints, and speaks the proxy's DPoP token flow: given the JWT and
+//! DPoP key from an OAuth session, every request is sent with a fresh
+//! DPoP proof bound to that key.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use istat_client::IstatClient;
+//!
+//! # async fn example() -> Result<(), istat_client::Error> {
+//! let client = IstatClient::new("https://istat.example.com");
+//! let statuses = client.list_statuses(None, Some(25)).await?;
+//! for status in statuses.statuses {
+//!     println!("{} {}", status.handle, status.emoji_url);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use jacquard_common::CowStr;
+use jacquard_common::IntoStatic;
+use jacquard_common::types::ident::AtIdentifier;
+use jacquard_common::types::string::{AtUri, Handle};
+use jacquard_oatproxy::token::TokenManager;
+use serde::Serialize;
+use std::str::FromStr;
+use std::sync::Mutex;
+
+use lexicons::vg_nat::istat::{
+    actor::get_profile::{GetProfile, GetProfileOutput},
+    moderation::{
+        blacklist_cid::{BlacklistCid, BlacklistCidOutput},
+        is_admin::IsAdminOutput,
+        list_audit_log::{AuditLogEntry, ListAuditLog, ListAuditLogOutput},
+        list_blacklisted::{ListBlacklisted, ListBlacklistedOutput},
+        remove_blacklist::{RemoveBlacklist, RemoveBlacklistOutput},
+    },
+    moji::{
+        delete_emoji::{DeleteEmoji, DeleteEmojiOutput},
+        search_emoji::{EmojiView, SearchEmoji, SearchEmojiOutput},
+        set_emoji_category::{SetEmojiCategory, SetEmojiCategoryOutput},
+    },
+    status::{
+        delete_status::{DeleteStatus, DeleteStatusOutput},
+        get_status::{GetStatus, GetStatusOutput},
+        list_statuses::{ListStatuses, ListStatusesOutput, StatusView},
+        list_user_statuses::{ListUserStatuses, ListUserStatusesOutput, UserStatusView},
+    },
+};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("http error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("xrpc error ({status}): {body}")]
+    Xrpc { status: u16, body: String },
+
+    #[error("invalid parameter: {0}")]
+    InvalidParameter(String),
+
+    #[error("encoding error: {0}")]
+    Encoding(String),
+
+    #[error("dpop error: {0}")]
+    Dpop(String),
+}
+
+/// Client for a proxy (or server) exposing the vg.nat.istat endpoints.
+pub struct IstatClient {
+    base_url: String,
+    http: reqwest::Client,
+    access_token: Option<String>,
+    dpop_key: Option<jose_jwk::Jwk>,
+    dpop_nonce: Mutex<Option<String>>,
+    token_manager: TokenManager,
+}
+
+impl IstatClient {
+    /// Create a client for the given proxy base URL. Public endpoints work
+    /// without further setup; authenticated ones need `with_access_token`
+    /// (and `with_dpop_key` when the token is DPoP-bound).
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        Self {
+            http: reqwest::Client::new(),
+            access_token: None,
+            dpop_key: None,
+            dpop_nonce: Mutex::new(None),
+            token_manager: TokenManager::new(base_url.clone()),
+            base_url,
+        }
+    }
+
+    /// Attach the access token issued by the proxy's token endpoint
+    pub fn with_access_token(mut self, token: impl Into<String>) -> Self {
+        self.access_token = Some(token.into());
+        self
+    }
+
+    /// Attach the DPoP key the token is bound to; requests then carry a
+    /// fresh DPoP proof and use the `DPoP` authorization scheme
+    pub fn with_dpop_key(mut self, key: jose_jwk::Jwk) -> Self {
+        self.dpop_key = Some(key);
+        self
+    }
+
+    // Queries
+
+    pub async fn get_profile(&self, actor: &str) -> Result<GetProfileOutput<'static>> {
+        let actor = AtIdentifier::from_str(actor)
+            .map_err(|e| Error::InvalidParameter(format!("actor: {}", e)))?;
+        let params = GetProfile { actor };
+        let body = self
+            .query("vg.nat.istat.actor.getProfile", Some(&params))
+            .await?;
+        parse_output::<GetProfileOutput>(&body)
+    }
+
+    pub async fn get_status(&self, handle: &str, rkey: &str) -> Result<GetStatusOutput<'static>> {
+        let handle = Handle::from_str(handle)
+            .map_err(|e| Error::InvalidParameter(format!("handle: {}", e)))?;
+        let params = GetStatus {
+            handle,
+            rkey: rkey.to_string().into(),
+        };
+        let body = self
+            .query("vg.nat.istat.status.getStatus", Some(&params))
+            .await?;
+        parse_output::<GetStatusOutput>(&body)
+    }
+
+    pub async fn list_statuses(
+        &self,
+        cursor: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<ListStatusesOutput<'static>> {
+        let params = ListStatuses {
+            cursor: cursor.map(|c| CowStr::from(c.to_string())),
+            limit,
+        };
+        let body = self
+            .query("vg.nat.istat.status.listStatuses", Some(&params))
+            .await?;
+        parse_output::<ListStatusesOutput>(&body)
+    }
+
+    pub async fn list_user_statuses(
+        &self,
+        handle: &str,
+        cursor: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<ListUserStatusesOutput<'static>> {
+        let handle = Handle::from_str(handle)
+            .map_err(|e| Error::InvalidParameter(format!("handle: {}", e)))?;
+        let params = ListUserStatuses {
+            cursor: cursor.map(|c| CowStr::from(c.to_string())),
+            handle,
+            limit,
+        };
+        let body = self
+            .query("vg.nat.istat.status.listUserStatuses", Some(&params))
+            .await?;
+        parse_output::<ListUserStatusesOutput>(&body)
+    }
+
+    pub async fn search_emoji(
+        &self,
+        query: &str,
+        category: Option<&str>,
+        tag: Option<&str>,
+        cursor: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<SearchEmojiOutput<'static>> {
+        let params = SearchEmoji {
+            category: category.map(|c| c.to_string().into()),
+            cursor: cursor.map(|c| CowStr::from(c.to_string())),
+            limit,
+            query: query.to_string().into(),
+            tag: tag.map(|t| t.to_string().into()),
+        };
+        let body = self
+            .query("vg.nat.istat.moji.searchEmoji", Some(&params))
+            .await?;
+        parse_output::<SearchEmojiOutput>(&body)
+    }
+
+    pub async fn is_admin(&self) -> Result<IsAdminOutput<'static>> {
+        let body = self
+            .query::<()>("vg.nat.istat.moderation.isAdmin", None)
+            .await?;
+        parse_output::<IsAdminOutput>(&body)
+    }
+
+    pub async fn list_blacklisted(
+        &self,
+        content_type: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<ListBlacklistedOutput<'static>> {
+        let params = ListBlacklisted {
+            content_type: content_type.map(|c| CowStr::from(c.to_string())),
+            limit,
+        };
+        let body = self
+            .query("vg.nat.istat.moderation.listBlacklisted", Some(&params))
+            .await?;
+        parse_output::<ListBlacklistedOutput>(&body)
+    }
+
+    pub async fn list_audit_log(
+        &self,
+        cursor: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<ListAuditLogOutput<'static>> {
+        let params = ListAuditLog {
+            cursor: cursor.map(|c| CowStr::from(c.to_string())),
+            limit,
+        };
+        let body = self
+            .query("vg.nat.istat.moderation.listAuditLog", Some(&params))
+            .await?;
+        parse_output::<ListAuditLogOutput>(&body)
+    }
+
+    // Procedures
+
+    pub async fn delete_status(&self, uri: &str) -> Result<DeleteStatusOutput<'static>> {
+        let uri =
+            AtUri::from_str(uri).map_err(|e| Error::InvalidParameter(format!("uri: {}", e)))?;
+        let input = DeleteStatus {
+            uri,
+            extra_data: None,
+        };
+        let body = self
+            .procedure("vg.nat.istat.status.deleteStatus", &input)
+            .await?;
+        parse_output::<DeleteStatusOutput>(&body)
+    }
+
+    pub async fn delete_emoji(&self, uri: &str) -> Result<DeleteEmojiOutput<'static>> {
+        let uri =
+            AtUri::from_str(uri).map_err(|e| Error::InvalidParameter(format!("uri: {}", e)))?;
+        let input = DeleteEmoji {
+            uri,
+            extra_data: None,
+        };
+        let body = self
+            .procedure("vg.nat.istat.moji.deleteEmoji", &input)
+            .await?;
+        parse_output::<DeleteEmojiOutput>(&body)
+    }
+
+    pub async fn set_emoji_category(
+        &self,
+        uri: &str,
+        category: Option<&str>,
+    ) -> Result<SetEmojiCategoryOutput<'static>> {
+        let uri =
+            AtUri::from_str(uri).map_err(|e| Error::InvalidParameter(format!("uri: {}", e)))?;
+        let input = SetEmojiCategory {
+            category: category.map(|c| c.to_string().into()),
+            uri,
+            extra_data: None,
+        };
+        let body = self
+            .procedure("vg.nat.istat.moji.setEmojiCategory", &input)
+            .await?;
+        parse_output::<SetEmojiCategoryOutput>(&body)
+    }
+
+    pub async fn blacklist_cid(
+        &self,
+        cid: &str,
+        content_type: &str,
+        reason: &str,
+        reason_details: Option<&str>,
+    ) -> Result<BlacklistCidOutput<'static>> {
+        let input = BlacklistCid {
+            cid: cid.to_string().into(),
+            content_type: content_type.to_string().into(),
+            reason: reason.to_string().into(),
+            reason_details: reason_details.map(|d| CowStr::from(d.to_string())),
+            extra_data: None,
+        };
+        let body = self
+            .procedure("vg.nat.istat.moderation.blacklistCid", &input)
+            .await?;
+        parse_output::<BlacklistCidOutput>(&body)
+    }
+
+    pub async fn remove_blacklist(&self, cid: &str) -> Result<RemoveBlacklistOutput<'static>> {
+        let input = RemoveBlacklist {
+            cid: cid.to_string().into(),
+            extra_data: None,
+        };
+        let body = self
+            .procedure("vg.nat.istat.moderation.removeBlacklist", &input)
+            .await?;
+        parse_output::<RemoveBlacklistOutput>(&body)
+    }
+
+    // Cursor iteration helpers
+
+    /// Fetch every page of the global status feed
+    pub async fn list_all_statuses(
+        &self,
+        page_size: Option<i64>,
+    ) -> Result<Vec<StatusView<'static>>> {
+        let mut all = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = self.list_statuses(cursor.as_deref(), page_size).await?;
+            all.extend(page.statuses);
+            match page.cursor {
+                Some(next) if Some(next.as_ref()) != cursor.as_deref() => {
+                    cursor = Some(next.to_string());
+                }
+                _ => break,
+            }
+        }
+        Ok(all)
+    }
+
+    /// Fetch every page of a user's statuses
+    pub async fn list_all_user_statuses(
+        &self,
+        handle: &str,
+        page_size: Option<i64>,
+    ) -> Result<Vec<UserStatusView<'static>>> {
+        let mut all = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = self
+                .list_user_statuses(handle, cursor.as_deref(), page_size)
+                .await?;
+            all.extend(page.statuses);
+            match page.cursor {
+                Some(next) if Some(next.as_ref()) != cursor.as_deref() => {
+                    cursor = Some(next.to_string());
+                }
+                _ => break,
+            }
+        }
+        Ok(all)
+    }
+
+    /// Fetch every page of the moderation audit log
+    pub async fn list_all_audit_log(
+        &self,
+        page_size: Option<i64>,
+    ) -> Result<Vec<AuditLogEntry<'static>>> {
+        let mut all = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = self.list_audit_log(cursor.as_deref(), page_size).await?;
+            all.extend(page.entries);
+            match page.cursor {
+                Some(next) if Some(next.as_ref()) != cursor.as_deref() => {
+                    cursor = Some(next.to_string());
+                }
+                _ => break,
+            }
+        }
+        Ok(all)
+    }
+
+    /// Fetch every page of emoji search results
+    pub async fn search_all_emoji(
+        &self,
+        query: &str,
+        category: Option<&str>,
+        tag: Option<&str>,
+        page_size: Option<i64>,
+    ) -> Result<Vec<EmojiView<'static>>> {
+        let mut all = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let page = self
+                .search_emoji(query, category, tag, cursor.as_deref(), page_size)
+                .await?;
+            all.extend(page.emojis);
+            match page.cursor {
+                Some(next) if Some(next.as_ref()) != cursor.as_deref() => {
+                    cursor = Some(next.to_string());
+                }
+                _ => break,
+            }
+        }
+        Ok(all)
+    }
+
+    // Transport
+
+    async fn query<P: Serialize>(&self, nsid: &str, params: Option<&P>) -> Result<Vec<u8>> {
+        let mut url = format!("{}/xrpc/{}", self.base_url, nsid);
+        if let Some(params) = params {
+            let qs = serde_urlencoded::to_string(params)
+                .map_err(|e| Error::Encoding(e.to_string()))?;
+            if !qs.is_empty() {
+                url = format!("{}?{}", url, qs);
+            }
+        }
+        self.send("GET", &url, None).await
+    }
+
+    async fn procedure<B: Serialize>(&self, nsid: &str, input: &B) -> Result<Vec<u8>> {
+        let url = format!("{}/xrpc/{}", self.base_url, nsid);
+        let body = serde_json::to_vec(input).map_err(|e| Error::Encoding(e.to_string()))?;
+        self.send("POST", &url, Some(body)).await
+    }
+
+    /// Send a request with authorization attached, retrying once if the
+    /// server demands a DPoP nonce (mirrors how the proxy talks to PDSes)
+    async fn send(&self, method: &str, url: &str, body: Option<Vec<u8>>) -> Result<Vec<u8>> {
+        let mut retried = false;
+
+        loop {
+            let mut request = match method {
+                "POST" => self.http.post(url),
+                _ => self.http.get(url),
+            };
+
+            if let Some(ref body) = body {
+                request = request
+                    .header("content-type", "application/json")
+                    .body(body.clone());
+            }
+
+            if let Some(ref token) = self.access_token {
+                match self.dpop_key {
+                    Some(ref key) => {
+                        let nonce = self.dpop_nonce.lock().unwrap().clone();
+                        let proof = self
+                            .token_manager
+                            .create_upstream_dpop_proof(
+                                method,
+                                url,
+                                Some(token),
+                                nonce.as_deref(),
+                                key,
+                            )
+                            .await
+                            .map_err(|e| Error::Dpop(e.to_string()))?;
+                        request = request
+                            .header("Authorization", format!("DPoP {}", token))
+                            .header("DPoP", proof);
+                    }
+                    None => {
+                        request = request.header("Authorization", format!("Bearer {}", token));
+                    }
+                }
+            }
+
+            let response = request.send().await?;
+            let status = response.status();
+
+            // Remember any nonce the server hands back for the next proof
+            if let Some(nonce) = response
+                .headers()
+                .get("DPoP-Nonce")
+                .and_then(|v| v.to_str().ok())
+            {
+                *self.dpop_nonce.lock().unwrap() = Some(nonce.to_string());
+
+                if (status == 400 || status == 401) && !retried && self.dpop_key.is_some() {
+                    retried = true;
+                    continue;
+                }
+            }
+
+            let bytes = response.bytes().await?;
+            if !status.is_success() {
+                return Err(Error::Xrpc {
+                    status: status.as_u16(),
+                    body: String::from_utf8_lossy(&bytes).to_string(),
+                });
+            }
+
+            return Ok(bytes.to_vec());
+        }
+    }
+}
+
+/// Parse an XRPC output and detach it from the response buffer
+fn parse_output<'de, T>(body: &'de [u8]) -> Result<<T as IntoStatic>::Output>
+where
+    T: serde::Deserialize<'de> + IntoStatic,
+{
+    let parsed: T = serde_json::from_slice(body).map_err(|e| Error::Encoding(e.to_string()))?;
+    Ok(parsed.into_static())
+}
@@ -0,0 +1,123 @@
+//! Microbenchmarks for the per-request hot path: downstream JWT
+//! validation, DPoP proof parsing and verification, and DPoP proof
+//! signing. Run with `cargo bench --features test-utils`.
+//!
+//! These are the three costs the XRPC proxy pays on every forwarded
+//! request, so regressions here are regressions in proxy overhead. The
+//! JWT numbers motivated the `KeyMaterial` cache: signature verification
+//! itself is tens of microseconds, which a key-store round-trip per
+//! request (a database read for SQL-backed stores) would dwarf.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use criterion::{Criterion, criterion_group, criterion_main};
+use jacquard_oatproxy::error::Result;
+use jacquard_oatproxy::jose::{self, JwsAlgorithm};
+use jacquard_oatproxy::store::KeyStore;
+use jacquard_oatproxy::test_utils::ScriptedDpopClient;
+use jacquard_oatproxy::token::{JwtTokenIssuer, TokenIssuer, TokenManager};
+
+const ISSUER: &str = "https://proxy.bench.test";
+
+/// Key store holding one fixed signing key, so benches measure crypto
+/// and parsing rather than storage
+struct StaticKeyStore {
+    signing_key: p256::ecdsa::SigningKey,
+}
+
+#[async_trait]
+impl KeyStore for StaticKeyStore {
+    async fn get_signing_key(&self) -> Result<p256::ecdsa::SigningKey> {
+        Ok(self.signing_key.clone())
+    }
+
+    async fn get_dpop_key(&self, _thumbprint: &str) -> Result<Option<jose_jwk::Jwk>> {
+        Ok(None)
+    }
+
+    async fn store_dpop_key(&self, _thumbprint: &str, _key: jose_jwk::Jwk) -> Result<()> {
+        Ok(())
+    }
+
+    async fn delete_dpop_key(&self, _thumbprint: &str) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn bench_jwt_validation(c: &mut Criterion) {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let key_store = Arc::new(StaticKeyStore {
+        signing_key: p256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng),
+    });
+    let token_manager = Arc::new(TokenManager::new(ISSUER.to_string()));
+    let issuer = JwtTokenIssuer::new(token_manager.clone(), key_store.clone());
+
+    let jwt = rt
+        .block_on(issuer.issue(
+            "did:plc:benchuser",
+            "bench-jkt",
+            "atproto",
+            Some("bench-session"),
+            None,
+            3600,
+        ))
+        .unwrap();
+
+    // Key store hit + verify on every call: what the proxy paid before
+    // the verifying-key cache existed
+    c.bench_function("jwt_validate_uncached_key", |b| {
+        b.to_async(&rt).iter(|| async {
+            token_manager
+                .validate_downstream_jwt(&jwt, &*key_store)
+                .await
+                .unwrap()
+        })
+    });
+
+    // Cached verifying key: what the proxy pays per request now
+    c.bench_function("jwt_validate_cached_key", |b| {
+        b.to_async(&rt)
+            .iter(|| async { issuer.validate(&jwt).await.unwrap() })
+    });
+}
+
+fn bench_dpop_verification(c: &mut Criterion) {
+    let client = ScriptedDpopClient::new();
+    let url = "https://proxy.bench.test/xrpc/app.bsky.feed.getTimeline";
+    let proof = client.proof("GET", url, None);
+
+    // Parse the compact JWS, rebuild the verifying key from the header
+    // JWK, and check the signature — the work the proxy does per proof
+    // before any nonce or replay bookkeeping
+    c.bench_function("dpop_parse_and_verify", |b| {
+        b.iter(|| {
+            let jws = jose::parse_compact(&proof, &[JwsAlgorithm::Es256]).unwrap();
+            let jwk = jws.header().jwk.clone().map(serde_json::Value::Object).unwrap();
+            let x = URL_SAFE_NO_PAD
+                .decode(jwk.get("x").and_then(|v| v.as_str()).unwrap())
+                .unwrap();
+            let y = URL_SAFE_NO_PAD
+                .decode(jwk.get("y").and_then(|v| v.as_str()).unwrap())
+                .unwrap();
+            let point = p256::EncodedPoint::from_affine_coordinates(
+                x.as_slice().into(),
+                y.as_slice().into(),
+                false,
+            );
+            let key = p256::ecdsa::VerifyingKey::from_encoded_point(&point).unwrap();
+            assert!(jws.signature_matches(&key));
+        })
+    });
+
+    // Signing side: the proxy mints one upstream proof per forwarded
+    // request, so this is part of per-request overhead too
+    c.bench_function("dpop_proof_sign", |b| {
+        b.iter(|| client.proof("GET", url, None))
+    });
+}
+
+criterion_group!(hot_path, bench_jwt_validation, bench_dpop_verification);
+criterion_main!(hot_path);
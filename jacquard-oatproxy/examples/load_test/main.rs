@@ -0,0 +1,210 @@
+//! Load test against the mock PDS: walks the OAuth flow once, then
+//! hammers an XRPC endpoint with DPoP-bound requests and reports
+//! throughput and latency percentiles.
+//!
+//! Every request pays the same per-request costs the proxy's hot path
+//! does — DPoP proof signing on the client side, proof parsing and
+//! ES256 verification on the server side — so a regression in those
+//! shows up here as lost throughput. Pair with the criterion benches
+//! (`cargo bench --features test-utils`) to attribute a regression to a
+//! specific stage.
+//!
+//! Run with:
+//!
+//! ```text
+//! cargo run --example load_test --features test-utils [concurrency] [requests-per-worker]
+//! ```
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use jacquard_oatproxy::test_utils::{MockPds, ScriptedDpopClient};
+use reqwest::StatusCode;
+
+/// Capture a `DPoP-Nonce` response header into the client for its next proof
+fn remember_nonce(client: &ScriptedDpopClient, response: &reqwest::Response) {
+    if let Some(nonce) = response
+        .headers()
+        .get("DPoP-Nonce")
+        .and_then(|v| v.to_str().ok())
+    {
+        client.set_nonce(nonce);
+    }
+}
+
+/// POST a form to an OAuth endpoint, retrying once after a
+/// `use_dpop_nonce` rejection; returns the final parsed JSON body
+async fn post_form(
+    http: &reqwest::Client,
+    dpop: &ScriptedDpopClient,
+    url: &str,
+    form: &str,
+) -> serde_json::Value {
+    for _ in 0..2 {
+        let response = http
+            .post(url)
+            .header("DPoP", dpop.proof("POST", url, None))
+            .header("content-type", "application/x-www-form-urlencoded")
+            .body(form.to_string())
+            .send()
+            .await
+            .expect("OAuth endpoint unreachable");
+        remember_nonce(dpop, &response);
+        let body: serde_json::Value = response.json().await.expect("non-JSON OAuth response");
+        if body["error"] != "use_dpop_nonce" {
+            return body;
+        }
+    }
+    panic!("nonce retry did not converge");
+}
+
+/// Walk PAR → authorize → token against the mock and return an access token
+async fn login(http: &reqwest::Client, pds: &MockPds, dpop: &ScriptedDpopClient) -> String {
+    let par = post_form(
+        http,
+        dpop,
+        &format!("{}/oauth/par", pds.url()),
+        "client_id=https%3A%2F%2Fclient.test%2Fmetadata.json\
+         &redirect_uri=https%3A%2F%2Fclient.test%2Fcallback\
+         &response_type=code&state=load-test&scope=atproto",
+    )
+    .await;
+    let request_uri = par["request_uri"].as_str().expect("PAR rejected");
+
+    let authorize = http
+        .get(format!("{}/oauth/authorize", pds.url()))
+        .query(&[("request_uri", request_uri)])
+        .send()
+        .await
+        .expect("authorize unreachable");
+    assert_eq!(authorize.status(), StatusCode::SEE_OTHER);
+    let location = authorize
+        .headers()
+        .get("location")
+        .and_then(|v| v.to_str().ok())
+        .expect("authorize did not redirect");
+    let code = url::Url::parse(location)
+        .ok()
+        .and_then(|u| {
+            u.query_pairs()
+                .find(|(k, _)| k == "code")
+                .map(|(_, v)| v.into_owned())
+        })
+        .expect("redirect missing code");
+
+    let tokens = post_form(
+        http,
+        dpop,
+        &format!("{}/oauth/token", pds.url()),
+        &format!("grant_type=authorization_code&code={}", urlencoding::encode(&code)),
+    )
+    .await;
+    tokens["access_token"]
+        .as_str()
+        .expect("token exchange failed")
+        .to_string()
+}
+
+#[tokio::main]
+async fn main() {
+    let mut args = std::env::args().skip(1);
+    let concurrency: usize = args
+        .next()
+        .map(|v| v.parse().expect("concurrency must be a number"))
+        .unwrap_or(8);
+    let per_worker: usize = args
+        .next()
+        .map(|v| v.parse().expect("requests-per-worker must be a number"))
+        .unwrap_or(250);
+
+    let pds = MockPds::spawn().await;
+    let http = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .expect("build HTTP client");
+    let dpop = Arc::new(ScriptedDpopClient::new());
+
+    let access_token = Arc::new(login(&http, &pds, &dpop).await);
+    let xrpc_url = Arc::new(format!(
+        "{}/xrpc/com.atproto.server.getSession",
+        pds.url()
+    ));
+
+    println!(
+        "load test: {} workers x {} requests against {}",
+        concurrency,
+        per_worker,
+        pds.url()
+    );
+
+    let started = Instant::now();
+    let mut workers = Vec::with_capacity(concurrency);
+    for _ in 0..concurrency {
+        let http = http.clone();
+        let dpop = dpop.clone();
+        let access_token = access_token.clone();
+        let xrpc_url = xrpc_url.clone();
+        workers.push(tokio::spawn(async move {
+            let mut latencies = Vec::with_capacity(per_worker);
+            let mut retries = 0usize;
+            for _ in 0..per_worker {
+                let request_started = Instant::now();
+                let mut response = http
+                    .get(xrpc_url.as_str())
+                    .header("authorization", format!("DPoP {}", access_token))
+                    .header(
+                        "DPoP",
+                        dpop.proof("GET", &xrpc_url, Some(&access_token)),
+                    )
+                    .send()
+                    .await
+                    .expect("XRPC endpoint unreachable");
+                if response.status() != StatusCode::OK {
+                    // Stale nonce: pick up the fresh one and retry once
+                    remember_nonce(&dpop, &response);
+                    retries += 1;
+                    response = http
+                        .get(xrpc_url.as_str())
+                        .header("authorization", format!("DPoP {}", access_token))
+                        .header(
+                            "DPoP",
+                            dpop.proof("GET", &xrpc_url, Some(&access_token)),
+                        )
+                        .send()
+                        .await
+                        .expect("XRPC endpoint unreachable");
+                }
+                assert_eq!(response.status(), StatusCode::OK, "request failed");
+                latencies.push(request_started.elapsed());
+            }
+            (latencies, retries)
+        }));
+    }
+
+    let mut latencies: Vec<Duration> = Vec::with_capacity(concurrency * per_worker);
+    let mut retries = 0usize;
+    for worker in workers {
+        let (worker_latencies, worker_retries) = worker.await.expect("worker panicked");
+        latencies.extend(worker_latencies);
+        retries += worker_retries;
+    }
+    let elapsed = started.elapsed();
+    latencies.sort();
+
+    let total = latencies.len();
+    let percentile = |p: f64| latencies[((total as f64 * p) as usize).min(total - 1)];
+    println!(
+        "{} requests in {:.2?} ({:.0} req/s), {} nonce retries",
+        total,
+        elapsed,
+        total as f64 / elapsed.as_secs_f64(),
+        retries
+    );
+    println!(
+        "latency p50 {:.2?}  p90 {:.2?}  p99 {:.2?}  max {:.2?}",
+        percentile(0.50),
+        percentile(0.90),
+        percentile(0.99),
+        latencies[total - 1]
+    );
+}
@@ -15,7 +15,6 @@ use memory_store::MemoryStore;
 use miette::{Context, IntoDiagnostic};
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tower_http::cors::CorsLayer;
 
 #[tokio::main]
 async fn main() -> miette::Result<()> {
@@ -33,20 +32,23 @@ async fn main() -> miette::Result<()> {
     // Create in-memory storage
     let store = Arc::new(MemoryStore::new());
 
-    // Configure the proxy
-    let config = ProxyConfig::new(url::Url::parse("http://127.0.0.1:3000").unwrap());
+    // Configure the proxy. A local demo opts into permissive CORS on the
+    // token endpoints; production deployments should list real origins
+    let config = ProxyConfig::new(url::Url::parse("http://127.0.0.1:3000").unwrap())
+        .with_cors_allowed_origins(vec!["*".to_string()]);
 
     // Build the OAuth proxy server
     let proxy = OAuthProxyServer::builder()
         .config(config)
         .session_store(store.clone())
         .key_store(store.clone())
-        .build()
+        .build_async()
+        .await
         .into_diagnostic()
         .wrap_err("failed to build OAuth proxy server")?;
 
-    // Create the axum app with all OAuth endpoints and CORS
-    let app = proxy.router().layer(CorsLayer::permissive());
+    // Create the axum app; the router applies its own CORS policy
+    let app = proxy.router();
 
     // Start the server
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
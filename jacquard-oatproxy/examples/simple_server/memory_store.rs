@@ -3,8 +3,12 @@ use chrono::{DateTime, Utc};
 use jacquard_common::IntoStatic;
 use jacquard_oatproxy::{
     error::Result,
+    migrate::{ExportEntry, ExportableStore},
     session::{OAuthSession, SessionId},
-    store::{DownstreamClientInfo, KeyStore, OAuthSessionStore, PARData, PendingAuth},
+    store::{
+        AccessTokenData, DownstreamClientInfo, GrantData, KeyStore, OAuthSessionStore, PARData,
+        PendingAuth, RefreshTokenData,
+    },
 };
 use p256::ecdsa::SigningKey;
 use rand::rngs::OsRng;
@@ -17,10 +21,14 @@ pub struct MemoryStore {
     pending_auths: Arc<RwLock<HashMap<String, PendingAuth>>>,
     downstream_clients: Arc<RwLock<HashMap<String, DownstreamClientInfo>>>,
     par_data: Arc<RwLock<HashMap<String, PARData>>>,
-    refresh_tokens: Arc<RwLock<HashMap<String, (String, String)>>>, // refresh_token -> (did, session_id)
+    refresh_tokens: Arc<RwLock<HashMap<String, RefreshTokenData>>>, // refresh_token -> mapping
+    access_tokens: Arc<RwLock<HashMap<String, AccessTokenData>>>, // opaque access_token -> data
     active_sessions: Arc<RwLock<HashMap<String, String>>>,          // did -> session_id
     session_dpop_keys: Arc<RwLock<HashMap<String, (String, jose_jwk::Jwk)>>>, // session_id -> (jkt, key)
+    dpop_keys: Arc<RwLock<HashMap<String, jose_jwk::Jwk>>>, // thumbprint -> key
     session_dpop_nonces: Arc<RwLock<HashMap<String, String>>>,                // session_id -> nonce
+    session_auth_methods: Arc<RwLock<HashMap<String, String>>>, // session_id -> auth method
+    grants: Arc<RwLock<HashMap<(String, String), GrantData>>>, // (did, client_id) -> grant
     signing_key: SigningKey,
     used_nonces: Arc<RwLock<HashMap<String, DateTime<Utc>>>>,
     // jacquard-oauth storage
@@ -39,9 +47,13 @@ impl MemoryStore {
             downstream_clients: Arc::new(RwLock::new(HashMap::new())),
             par_data: Arc::new(RwLock::new(HashMap::new())),
             refresh_tokens: Arc::new(RwLock::new(HashMap::new())),
+            access_tokens: Arc::new(RwLock::new(HashMap::new())),
             active_sessions: Arc::new(RwLock::new(HashMap::new())),
             session_dpop_keys: Arc::new(RwLock::new(HashMap::new())),
+            dpop_keys: Arc::new(RwLock::new(HashMap::new())),
             session_dpop_nonces: Arc::new(RwLock::new(HashMap::new())),
+            session_auth_methods: Arc::new(RwLock::new(HashMap::new())),
+            grants: Arc::new(RwLock::new(HashMap::new())),
             signing_key,
             used_nonces: Arc::new(RwLock::new(HashMap::new())),
             auth_requests: Arc::new(RwLock::new(HashMap::new())),
@@ -115,26 +127,36 @@ impl OAuthSessionStore for MemoryStore {
     }
 
     async fn consume_par_data(&self, request_uri: &str) -> Result<Option<PARData>> {
+        // remove() under the write lock makes this an atomic get-and-delete
         Ok(self.par_data.write().unwrap().remove(request_uri))
     }
 
+    async fn count_active_par_requests(&self, client_id: &str, now: DateTime<Utc>) -> Result<u64> {
+        Ok(self
+            .par_data
+            .read()
+            .unwrap()
+            .values()
+            .filter(|data| data.client_id == client_id && data.expires_at > now)
+            .count() as u64)
+    }
+
     async fn store_refresh_token_mapping(
         &self,
         refresh_token: &str,
-        account_did: String,
-        session_id: String,
+        data: RefreshTokenData,
     ) -> Result<()> {
         self.refresh_tokens
             .write()
             .unwrap()
-            .insert(refresh_token.to_string(), (account_did, session_id));
+            .insert(refresh_token.to_string(), data);
         Ok(())
     }
 
     async fn get_refresh_token_mapping(
         &self,
         refresh_token: &str,
-    ) -> Result<Option<(String, String)>> {
+    ) -> Result<Option<RefreshTokenData>> {
         Ok(self
             .refresh_tokens
             .read()
@@ -143,6 +165,82 @@ impl OAuthSessionStore for MemoryStore {
             .cloned())
     }
 
+    async fn delete_refresh_token_mapping(&self, refresh_token: &str) -> Result<()> {
+        self.refresh_tokens.write().unwrap().remove(refresh_token);
+        Ok(())
+    }
+
+    async fn delete_expired_refresh_tokens(&self, now: DateTime<Utc>) -> Result<u64> {
+        let mut tokens = self.refresh_tokens.write().unwrap();
+        let before = tokens.len();
+        tokens.retain(|_, data| {
+            !data.absolute_expires_at.is_some_and(|t| t < now)
+                && !data.idle_expires_at.is_some_and(|t| t < now)
+        });
+        Ok((before - tokens.len()) as u64)
+    }
+
+    async fn record_grant(&self, did: &str, client_id: &str, scope: Option<String>) -> Result<()> {
+        let mut grants = self.grants.write().unwrap();
+        let now = Utc::now();
+        grants
+            .entry((did.to_string(), client_id.to_string()))
+            .and_modify(|grant| {
+                if scope.is_some() {
+                    grant.scope = scope.clone();
+                }
+                grant.last_used_at = now;
+            })
+            .or_insert(GrantData {
+                client_id: client_id.to_string(),
+                scope,
+                created_at: now,
+                last_used_at: now,
+            });
+        Ok(())
+    }
+
+    async fn list_grants(&self, did: &str) -> Result<Vec<GrantData>> {
+        Ok(self
+            .grants
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|((grant_did, _), _)| grant_did == did)
+            .map(|(_, grant)| grant.clone())
+            .collect())
+    }
+
+    async fn delete_grant(&self, did: &str, client_id: &str) -> Result<u64> {
+        self.grants
+            .write()
+            .unwrap()
+            .remove(&(did.to_string(), client_id.to_string()));
+        let mut tokens = self.refresh_tokens.write().unwrap();
+        let before = tokens.len();
+        tokens.retain(|_, data| {
+            data.account_did != did || data.client_id.as_deref() != Some(client_id)
+        });
+        Ok((before - tokens.len()) as u64)
+    }
+
+    async fn store_access_token(&self, access_token: &str, data: AccessTokenData) -> Result<()> {
+        self.access_tokens
+            .write()
+            .unwrap()
+            .insert(access_token.to_string(), data);
+        Ok(())
+    }
+
+    async fn get_access_token(&self, access_token: &str) -> Result<Option<AccessTokenData>> {
+        Ok(self.access_tokens.read().unwrap().get(access_token).cloned())
+    }
+
+    async fn delete_access_token(&self, access_token: &str) -> Result<()> {
+        self.access_tokens.write().unwrap().remove(access_token);
+        Ok(())
+    }
+
     async fn store_active_session(&self, did: &str, session_id: String) -> Result<()> {
         self.active_sessions
             .write()
@@ -155,6 +253,11 @@ impl OAuthSessionStore for MemoryStore {
         Ok(self.active_sessions.read().unwrap().get(did).cloned())
     }
 
+    async fn clear_active_session(&self, did: &str) -> Result<()> {
+        self.active_sessions.write().unwrap().remove(did);
+        Ok(())
+    }
+
     async fn store_session_dpop_key(
         &self,
         session_id: &str,
@@ -180,6 +283,23 @@ impl OAuthSessionStore for MemoryStore {
             .cloned())
     }
 
+    async fn store_session_auth_method(&self, session_id: &str, auth_method: String) -> Result<()> {
+        self.session_auth_methods
+            .write()
+            .unwrap()
+            .insert(session_id.to_string(), auth_method);
+        Ok(())
+    }
+
+    async fn get_session_auth_method(&self, session_id: &str) -> Result<Option<String>> {
+        Ok(self
+            .session_auth_methods
+            .read()
+            .unwrap()
+            .get(session_id)
+            .cloned())
+    }
+
     async fn update_session_dpop_nonce(&self, session_id: &str, nonce: String) -> Result<()> {
         self.session_dpop_nonces
             .write()
@@ -211,6 +331,60 @@ impl OAuthSessionStore for MemoryStore {
     }
 }
 
+#[async_trait]
+impl ExportableStore for MemoryStore {
+    async fn export_entries(&self) -> Result<Vec<ExportEntry>> {
+        let mut entries = Vec::new();
+
+        for session in self.sessions.read().unwrap().values() {
+            entries.push(ExportEntry::Session {
+                session: session.clone(),
+            });
+        }
+        for data in self.oauth_sessions.read().unwrap().values() {
+            entries.push(ExportEntry::UpstreamSession { data: data.clone() });
+        }
+        for (session_id, (dpop_jkt, key)) in self.session_dpop_keys.read().unwrap().iter() {
+            entries.push(ExportEntry::DpopKey {
+                session_id: session_id.clone(),
+                dpop_jkt: dpop_jkt.clone(),
+                key: key.clone(),
+            });
+        }
+        for (session_id, nonce) in self.session_dpop_nonces.read().unwrap().iter() {
+            entries.push(ExportEntry::DpopNonce {
+                session_id: session_id.clone(),
+                nonce: nonce.clone(),
+            });
+        }
+        for (session_id, auth_method) in self.session_auth_methods.read().unwrap().iter() {
+            entries.push(ExportEntry::AuthMethod {
+                session_id: session_id.clone(),
+                auth_method: auth_method.clone(),
+            });
+        }
+        for (refresh_token, data) in self.refresh_tokens.read().unwrap().iter() {
+            entries.push(ExportEntry::RefreshToken {
+                refresh_token: refresh_token.clone(),
+                account_did: data.account_did.clone(),
+                session_id: data.session_id.clone(),
+                client_id: data.client_id.clone(),
+                absolute_expires_at: data.absolute_expires_at,
+                idle_expires_at: data.idle_expires_at,
+                dpop_jkt: data.dpop_jkt.clone(),
+            });
+        }
+        for (did, session_id) in self.active_sessions.read().unwrap().iter() {
+            entries.push(ExportEntry::ActiveSession {
+                did: did.clone(),
+                session_id: session_id.clone(),
+            });
+        }
+
+        Ok(entries)
+    }
+}
+
 #[async_trait]
 impl KeyStore for MemoryStore {
     async fn get_signing_key(&self) -> Result<SigningKey> {
@@ -218,7 +392,10 @@ impl KeyStore for MemoryStore {
     }
 
     async fn get_dpop_key(&self, thumbprint: &str) -> Result<Option<jose_jwk::Jwk>> {
-        // Search through stored session keys
+        if let Some(key) = self.dpop_keys.read().unwrap().get(thumbprint) {
+            return Ok(Some(key.clone()));
+        }
+        // Fall back to searching stored session keys
         Ok(self
             .session_dpop_keys
             .read()
@@ -227,6 +404,19 @@ impl KeyStore for MemoryStore {
             .find(|(jkt, _)| jkt == thumbprint)
             .map(|(_, key)| key.clone()))
     }
+
+    async fn store_dpop_key(&self, thumbprint: &str, key: jose_jwk::Jwk) -> Result<()> {
+        self.dpop_keys
+            .write()
+            .unwrap()
+            .insert(thumbprint.to_string(), key);
+        Ok(())
+    }
+
+    async fn delete_dpop_key(&self, thumbprint: &str) -> Result<()> {
+        self.dpop_keys.write().unwrap().remove(thumbprint);
+        Ok(())
+    }
 }
 
 // Implement ClientAuthStore trait for jacquard-oauth compatibility
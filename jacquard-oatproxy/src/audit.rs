@@ -0,0 +1,136 @@
+//! Structured audit trail for OAuth-security events.
+//!
+//! Handlers report security-relevant events — replayed DPoP proofs,
+//! reused refresh tokens, failed signatures — to an [`AuditSink`] with
+//! whatever request context was known at the time, so operators get
+//! forensics without scraping info logs. The default
+//! [`TracingAuditSink`] emits each record as a structured `tracing`
+//! event on its own target; deployments that need durable storage plug
+//! in their own sink through the server builder.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use http::HeaderMap;
+use serde::Serialize;
+
+/// What happened, for filtering and alerting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEvent {
+    /// A DPoP proof presented a jti that was already consumed
+    DpopReplayDetected,
+    /// A refresh token was presented that is unknown or already rotated
+    RefreshTokenReused,
+    /// A client's metadata document failed to fetch or validate
+    InvalidClientMetadata,
+    /// A token or proof signature failed verification
+    SignatureFailure,
+    /// A client failed endpoint authentication
+    ClientAuthFailure,
+    /// A bound token was used from a different network context
+    SessionBindingMismatch,
+    /// A refresh token was presented under a DPoP key other than the
+    /// one it was issued to; the detail records what the configured
+    /// policy did with it
+    RefreshKeyMismatch,
+    /// An idle session was deleted by the retention policy; hosts that
+    /// mirror session state can key their own cleanup off this
+    SessionPurged,
+}
+
+/// A security-relevant event with the context known when it fired.
+///
+/// Fields are `Option` because events fire at different depths of a
+/// request: a signature failure may precede knowing the DID, a refresh
+/// reuse may precede knowing the client.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuditRecord {
+    /// What happened
+    pub event: AuditEvent,
+    /// When it happened
+    pub at: DateTime<Utc>,
+    /// Account DID, when the request got far enough to establish one
+    pub did: Option<String>,
+    /// Downstream client_id, when known
+    pub client_id: Option<String>,
+    /// Peer IP, from `X-Forwarded-For` when a proxy fronts the server
+    pub ip: Option<String>,
+    /// `User-Agent` header of the offending request
+    pub user_agent: Option<String>,
+    /// Free-form detail: the failing jti, the error text, etc.
+    pub detail: Option<String>,
+}
+
+impl AuditRecord {
+    /// A record for `event` stamped now, with all context unset
+    pub fn new(event: AuditEvent) -> Self {
+        Self {
+            event,
+            at: Utc::now(),
+            did: None,
+            client_id: None,
+            ip: None,
+            user_agent: None,
+            detail: None,
+        }
+    }
+
+    /// Fill `ip` and `user_agent` from request headers
+    pub fn with_request_context(mut self, headers: &HeaderMap) -> Self {
+        self.ip = headers
+            .get("x-forwarded-for")
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.split(',').next().unwrap_or(v).trim().to_string());
+        self.user_agent = headers
+            .get(http::header::USER_AGENT)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+        self
+    }
+
+    pub fn with_did(mut self, did: impl Into<String>) -> Self {
+        self.did = Some(did.into());
+        self
+    }
+
+    pub fn with_client_id(mut self, client_id: impl Into<String>) -> Self {
+        self.client_id = Some(client_id.into());
+        self
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+/// Destination for audit records.
+///
+/// Sinks must not fail the request they're auditing: errors stay inside
+/// the sink (log and drop, buffer, whatever fits the backend).
+#[async_trait]
+pub trait AuditSink: Send + Sync {
+    async fn record(&self, record: AuditRecord);
+}
+
+/// The default sink: each record becomes a structured `tracing` warning
+/// on the `oatproxy::audit` target, so operators can route or silence
+/// the stream independently of the rest of the crate's logging.
+pub struct TracingAuditSink;
+
+#[async_trait]
+impl AuditSink for TracingAuditSink {
+    async fn record(&self, record: AuditRecord) {
+        tracing::warn!(
+            target: "oatproxy::audit",
+            event = ?record.event,
+            at = %record.at,
+            did = record.did.as_deref().unwrap_or("-"),
+            client_id = record.client_id.as_deref().unwrap_or("-"),
+            ip = record.ip.as_deref().unwrap_or("-"),
+            user_agent = record.user_agent.as_deref().unwrap_or("-"),
+            detail = record.detail.as_deref().unwrap_or("-"),
+            "audit event"
+        );
+    }
+}
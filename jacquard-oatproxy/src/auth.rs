@@ -4,26 +4,54 @@
 //! authenticated user information.
 
 use crate::error::{Error, Result};
-use crate::store::KeyStore;
-use base64::Engine;
+use crate::jose::{self, JwsAlgorithm};
+use crate::store::{KeyMaterial, KeyStore};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-/// JWT claims issued by the proxy to downstream clients.
+/// Claims carried by a downstream access token issued by the proxy.
+///
+/// This is the one claims struct shared by issuance ([`TokenManager`]),
+/// validation, the axum extractors, and introspection, so every consumer
+/// sees exactly what was issued.
+///
+/// [`TokenManager`]: crate::token::TokenManager
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProxyJwtClaims {
-    /// Subject (account DID)
-    pub sub: String,
     /// Issuer (proxy URL)
     pub iss: String,
-    /// Audience (client ID)
+    /// Subject (account DID)
+    pub sub: String,
+    /// Audience (the proxy issuer)
     pub aud: String,
-    /// Issued at (Unix timestamp)
-    pub iat: i64,
     /// Expiration time (Unix timestamp)
     pub exp: i64,
-    /// Session ID
-    pub session_id: String,
+    /// Issued at (Unix timestamp)
+    pub iat: i64,
+    /// Not-before time (Unix timestamp), if present
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nbf: Option<i64>,
+    /// Scope granted to the token
+    pub scope: String,
+    /// DPoP confirmation claim binding the token to a client key
+    pub cnf: ConfirmationClaim,
+    /// Upstream session the token was issued against; `None` on tokens
+    /// minted before this claim existed and on service-client grants,
+    /// which have no upstream session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    /// Network-context binding fingerprint (hashed client IP prefix and
+    /// User-Agent) stamped at issuance when session binding is enabled;
+    /// absent otherwise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bind: Option<String>,
+}
+
+/// DPoP confirmation claim (RFC 9449 `cnf`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfirmationClaim {
+    /// JWK thumbprint of the client's DPoP key
+    pub jkt: String,
 }
 
 /// Validates a JWT issued by the proxy.
@@ -35,59 +63,165 @@ pub async fn validate_proxy_jwt<K: KeyStore>(
     expected_issuer: &str,
 ) -> Result<ProxyJwtClaims> {
     let signing_key = key_store.get_signing_key().await?;
-    let verifying_key = signing_key.verifying_key();
+    validate_proxy_jwt_with_key(token, signing_key.verifying_key(), expected_issuer)
+}
 
-    // Split JWT into parts
-    let parts: Vec<&str> = token.split('.').collect();
-    if parts.len() != 3 {
-        return Err(Error::InvalidRequest("invalid JWT format".to_string()));
+/// Validates a proxy JWT against an already-resolved verifying key.
+///
+/// Split out from [`validate_proxy_jwt`] so per-request callers holding a
+/// [`KeyMaterial`](crate::store::KeyMaterial) cache skip the key-store
+/// read.
+pub fn validate_proxy_jwt_with_key(
+    token: &str,
+    verifying_key: &p256::ecdsa::VerifyingKey,
+    expected_issuer: &str,
+) -> Result<ProxyJwtClaims> {
+    // Signature is checked before any claims are deserialized
+    let claims: ProxyJwtClaims = jose::parse_compact(token, &[JwsAlgorithm::Es256])?
+        .verify_es256(verifying_key)?
+        .claims()?;
+
+    // Validate issuer
+    if claims.iss != expected_issuer {
+        return Err(Error::InvalidRequest(format!(
+            "invalid issuer: expected {}, got {}",
+            expected_issuer, claims.iss
+        )));
     }
 
-    let header_b64 = parts[0];
-    let payload_b64 = parts[1];
-    let signature_b64 = parts[2];
+    jose::validate_timestamps(
+        claims.exp,
+        Some(claims.iat),
+        claims.nbf,
+        jose::DEFAULT_CLOCK_SKEW_LEEWAY_SECONDS,
+    )?;
 
-    // Decode signature
-    let signature = base64::engine::general_purpose::URL_SAFE_NO_PAD
-        .decode(signature_b64)
-        .map_err(|e| Error::InvalidRequest(format!("invalid signature: {}", e)))?;
+    Ok(claims)
+}
 
-    // Verify signature
-    let message = format!("{}.{}", header_b64, payload_b64);
-    let signature_bytes: [u8; 64] = signature
-        .try_into()
-        .map_err(|_| Error::InvalidRequest("invalid signature length".to_string()))?;
+/// Claims carried in a `private_key_jwt` client assertion (RFC 7523).
+#[derive(Debug, Clone, Deserialize)]
+pub struct ClientAssertionClaims {
+    /// Issuer (must equal the client_id)
+    pub iss: String,
+    /// Subject (must equal the client_id)
+    pub sub: String,
+    /// Audience (must include the proxy issuer)
+    pub aud: serde_json::Value,
+    /// Expiration time (Unix timestamp)
+    pub exp: i64,
+    /// Not-before time (Unix timestamp), if present
+    #[serde(default)]
+    pub nbf: Option<i64>,
+    /// Unique token identifier
+    pub jti: String,
+}
 
-    let sig = p256::ecdsa::Signature::from_bytes(&signature_bytes.into())
-        .map_err(|e| Error::InvalidRequest(format!("invalid signature: {}", e)))?;
+/// Verifies a `private_key_jwt` client assertion against the client's
+/// registered JWKS.
+///
+/// The client's metadata document is fetched from `client_id` (a URL under
+/// ATProto client metadata conventions) and must contain either an inline
+/// `jwks` or a `jwks_uri`. The assertion must be signed with ES256 by one of
+/// the registered keys, have `iss` and `sub` equal to `client_id`, an `aud`
+/// that includes `expected_audience`, and an unexpired `exp`.
+pub async fn verify_client_assertion(
+    assertion: &str,
+    client_id: &str,
+    expected_audience: &str,
+) -> Result<ClientAssertionClaims> {
+    // Only ES256 is accepted, matching the keys ATProto clients register
+    let jws = jose::parse_compact(assertion, &[JwsAlgorithm::Es256])?;
+    let kid = jws.header().kid.clone();
+
+    // Fetch the client metadata document to find the registered JWKS
+    let metadata: serde_json::Value = reqwest::get(client_id)
+        .await
+        .map_err(|e| Error::NetworkError(format!("failed to fetch client metadata: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| Error::NetworkError(format!("invalid client metadata: {}", e)))?;
+
+    let jwks = if let Some(jwks) = metadata.get("jwks") {
+        jwks.clone()
+    } else if let Some(jwks_uri) = metadata.get("jwks_uri").and_then(|u| u.as_str()) {
+        reqwest::get(jwks_uri)
+            .await
+            .map_err(|e| Error::NetworkError(format!("failed to fetch client JWKS: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| Error::NetworkError(format!("invalid client JWKS: {}", e)))?
+    } else {
+        tracing::warn!("client {} has no registered jwks or jwks_uri", client_id);
+        return Err(Error::InvalidClient);
+    };
 
-    use p256::ecdsa::signature::Verifier;
-    verifying_key
-        .verify(message.as_bytes(), &sig)
-        .map_err(|e| Error::InvalidRequest(format!("JWT verification failed: {}", e)))?;
+    let keys = jwks
+        .get("keys")
+        .and_then(|k| k.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    // Try the key matching kid first, otherwise any registered P-256 key
+    let mut verified = false;
+    for key_value in &keys {
+        if let Some(kid) = kid.as_deref() {
+            if key_value.get("kid").and_then(|k| k.as_str()) != Some(kid) {
+                continue;
+            }
+        }
 
-    // Decode payload
-    let payload_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD
-        .decode(payload_b64)
-        .map_err(|e| Error::InvalidRequest(format!("invalid payload: {}", e)))?;
+        let Ok(jwk) = serde_json::from_value::<jose_jwk::Jwk>(key_value.clone()) else {
+            continue;
+        };
+        let Ok(jose_jwk::crypto::Key::P256(jose_jwk::crypto::Kind::Public(public_key))) =
+            jose_jwk::crypto::Key::try_from(&jwk.key)
+        else {
+            continue;
+        };
+
+        let verifying_key = p256::ecdsa::VerifyingKey::from(&public_key);
+        if jws.signature_matches(&verifying_key) {
+            verified = true;
+            break;
+        }
+    }
 
-    let claims: ProxyJwtClaims = serde_json::from_slice(&payload_bytes)
-        .map_err(|e| Error::InvalidRequest(format!("invalid JWT claims: {}", e)))?;
+    if !verified {
+        tracing::warn!(
+            "client_assertion signature did not match any registered key for {}",
+            client_id
+        );
+        return Err(Error::InvalidClient);
+    }
 
-    // Validate issuer
-    if claims.iss != expected_issuer {
-        return Err(Error::InvalidRequest(format!(
-            "invalid issuer: expected {}, got {}",
-            expected_issuer, claims.iss
-        )));
+    // Claims are only deserialized now that a registered key verified
+    let claims: ClientAssertionClaims = jws.into_verified().claims()?;
+
+    if claims.iss != client_id || claims.sub != client_id {
+        tracing::warn!("client_assertion iss/sub does not match client_id");
+        return Err(Error::InvalidClient);
     }
 
-    // Validate expiration
-    let now = chrono::Utc::now().timestamp();
-    if claims.exp < now {
-        return Err(Error::InvalidRequest("JWT expired".to_string()));
+    let audience_matches = match &claims.aud {
+        serde_json::Value::String(aud) => aud == expected_audience,
+        serde_json::Value::Array(auds) => {
+            auds.iter().any(|a| a.as_str() == Some(expected_audience))
+        }
+        _ => false,
+    };
+    if !audience_matches {
+        tracing::warn!("client_assertion aud does not match proxy issuer");
+        return Err(Error::InvalidClient);
     }
 
+    jose::validate_timestamps(
+        claims.exp,
+        None,
+        claims.nbf,
+        jose::DEFAULT_CLOCK_SKEW_LEEWAY_SECONDS,
+    )?;
+
     Ok(claims)
 }
 
@@ -100,6 +234,179 @@ pub fn extract_bearer_token(auth_header: &str) -> Option<&str> {
         .or_else(|| auth_header.strip_prefix("bearer "))
 }
 
+/// SHA-256 digest of a downstream secret (refresh token, auth code,
+/// opaque access token), used as the storage key so a store compromise
+/// doesn't leak usable credentials.
+///
+/// The `sha256:` prefix keeps hashed keys distinguishable from legacy
+/// plaintext rows, which lookups fall back to during migration.
+pub fn token_digest(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(token.as_bytes());
+    format!("sha256:{}", hex::encode(hasher.finalize()))
+}
+
+/// CSRF token paired with a session cookie, derived rather than stored.
+///
+/// Cookie-mode clients receive this value in the token response and echo
+/// it in `X-CSRF-Token` on mutating requests; the proxy recomputes it from
+/// the cookie (double-submit), so no extra server-side state is needed.
+/// The domain prefix keeps it from colliding with [`token_digest`] output.
+pub fn cookie_csrf_token(cookie_value: &str) -> String {
+    use sha2::{Digest, Sha256};
+    let mut hasher = Sha256::new();
+    hasher.update(b"oatproxy-csrf:");
+    hasher.update(cookie_value.as_bytes());
+    hex::encode(hasher.finalize())
+}
+
+/// Fingerprint of the network context a request arrived from, for
+/// optional session binding.
+///
+/// Hashes the client IP truncated to the configured prefix (so mobile
+/// clients hopping addresses inside one carrier block keep working)
+/// together with the `User-Agent`. The IP comes from the first
+/// `X-Forwarded-For` entry; without one the fingerprint covers the
+/// User-Agent alone. Returns `None` when neither signal is present.
+pub fn session_binding_fingerprint(
+    headers: &http::HeaderMap,
+    ipv4_prefix_bits: u8,
+    ipv6_prefix_bits: u8,
+) -> Option<String> {
+    use sha2::{Digest, Sha256};
+    use std::net::IpAddr;
+
+    let ip_prefix = headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .and_then(|v| v.trim().parse::<IpAddr>().ok())
+        .map(|ip| match ip {
+            IpAddr::V4(v4) => {
+                let bits = u32::from(ipv4_prefix_bits.min(32));
+                let masked = if bits == 0 {
+                    0
+                } else {
+                    u32::from(v4) & (u32::MAX << (32 - bits))
+                };
+                format!("v4/{}/{}", bits, masked)
+            }
+            IpAddr::V6(v6) => {
+                let bits = u32::from(ipv6_prefix_bits.min(128));
+                let masked = if bits == 0 {
+                    0
+                } else {
+                    u128::from(v6) & (u128::MAX << (128 - bits))
+                };
+                format!("v6/{}/{}", bits, masked)
+            }
+        });
+    let user_agent = headers
+        .get(http::header::USER_AGENT)
+        .and_then(|v| v.to_str().ok());
+
+    if ip_prefix.is_none() && user_agent.is_none() {
+        return None;
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(b"oatproxy-bind:");
+    hasher.update(ip_prefix.as_deref().unwrap_or("-").as_bytes());
+    hasher.update(b"|");
+    hasher.update(user_agent.unwrap_or("-").as_bytes());
+    Some(hex::encode(hasher.finalize()))
+}
+
+/// Compares two secrets without leaking the length of a matching prefix
+/// through timing.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Byte secret that zeroizes on drop and redacts itself in `Debug`.
+///
+/// Used for HMAC secrets and similar raw key material held in config:
+/// structs containing one can keep `#[derive(Debug)]` without dumping
+/// the secret into logs, and the bytes are wiped when the value is
+/// dropped. Derefs to `[u8]` so call sites read it like the plain
+/// `Vec<u8>` it replaces.
+#[derive(Clone)]
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Self(bytes.into())
+    }
+}
+
+impl std::ops::Deref for SecretBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for SecretBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretBytes([redacted; {}])", self.0.len())
+    }
+}
+
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.0.zeroize();
+    }
+}
+
+/// String secret with the same hygiene as [`SecretBytes`]: redacted
+/// `Debug`, zeroized on drop. Used for client secrets and other
+/// credentials that arrive as text.
+#[derive(Clone)]
+pub struct SecretString(String);
+
+impl SecretString {
+    pub fn new(secret: impl Into<String>) -> Self {
+        Self(secret.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl std::fmt::Debug for SecretString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretString([redacted; {}])", self.0.len())
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        use zeroize::Zeroize;
+        self.0.zeroize();
+    }
+}
+
 #[cfg(feature = "axum")]
 pub mod axum_extractors {
     //! Axum extractors for authenticated requests.
@@ -111,18 +418,43 @@ pub mod axum_extractors {
     };
 
     /// State required for JWT validation.
+    ///
+    /// Validation goes through a shared [`KeyMaterial`] cache so the
+    /// extractors don't read the key store on every request.
     #[derive(Clone)]
     pub struct AuthState<K: KeyStore> {
         pub key_store: Arc<K>,
         pub issuer: String,
+        key_material: Arc<KeyMaterial<K>>,
     }
 
     impl<K: KeyStore> AuthState<K> {
         pub fn new(key_store: Arc<K>, issuer: String) -> Self {
-            Self { key_store, issuer }
+            let key_material = Arc::new(KeyMaterial::new(key_store.clone()));
+            Self {
+                key_store,
+                issuer,
+                key_material,
+            }
         }
     }
 
+    /// Validate a bearer token through the state's key-material cache
+    async fn validate<K: KeyStore>(
+        auth_state: &AuthState<K>,
+        auth_header: Option<&str>,
+    ) -> std::result::Result<ProxyJwtClaims, StatusCode> {
+        let token = extract_bearer_token(auth_header.ok_or(StatusCode::UNAUTHORIZED)?)
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+        let verifying_key = auth_state
+            .key_material
+            .verifying_key()
+            .await
+            .map_err(|_| StatusCode::UNAUTHORIZED)?;
+        validate_proxy_jwt_with_key(token, &verifying_key, &auth_state.issuer)
+            .map_err(|_| StatusCode::UNAUTHORIZED)
+    }
+
     /// Extractor for authenticated DID from JWT.
     ///
     /// # Example
@@ -158,12 +490,7 @@ pub mod axum_extractors {
                 .and_then(|h| h.to_str().ok());
 
             async move {
-                let token = extract_bearer_token(auth_header.ok_or(StatusCode::UNAUTHORIZED)?)
-                    .ok_or(StatusCode::UNAUTHORIZED)?;
-
-                let claims = validate_proxy_jwt(token, &*auth_state.key_store, &auth_state.issuer)
-                    .await
-                    .map_err(|_| StatusCode::UNAUTHORIZED)?;
+                let claims = validate(&auth_state, auth_header).await?;
 
                 Ok(AuthenticatedUser(claims.sub, std::marker::PhantomData))
             }
@@ -195,15 +522,140 @@ pub mod axum_extractors {
                 .and_then(|h| h.to_str().ok());
 
             async move {
-                let token = extract_bearer_token(auth_header.ok_or(StatusCode::UNAUTHORIZED)?)
-                    .ok_or(StatusCode::UNAUTHORIZED)?;
-
-                let claims = validate_proxy_jwt(token, &*auth_state.key_store, &auth_state.issuer)
-                    .await
-                    .map_err(|_| StatusCode::UNAUTHORIZED)?;
+                let claims = validate(&auth_state, auth_header).await?;
 
                 Ok(AuthenticatedClaims(claims, std::marker::PhantomData))
             }
         }
     }
+
+    /// A scope a [`RequireScope`] extractor enforces, named at the type
+    /// level so routes declare their requirement in the signature.
+    ///
+    /// Markers for the standard ATProto scopes are provided below; host
+    /// applications can define their own:
+    ///
+    /// ```rust,ignore
+    /// struct ModerationScope;
+    /// impl RequiredScope for ModerationScope {
+    ///     const SCOPE: &'static str = "com.example.moderation";
+    /// }
+    /// ```
+    pub trait RequiredScope {
+        /// The scope value as it appears in the token's `scope` claim
+        const SCOPE: &'static str;
+    }
+
+    /// Marker for the `atproto` scope
+    pub struct Atproto;
+    impl RequiredScope for Atproto {
+        const SCOPE: &'static str = "atproto";
+    }
+
+    /// Marker for the `transition:generic` scope
+    pub struct TransitionGeneric;
+    impl RequiredScope for TransitionGeneric {
+        const SCOPE: &'static str = "transition:generic";
+    }
+
+    /// Marker for the `transition:chat.bsky` scope
+    pub struct TransitionChatBsky;
+    impl RequiredScope for TransitionChatBsky {
+        const SCOPE: &'static str = "transition:chat.bsky";
+    }
+
+    /// Extractor that authenticates the request and requires a scope,
+    /// rejecting with 403 when the token doesn't carry it.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// async fn protected_handler(
+    ///     RequireScope(claims, ..): RequireScope<MyKeyStore, TransitionGeneric>,
+    /// ) -> String {
+    ///     format!("Hello, {}!", claims.sub)
+    /// }
+    /// ```
+    pub struct RequireScope<K: KeyStore, Sc: RequiredScope>(
+        pub ProxyJwtClaims,
+        std::marker::PhantomData<(K, Sc)>,
+    );
+
+    impl<S, K, Sc> FromRequestParts<S> for RequireScope<K, Sc>
+    where
+        S: Send + Sync,
+        K: KeyStore + Clone + Send + Sync + 'static,
+        Sc: RequiredScope + Send + Sync + 'static,
+        Arc<AuthState<K>>: axum::extract::FromRef<S>,
+    {
+        type Rejection = StatusCode;
+
+        fn from_request_parts(
+            parts: &mut Parts,
+            state: &S,
+        ) -> impl std::future::Future<Output = std::result::Result<Self, Self::Rejection>> + Send
+        {
+            let auth_state: Arc<AuthState<K>> =
+                <Arc<AuthState<K>> as axum::extract::FromRef<S>>::from_ref(state);
+
+            let auth_header = parts
+                .headers
+                .get(axum::http::header::AUTHORIZATION)
+                .and_then(|h| h.to_str().ok());
+
+            async move {
+                let claims = validate(&auth_state, auth_header).await?;
+
+                if !scope_granted(&claims.scope, Sc::SCOPE) {
+                    return Err(StatusCode::FORBIDDEN);
+                }
+
+                Ok(RequireScope(claims, std::marker::PhantomData))
+            }
+        }
+    }
+
+    /// Extractor providing the authenticated DID and granted scopes, for
+    /// handlers that branch on scope rather than requiring one up front.
+    pub struct ScopedUser<K: KeyStore>(pub String, pub Vec<String>, std::marker::PhantomData<K>);
+
+    impl<S, K> FromRequestParts<S> for ScopedUser<K>
+    where
+        S: Send + Sync,
+        K: KeyStore + Clone + Send + Sync + 'static,
+        Arc<AuthState<K>>: axum::extract::FromRef<S>,
+    {
+        type Rejection = StatusCode;
+
+        fn from_request_parts(
+            parts: &mut Parts,
+            state: &S,
+        ) -> impl std::future::Future<Output = std::result::Result<Self, Self::Rejection>> + Send
+        {
+            let auth_state: Arc<AuthState<K>> =
+                <Arc<AuthState<K>> as axum::extract::FromRef<S>>::from_ref(state);
+
+            let auth_header = parts
+                .headers
+                .get(axum::http::header::AUTHORIZATION)
+                .and_then(|h| h.to_str().ok());
+
+            async move {
+                let claims = validate(&auth_state, auth_header).await?;
+
+                let scopes = claims
+                    .scope
+                    .split_whitespace()
+                    .map(|s| s.to_string())
+                    .collect();
+
+                Ok(ScopedUser(claims.sub, scopes, std::marker::PhantomData))
+            }
+        }
+    }
+
+    /// Whether a space-separated `scope` claim grants `required`.
+    pub fn scope_granted(scope: &str, required: &str) -> bool {
+        scope.split_whitespace().any(|s| s == required)
+    }
 }
@@ -0,0 +1,115 @@
+//! Upstream response caching for read-only XRPC GETs.
+//!
+//! Repeated proxied calls to public read endpoints (getProfile,
+//! resolveHandle) hit the PDS once per request even though the answers
+//! rarely change. The proxy can keep a short-lived response cache in front
+//! of the upstream for GETs whose NSID is on the configured allowlist,
+//! keyed by (DID, method, params) and honoring the upstream's
+//! `Cache-Control`. Caching is opt-in: with an empty allowlist (the
+//! default) nothing is cached. The default store is in-memory; embedders
+//! can provide their own via `OAuthProxyServerBuilder::response_cache`.
+
+use crate::error::Result;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// A cached upstream response body with the headers worth replaying
+#[derive(Debug, Clone)]
+pub struct CachedResponse {
+    /// HTTP status of the upstream response
+    pub status: u16,
+    /// Response headers to replay (already filtered by the return policy)
+    pub headers: Vec<(String, String)>,
+    /// Response body
+    pub body: Vec<u8>,
+}
+
+/// Pluggable store for cached upstream responses.
+#[async_trait]
+pub trait ResponseCache: Send + Sync {
+    /// Get a cached response if present and not expired
+    async fn get(&self, key: &str) -> Result<Option<CachedResponse>>;
+
+    /// Store a response under the key for `ttl`
+    async fn put(&self, key: &str, response: CachedResponse, ttl: Duration) -> Result<()>;
+}
+
+/// In-memory TTL cache used by default.
+pub struct MemoryResponseCache {
+    entries: RwLock<HashMap<String, (CachedResponse, Instant, Duration)>>,
+    max_entries: usize,
+}
+
+impl MemoryResponseCache {
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            max_entries,
+        }
+    }
+}
+
+#[async_trait]
+impl ResponseCache for MemoryResponseCache {
+    async fn get(&self, key: &str) -> Result<Option<CachedResponse>> {
+        let entries = self.entries.read().unwrap();
+        if let Some((response, stored_at, ttl)) = entries.get(key) {
+            if stored_at.elapsed() < *ttl {
+                return Ok(Some(response.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn put(&self, key: &str, response: CachedResponse, ttl: Duration) -> Result<()> {
+        let mut entries = self.entries.write().unwrap();
+        // Evict expired entries first; if the cache is still full, drop it
+        // on the floor rather than grow without bound
+        if entries.len() >= self.max_entries {
+            entries.retain(|_, (_, stored_at, ttl)| stored_at.elapsed() < *ttl);
+            if entries.len() >= self.max_entries {
+                return Ok(());
+            }
+        }
+        entries.insert(key.to_string(), (response, Instant::now(), ttl));
+        Ok(())
+    }
+}
+
+/// Cache key for a proxied GET: the requesting DID, the XRPC method, and
+/// the query string. Keyed per DID so one user's (possibly personalized)
+/// response is never served to another.
+pub fn cache_key(did: &str, path_and_query: &str) -> String {
+    format!("{}|{}", did, path_and_query)
+}
+
+/// TTL the upstream allows for this response, if it is cacheable at all
+///
+/// Follows `Cache-Control`: `no-store`, `no-cache`, and `private` disable
+/// caching, otherwise a positive `max-age` sets the TTL. Responses
+/// without a `Cache-Control` header are not cached.
+pub fn cache_ttl_from_headers(headers: &http::HeaderMap) -> Option<Duration> {
+    let cache_control = headers.get("cache-control")?.to_str().ok()?;
+    let mut max_age = None;
+    for directive in cache_control.split(',') {
+        let directive = directive.trim().to_ascii_lowercase();
+        if directive == "no-store" || directive == "no-cache" || directive == "private" {
+            return None;
+        }
+        if let Some(seconds) = directive.strip_prefix("max-age=") {
+            max_age = seconds.parse::<u64>().ok();
+        }
+    }
+    match max_age {
+        Some(seconds) if seconds > 0 => Some(Duration::from_secs(seconds)),
+        _ => None,
+    }
+}
+
+/// Extract the NSID from an XRPC path like `/xrpc/app.bsky.actor.getProfile`
+pub fn nsid_from_path(path: &str) -> Option<&str> {
+    path.strip_prefix("/xrpc/")
+        .map(|rest| rest.split('?').next().unwrap_or(rest))
+}
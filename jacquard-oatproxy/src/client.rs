@@ -0,0 +1,468 @@
+//! Typed downstream client for Rust services that consume the proxy.
+//!
+//! Backends and integration tests that want to talk to a running proxy
+//! otherwise have to re-implement the downstream half of the protocol:
+//! DPoP key management and proof signing, the PAR → authorize → token
+//! dance, rotation-aware refresh, and the nonce retry on every call.
+//! [`ProxyClient`] packages all of that behind a handful of methods, so a
+//! service can run the login flow, hold the resulting session, and issue
+//! authenticated XRPC requests without touching a JWT by hand.
+//!
+//! ```no_run
+//! # async fn example() -> jacquard_oatproxy::error::Result<()> {
+//! use jacquard_oatproxy::client::ProxyClient;
+//!
+//! let client = ProxyClient::new(
+//!     url::Url::parse("https://proxy.example.com").unwrap(),
+//!     "https://app.example.com/client-metadata.json",
+//!     "https://app.example.com/callback",
+//! );
+//! let authorize_url = client.begin_authorization("user.bsky.social", "atproto").await?;
+//! // ...send the user to `authorize_url`; the callback delivers a code...
+//! # let code = "";
+//! client.exchange_code(code).await?;
+//! let profile = client
+//!     .xrpc(reqwest::Method::GET, "app.bsky.actor.getProfile?actor=user.bsky.social", None)
+//!     .await?;
+//! # Ok(()) }
+//! ```
+
+use crate::error::{Error, Result};
+use crate::token::generate_token;
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use p256::ecdsa::SigningKey;
+use p256::ecdsa::signature::Signer;
+use serde::Deserialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
+use url::Url;
+
+/// How close to expiry an access token is refreshed proactively rather
+/// than risking a 401 on the next request
+const REFRESH_MARGIN_SECONDS: i64 = 30;
+
+/// The tokens and identity from the most recent grant
+#[derive(Debug, Clone)]
+struct TokenState {
+    access_token: String,
+    refresh_token: Option<String>,
+    did: String,
+    scope: String,
+    expires_at: DateTime<Utc>,
+}
+
+/// Shape of the proxy's PAR response
+#[derive(Debug, Deserialize)]
+struct ParResponse {
+    request_uri: String,
+}
+
+/// The endpoint URLs discovered from the proxy's authorization server
+/// metadata, so the client tracks [`EndpointPaths`](crate::config::EndpointPaths)
+/// overrides instead of assuming the defaults
+#[derive(Debug, Clone, Deserialize)]
+struct DiscoveredEndpoints {
+    pushed_authorization_request_endpoint: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+}
+
+/// Shape of the proxy's token response
+#[derive(Debug, Deserialize)]
+struct TokenGrant {
+    access_token: String,
+    expires_in: u64,
+    refresh_token: Option<String>,
+    scope: String,
+    sub: String,
+}
+
+/// A downstream OAuth client for one user session against the proxy.
+///
+/// Holds a P-256 DPoP keypair for its lifetime and remembers the
+/// proxy-issued nonce between calls, so every request carries a valid
+/// proof and the `use_dpop_nonce` retry happens internally. Token state
+/// lives behind a mutex: the client is `Sync`, and a refresh triggered by
+/// one request is observed by the rest.
+///
+/// The client authenticates as a public client (`auth_method: none`);
+/// confidential-client assertions are the host application's concern.
+pub struct ProxyClient {
+    http: reqwest::Client,
+    proxy_url: Url,
+    client_id: String,
+    redirect_uri: String,
+    key: SigningKey,
+    jkt: String,
+    nonce: Mutex<Option<String>>,
+    tokens: Mutex<Option<TokenState>>,
+    endpoints: Mutex<Option<DiscoveredEndpoints>>,
+}
+
+impl ProxyClient {
+    /// Create a client for the proxy at `proxy_url` with a fresh DPoP
+    /// keypair.
+    pub fn new(
+        proxy_url: Url,
+        client_id: impl Into<String>,
+        redirect_uri: impl Into<String>,
+    ) -> Self {
+        Self::with_dpop_key(
+            proxy_url,
+            client_id,
+            redirect_uri,
+            SigningKey::random(&mut rand::rngs::OsRng),
+        )
+    }
+
+    /// Create a client with a caller-provided DPoP key, for services that
+    /// persist the keypair so sessions survive a restart.
+    pub fn with_dpop_key(
+        proxy_url: Url,
+        client_id: impl Into<String>,
+        redirect_uri: impl Into<String>,
+        key: SigningKey,
+    ) -> Self {
+        let jkt = jwk_thumbprint(&public_jwk(&key));
+        Self {
+            http: reqwest::Client::new(),
+            proxy_url,
+            client_id: client_id.into(),
+            redirect_uri: redirect_uri.into(),
+            key,
+            jkt,
+            nonce: Mutex::new(None),
+            tokens: Mutex::new(None),
+            endpoints: Mutex::new(None),
+        }
+    }
+
+    /// RFC 7638 thumbprint of this client's DPoP key
+    pub fn jkt(&self) -> &str {
+        &self.jkt
+    }
+
+    /// The DID of the authenticated user, once a grant has completed
+    pub fn did(&self) -> Option<String> {
+        self.tokens.lock().unwrap().as_ref().map(|t| t.did.clone())
+    }
+
+    /// The scope of the current grant, once one has completed
+    pub fn scope(&self) -> Option<String> {
+        self.tokens
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|t| t.scope.clone())
+    }
+
+    /// Push an authorization request and return the URL to send the user
+    /// to.
+    ///
+    /// Runs PAR against the proxy with this client's identity and DPoP
+    /// key, then builds the authorize URL carrying the returned
+    /// `request_uri`. The user completes login there; the proxy delivers
+    /// an authorization code to `redirect_uri`, which
+    /// [`exchange_code`](Self::exchange_code) turns into tokens.
+    pub async fn begin_authorization(&self, login_hint: &str, scope: &str) -> Result<Url> {
+        let endpoints = self.discover().await?;
+        let form = [
+            ("client_id", self.client_id.as_str()),
+            ("redirect_uri", self.redirect_uri.as_str()),
+            ("response_type", "code"),
+            ("scope", scope),
+            ("login_hint", login_hint),
+        ];
+        let response = self
+            .post_form_with_dpop(&endpoints.pushed_authorization_request_endpoint, &form)
+            .await?;
+        let response = error_for_status(response).await?;
+        let par: ParResponse = response
+            .json()
+            .await
+            .map_err(|e| Error::NetworkError(format!("invalid PAR response: {}", e)))?;
+
+        let mut authorize = Url::parse(&endpoints.authorization_endpoint)
+            .map_err(|e| Error::InvalidRequest(format!("invalid authorize URL: {}", e)))?;
+        authorize
+            .query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("request_uri", &par.request_uri);
+        Ok(authorize)
+    }
+
+    /// Exchange an authorization code for tokens, storing them on the
+    /// client for [`xrpc`](Self::xrpc) to use.
+    pub async fn exchange_code(&self, code: &str) -> Result<()> {
+        let token_url = self.discover().await?.token_endpoint;
+        let form = [
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("client_id", self.client_id.as_str()),
+            ("redirect_uri", self.redirect_uri.as_str()),
+        ];
+        let response = self.post_form_with_dpop(&token_url, &form).await?;
+        self.store_grant(response).await
+    }
+
+    /// Exchange the held refresh token for a fresh token pair.
+    ///
+    /// [`xrpc`](Self::xrpc) calls this on its own near expiry and after a
+    /// 401; it only needs calling directly when the service wants to
+    /// control refresh timing itself.
+    pub async fn refresh(&self) -> Result<()> {
+        let refresh_token = self
+            .tokens
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|t| t.refresh_token.clone())
+            .ok_or(Error::SessionNotFound)?;
+
+        let token_url = self.discover().await?.token_endpoint;
+        let form = [
+            ("grant_type", "refresh_token"),
+            ("refresh_token", refresh_token.as_str()),
+            ("client_id", self.client_id.as_str()),
+        ];
+        let response = self.post_form_with_dpop(&token_url, &form).await?;
+        self.store_grant(response).await
+    }
+
+    /// Make an authenticated XRPC request through the proxy.
+    ///
+    /// `path` is everything after `/xrpc/` — the NSID plus any query
+    /// string. The access token and a DPoP proof (with `ath` binding) are
+    /// attached, the token is refreshed proactively near expiry, and a
+    /// 401 triggers one refresh-and-retry before the response is handed
+    /// back as-is.
+    pub async fn xrpc(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<reqwest::Response> {
+        let expiring = self.tokens.lock().unwrap().as_ref().is_some_and(|t| {
+            t.expires_at < Utc::now() + chrono::Duration::seconds(REFRESH_MARGIN_SECONDS)
+        });
+        if expiring {
+            self.refresh().await?;
+        }
+
+        let mut refreshed = false;
+        loop {
+            let response = self.xrpc_once(method.clone(), path, body.clone()).await?;
+            if response.status() == reqwest::StatusCode::UNAUTHORIZED && !refreshed {
+                refreshed = true;
+                self.refresh().await?;
+                continue;
+            }
+            return Ok(response);
+        }
+    }
+
+    /// One XRPC attempt with the current token, nonce retry included
+    async fn xrpc_once(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<reqwest::Response> {
+        let access_token = self
+            .tokens
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|t| t.access_token.clone())
+            .ok_or(Error::SessionNotFound)?;
+        let url = self.endpoint(&format!("/xrpc/{}", path));
+
+        let mut nonce_retried = false;
+        loop {
+            let proof = self.proof(method.as_str(), &url, Some(&access_token));
+            let mut request = self
+                .http
+                .request(method.clone(), &url)
+                .header("Authorization", format!("DPoP {}", access_token))
+                .header("DPoP", proof);
+            if let Some(body) = &body {
+                request = request.json(body);
+            }
+            let response = request
+                .send()
+                .await
+                .map_err(|e| Error::NetworkError(format!("XRPC request failed: {}", e)))?;
+
+            if self.remember_nonce(&response) && response.status() == 401 && !nonce_retried {
+                nonce_retried = true;
+                continue;
+            }
+            return Ok(response);
+        }
+    }
+
+    /// POST a form to an OAuth endpoint with a DPoP proof, replaying once
+    /// when the proxy demands a fresh nonce
+    async fn post_form_with_dpop(
+        &self,
+        url: &str,
+        form: &[(&str, &str)],
+    ) -> Result<reqwest::Response> {
+        let mut nonce_retried = false;
+        loop {
+            let proof = self.proof("POST", url, None);
+            let response = self
+                .http
+                .post(url)
+                .header("DPoP", proof)
+                .form(form)
+                .send()
+                .await
+                .map_err(|e| Error::NetworkError(format!("request failed: {}", e)))?;
+
+            // A 4xx carrying a DPoP-Nonce header is the use_dpop_nonce
+            // dance; anything else is the caller's to interpret
+            if self.remember_nonce(&response)
+                && response.status().is_client_error()
+                && !nonce_retried
+            {
+                nonce_retried = true;
+                continue;
+            }
+            return Ok(response);
+        }
+    }
+
+    /// Parse a token response and replace the held token state
+    async fn store_grant(&self, response: reqwest::Response) -> Result<()> {
+        let response = error_for_status(response).await?;
+        let grant: TokenGrant = response
+            .json()
+            .await
+            .map_err(|e| Error::NetworkError(format!("invalid token response: {}", e)))?;
+
+        *self.tokens.lock().unwrap() = Some(TokenState {
+            access_token: grant.access_token,
+            refresh_token: grant.refresh_token,
+            did: grant.sub,
+            scope: grant.scope,
+            expires_at: Utc::now() + chrono::Duration::seconds(grant.expires_in as i64),
+        });
+        Ok(())
+    }
+
+    /// Capture a `DPoP-Nonce` response header for the next proof,
+    /// returning whether one was present
+    fn remember_nonce(&self, response: &reqwest::Response) -> bool {
+        if let Some(nonce) = response
+            .headers()
+            .get("dpop-nonce")
+            .and_then(|v| v.to_str().ok())
+        {
+            *self.nonce.lock().unwrap() = Some(nonce.to_string());
+            true
+        } else {
+            false
+        }
+    }
+
+    /// OAuth endpoint URLs from the proxy's RFC 8414 metadata document,
+    /// fetched once and cached for the client's lifetime
+    async fn discover(&self) -> Result<DiscoveredEndpoints> {
+        if let Some(endpoints) = self.endpoints.lock().unwrap().clone() {
+            return Ok(endpoints);
+        }
+        let metadata_url = self.endpoint("/.well-known/oauth-authorization-server");
+        let endpoints: DiscoveredEndpoints = self
+            .http
+            .get(&metadata_url)
+            .send()
+            .await
+            .map_err(|e| Error::NetworkError(format!("metadata fetch failed: {}", e)))?
+            .json()
+            .await
+            .map_err(|e| Error::NetworkError(format!("invalid metadata document: {}", e)))?;
+        *self.endpoints.lock().unwrap() = Some(endpoints.clone());
+        Ok(endpoints)
+    }
+
+    /// Absolute URL for a path on the proxy
+    fn endpoint(&self, path: &str) -> String {
+        format!(
+            "{}{}",
+            self.proxy_url.as_str().trim_end_matches('/'),
+            path
+        )
+    }
+
+    /// Sign a DPoP proof for a request, with the remembered nonce and an
+    /// `ath` claim when a token is bound
+    fn proof(&self, method: &str, url: &str, access_token: Option<&str>) -> String {
+        let header = json!({
+            "typ": "dpop+jwt",
+            "alg": "ES256",
+            "jwk": public_jwk(&self.key),
+        });
+
+        let mut payload = json!({
+            "jti": generate_token(16),
+            "htm": method,
+            "htu": url,
+            "iat": Utc::now().timestamp(),
+        });
+        if let Some(nonce) = self.nonce.lock().unwrap().clone() {
+            payload["nonce"] = json!(nonce);
+        }
+        if let Some(token) = access_token {
+            let hash = Sha256::digest(token.as_bytes());
+            payload["ath"] = json!(URL_SAFE_NO_PAD.encode(hash));
+        }
+
+        let header_b64 = URL_SAFE_NO_PAD.encode(header.to_string());
+        let payload_b64 = URL_SAFE_NO_PAD.encode(payload.to_string());
+        let signing_input = format!("{}.{}", header_b64, payload_b64);
+        let signature: p256::ecdsa::Signature = self.key.sign(signing_input.as_bytes());
+        let signature_b64 = URL_SAFE_NO_PAD.encode(signature.to_bytes());
+
+        format!("{}.{}.{}", header_b64, payload_b64, signature_b64)
+    }
+}
+
+/// Turn a non-2xx OAuth response into an [`Error`] carrying the body
+async fn error_for_status(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let body = response.text().await.unwrap_or_default();
+    Err(Error::InvalidRequest(format!(
+        "proxy answered {}: {}",
+        status, body
+    )))
+}
+
+/// Public JWK for a P-256 signing key, in proof-header shape
+fn public_jwk(key: &SigningKey) -> serde_json::Value {
+    let point = key.verifying_key().to_encoded_point(false);
+    json!({
+        "kty": "EC",
+        "crv": "P-256",
+        "x": URL_SAFE_NO_PAD.encode(point.x().expect("affine x")),
+        "y": URL_SAFE_NO_PAD.encode(point.y().expect("affine y")),
+    })
+}
+
+/// RFC 7638 thumbprint over the canonical JWK members
+fn jwk_thumbprint(jwk: &serde_json::Value) -> String {
+    let canonical = json!({
+        "crv": jwk.get("crv"),
+        "kty": jwk.get("kty"),
+        "x": jwk.get("x"),
+        "y": jwk.get("y"),
+    });
+    let hash = Sha256::digest(canonical.to_string().as_bytes());
+    URL_SAFE_NO_PAD.encode(hash)
+}
@@ -0,0 +1,140 @@
+//! Serialization of session blobs for storage backends.
+//!
+//! The storage traits deal in structured values, and most SQL-backed
+//! stores persist them as serialized blobs — historically bare JSON
+//! strings, which get large and slow to parse at high traffic.
+//! [`StoreCodec`] lets a store pick the wire format: JSON stays the
+//! default, with CBOR and postcard available behind the `cbor` and
+//! `postcard` features.
+//!
+//! Non-JSON encodings are wrapped in a versioned envelope naming the
+//! codec that wrote them, and every codec decodes every envelope it has
+//! the feature for, so a deployment can switch codecs without migrating
+//! rows: old rows keep deserializing, new rows are written in the new
+//! format. Bare JSON (no envelope) is always accepted, so rows written
+//! before this module existed load unchanged.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::error::{Error, Result};
+
+/// Current version of the blob envelope format
+pub const CODEC_ENVELOPE_VERSION: u32 = 1;
+
+/// Envelope header prefix; cannot start a JSON document, so enveloped
+/// and bare-JSON rows are distinguishable from their first byte
+const ENVELOPE_MAGIC: &str = "#oatc:";
+
+/// Wire format a store serializes session blobs with.
+///
+/// The variant only controls what [`encode`](Self::encode) writes;
+/// [`decode`](Self::decode) dispatches on the envelope header, so any
+/// variant reads rows written by any other (feature permitting).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StoreCodec {
+    /// Bare JSON with no envelope — the format stores wrote before
+    /// codecs existed, and the default
+    #[default]
+    Json,
+    /// CBOR in a versioned envelope; compact and fast to parse
+    #[cfg(feature = "cbor")]
+    Cbor,
+    /// postcard in a versioned envelope; the smallest of the three
+    #[cfg(feature = "postcard")]
+    Postcard,
+}
+
+impl StoreCodec {
+    /// Stable identifier recorded in the envelope header
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            #[cfg(feature = "cbor")]
+            Self::Cbor => "cbor",
+            #[cfg(feature = "postcard")]
+            Self::Postcard => "postcard",
+        }
+    }
+
+    /// Serialize a value in this codec's wire format
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>> {
+        match self {
+            Self::Json => serde_json::to_vec(value)
+                .map_err(|e| Error::StorageError(format!("json encode: {}", e))),
+            #[cfg(feature = "cbor")]
+            Self::Cbor => {
+                let mut bytes = envelope_header(self.id()).into_bytes();
+                ciborium::into_writer(value, &mut bytes)
+                    .map_err(|e| Error::StorageError(format!("cbor encode: {}", e)))?;
+                Ok(bytes)
+            }
+            #[cfg(feature = "postcard")]
+            Self::Postcard => {
+                let mut bytes = envelope_header(self.id()).into_bytes();
+                bytes.extend(
+                    postcard::to_stdvec(value)
+                        .map_err(|e| Error::StorageError(format!("postcard encode: {}", e)))?,
+                );
+                Ok(bytes)
+            }
+        }
+    }
+
+    /// Deserialize a blob written by any codec, dispatching on its
+    /// envelope header; bytes without one are treated as bare JSON
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T> {
+        let Some((codec_id, payload)) = parse_envelope(bytes)? else {
+            return serde_json::from_slice(bytes)
+                .map_err(|e| Error::StorageError(format!("json decode: {}", e)));
+        };
+        match codec_id {
+            "json" => serde_json::from_slice(payload)
+                .map_err(|e| Error::StorageError(format!("json decode: {}", e))),
+            #[cfg(feature = "cbor")]
+            "cbor" => ciborium::from_reader(payload)
+                .map_err(|e| Error::StorageError(format!("cbor decode: {}", e))),
+            #[cfg(feature = "postcard")]
+            "postcard" => postcard::from_bytes(payload)
+                .map_err(|e| Error::StorageError(format!("postcard decode: {}", e))),
+            other => Err(Error::StorageError(format!(
+                "blob written by unsupported codec {:?} (missing feature?)",
+                other
+            ))),
+        }
+    }
+}
+
+/// Build the envelope header for a codec id:
+/// `#oatc:<version>:<id>\n`
+#[cfg(any(feature = "cbor", feature = "postcard"))]
+fn envelope_header(codec_id: &str) -> String {
+    format!("{}{}:{}\n", ENVELOPE_MAGIC, CODEC_ENVELOPE_VERSION, codec_id)
+}
+
+/// Split an enveloped blob into its codec id and payload; `None` when
+/// the magic is absent (a bare legacy blob)
+fn parse_envelope(bytes: &[u8]) -> Result<Option<(&str, &[u8])>> {
+    if !bytes.starts_with(ENVELOPE_MAGIC.as_bytes()) {
+        return Ok(None);
+    }
+    let newline = bytes
+        .iter()
+        .position(|&b| b == b'\n')
+        .ok_or_else(|| Error::StorageError("truncated codec envelope".into()))?;
+    let header = std::str::from_utf8(&bytes[ENVELOPE_MAGIC.len()..newline])
+        .map_err(|_| Error::StorageError("malformed codec envelope".into()))?;
+    let (version, codec_id) = header
+        .split_once(':')
+        .ok_or_else(|| Error::StorageError("malformed codec envelope".into()))?;
+    let version: u32 = version
+        .parse()
+        .map_err(|_| Error::StorageError("malformed codec envelope".into()))?;
+    if version > CODEC_ENVELOPE_VERSION {
+        return Err(Error::StorageError(format!(
+            "codec envelope version {} is newer than this build supports",
+            version
+        )));
+    }
+    Ok(Some((codec_id, &bytes[newline + 1..])))
+}
@@ -1,13 +1,153 @@
+use std::path::Path;
+
+use jacquard_common::IntoStatic;
 use jacquard_oauth::atproto::{AtprotoClientMetadata, GrantType};
 use jacquard_oauth::scopes::Scope;
+use serde::Deserialize;
 use url::Url;
 
+use crate::error::{Error, Result};
+
+/// A confidential client allowed to use the `client_credentials` grant.
+///
+/// Service clients act on behalf of a pre-provisioned upstream session
+/// (e.g. a backend worker that completed an interactive flow once), so
+/// they can mint downstream JWTs without user interaction.
+#[derive(Debug, Clone)]
+pub struct ServiceClient {
+    /// Client identifier presented in token requests
+    pub client_id: String,
+    /// Shared secret for client authentication; redacted in `Debug`
+    /// output and zeroized on drop
+    pub client_secret: crate::auth::SecretString,
+    /// DID of the account whose upstream session this client uses
+    pub did: String,
+}
+
+/// Per-client token lifetime overrides, keyed by downstream `client_id`.
+///
+/// `None` fields fall back to the global values in [`ProxyConfig`]. The
+/// registry matches both public downstream clients and configured
+/// [`ServiceClient`]s.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ClientTokenPolicy {
+    /// Client identifier the overrides apply to
+    pub client_id: String,
+    /// Downstream access token expiry in seconds
+    pub access_token_expiry_seconds: Option<i64>,
+    /// Hard refresh token lifetime in seconds from the original grant
+    /// (0 disables)
+    pub refresh_token_absolute_lifetime_seconds: Option<i64>,
+    /// Refresh token idle timeout in seconds, reset on every use
+    /// (0 disables)
+    pub refresh_token_idle_timeout_seconds: Option<i64>,
+}
+
+/// What the refresh grant does when the presented DPoP key differs from
+/// the one the refresh token was issued to.
+///
+/// Public clients keep their DPoP keypair in browser storage, so a
+/// cleared cache or new device shows up as a valid refresh token under a
+/// fresh key. [`Rebind`](Self::Rebind) accepts the grant and re-binds
+/// the rotated token to the new key; [`Reject`](Self::Reject) revokes
+/// the token and fails the grant, forcing a full re-authorization.
+/// Either outcome emits an audit event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefreshKeyPolicy {
+    /// Accept the grant and bind the rotated token to the new key
+    #[default]
+    Rebind,
+    /// Revoke the token and reject the grant
+    Reject,
+}
+
+impl std::str::FromStr for RefreshKeyPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "rebind" => Ok(Self::Rebind),
+            "reject" => Ok(Self::Reject),
+            other => Err(format!(
+                "unknown refresh key policy {:?} (expected \"rebind\" or \"reject\")",
+                other
+            )),
+        }
+    }
+}
+
+/// Paths of the HTTP endpoints this proxy serves, relative to
+/// [`ProxyConfig::host`].
+///
+/// The router and both discovery metadata documents derive from these
+/// paths, so overriding one here keeps the route and every advertised
+/// URL in sync. The `.well-known` discovery paths themselves are fixed
+/// by RFC 8414 and cannot be moved.
+#[derive(Debug, Clone)]
+pub struct EndpointPaths {
+    /// Client metadata document (default: `/oauth-client-metadata.json`)
+    pub client_metadata: String,
+    /// JWKS document (default: `/oauth/jwks.json`)
+    pub jwks: String,
+    /// Pushed authorization request endpoint (default: `/oauth/par`)
+    pub par: String,
+    /// Authorization endpoint (default: `/oauth/authorize`)
+    pub authorize: String,
+    /// Upstream authorization callback (default: `/oauth/return`)
+    pub oauth_return: String,
+    /// Token endpoint (default: `/oauth/token`)
+    pub token: String,
+    /// Revocation endpoint (default: `/oauth/revoke`)
+    pub revoke: String,
+    /// Introspection endpoint (default: `/oauth/introspect`)
+    pub introspect: String,
+    /// End-session endpoint (default: `/oauth/logout`)
+    pub logout: String,
+    /// Grant listing/revocation endpoint for end users
+    /// (default: `/oauth/grants`)
+    pub grants: String,
+}
+
+impl Default for EndpointPaths {
+    fn default() -> Self {
+        Self {
+            client_metadata: "/oauth-client-metadata.json".to_string(),
+            jwks: "/oauth/jwks.json".to_string(),
+            par: "/oauth/par".to_string(),
+            authorize: "/oauth/authorize".to_string(),
+            oauth_return: "/oauth/return".to_string(),
+            token: "/oauth/token".to_string(),
+            revoke: "/oauth/revoke".to_string(),
+            introspect: "/oauth/introspect".to_string(),
+            logout: "/oauth/logout".to_string(),
+            grants: "/oauth/grants".to_string(),
+        }
+    }
+}
+
 /// Configuration for the OAuth proxy server
 #[derive(Debug, Clone)]
 pub struct ProxyConfig {
     /// Public HTTPS address of this proxy
     pub host: Url,
 
+    /// Paths the router serves OAuth endpoints at and that metadata
+    /// documents advertise
+    pub endpoints: EndpointPaths,
+
+    /// Issuer identifier for metadata documents and downstream token
+    /// claims; `None` uses `host` without a trailing slash
+    pub issuer_override: Option<String>,
+
+    /// Absolute URL of the upstream authorization callback when it lives
+    /// somewhere other than `host` + `endpoints.oauth_return` (a different
+    /// path, or a dedicated callback domain). The client metadata
+    /// registers this as the redirect URI and the router serves its path;
+    /// `None` derives the callback from `host` and the endpoint path
+    pub return_url_override: Option<Url>,
+
     /// OAuth scopes to request
     pub scope: Vec<Scope<'static>>,
 
@@ -17,11 +157,245 @@ pub struct ProxyConfig {
     /// Default PDS for unauthenticated/public requests
     pub default_pds: Url,
 
-    /// HMAC secret for DPoP nonce generation (32+ bytes recommended)
-    pub dpop_nonce_hmac_secret: Vec<u8>,
+    /// HMAC secret for DPoP nonce generation (32+ bytes recommended);
+    /// redacted in `Debug` output and zeroized on drop
+    pub dpop_nonce_hmac_secret: crate::auth::SecretBytes,
+
+    /// Previous DPoP nonce HMAC secret, still accepted for validation
+    /// during rotation so outstanding nonces and in-flight PARs don't
+    /// break; new nonces are always minted with the primary secret and
+    /// the 5-minute nonce lifetime bounds the effective overlap. Unset
+    /// once rotation is complete (default: None)
+    pub dpop_nonce_hmac_secret_previous: Option<crate::auth::SecretBytes>,
 
     /// Downstream token expiry in seconds (default: 3600 = 1 hour)
     pub downstream_token_expiry_seconds: i64,
+
+    /// Hard lifetime of downstream refresh tokens in seconds, measured
+    /// from the original grant and surviving rotation; 0 disables
+    /// (default: 31536000 = 1 year, matching the upstream session)
+    pub refresh_token_absolute_lifetime_seconds: i64,
+
+    /// Idle timeout of downstream refresh tokens in seconds, reset on
+    /// every use; 0 disables (default: 0)
+    pub refresh_token_idle_timeout_seconds: i64,
+
+    /// Idle timeout of proxy sessions in seconds, measured from
+    /// `last_used_at`; sessions idle past this are revoked on their next
+    /// proxied request or refresh attempt. 0 disables (default: 0)
+    pub session_idle_timeout_seconds: i64,
+
+    /// What the refresh grant does when the presented DPoP key differs
+    /// from the one the refresh token was issued to; see
+    /// [`RefreshKeyPolicy`] (default: [`RefreshKeyPolicy::Rebind`])
+    pub refresh_key_policy: RefreshKeyPolicy,
+
+    /// How long an upstream session may sit unused before the cleanup
+    /// worker deletes it, in seconds; users who never return stop
+    /// accumulating rows. 0 disables retention (default: 0)
+    pub session_retention_seconds: i64,
+
+    /// How often the cleanup worker runs, in seconds
+    /// (default: 3600 = 1 hour)
+    pub cleanup_interval_seconds: i64,
+
+    /// Per-client token lifetime overrides
+    pub client_token_policies: Vec<ClientTokenPolicy>,
+
+    /// Maximum outstanding (unexpired) pushed authorization requests a
+    /// single client_id may hold at once; 0 disables the cap
+    /// (default: 32)
+    pub max_pending_par_per_client: u64,
+
+    /// Clock-skew leeway in seconds applied when validating `exp`/`iat`/
+    /// `nbf` on downstream JWTs and as the future-skew window for DPoP
+    /// proofs (default: 30)
+    pub clock_skew_leeway_seconds: i64,
+
+    /// Concurrent proxied XRPC requests allowed per account DID; 0
+    /// disables the per-DID limit (default: 32)
+    pub xrpc_max_concurrent_per_did: usize,
+
+    /// Concurrent proxied XRPC requests allowed per downstream client
+    /// key (DPoP JKT); 0 disables the per-client limit (default: 16)
+    pub xrpc_max_concurrent_per_client: usize,
+
+    /// How many requests past a concurrency limit may wait for a slot
+    /// before the proxy answers 429 (default: 64)
+    pub xrpc_queue_depth: usize,
+
+    /// XRPC methods (NSIDs) whose GET responses may be served from the
+    /// response cache, honoring upstream `Cache-Control`.
+    /// Empty disables response caching (the default).
+    pub xrpc_cacheable_nsids: Vec<String>,
+
+    /// Largest response body the cache will hold, in bytes; bigger
+    /// responses are proxied through uncached (default: 64 KiB)
+    pub xrpc_cache_max_entry_bytes: usize,
+
+    /// Largest request body accepted on OAuth endpoints (PAR, token,
+    /// revoke, ...), in bytes (default: 1 MiB)
+    pub oauth_max_body_bytes: usize,
+
+    /// Largest request body accepted on proxied XRPC endpoints, in bytes
+    /// (default: 50 MiB)
+    pub xrpc_max_body_bytes: usize,
+
+    /// Larger body allowance for `com.atproto.repo.uploadBlob`, in bytes
+    /// (default: 100 MiB)
+    pub xrpc_upload_max_body_bytes: usize,
+
+    /// Largest request URL (path plus query) the proxy will forward, in
+    /// bytes (default: 8 KiB)
+    pub max_request_url_bytes: usize,
+
+    /// Additional authenticated path prefixes the router proxies upstream
+    /// with the same token and DPoP handling as `/xrpc` — for PDS
+    /// features that live outside XRPC, like OAuth-protected blob routes.
+    /// Each entry must start with `/` and is registered as
+    /// `{prefix}/{*path}` (default: empty)
+    pub extra_proxy_paths: Vec<String>,
+
+    /// Origins allowed to make cross-origin requests to the sensitive
+    /// endpoints (PAR, token, revoke, introspect, logout, and the XRPC
+    /// proxy); `"*"` allows any origin. Metadata and JWKS responses are
+    /// always served permissively — they are public documents. Empty
+    /// disables cross-origin access to the sensitive group (the default)
+    pub cors_allowed_origins: Vec<String>,
+
+    /// Whether CORS responses on the sensitive endpoints allow
+    /// credentialed requests; rejected in combination with a `"*"` entry
+    /// in `cors_allowed_origins` (default: false)
+    pub cors_allow_credentials: bool,
+
+    /// Bearer token that unlocks the operator admin endpoint
+    /// (`POST /oauth/admin/maintenance`); the route is not registered
+    /// when unset (default: `None`)
+    pub admin_token: Option<crate::auth::SecretString>,
+
+    /// Bytes of OS-sourced entropy per generated authorization code,
+    /// refresh token, and opaque access token, base64url-encoded. Values
+    /// below `token::MIN_TOKEN_ENTROPY_BYTES` (16) are raised to the
+    /// minimum (default: 32)
+    pub token_entropy_bytes: usize,
+
+    /// Issue opaque random access tokens persisted in the store instead of
+    /// self-contained ES256 JWTs. Opaque tokens can be revoked before they
+    /// expire, at the cost of a store lookup per request; both kinds share
+    /// the same cnf/JKT binding semantics (default: false)
+    pub opaque_access_tokens: bool,
+
+    /// Require clients to use pushed authorization requests; when set, the
+    /// authorize endpoint only accepts a `request_uri` from PAR. Advertised
+    /// as `require_pushed_authorization_requests` (default: true)
+    pub require_par: bool,
+
+    /// Token endpoint auth methods clients may use, advertised as
+    /// `token_endpoint_auth_methods_supported` and enforced at the
+    /// endpoints (default: ["none", "private_key_jwt"])
+    pub token_endpoint_auth_methods: Vec<String>,
+
+    /// DPoP proof algorithms accepted from downstream clients, advertised
+    /// as `dpop_signing_alg_values_supported`. Only algorithms the proxy
+    /// can actually verify are meaningful here
+    /// (default: ["ES256", "ES256K", "EdDSA"])
+    pub dpop_signing_algs: Vec<String>,
+
+    /// Include the RFC 9207 `iss` parameter in downstream authorization
+    /// responses (mix-up protection) and advertise it as
+    /// `authorization_response_iss_parameter_supported`. Only disable for
+    /// clients that choke on unexpected response parameters
+    /// (default: true)
+    pub authorization_response_iss: bool,
+
+    /// Bind downstream tokens to the network context that created them:
+    /// `"off"` disables, `"warn"` logs mismatches but serves the request,
+    /// `"enforce"` rejects mismatches with 401. Binding covers a hashed
+    /// client IP prefix and User-Agent, so it can break mobile clients
+    /// whose addresses move between carrier blocks (default: "off")
+    pub session_binding_mode: String,
+
+    /// IPv4 prefix length the binding fingerprint truncates addresses
+    /// to before hashing (default: 24)
+    pub session_binding_ipv4_prefix_bits: u8,
+
+    /// IPv6 prefix length the binding fingerprint truncates addresses
+    /// to before hashing (default: 48)
+    pub session_binding_ipv6_prefix_bits: u8,
+
+    /// Issue an HttpOnly session cookie alongside the token response so
+    /// browser apps can make XRPC requests without holding any token in
+    /// JavaScript. Cookie-authenticated requests are CSRF-protected via a
+    /// double-submit token instead of downstream DPoP; upstream requests
+    /// still carry proxy-generated DPoP proofs (default: false)
+    pub cookie_sessions: bool,
+
+    /// Name of the session cookie. The `__Host-` prefix locks the cookie
+    /// to this host over HTTPS (default: "__Host-oatproxy-session")
+    pub cookie_name: String,
+
+    /// Session cookie lifetime in seconds (default: 30 days)
+    pub cookie_max_age_seconds: i64,
+
+    /// Confidential clients allowed to use the `client_credentials` grant
+    pub service_clients: Vec<ServiceClient>,
+
+    /// Audiences (service DIDs) downstream clients may request service auth
+    /// tokens for via `com.atproto.server.getServiceAuth`.
+    /// Empty means no restriction.
+    pub service_auth_allowed_auds: Vec<String>,
+
+    /// Lexicon methods (lxm) downstream clients may bind service auth tokens
+    /// to. Empty means no restriction.
+    pub service_auth_allowed_lxms: Vec<String>,
+
+    /// Service DIDs that may be targeted directly via the `atproto-proxy`
+    /// header. Requests naming other services are still forwarded to the
+    /// PDS, which performs the proxying itself. Empty disables direct
+    /// routing.
+    pub atproto_proxy_allowed_dids: Vec<String>,
+
+    /// How long successful identity/PDS resolutions are cached, in seconds
+    /// (default: 300 = 5 minutes)
+    pub resolution_cache_ttl_seconds: u64,
+
+    /// How long resolution misses are negative-cached, in seconds
+    /// (default: 60)
+    pub resolution_negative_cache_ttl_seconds: u64,
+
+    /// Connect timeout for upstream requests in seconds (default: 5)
+    pub upstream_connect_timeout_seconds: u64,
+
+    /// Overall timeout for upstream requests in seconds (default: 30)
+    pub upstream_read_timeout_seconds: u64,
+
+    /// Extra attempts for idempotent GET requests that hit a transport
+    /// error or 502/503/504 (default: 2)
+    pub upstream_get_retries: u32,
+
+    /// Consecutive upstream failures before a host's circuit opens
+    /// (default: 5)
+    pub circuit_breaker_failure_threshold: u32,
+
+    /// How long an open circuit fails fast before letting a probe request
+    /// through, in seconds (default: 30)
+    pub circuit_breaker_reset_seconds: u64,
+
+    /// If non-empty, only these request headers are forwarded upstream
+    /// (the built-in denylist still applies)
+    pub request_header_allowlist: Vec<String>,
+
+    /// Request headers never forwarded upstream, in addition to the
+    /// built-in denylist (hop-by-hop, credentials, client IP headers)
+    pub request_header_denylist: Vec<String>,
+
+    /// If non-empty, only these response headers are passed back to the
+    /// client (the built-in denylist still applies)
+    pub response_header_allowlist: Vec<String>,
+
+    /// Response headers never passed back to the client, in addition to
+    /// the built-in denylist (Set-Cookie, hop-by-hop headers)
+    pub response_header_denylist: Vec<String>,
 }
 
 impl ProxyConfig {
@@ -29,6 +403,7 @@ impl ProxyConfig {
     pub fn new(host: impl Into<Url>) -> Self {
         let host = host.into();
         let host_str = host.as_str().trim_end_matches('/');
+        let endpoints = EndpointPaths::default();
 
         let default_scopes = vec![
             Scope::parse("atproto").expect("valid scope"),
@@ -39,7 +414,7 @@ impl ProxyConfig {
         let client_metadata = if host_str.contains("localhost") || host_str.contains("127.0.0.1") {
             AtprotoClientMetadata::new_localhost(
                 Some(vec![
-                    format!("{}/oauth/return", host_str)
+                    format!("{}{}", host_str, endpoints.oauth_return)
                         .parse()
                         .expect("valid url"),
                 ]),
@@ -47,19 +422,19 @@ impl ProxyConfig {
             )
         } else {
             let mut metadata = AtprotoClientMetadata::new(
-                format!("{}/oauth-client-metadata.json", host_str)
+                format!("{}{}", host_str, endpoints.client_metadata)
                     .parse()
                     .expect("valid url"),
                 Some(host.clone()), // client_uri
                 vec![
-                    format!("{}/oauth/return", host_str)
+                    format!("{}{}", host_str, endpoints.oauth_return)
                         .parse()
                         .expect("valid url"),
                 ],
                 vec![GrantType::AuthorizationCode, GrantType::RefreshToken],
                 default_scopes.clone(),
                 Some(
-                    format!("{}/oauth/jwks.json", host_str)
+                    format!("{}{}", host_str, endpoints.jwks)
                         .parse()
                         .expect("valid url"),
                 ),
@@ -76,14 +451,197 @@ impl ProxyConfig {
 
         Self {
             host: host.clone(),
+            endpoints,
+            issuer_override: None,
+            return_url_override: None,
             scope: default_scopes.clone(),
             client_metadata,
             default_pds: Url::parse("https://public.api.bsky.app").expect("valid url"),
-            dpop_nonce_hmac_secret: b"insecure-default-dpop-nonce-secret".to_vec(),
+            dpop_nonce_hmac_secret: crate::auth::SecretBytes::new(
+                b"insecure-default-dpop-nonce-secret".as_slice(),
+            ),
+            dpop_nonce_hmac_secret_previous: None,
             downstream_token_expiry_seconds: 3600, // 1 hour default
+            refresh_token_absolute_lifetime_seconds: 31_536_000, // 1 year
+            refresh_token_idle_timeout_seconds: 0,
+            session_idle_timeout_seconds: 0,
+            refresh_key_policy: RefreshKeyPolicy::Rebind,
+            session_retention_seconds: 0,
+            cleanup_interval_seconds: 3600,
+            client_token_policies: Vec::new(),
+            max_pending_par_per_client: 32,
+            clock_skew_leeway_seconds: crate::jose::DEFAULT_CLOCK_SKEW_LEEWAY_SECONDS,
+            xrpc_max_concurrent_per_did: 32,
+            xrpc_max_concurrent_per_client: 16,
+            xrpc_queue_depth: 64,
+            xrpc_cacheable_nsids: Vec::new(),
+            xrpc_cache_max_entry_bytes: 64 * 1024,
+            oauth_max_body_bytes: 1024 * 1024,
+            xrpc_max_body_bytes: 50 * 1024 * 1024,
+            xrpc_upload_max_body_bytes: 100 * 1024 * 1024,
+            max_request_url_bytes: 8 * 1024,
+            extra_proxy_paths: Vec::new(),
+            cors_allowed_origins: Vec::new(),
+            cors_allow_credentials: false,
+            admin_token: None,
+            token_entropy_bytes: 32,
+            opaque_access_tokens: false,
+            require_par: true,
+            token_endpoint_auth_methods: vec!["none".to_string(), "private_key_jwt".to_string()],
+            authorization_response_iss: true,
+            session_binding_mode: "off".to_string(),
+            session_binding_ipv4_prefix_bits: 24,
+            session_binding_ipv6_prefix_bits: 48,
+            dpop_signing_algs: vec![
+                "ES256".to_string(),
+                "ES256K".to_string(),
+                "EdDSA".to_string(),
+            ],
+            cookie_sessions: false,
+            cookie_name: "__Host-oatproxy-session".to_string(),
+            cookie_max_age_seconds: 30 * 24 * 3600,
+            service_clients: Vec::new(),
+            service_auth_allowed_auds: Vec::new(),
+            service_auth_allowed_lxms: Vec::new(),
+            atproto_proxy_allowed_dids: Vec::new(),
+            resolution_cache_ttl_seconds: 300,
+            resolution_negative_cache_ttl_seconds: 60,
+            upstream_connect_timeout_seconds: 5,
+            upstream_read_timeout_seconds: 30,
+            upstream_get_retries: 2,
+            circuit_breaker_failure_threshold: 5,
+            circuit_breaker_reset_seconds: 30,
+            request_header_allowlist: Vec::new(),
+            request_header_denylist: Vec::new(),
+            response_header_allowlist: Vec::new(),
+            response_header_denylist: Vec::new(),
+        }
+    }
+
+    /// Load configuration from `OATPROXY_*` environment variables.
+    ///
+    /// `OATPROXY_HOST` is required; every other variable is optional and
+    /// absent ones keep the defaults from [`ProxyConfig::new`]. List-valued
+    /// variables are comma-separated, and `OATPROXY_SERVICE_CLIENTS` takes
+    /// `client_id:client_secret:did` entries. Malformed values are rejected
+    /// with the offending variable named in the error.
+    pub fn from_env() -> Result<Self> {
+        ProxyConfigFile::from_env()?.into_config()
+    }
+
+    /// Load configuration from a TOML or YAML file, chosen by extension.
+    ///
+    /// The file mirrors the fields of [`ProxyConfigFile`]; only `host` is
+    /// required. Unknown keys and malformed values are rejected with the
+    /// offending field named in the error.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        ProxyConfigFile::from_path(path.as_ref())?.into_config()
+    }
+
+    /// Issuer identifier used in metadata documents and downstream token
+    /// `iss`/`aud` claims: the override if set, otherwise `host` without
+    /// a trailing slash
+    pub fn issuer(&self) -> String {
+        match &self.issuer_override {
+            Some(issuer) => issuer.clone(),
+            None => self.host.as_str().trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Absolute URL for a path under `host`
+    pub fn endpoint_url(&self, path: &str) -> String {
+        format!("{}{}", self.host.as_str().trim_end_matches('/'), path)
+    }
+
+    /// Advertised client metadata document URL
+    pub fn client_metadata_url(&self) -> String {
+        self.endpoint_url(&self.endpoints.client_metadata)
+    }
+
+    /// Advertised JWKS URL
+    pub fn jwks_url(&self) -> String {
+        self.endpoint_url(&self.endpoints.jwks)
+    }
+
+    /// Advertised pushed authorization request endpoint URL
+    pub fn par_url(&self) -> String {
+        self.endpoint_url(&self.endpoints.par)
+    }
+
+    /// Advertised authorization endpoint URL
+    pub fn authorize_url(&self) -> String {
+        self.endpoint_url(&self.endpoints.authorize)
+    }
+
+    /// Upstream authorization callback URL (the registered redirect URI)
+    pub fn return_url(&self) -> String {
+        match &self.return_url_override {
+            Some(url) => url.as_str().trim_end_matches('/').to_string(),
+            None => self.endpoint_url(&self.endpoints.oauth_return),
         }
     }
 
+    /// Path the router serves the upstream authorization callback at:
+    /// the override's path when one is set, the endpoint path otherwise
+    pub fn return_path(&self) -> &str {
+        match &self.return_url_override {
+            Some(url) => url.path(),
+            None => &self.endpoints.oauth_return,
+        }
+    }
+
+    /// Advertised token endpoint URL
+    pub fn token_url(&self) -> String {
+        self.endpoint_url(&self.endpoints.token)
+    }
+
+    /// Advertised revocation endpoint URL
+    pub fn revoke_url(&self) -> String {
+        self.endpoint_url(&self.endpoints.revoke)
+    }
+
+    /// Advertised introspection endpoint URL
+    pub fn introspect_url(&self) -> String {
+        self.endpoint_url(&self.endpoints.introspect)
+    }
+
+    /// Advertised end-session endpoint URL
+    pub fn logout_url(&self) -> String {
+        self.endpoint_url(&self.endpoints.logout)
+    }
+
+    /// Look up the token policy registered for a client, if any
+    pub fn client_token_policy(&self, client_id: Option<&str>) -> Option<&ClientTokenPolicy> {
+        let client_id = client_id?;
+        self.client_token_policies
+            .iter()
+            .find(|p| p.client_id == client_id)
+    }
+
+    /// Downstream access token expiry for a client, honoring any
+    /// registered override
+    pub fn downstream_token_expiry_for(&self, client_id: Option<&str>) -> i64 {
+        self.client_token_policy(client_id)
+            .and_then(|p| p.access_token_expiry_seconds)
+            .unwrap_or(self.downstream_token_expiry_seconds)
+    }
+
+    /// Absolute refresh token lifetime for a client in seconds, honoring
+    /// any registered override; 0 means unlimited
+    pub fn refresh_token_absolute_lifetime_for(&self, client_id: Option<&str>) -> i64 {
+        self.client_token_policy(client_id)
+            .and_then(|p| p.refresh_token_absolute_lifetime_seconds)
+            .unwrap_or(self.refresh_token_absolute_lifetime_seconds)
+    }
+
+    /// Refresh token idle timeout for a client in seconds, honoring any
+    /// registered override; 0 means no idle timeout
+    pub fn refresh_token_idle_timeout_for(&self, client_id: Option<&str>) -> i64 {
+        self.client_token_policy(client_id)
+            .and_then(|p| p.refresh_token_idle_timeout_seconds)
+            .unwrap_or(self.refresh_token_idle_timeout_seconds)
+    }
+
     /// Set custom scopes
     pub fn with_scopes(mut self, scopes: Vec<Scope<'static>>) -> Self {
         self.scope = scopes;
@@ -98,7 +656,13 @@ impl ProxyConfig {
 
     /// Set HMAC secret for DPoP nonce generation
     pub fn with_dpop_nonce_secret(mut self, secret: Vec<u8>) -> Self {
-        self.dpop_nonce_hmac_secret = secret;
+        self.dpop_nonce_hmac_secret = crate::auth::SecretBytes::new(secret);
+        self
+    }
+
+    /// Set the previous DPoP nonce secret accepted during rotation
+    pub fn with_dpop_nonce_secret_previous(mut self, secret: Vec<u8>) -> Self {
+        self.dpop_nonce_hmac_secret_previous = Some(crate::auth::SecretBytes::new(secret));
         self
     }
 
@@ -108,6 +672,220 @@ impl ProxyConfig {
         self
     }
 
+    /// Set the absolute and idle refresh token lifetimes in seconds
+    /// (0 disables either bound)
+    pub fn with_refresh_token_lifetimes(
+        mut self,
+        absolute_seconds: i64,
+        idle_seconds: i64,
+    ) -> Self {
+        self.refresh_token_absolute_lifetime_seconds = absolute_seconds;
+        self.refresh_token_idle_timeout_seconds = idle_seconds;
+        self
+    }
+
+    /// Set the session idle timeout in seconds (0 disables)
+    pub fn with_session_idle_timeout(mut self, seconds: i64) -> Self {
+        self.session_idle_timeout_seconds = seconds;
+        self
+    }
+
+    /// Set the refresh-grant policy for a changed DPoP key
+    pub fn with_refresh_key_policy(mut self, policy: RefreshKeyPolicy) -> Self {
+        self.refresh_key_policy = policy;
+        self
+    }
+
+    /// Set how long unused upstream sessions are retained (0 disables)
+    pub fn with_session_retention(mut self, seconds: i64) -> Self {
+        self.session_retention_seconds = seconds;
+        self
+    }
+
+    /// Set how often the cleanup worker runs
+    pub fn with_cleanup_interval(mut self, seconds: i64) -> Self {
+        self.cleanup_interval_seconds = seconds;
+        self
+    }
+
+    /// Register a per-client token lifetime policy
+    pub fn with_client_token_policy(mut self, policy: ClientTokenPolicy) -> Self {
+        self.client_token_policies.push(policy);
+        self
+    }
+
+    /// Cap how many outstanding PARs a single client_id may hold
+    /// (0 disables the cap)
+    pub fn with_max_pending_par_per_client(mut self, max: u64) -> Self {
+        self.max_pending_par_per_client = max;
+        self
+    }
+
+    /// Set the clock-skew leeway for exp/iat validation
+    pub fn with_clock_skew_leeway_seconds(mut self, seconds: i64) -> Self {
+        self.clock_skew_leeway_seconds = seconds;
+        self
+    }
+
+    /// Set the clock-skew tolerance as a [`chrono::Duration`]
+    ///
+    /// Applied to every exp/iat/nbf check on downstream JWTs and to the
+    /// future-skew window of DPoP proof validation.
+    pub fn with_clock_skew(self, skew: chrono::Duration) -> Self {
+        self.with_clock_skew_leeway_seconds(skew.num_seconds())
+    }
+
+    /// Cap concurrent proxied XRPC requests per account DID
+    /// (0 disables the limit)
+    pub fn with_xrpc_max_concurrent_per_did(mut self, max: usize) -> Self {
+        self.xrpc_max_concurrent_per_did = max;
+        self
+    }
+
+    /// Cap concurrent proxied XRPC requests per downstream client key
+    /// (0 disables the limit)
+    pub fn with_xrpc_max_concurrent_per_client(mut self, max: usize) -> Self {
+        self.xrpc_max_concurrent_per_client = max;
+        self
+    }
+
+    /// Set how many requests past a concurrency limit may queue before
+    /// the proxy answers 429
+    pub fn with_xrpc_queue_depth(mut self, depth: usize) -> Self {
+        self.xrpc_queue_depth = depth;
+        self
+    }
+
+    /// Allow GET responses for these XRPC methods to be cached
+    pub fn with_xrpc_cacheable_nsids(mut self, nsids: Vec<String>) -> Self {
+        self.xrpc_cacheable_nsids = nsids;
+        self
+    }
+
+    /// Set the largest response body the response cache will hold
+    pub fn with_xrpc_cache_max_entry_bytes(mut self, bytes: usize) -> Self {
+        self.xrpc_cache_max_entry_bytes = bytes;
+        self
+    }
+
+    /// Set the largest request body accepted on OAuth endpoints
+    pub fn with_oauth_max_body_bytes(mut self, bytes: usize) -> Self {
+        self.oauth_max_body_bytes = bytes;
+        self
+    }
+
+    /// Set the largest request body accepted on proxied XRPC endpoints
+    pub fn with_xrpc_max_body_bytes(mut self, bytes: usize) -> Self {
+        self.xrpc_max_body_bytes = bytes;
+        self
+    }
+
+    /// Set the body allowance for `com.atproto.repo.uploadBlob`
+    pub fn with_xrpc_upload_max_body_bytes(mut self, bytes: usize) -> Self {
+        self.xrpc_upload_max_body_bytes = bytes;
+        self
+    }
+
+    /// Set the largest request URL the proxy will forward
+    pub fn with_max_request_url_bytes(mut self, bytes: usize) -> Self {
+        self.max_request_url_bytes = bytes;
+        self
+    }
+
+    /// Set additional authenticated path prefixes the router proxies
+    /// upstream alongside `/xrpc`
+    pub fn with_extra_proxy_paths(mut self, paths: Vec<String>) -> Self {
+        self.extra_proxy_paths = paths;
+        self
+    }
+
+    /// Set the origins allowed cross-origin access to the sensitive
+    /// endpoints; `"*"` allows any origin
+    pub fn with_cors_allowed_origins(mut self, origins: Vec<String>) -> Self {
+        self.cors_allowed_origins = origins;
+        self
+    }
+
+    /// Allow credentialed cross-origin requests to the sensitive endpoints
+    pub fn with_cors_allow_credentials(mut self, allow: bool) -> Self {
+        self.cors_allow_credentials = allow;
+        self
+    }
+
+    /// Set the bearer token that unlocks the operator admin endpoint
+    pub fn with_admin_token(mut self, token: impl Into<String>) -> Self {
+        self.admin_token = Some(crate::auth::SecretString::new(token));
+        self
+    }
+
+    /// Set how many bytes of entropy generated codes and tokens carry
+    pub fn with_token_entropy_bytes(mut self, bytes: usize) -> Self {
+        self.token_entropy_bytes = bytes;
+        self
+    }
+
+    /// Issue opaque store-backed access tokens instead of ES256 JWTs
+    pub fn with_opaque_access_tokens(mut self, enabled: bool) -> Self {
+        self.opaque_access_tokens = enabled;
+        self
+    }
+
+    /// Require pushed authorization requests at the authorize endpoint
+    pub fn with_require_par(mut self, required: bool) -> Self {
+        self.require_par = required;
+        self
+    }
+
+    /// Set the token endpoint auth methods clients may use
+    pub fn with_token_endpoint_auth_methods(mut self, methods: Vec<String>) -> Self {
+        self.token_endpoint_auth_methods = methods;
+        self
+    }
+
+    /// Set the DPoP proof algorithms accepted from downstream clients
+    /// Enable or disable the RFC 9207 `iss` authorization response
+    /// parameter
+    pub fn with_authorization_response_iss(mut self, enabled: bool) -> Self {
+        self.authorization_response_iss = enabled;
+        self
+    }
+
+    /// Set the session binding mode ("off", "warn", or "enforce")
+    pub fn with_session_binding_mode(mut self, mode: impl Into<String>) -> Self {
+        self.session_binding_mode = mode.into();
+        self
+    }
+
+    /// Set the IP prefix lengths the binding fingerprint truncates to
+    pub fn with_session_binding_prefix_bits(mut self, ipv4_bits: u8, ipv6_bits: u8) -> Self {
+        self.session_binding_ipv4_prefix_bits = ipv4_bits;
+        self.session_binding_ipv6_prefix_bits = ipv6_bits;
+        self
+    }
+
+    pub fn with_dpop_signing_algs(mut self, algs: Vec<String>) -> Self {
+        self.dpop_signing_algs = algs;
+        self
+    }
+
+    /// Issue HttpOnly session cookies alongside token responses
+    pub fn with_cookie_sessions(mut self, enabled: bool) -> Self {
+        self.cookie_sessions = enabled;
+        self
+    }
+
+    /// Set the session cookie name
+    pub fn with_cookie_name(mut self, name: impl Into<String>) -> Self {
+        self.cookie_name = name.into();
+        self
+    }
+
+    /// Set the session cookie lifetime in seconds
+    pub fn with_cookie_max_age_seconds(mut self, seconds: i64) -> Self {
+        self.cookie_max_age_seconds = seconds;
+        self
+    }
+
     /// Set client name
     pub fn with_client_name(mut self, name: impl Into<String>) -> Self {
         self.client_metadata.client_name = Some(name.into().into());
@@ -138,6 +916,133 @@ impl ProxyConfig {
         self
     }
 
+    /// Override the issuer identifier advertised in metadata and stamped
+    /// into downstream token claims
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuer_override = Some(issuer.into());
+        self
+    }
+
+    /// Override the paths the router serves OAuth endpoints at.
+    ///
+    /// Recomputes the client metadata URLs derived from these paths
+    /// (client_id, redirect URI, JWKS URI), so apply this before
+    /// overriding any of those individually.
+    pub fn with_endpoint_paths(mut self, endpoints: EndpointPaths) -> Self {
+        self.endpoints = endpoints;
+        self.client_metadata.redirect_uris =
+            vec![self.return_url().parse().expect("valid url")];
+        if self.client_metadata.jwks_uri.is_some() {
+            self.client_metadata.client_id =
+                self.client_metadata_url().parse().expect("valid url");
+            self.client_metadata.jwks_uri =
+                Some(self.jwks_url().parse().expect("valid url"));
+        }
+        self
+    }
+
+    /// Override the upstream authorization callback URL.
+    ///
+    /// Recomputes the registered redirect URI so the client metadata,
+    /// router, and callback validation all agree on the new location.
+    pub fn with_return_url(mut self, url: Url) -> Self {
+        self.return_url_override = Some(url);
+        self.client_metadata.redirect_uris =
+            vec![self.return_url().parse().expect("valid url")];
+        self
+    }
+
+    /// Register a confidential client for the `client_credentials` grant
+    pub fn with_service_client(
+        mut self,
+        client_id: impl Into<String>,
+        client_secret: impl Into<String>,
+        did: impl Into<String>,
+    ) -> Self {
+        self.service_clients.push(ServiceClient {
+            client_id: client_id.into(),
+            client_secret: crate::auth::SecretString::new(client_secret),
+            did: did.into(),
+        });
+        self
+    }
+
+    /// Restrict which audiences downstream clients may request service auth
+    /// tokens for
+    pub fn with_service_auth_allowed_auds(mut self, auds: Vec<String>) -> Self {
+        self.service_auth_allowed_auds = auds;
+        self
+    }
+
+    /// Restrict which lexicon methods downstream clients may bind service
+    /// auth tokens to
+    pub fn with_service_auth_allowed_lxms(mut self, lxms: Vec<String>) -> Self {
+        self.service_auth_allowed_lxms = lxms;
+        self
+    }
+
+    /// Allow direct routing to a service DID named in the `atproto-proxy`
+    /// header
+    pub fn with_atproto_proxy_allowed_dids(mut self, dids: Vec<String>) -> Self {
+        self.atproto_proxy_allowed_dids = dids;
+        self
+    }
+
+    /// Restrict forwarded request headers to an allowlist
+    pub fn with_request_header_allowlist(mut self, headers: Vec<String>) -> Self {
+        self.request_header_allowlist = headers;
+        self
+    }
+
+    /// Add request headers to the forwarding denylist
+    pub fn with_request_header_denylist(mut self, headers: Vec<String>) -> Self {
+        self.request_header_denylist = headers;
+        self
+    }
+
+    /// Restrict returned response headers to an allowlist
+    pub fn with_response_header_allowlist(mut self, headers: Vec<String>) -> Self {
+        self.response_header_allowlist = headers;
+        self
+    }
+
+    /// Add response headers to the return denylist
+    pub fn with_response_header_denylist(mut self, headers: Vec<String>) -> Self {
+        self.response_header_denylist = headers;
+        self
+    }
+
+    /// Set TTLs for the identity/PDS resolution cache
+    pub fn with_resolution_cache_ttls(
+        mut self,
+        ttl_seconds: u64,
+        negative_ttl_seconds: u64,
+    ) -> Self {
+        self.resolution_cache_ttl_seconds = ttl_seconds;
+        self.resolution_negative_cache_ttl_seconds = negative_ttl_seconds;
+        self
+    }
+
+    /// Set connect and overall timeouts for upstream requests
+    pub fn with_upstream_timeouts(mut self, connect_seconds: u64, read_seconds: u64) -> Self {
+        self.upstream_connect_timeout_seconds = connect_seconds;
+        self.upstream_read_timeout_seconds = read_seconds;
+        self
+    }
+
+    /// Set how many extra attempts idempotent GET requests get
+    pub fn with_upstream_get_retries(mut self, retries: u32) -> Self {
+        self.upstream_get_retries = retries;
+        self
+    }
+
+    /// Tune the per-host circuit breaker
+    pub fn with_circuit_breaker(mut self, failure_threshold: u32, reset_seconds: u64) -> Self {
+        self.circuit_breaker_failure_threshold = failure_threshold;
+        self.circuit_breaker_reset_seconds = reset_seconds;
+        self
+    }
+
     /// Set policy URI
     pub fn with_policy_uri(mut self, uri: Url) -> Self {
         self.client_metadata.privacy_policy_uri = Some(uri);
@@ -173,3 +1078,565 @@ impl ProxyConfig {
         })
     }
 }
+
+/// One `[[service_clients]]` entry in a config file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServiceClientEntry {
+    pub client_id: String,
+    pub client_secret: String,
+    pub did: String,
+}
+
+/// Plain-data mirror of [`ProxyConfig`] read by [`ProxyConfig::from_file`]
+/// and [`ProxyConfig::from_env`].
+///
+/// Every field except `host` is optional; absent fields keep the defaults
+/// from [`ProxyConfig::new`]. URLs, scopes, and secrets stay strings here
+/// and are validated by [`ProxyConfigFile::into_config`], which names the
+/// offending field in its errors. Unknown keys are rejected at parse time.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProxyConfigFile {
+    pub host: Option<String>,
+    pub issuer: Option<String>,
+    pub return_url: Option<String>,
+    pub scopes: Option<Vec<String>>,
+    pub client_name: Option<String>,
+    pub client_uri: Option<String>,
+    pub tos_uri: Option<String>,
+    pub logo_uri: Option<String>,
+    pub policy_uri: Option<String>,
+    pub redirect_uris: Option<Vec<String>>,
+    pub default_pds: Option<String>,
+    pub dpop_nonce_hmac_secret: Option<String>,
+    pub dpop_nonce_hmac_secret_previous: Option<String>,
+    pub downstream_token_expiry_seconds: Option<i64>,
+    pub refresh_token_absolute_lifetime_seconds: Option<i64>,
+    pub refresh_token_idle_timeout_seconds: Option<i64>,
+    pub session_idle_timeout_seconds: Option<i64>,
+    pub refresh_key_policy: Option<RefreshKeyPolicy>,
+    pub session_retention_seconds: Option<i64>,
+    pub cleanup_interval_seconds: Option<i64>,
+    pub client_token_policies: Option<Vec<ClientTokenPolicy>>,
+    pub max_pending_par_per_client: Option<u64>,
+    pub clock_skew_leeway_seconds: Option<i64>,
+    pub xrpc_max_concurrent_per_did: Option<usize>,
+    pub xrpc_max_concurrent_per_client: Option<usize>,
+    pub xrpc_queue_depth: Option<usize>,
+    pub xrpc_cacheable_nsids: Option<Vec<String>>,
+    pub xrpc_cache_max_entry_bytes: Option<usize>,
+    pub oauth_max_body_bytes: Option<usize>,
+    pub xrpc_max_body_bytes: Option<usize>,
+    pub xrpc_upload_max_body_bytes: Option<usize>,
+    pub max_request_url_bytes: Option<usize>,
+    pub extra_proxy_paths: Option<Vec<String>>,
+    pub cors_allowed_origins: Option<Vec<String>>,
+    pub cors_allow_credentials: Option<bool>,
+    pub admin_token: Option<String>,
+    pub token_entropy_bytes: Option<usize>,
+    pub opaque_access_tokens: Option<bool>,
+    pub require_par: Option<bool>,
+    pub authorization_response_iss: Option<bool>,
+    pub session_binding_mode: Option<String>,
+    pub session_binding_ipv4_prefix_bits: Option<u8>,
+    pub session_binding_ipv6_prefix_bits: Option<u8>,
+    pub token_endpoint_auth_methods: Option<Vec<String>>,
+    pub dpop_signing_algs: Option<Vec<String>>,
+    pub cookie_sessions: Option<bool>,
+    pub cookie_name: Option<String>,
+    pub cookie_max_age_seconds: Option<i64>,
+    pub service_clients: Option<Vec<ServiceClientEntry>>,
+    pub service_auth_allowed_auds: Option<Vec<String>>,
+    pub service_auth_allowed_lxms: Option<Vec<String>>,
+    pub atproto_proxy_allowed_dids: Option<Vec<String>>,
+    pub resolution_cache_ttl_seconds: Option<u64>,
+    pub resolution_negative_cache_ttl_seconds: Option<u64>,
+    pub upstream_connect_timeout_seconds: Option<u64>,
+    pub upstream_read_timeout_seconds: Option<u64>,
+    pub upstream_get_retries: Option<u32>,
+    pub circuit_breaker_failure_threshold: Option<u32>,
+    pub circuit_breaker_reset_seconds: Option<u64>,
+    pub request_header_allowlist: Option<Vec<String>>,
+    pub request_header_denylist: Option<Vec<String>>,
+    pub response_header_allowlist: Option<Vec<String>>,
+    pub response_header_denylist: Option<Vec<String>>,
+}
+
+fn parse_url(field: &str, value: &str) -> Result<Url> {
+    value
+        .parse()
+        .map_err(|e| Error::ConfigError(format!("`{}`: invalid URL {:?}: {}", field, value, e)))
+}
+
+impl ProxyConfigFile {
+    /// Parse a config file, dispatching on its extension
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| Error::ConfigError(format!("failed to read {}: {}", path.display(), e)))?;
+
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str(&contents)
+                .map_err(|e| Error::ConfigError(format!("{}: {}", path.display(), e))),
+            Some("yaml") | Some("yml") => serde_yaml::from_str(&contents)
+                .map_err(|e| Error::ConfigError(format!("{}: {}", path.display(), e))),
+            _ => Err(Error::ConfigError(format!(
+                "{}: unsupported extension (expected .toml, .yaml, or .yml)",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Read the same fields from `OATPROXY_*` environment variables
+    pub fn from_env() -> Result<Self> {
+        fn var(name: &str) -> Option<String> {
+            std::env::var(name).ok().filter(|v| !v.is_empty())
+        }
+
+        fn list(name: &str) -> Option<Vec<String>> {
+            var(name).map(|v| {
+                v.split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect()
+            })
+        }
+
+        fn parse_var<T: std::str::FromStr>(name: &str) -> Result<Option<T>>
+        where
+            T::Err: std::fmt::Display,
+        {
+            match var(name) {
+                Some(v) => v.parse().map(Some).map_err(|e| {
+                    Error::ConfigError(format!("`{}`: invalid value {:?}: {}", name, v, e))
+                }),
+                None => Ok(None),
+            }
+        }
+
+        if var("OATPROXY_HOST").is_none() {
+            return Err(Error::ConfigError("`OATPROXY_HOST` is required".into()));
+        }
+
+        let service_clients = match var("OATPROXY_SERVICE_CLIENTS") {
+            Some(v) => {
+                let mut clients = Vec::new();
+                for entry in v.split(',').filter(|s| !s.trim().is_empty()) {
+                    let parts: Vec<&str> = entry.trim().splitn(3, ':').collect();
+                    if parts.len() != 3 {
+                        return Err(Error::ConfigError(
+                            "`OATPROXY_SERVICE_CLIENTS`: entries must be \
+                             client_id:client_secret:did"
+                                .into(),
+                        ));
+                    }
+                    clients.push(ServiceClientEntry {
+                        client_id: parts[0].to_string(),
+                        client_secret: parts[1].to_string(),
+                        did: parts[2].to_string(),
+                    });
+                }
+                Some(clients)
+            }
+            None => None,
+        };
+
+        Ok(Self {
+            host: var("OATPROXY_HOST"),
+            issuer: var("OATPROXY_ISSUER"),
+            return_url: var("OATPROXY_RETURN_URL"),
+            scopes: list("OATPROXY_SCOPES"),
+            client_name: var("OATPROXY_CLIENT_NAME"),
+            client_uri: var("OATPROXY_CLIENT_URI"),
+            tos_uri: var("OATPROXY_TOS_URI"),
+            logo_uri: var("OATPROXY_LOGO_URI"),
+            policy_uri: var("OATPROXY_POLICY_URI"),
+            redirect_uris: list("OATPROXY_REDIRECT_URIS"),
+            default_pds: var("OATPROXY_DEFAULT_PDS"),
+            dpop_nonce_hmac_secret: var("OATPROXY_DPOP_NONCE_HMAC_SECRET"),
+            dpop_nonce_hmac_secret_previous: var("OATPROXY_DPOP_NONCE_HMAC_SECRET_PREVIOUS"),
+            downstream_token_expiry_seconds: parse_var(
+                "OATPROXY_DOWNSTREAM_TOKEN_EXPIRY_SECONDS",
+            )?,
+            refresh_token_absolute_lifetime_seconds: parse_var(
+                "OATPROXY_REFRESH_TOKEN_ABSOLUTE_LIFETIME_SECONDS",
+            )?,
+            refresh_token_idle_timeout_seconds: parse_var(
+                "OATPROXY_REFRESH_TOKEN_IDLE_TIMEOUT_SECONDS",
+            )?,
+            session_idle_timeout_seconds: parse_var("OATPROXY_SESSION_IDLE_TIMEOUT_SECONDS")?,
+            refresh_key_policy: parse_var("OATPROXY_REFRESH_KEY_POLICY")?,
+            session_retention_seconds: parse_var("OATPROXY_SESSION_RETENTION_SECONDS")?,
+            cleanup_interval_seconds: parse_var("OATPROXY_CLEANUP_INTERVAL_SECONDS")?,
+            // Per-client policies are structured; configure them via file
+            client_token_policies: None,
+            max_pending_par_per_client: parse_var("OATPROXY_MAX_PENDING_PAR_PER_CLIENT")?,
+            clock_skew_leeway_seconds: parse_var("OATPROXY_CLOCK_SKEW_LEEWAY_SECONDS")?,
+            xrpc_max_concurrent_per_did: parse_var("OATPROXY_XRPC_MAX_CONCURRENT_PER_DID")?,
+            xrpc_max_concurrent_per_client: parse_var("OATPROXY_XRPC_MAX_CONCURRENT_PER_CLIENT")?,
+            xrpc_queue_depth: parse_var("OATPROXY_XRPC_QUEUE_DEPTH")?,
+            xrpc_cacheable_nsids: list("OATPROXY_XRPC_CACHEABLE_NSIDS"),
+            xrpc_cache_max_entry_bytes: parse_var("OATPROXY_XRPC_CACHE_MAX_ENTRY_BYTES")?,
+            oauth_max_body_bytes: parse_var("OATPROXY_OAUTH_MAX_BODY_BYTES")?,
+            xrpc_max_body_bytes: parse_var("OATPROXY_XRPC_MAX_BODY_BYTES")?,
+            xrpc_upload_max_body_bytes: parse_var("OATPROXY_XRPC_UPLOAD_MAX_BODY_BYTES")?,
+            max_request_url_bytes: parse_var("OATPROXY_MAX_REQUEST_URL_BYTES")?,
+            extra_proxy_paths: list("OATPROXY_EXTRA_PROXY_PATHS"),
+            cors_allowed_origins: list("OATPROXY_CORS_ALLOWED_ORIGINS"),
+            cors_allow_credentials: parse_var("OATPROXY_CORS_ALLOW_CREDENTIALS")?,
+            admin_token: var("OATPROXY_ADMIN_TOKEN"),
+            token_entropy_bytes: parse_var("OATPROXY_TOKEN_ENTROPY_BYTES")?,
+            opaque_access_tokens: parse_var("OATPROXY_OPAQUE_ACCESS_TOKENS")?,
+            require_par: parse_var("OATPROXY_REQUIRE_PAR")?,
+            authorization_response_iss: parse_var("OATPROXY_AUTHORIZATION_RESPONSE_ISS")?,
+            session_binding_mode: var("OATPROXY_SESSION_BINDING_MODE"),
+            session_binding_ipv4_prefix_bits: parse_var(
+                "OATPROXY_SESSION_BINDING_IPV4_PREFIX_BITS",
+            )?,
+            session_binding_ipv6_prefix_bits: parse_var(
+                "OATPROXY_SESSION_BINDING_IPV6_PREFIX_BITS",
+            )?,
+            token_endpoint_auth_methods: list("OATPROXY_TOKEN_ENDPOINT_AUTH_METHODS"),
+            dpop_signing_algs: list("OATPROXY_DPOP_SIGNING_ALGS"),
+            cookie_sessions: parse_var("OATPROXY_COOKIE_SESSIONS")?,
+            cookie_name: parse_var("OATPROXY_COOKIE_NAME")?,
+            cookie_max_age_seconds: parse_var("OATPROXY_COOKIE_MAX_AGE_SECONDS")?,
+            service_clients,
+            service_auth_allowed_auds: list("OATPROXY_SERVICE_AUTH_AUDS"),
+            service_auth_allowed_lxms: list("OATPROXY_SERVICE_AUTH_LXMS"),
+            atproto_proxy_allowed_dids: list("OATPROXY_ATPROTO_PROXY_DIDS"),
+            resolution_cache_ttl_seconds: parse_var("OATPROXY_RESOLUTION_CACHE_TTL_SECONDS")?,
+            resolution_negative_cache_ttl_seconds: parse_var(
+                "OATPROXY_RESOLUTION_NEGATIVE_CACHE_TTL_SECONDS",
+            )?,
+            upstream_connect_timeout_seconds: parse_var(
+                "OATPROXY_UPSTREAM_CONNECT_TIMEOUT_SECONDS",
+            )?,
+            upstream_read_timeout_seconds: parse_var("OATPROXY_UPSTREAM_READ_TIMEOUT_SECONDS")?,
+            upstream_get_retries: parse_var("OATPROXY_UPSTREAM_GET_RETRIES")?,
+            circuit_breaker_failure_threshold: parse_var(
+                "OATPROXY_CIRCUIT_BREAKER_FAILURE_THRESHOLD",
+            )?,
+            circuit_breaker_reset_seconds: parse_var("OATPROXY_CIRCUIT_BREAKER_RESET_SECONDS")?,
+            request_header_allowlist: list("OATPROXY_REQUEST_HEADER_ALLOWLIST"),
+            request_header_denylist: list("OATPROXY_REQUEST_HEADER_DENYLIST"),
+            response_header_allowlist: list("OATPROXY_RESPONSE_HEADER_ALLOWLIST"),
+            response_header_denylist: list("OATPROXY_RESPONSE_HEADER_DENYLIST"),
+        })
+    }
+
+    /// Validate the raw values and build a [`ProxyConfig`]
+    pub fn into_config(self) -> Result<ProxyConfig> {
+        let host = self
+            .host
+            .ok_or_else(|| Error::ConfigError("`host` is required".into()))?;
+        let mut config = ProxyConfig::new(parse_url("host", &host)?);
+
+        if let Some(url) = self.return_url {
+            config = config.with_return_url(parse_url("return_url", &url)?);
+        }
+        if let Some(issuer) = self.issuer {
+            config = config.with_issuer(issuer);
+        }
+
+        if let Some(scopes) = self.scopes {
+            let mut parsed = Vec::with_capacity(scopes.len());
+            for scope in &scopes {
+                let scope = Scope::parse(scope).map_err(|e| {
+                    Error::ConfigError(format!("`scopes`: invalid scope {:?}: {}", scope, e))
+                })?;
+                parsed.push(scope.into_static());
+            }
+            config = config.with_scopes(parsed);
+        }
+
+        if let Some(name) = self.client_name {
+            config = config.with_client_name(name);
+        }
+        if let Some(uri) = self.client_uri {
+            config = config.with_client_uri(parse_url("client_uri", &uri)?);
+        }
+        if let Some(uri) = self.tos_uri {
+            config = config.with_tos_uri(parse_url("tos_uri", &uri)?);
+        }
+        if let Some(uri) = self.logo_uri {
+            config = config.with_logo_uri(parse_url("logo_uri", &uri)?);
+        }
+        if let Some(uri) = self.policy_uri {
+            config = config.with_policy_uri(parse_url("policy_uri", &uri)?);
+        }
+        if let Some(uris) = self.redirect_uris {
+            let mut parsed = Vec::with_capacity(uris.len());
+            for uri in &uris {
+                parsed.push(parse_url("redirect_uris", uri)?);
+            }
+            config = config.with_redirect_uris(parsed);
+        }
+        if let Some(pds) = self.default_pds {
+            config = config.with_default_pds(parse_url("default_pds", &pds)?);
+        }
+
+        if let Some(secret) = self.dpop_nonce_hmac_secret {
+            if secret.is_empty() {
+                return Err(Error::ConfigError(
+                    "`dpop_nonce_hmac_secret` must not be empty".into(),
+                ));
+            }
+            config = config.with_dpop_nonce_secret(secret.into_bytes());
+        }
+        if let Some(secret) = self.dpop_nonce_hmac_secret_previous {
+            if secret.is_empty() {
+                return Err(Error::ConfigError(
+                    "`dpop_nonce_hmac_secret_previous` must not be empty".into(),
+                ));
+            }
+            config = config.with_dpop_nonce_secret_previous(secret.into_bytes());
+        }
+        if let Some(seconds) = self.downstream_token_expiry_seconds {
+            if seconds <= 0 {
+                return Err(Error::ConfigError(
+                    "`downstream_token_expiry_seconds` must be positive".into(),
+                ));
+            }
+            config = config.with_downstream_token_expiry(seconds);
+        }
+        if self.refresh_token_absolute_lifetime_seconds.is_some()
+            || self.refresh_token_idle_timeout_seconds.is_some()
+        {
+            let absolute = self
+                .refresh_token_absolute_lifetime_seconds
+                .unwrap_or(config.refresh_token_absolute_lifetime_seconds);
+            let idle = self
+                .refresh_token_idle_timeout_seconds
+                .unwrap_or(config.refresh_token_idle_timeout_seconds);
+            if absolute < 0 || idle < 0 {
+                return Err(Error::ConfigError(
+                    "refresh token lifetimes must not be negative".into(),
+                ));
+            }
+            config = config.with_refresh_token_lifetimes(absolute, idle);
+        }
+        if let Some(seconds) = self.session_idle_timeout_seconds {
+            if seconds < 0 {
+                return Err(Error::ConfigError(
+                    "`session_idle_timeout_seconds` must not be negative".into(),
+                ));
+            }
+            config = config.with_session_idle_timeout(seconds);
+        }
+        if let Some(policy) = self.refresh_key_policy {
+            config = config.with_refresh_key_policy(policy);
+        }
+        if let Some(seconds) = self.session_retention_seconds {
+            if seconds < 0 {
+                return Err(Error::ConfigError(
+                    "`session_retention_seconds` must not be negative".into(),
+                ));
+            }
+            config = config.with_session_retention(seconds);
+        }
+        if let Some(seconds) = self.cleanup_interval_seconds {
+            if seconds <= 0 {
+                return Err(Error::ConfigError(
+                    "`cleanup_interval_seconds` must be positive".into(),
+                ));
+            }
+            config = config.with_cleanup_interval(seconds);
+        }
+        if let Some(policies) = self.client_token_policies {
+            for policy in policies {
+                if policy.client_id.is_empty() {
+                    return Err(Error::ConfigError(
+                        "`client_token_policies`: `client_id` must not be empty".into(),
+                    ));
+                }
+                config = config.with_client_token_policy(policy);
+            }
+        }
+        if let Some(max) = self.max_pending_par_per_client {
+            config = config.with_max_pending_par_per_client(max);
+        }
+        if let Some(seconds) = self.clock_skew_leeway_seconds {
+            config = config.with_clock_skew_leeway_seconds(seconds);
+        }
+        if let Some(max) = self.xrpc_max_concurrent_per_did {
+            config = config.with_xrpc_max_concurrent_per_did(max);
+        }
+        if let Some(max) = self.xrpc_max_concurrent_per_client {
+            config = config.with_xrpc_max_concurrent_per_client(max);
+        }
+        if let Some(depth) = self.xrpc_queue_depth {
+            config = config.with_xrpc_queue_depth(depth);
+        }
+        if let Some(nsids) = self.xrpc_cacheable_nsids {
+            config = config.with_xrpc_cacheable_nsids(nsids);
+        }
+        if let Some(bytes) = self.xrpc_cache_max_entry_bytes {
+            config = config.with_xrpc_cache_max_entry_bytes(bytes);
+        }
+        if let Some(bytes) = self.oauth_max_body_bytes {
+            config = config.with_oauth_max_body_bytes(bytes);
+        }
+        if let Some(bytes) = self.xrpc_max_body_bytes {
+            config = config.with_xrpc_max_body_bytes(bytes);
+        }
+        if let Some(bytes) = self.xrpc_upload_max_body_bytes {
+            config = config.with_xrpc_upload_max_body_bytes(bytes);
+        }
+        if let Some(bytes) = self.max_request_url_bytes {
+            config = config.with_max_request_url_bytes(bytes);
+        }
+        if let Some(paths) = self.extra_proxy_paths {
+            for path in &paths {
+                if !path.starts_with('/') || path.trim_end_matches('/').is_empty() {
+                    return Err(Error::ConfigError(format!(
+                        "`extra_proxy_paths`: {:?} must be a non-root path starting with `/`",
+                        path
+                    )));
+                }
+                if path.trim_end_matches('/') == "/xrpc" {
+                    return Err(Error::ConfigError(
+                        "`extra_proxy_paths`: `/xrpc` is always proxied".into(),
+                    ));
+                }
+            }
+            config = config.with_extra_proxy_paths(paths);
+        }
+        if let Some(origins) = self.cors_allowed_origins {
+            for origin in &origins {
+                if origin != "*" {
+                    parse_url("cors_allowed_origins", origin)?;
+                }
+            }
+            config = config.with_cors_allowed_origins(origins);
+        }
+        if let Some(allow) = self.cors_allow_credentials {
+            config = config.with_cors_allow_credentials(allow);
+        }
+        if let Some(token) = self.admin_token {
+            config = config.with_admin_token(token);
+        }
+        if config.cors_allow_credentials && config.cors_allowed_origins.iter().any(|o| o == "*") {
+            return Err(Error::ConfigError(
+                "`cors_allow_credentials` cannot be combined with a \"*\" entry in \
+                 `cors_allowed_origins`"
+                    .into(),
+            ));
+        }
+        if let Some(bytes) = self.token_entropy_bytes {
+            config = config.with_token_entropy_bytes(bytes);
+        }
+        if let Some(enabled) = self.opaque_access_tokens {
+            config = config.with_opaque_access_tokens(enabled);
+        }
+        if let Some(required) = self.require_par {
+            config = config.with_require_par(required);
+        }
+        if let Some(methods) = self.token_endpoint_auth_methods {
+            config = config.with_token_endpoint_auth_methods(methods);
+        }
+        if let Some(algs) = self.dpop_signing_algs {
+            config = config.with_dpop_signing_algs(algs);
+        }
+        if let Some(enabled) = self.authorization_response_iss {
+            config = config.with_authorization_response_iss(enabled);
+        }
+        if let Some(mode) = self.session_binding_mode {
+            if !matches!(mode.as_str(), "off" | "warn" | "enforce") {
+                return Err(Error::ConfigError(format!(
+                    "`session_binding_mode` must be \"off\", \"warn\", or \"enforce\", got {:?}",
+                    mode
+                )));
+            }
+            config = config.with_session_binding_mode(mode);
+        }
+        if self.session_binding_ipv4_prefix_bits.is_some()
+            || self.session_binding_ipv6_prefix_bits.is_some()
+        {
+            let ipv4 = self
+                .session_binding_ipv4_prefix_bits
+                .unwrap_or(config.session_binding_ipv4_prefix_bits);
+            let ipv6 = self
+                .session_binding_ipv6_prefix_bits
+                .unwrap_or(config.session_binding_ipv6_prefix_bits);
+            if ipv4 > 32 || ipv6 > 128 {
+                return Err(Error::ConfigError(
+                    "session binding prefix bits must fit the address width".into(),
+                ));
+            }
+            config = config.with_session_binding_prefix_bits(ipv4, ipv6);
+        }
+        if let Some(enabled) = self.cookie_sessions {
+            config = config.with_cookie_sessions(enabled);
+        }
+        if let Some(name) = self.cookie_name {
+            config = config.with_cookie_name(name);
+        }
+        if let Some(seconds) = self.cookie_max_age_seconds {
+            config = config.with_cookie_max_age_seconds(seconds);
+        }
+
+        if let Some(clients) = self.service_clients {
+            for client in clients {
+                if client.client_id.is_empty()
+                    || client.client_secret.is_empty()
+                    || client.did.is_empty()
+                {
+                    return Err(Error::ConfigError(
+                        "`service_clients`: client_id, client_secret, and did must all be \
+                         non-empty"
+                            .into(),
+                    ));
+                }
+                config =
+                    config.with_service_client(client.client_id, client.client_secret, client.did);
+            }
+        }
+        if let Some(auds) = self.service_auth_allowed_auds {
+            config = config.with_service_auth_allowed_auds(auds);
+        }
+        if let Some(lxms) = self.service_auth_allowed_lxms {
+            config = config.with_service_auth_allowed_lxms(lxms);
+        }
+        if let Some(dids) = self.atproto_proxy_allowed_dids {
+            config = config.with_atproto_proxy_allowed_dids(dids);
+        }
+
+        if let Some(ttl) = self.resolution_cache_ttl_seconds {
+            config.resolution_cache_ttl_seconds = ttl;
+        }
+        if let Some(ttl) = self.resolution_negative_cache_ttl_seconds {
+            config.resolution_negative_cache_ttl_seconds = ttl;
+        }
+        if let Some(seconds) = self.upstream_connect_timeout_seconds {
+            config.upstream_connect_timeout_seconds = seconds;
+        }
+        if let Some(seconds) = self.upstream_read_timeout_seconds {
+            config.upstream_read_timeout_seconds = seconds;
+        }
+        if let Some(retries) = self.upstream_get_retries {
+            config = config.with_upstream_get_retries(retries);
+        }
+        if let Some(threshold) = self.circuit_breaker_failure_threshold {
+            config.circuit_breaker_failure_threshold = threshold;
+        }
+        if let Some(seconds) = self.circuit_breaker_reset_seconds {
+            config.circuit_breaker_reset_seconds = seconds;
+        }
+
+        if let Some(headers) = self.request_header_allowlist {
+            config = config.with_request_header_allowlist(headers);
+        }
+        if let Some(headers) = self.request_header_denylist {
+            config = config.with_request_header_denylist(headers);
+        }
+        if let Some(headers) = self.response_header_allowlist {
+            config = config.with_response_header_allowlist(headers);
+        }
+        if let Some(headers) = self.response_header_denylist {
+            config = config.with_response_header_denylist(headers);
+        }
+
+        Ok(config)
+    }
+}
@@ -0,0 +1,105 @@
+//! Pre-flight conformance checks for a proxy deployment.
+//!
+//! A proxy that starts cleanly can still be mis-deployed: the public
+//! host unreachable, a reverse proxy serving stale metadata, a JWKS
+//! document from a previous signing key, the nonce secret left on the
+//! insecure default.
+//! [`validate_deployment`](crate::server::OAuthProxyServer::validate_deployment)
+//! probes for these from the outside — fetching the published documents
+//! the way a client would — and returns a [`DeploymentReport`] operators
+//! can log at startup or print from a CLI before taking traffic.
+
+use serde::Serialize;
+use std::fmt;
+
+/// Outcome of one deployment check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// The deployment looks right
+    Pass,
+    /// Works, but something deserves operator attention
+    Warn,
+    /// Broken or insecure; the deployment should not take traffic
+    Fail,
+}
+
+/// One probe and what it found
+#[derive(Debug, Clone, Serialize)]
+pub struct DeploymentCheck {
+    /// Short stable identifier for the check (`host_scheme`,
+    /// `metadata_document`, ...)
+    pub name: &'static str,
+    /// How the check came out
+    pub status: CheckStatus,
+    /// What was observed, in operator-readable terms
+    pub detail: String,
+}
+
+/// The full set of findings from one validation run.
+///
+/// Serializable for structured logging; `Display` renders one line per
+/// check for CLI output.
+#[derive(Debug, Clone, Serialize)]
+pub struct DeploymentReport {
+    /// Every check that ran, in execution order
+    pub checks: Vec<DeploymentCheck>,
+}
+
+impl DeploymentReport {
+    /// An empty report to accumulate checks into
+    pub fn new() -> Self {
+        Self { checks: Vec::new() }
+    }
+
+    /// Whether no check failed (warnings don't count against passing)
+    pub fn passed(&self) -> bool {
+        !self
+            .checks
+            .iter()
+            .any(|check| check.status == CheckStatus::Fail)
+    }
+
+    /// Record a passing check
+    pub fn pass(&mut self, name: &'static str, detail: impl Into<String>) {
+        self.push(name, CheckStatus::Pass, detail);
+    }
+
+    /// Record a warning
+    pub fn warn(&mut self, name: &'static str, detail: impl Into<String>) {
+        self.push(name, CheckStatus::Warn, detail);
+    }
+
+    /// Record a failure
+    pub fn fail(&mut self, name: &'static str, detail: impl Into<String>) {
+        self.push(name, CheckStatus::Fail, detail);
+    }
+
+    fn push(&mut self, name: &'static str, status: CheckStatus, detail: impl Into<String>) {
+        self.checks.push(DeploymentCheck {
+            name,
+            status,
+            detail: detail.into(),
+        });
+    }
+}
+
+impl Default for DeploymentReport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for DeploymentReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            let tag = match check.status {
+                CheckStatus::Pass => "PASS",
+                CheckStatus::Warn => "WARN",
+                CheckStatus::Fail => "FAIL",
+            };
+            writeln!(f, "[{}] {}: {}", tag, check.name, check.detail)?;
+        }
+        Ok(())
+    }
+}
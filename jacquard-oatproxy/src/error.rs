@@ -16,6 +16,7 @@ pub enum Error {
     UnauthorizedClient,
     UnsupportedGrantType,
     Unauthorized,
+    InsufficientScope(String), // The scope the call required but the grant lacks
     DpopProofRequired,
     DpopNonceRequired(String), // Contains the nonce to send back
 
@@ -35,6 +36,14 @@ pub enum Error {
 
     // Network errors
     NetworkError(String),
+    UpstreamUnavailable(String), // Circuit breaker is open for this host
+    TooManyRequests(String),     // Concurrency/queue limit exceeded
+    PayloadTooLarge(String),     // Request body over the configured limit
+    UriTooLong,                  // Request URL over the configured limit
+    Maintenance(u64),            // Maintenance mode; carries Retry-After seconds
+
+    // Configuration errors (names the offending field or file)
+    ConfigError(String),
 
     // Generic errors
     Internal(String),
@@ -52,6 +61,7 @@ impl fmt::Display for Error {
             Error::UnauthorizedClient => write!(f, "unauthorized_client"),
             Error::UnsupportedGrantType => write!(f, "unsupported_grant_type"),
             Error::Unauthorized => write!(f, "unauthorized"),
+            Error::InsufficientScope(scope) => write!(f, "insufficient_scope: {}", scope),
             Error::DpopProofRequired => write!(f, "DPoP proof required"),
             Error::DpopNonceRequired(_) => write!(f, "use_dpop_nonce"),
             Error::DpopMethodMismatch => write!(f, "DPoP htm mismatch"),
@@ -63,6 +73,12 @@ impl fmt::Display for Error {
             Error::KeyGenerationFailed => write!(f, "key generation failed"),
             Error::StorageError(msg) => write!(f, "storage error: {}", msg),
             Error::NetworkError(msg) => write!(f, "network error: {}", msg),
+            Error::UpstreamUnavailable(host) => write!(f, "upstream unavailable: {}", host),
+            Error::TooManyRequests(msg) => write!(f, "too many requests: {}", msg),
+            Error::PayloadTooLarge(msg) => write!(f, "payload too large: {}", msg),
+            Error::UriTooLong => write!(f, "request URL too long"),
+            Error::Maintenance(_) => write!(f, "temporarily unavailable: maintenance"),
+            Error::ConfigError(msg) => write!(f, "config error: {}", msg),
             Error::Internal(msg) => write!(f, "internal error: {}", msg),
         }
     }
@@ -106,6 +122,39 @@ impl axum::response::IntoResponse for Error {
                     .into_response();
             }
             Error::InvalidRequest(_) => StatusCode::BAD_REQUEST,
+            Error::InsufficientScope(ref scope) => {
+                // OAuth-style error body naming the scope that was missing
+                let error_body = serde_json::json!({
+                    "error": "insufficient_scope",
+                    "error_description": format!("requires scope: {}", scope),
+                });
+                return (StatusCode::FORBIDDEN, Json(error_body)).into_response();
+            }
+            Error::PayloadTooLarge(ref msg) => {
+                // OAuth-style error body so clients can surface the limit
+                let error_body = serde_json::json!({
+                    "error": "invalid_request",
+                    "error_description": format!("payload too large: {}", msg),
+                });
+                return (StatusCode::PAYLOAD_TOO_LARGE, Json(error_body)).into_response();
+            }
+            Error::UriTooLong => StatusCode::URI_TOO_LONG,
+            Error::Maintenance(retry_after) => {
+                // OAuth-style body plus Retry-After so well-behaved
+                // clients back off for the announced window
+                let error_body = serde_json::json!({
+                    "error": "temporarily_unavailable",
+                    "error_description": "the proxy is in maintenance mode",
+                });
+                return (
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    [(axum::http::header::RETRY_AFTER, retry_after.to_string())],
+                    Json(error_body),
+                )
+                    .into_response();
+            }
+            Error::UpstreamUnavailable(_) => StatusCode::BAD_GATEWAY,
+            Error::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -0,0 +1,562 @@
+//! Hardened compact-JWS parsing shared by every JWT and DPoP consumer in
+//! the crate.
+//!
+//! JWT handling used to be spread across `auth.rs`, `token.rs`, and
+//! `server.rs` as ad-hoc string splits and `serde_json` probing, each copy
+//! with slightly different validation. This module is the single path:
+//! strict typed header parsing, an explicit algorithm allowlist, bounded
+//! input sizes, and an API shape that makes it impossible to read claims
+//! before the signature has been verified — [`parse_compact`] yields an
+//! [`UnverifiedJws`] whose payload is only reachable through
+//! [`UnverifiedJws::verify_es256`] or [`UnverifiedJws::verify_key`].
+
+use crate::error::{Error, Result};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use p256::ecdsa::signature::Verifier;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+/// Default clock-skew leeway applied to `exp`/`iat` checks, in seconds
+pub const DEFAULT_CLOCK_SKEW_LEEWAY_SECONDS: i64 = 30;
+
+/// Upper bound on accepted compact JWS length; anything larger is rejected
+/// before any decoding work happens
+const MAX_TOKEN_LENGTH: usize = 16 * 1024;
+
+/// Upper bound on the decoded header, which we parse before verification
+const MAX_HEADER_LENGTH: usize = 4 * 1024;
+
+/// Signature algorithms the proxy is willing to accept
+///
+/// Everything the proxy itself signs is ES256; downstream DPoP proofs may
+/// additionally use ES256K or EdDSA, matching the algorithm lists the
+/// metadata advertises. The allowlist exists so `none`, HMAC downgrades,
+/// and unknown algorithms are rejected by name before any key material is
+/// touched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JwsAlgorithm {
+    Es256,
+    Es256K,
+    Ed25519,
+}
+
+impl JwsAlgorithm {
+    /// The RFC 7518 name for this algorithm
+    pub fn name(&self) -> &'static str {
+        match self {
+            JwsAlgorithm::Es256 => "ES256",
+            JwsAlgorithm::Es256K => "ES256K",
+            JwsAlgorithm::Ed25519 => "EdDSA",
+        }
+    }
+}
+
+/// A public key for verifying a compact JWS, tagged with its algorithm so
+/// a key of one type can never check a signature claiming another.
+pub enum JwsVerifyingKey {
+    Es256(p256::ecdsa::VerifyingKey),
+    Es256K(k256::ecdsa::VerifyingKey),
+    Ed25519(ed25519_dalek::VerifyingKey),
+}
+
+impl JwsVerifyingKey {
+    /// The algorithm this key verifies
+    pub fn algorithm(&self) -> JwsAlgorithm {
+        match self {
+            JwsVerifyingKey::Es256(_) => JwsAlgorithm::Es256,
+            JwsVerifyingKey::Es256K(_) => JwsAlgorithm::Es256K,
+            JwsVerifyingKey::Ed25519(_) => JwsAlgorithm::Ed25519,
+        }
+    }
+}
+
+/// Protected header of a compact JWS, parsed strictly
+///
+/// Fields with the wrong JSON type (an `alg` that isn't a string, a `jwk`
+/// that isn't an object) fail the whole parse instead of being skipped.
+#[derive(Debug, Clone, Deserialize)]
+pub struct JoseHeader {
+    /// Signature algorithm name
+    pub alg: String,
+    /// Token type ("JWT", "dpop+jwt", ...)
+    #[serde(default)]
+    pub typ: Option<String>,
+    /// Key identifier for JWKS lookups
+    #[serde(default)]
+    pub kid: Option<String>,
+    /// Embedded public key (DPoP proofs)
+    #[serde(default)]
+    pub jwk: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+/// A structurally valid compact JWS whose signature has not been checked
+///
+/// The header is available (callers need `jwk`/`kid` to pick a verification
+/// key), but the payload is private: the only way to reach the claims is
+/// [`Self::verify_es256`], which enforces signature-before-parse ordering.
+pub struct UnverifiedJws<'a> {
+    header: JoseHeader,
+    algorithm: JwsAlgorithm,
+    signing_input: &'a str,
+    payload_b64: &'a str,
+    signature: [u8; 64],
+}
+
+impl<'a> UnverifiedJws<'a> {
+    /// The parsed protected header
+    pub fn header(&self) -> &JoseHeader {
+        &self.header
+    }
+
+    /// The algorithm named by the header (already on the allowlist)
+    pub fn algorithm(&self) -> JwsAlgorithm {
+        self.algorithm
+    }
+
+    /// Verify the ES256 signature and unlock the payload
+    pub fn verify_es256(self, key: &p256::ecdsa::VerifyingKey) -> Result<VerifiedJws<'a>> {
+        self.verify_key(&JwsVerifyingKey::Es256(*key))
+    }
+
+    /// Verify the signature against a typed key and unlock the payload
+    pub fn verify_key(self, key: &JwsVerifyingKey) -> Result<VerifiedJws<'a>> {
+        if !self.signature_matches_key(key) {
+            return Err(Error::InvalidRequest(
+                "signature verification failed".to_string(),
+            ));
+        }
+        Ok(VerifiedJws {
+            header: self.header,
+            payload_b64: self.payload_b64,
+        })
+    }
+
+    /// Whether the ES256 signature checks out against `key`, without
+    /// consuming the token; used when trying several candidate keys from a
+    /// JWKS
+    pub fn signature_matches(&self, key: &p256::ecdsa::VerifyingKey) -> bool {
+        self.signature_matches_key(&JwsVerifyingKey::Es256(*key))
+    }
+
+    /// Whether the signature checks out against a typed key.
+    ///
+    /// Always false when the header's `alg` doesn't match the key's
+    /// algorithm, so a confused caller can't validate an ES256K signature
+    /// with an Ed25519 key or vice versa.
+    pub fn signature_matches_key(&self, key: &JwsVerifyingKey) -> bool {
+        if self.algorithm != key.algorithm() {
+            return false;
+        }
+        let input = self.signing_input.as_bytes();
+        match key {
+            JwsVerifyingKey::Es256(key) => {
+                p256::ecdsa::Signature::from_bytes(&self.signature.into())
+                    .is_ok_and(|sig| key.verify(input, &sig).is_ok())
+            }
+            JwsVerifyingKey::Es256K(key) => {
+                k256::ecdsa::Signature::from_bytes(&self.signature.into())
+                    .is_ok_and(|sig| key.verify(input, &sig).is_ok())
+            }
+            JwsVerifyingKey::Ed25519(key) => {
+                let sig = ed25519_dalek::Signature::from_bytes(&self.signature);
+                key.verify_strict(input, &sig).is_ok()
+            }
+        }
+    }
+
+    /// Unlock the payload after an external verification succeeded
+    ///
+    /// Only for the [`Self::signature_matches`] multi-key path; callers
+    /// must have seen `signature_matches` return true first.
+    pub fn into_verified(self) -> VerifiedJws<'a> {
+        VerifiedJws {
+            header: self.header,
+            payload_b64: self.payload_b64,
+        }
+    }
+}
+
+/// A compact JWS whose signature has been verified; claims are now safe
+/// to deserialize
+pub struct VerifiedJws<'a> {
+    header: JoseHeader,
+    payload_b64: &'a str,
+}
+
+impl VerifiedJws<'_> {
+    /// The parsed protected header
+    pub fn header(&self) -> &JoseHeader {
+        &self.header
+    }
+
+    /// Deserialize the payload into a typed claims struct
+    pub fn claims<T: DeserializeOwned>(&self) -> Result<T> {
+        let payload = URL_SAFE_NO_PAD
+            .decode(self.payload_b64)
+            .map_err(|e| Error::InvalidRequest(format!("invalid payload encoding: {}", e)))?;
+        serde_json::from_slice(&payload)
+            .map_err(|e| Error::InvalidRequest(format!("invalid claims: {}", e)))
+    }
+}
+
+/// Parse a compact JWS into its unverified form
+///
+/// Enforces, in order: an overall size cap, exactly three non-empty
+/// dot-separated segments, a bounded strictly-typed header whose `alg` is
+/// on `allowed`, and a 64-byte signature. The payload is not decoded here.
+pub fn parse_compact<'a>(token: &'a str, allowed: &[JwsAlgorithm]) -> Result<UnverifiedJws<'a>> {
+    if token.len() > MAX_TOKEN_LENGTH {
+        return Err(Error::InvalidRequest("token too large".to_string()));
+    }
+
+    let mut segments = token.split('.');
+    let (Some(header_b64), Some(payload_b64), Some(signature_b64), None) = (
+        segments.next(),
+        segments.next(),
+        segments.next(),
+        segments.next(),
+    ) else {
+        return Err(Error::InvalidRequest("invalid JWT format".to_string()));
+    };
+    if header_b64.is_empty() || payload_b64.is_empty() || signature_b64.is_empty() {
+        return Err(Error::InvalidRequest("invalid JWT format".to_string()));
+    }
+
+    let header_bytes = URL_SAFE_NO_PAD
+        .decode(header_b64)
+        .map_err(|e| Error::InvalidRequest(format!("invalid header encoding: {}", e)))?;
+    if header_bytes.len() > MAX_HEADER_LENGTH {
+        return Err(Error::InvalidRequest("header too large".to_string()));
+    }
+    let header: JoseHeader = serde_json::from_slice(&header_bytes)
+        .map_err(|e| Error::InvalidRequest(format!("invalid header JSON: {}", e)))?;
+
+    let Some(algorithm) = allowed.iter().copied().find(|a| a.name() == header.alg) else {
+        return Err(Error::InvalidRequest(format!(
+            "unsupported algorithm: {}",
+            header.alg
+        )));
+    };
+
+    // All accepted algorithms use fixed 64-byte signatures (r||s for the
+    // ECDSA curves, R||s for Ed25519); per-algorithm parsing happens at
+    // verification time
+    let signature_bytes = URL_SAFE_NO_PAD
+        .decode(signature_b64)
+        .map_err(|e| Error::InvalidRequest(format!("invalid signature encoding: {}", e)))?;
+    let signature: [u8; 64] = signature_bytes
+        .try_into()
+        .map_err(|_| Error::InvalidRequest("invalid signature length".to_string()))?;
+
+    // The signing input is everything before the second dot
+    let signing_input = &token[..header_b64.len() + 1 + payload_b64.len()];
+
+    Ok(UnverifiedJws {
+        header,
+        algorithm,
+        signing_input,
+        payload_b64,
+        signature,
+    })
+}
+
+/// Validate `exp` (and optionally `iat`/`nbf`) against the current time
+/// with clock-skew leeway
+///
+/// A token is expired once `exp + leeway` is in the past, rejected as
+/// issued in the future if `iat - leeway` is ahead of now, and rejected
+/// as not yet valid while `nbf - leeway` is ahead of now.
+pub fn validate_timestamps(
+    exp: i64,
+    iat: Option<i64>,
+    nbf: Option<i64>,
+    leeway_seconds: i64,
+) -> Result<()> {
+    let now = chrono::Utc::now().timestamp();
+    if exp.saturating_add(leeway_seconds) < now {
+        return Err(Error::InvalidRequest("token expired".to_string()));
+    }
+    if let Some(iat) = iat {
+        if iat.saturating_sub(leeway_seconds) > now {
+            return Err(Error::InvalidRequest(
+                "token issued in the future".to_string(),
+            ));
+        }
+    }
+    if let Some(nbf) = nbf {
+        if nbf.saturating_sub(leeway_seconds) > now {
+            return Err(Error::InvalidRequest("token not yet valid".to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Strip private components from a JWK, keeping only the public key.
+///
+/// EC and OKP keys lose `d`, symmetric keys lose `k` entirely. Use this
+/// anywhere a stored keypair leaves the trust boundary: DPoP proof
+/// headers, and any log or export that serializes session key material.
+pub fn public_jwk(jwk: &jose_jwk::Jwk) -> jose_jwk::Jwk {
+    jose_jwk::Jwk {
+        key: match &jwk.key {
+            jose_jwk::Key::Ec(ec) => jose_jwk::Key::Ec(jose_jwk::Ec {
+                crv: ec.crv.clone(),
+                x: ec.x.clone(),
+                y: ec.y.clone(),
+                d: None,
+            }),
+            jose_jwk::Key::Okp(okp) => jose_jwk::Key::Okp(jose_jwk::Okp {
+                crv: okp.crv.clone(),
+                x: okp.x.clone(),
+                d: None,
+            }),
+            jose_jwk::Key::Rsa(rsa) => jose_jwk::Key::Rsa(jose_jwk::Rsa {
+                n: rsa.n.clone(),
+                e: rsa.e.clone(),
+                prm: None,
+            }),
+            // Symmetric keys have no public half; an empty key is the
+            // only safe thing to emit
+            jose_jwk::Key::Oct(_) => jose_jwk::Key::Oct(jose_jwk::Oct {
+                k: jose_jwk::jose_b64::serde::Secret::from(Vec::new()),
+            }),
+        },
+        prm: jwk.prm.clone(),
+    }
+}
+
+/// Generate a fresh P-256 keypair for DPoP use, returned as a JWK with
+/// its private `d` component and the RFC 7638 thumbprint.
+///
+/// Entropy comes from the OS. Callers that persist the key should go
+/// through [`KeyStore::create_dpop_key`](crate::store::KeyStore::create_dpop_key),
+/// which wraps this and stores the result in one step.
+pub fn generate_p256_dpop_jwk() -> (String, jose_jwk::Jwk) {
+    use base64::Engine;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use sha2::{Digest, Sha256};
+
+    let signing_key = p256::ecdsa::SigningKey::random(&mut rand::rngs::OsRng);
+    let point = signing_key.verifying_key().to_encoded_point(false);
+    let x = point.x().expect("uncompressed point has x");
+    let y = point.y().expect("uncompressed point has y");
+
+    let x_b64 = URL_SAFE_NO_PAD.encode(x);
+    let y_b64 = URL_SAFE_NO_PAD.encode(y);
+
+    // RFC 7638: required EC members in lexicographic order, no whitespace
+    let canonical = format!(
+        r#"{{"crv":"P-256","kty":"EC","x":"{}","y":"{}"}}"#,
+        x_b64, y_b64
+    );
+    let thumbprint = URL_SAFE_NO_PAD.encode(Sha256::digest(canonical.as_bytes()));
+
+    let jwk = jose_jwk::Jwk {
+        key: jose_jwk::Key::Ec(jose_jwk::Ec {
+            crv: jose_jwk::EcCurves::P256,
+            x: jose_jwk::jose_b64::serde::Bytes::from(x.as_slice().to_vec()),
+            y: jose_jwk::jose_b64::serde::Bytes::from(y.as_slice().to_vec()),
+            d: Some(jose_jwk::jose_b64::serde::Secret::from(
+                signing_key.to_bytes().as_slice().to_vec(),
+            )),
+        }),
+        prm: Default::default(),
+    };
+
+    (thumbprint, jwk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::SigningKey;
+    use p256::ecdsa::signature::Signer;
+    use proptest::prelude::*;
+
+    const ALLOWED: &[JwsAlgorithm] = &[JwsAlgorithm::Es256];
+
+    fn test_key() -> SigningKey {
+        SigningKey::from_slice(&[7u8; 32]).unwrap()
+    }
+
+    fn sign(key: &SigningKey, header: &str, payload: &str) -> String {
+        let header_b64 = URL_SAFE_NO_PAD.encode(header);
+        let payload_b64 = URL_SAFE_NO_PAD.encode(payload);
+        let input = format!("{}.{}", header_b64, payload_b64);
+        let signature: p256::ecdsa::Signature = key.sign(input.as_bytes());
+        format!("{}.{}", input, URL_SAFE_NO_PAD.encode(signature.to_bytes()))
+    }
+
+    #[test]
+    fn round_trip_verifies() {
+        let key = test_key();
+        let token = sign(&key, r#"{"alg":"ES256","typ":"JWT"}"#, r#"{"sub":"did:x"}"#);
+        let verified = parse_compact(&token, ALLOWED)
+            .unwrap()
+            .verify_es256(key.verifying_key())
+            .unwrap();
+        let claims: serde_json::Value = verified.claims().unwrap();
+        assert_eq!(claims["sub"], "did:x");
+    }
+
+    const DPOP_ALLOWED: &[JwsAlgorithm] = &[
+        JwsAlgorithm::Es256,
+        JwsAlgorithm::Es256K,
+        JwsAlgorithm::Ed25519,
+    ];
+
+    #[test]
+    fn es256k_round_trip_verifies() {
+        let key = k256::ecdsa::SigningKey::from_slice(&[9u8; 32]).unwrap();
+        let header_b64 = URL_SAFE_NO_PAD.encode(r#"{"alg":"ES256K"}"#);
+        let payload_b64 = URL_SAFE_NO_PAD.encode(r#"{"sub":"did:k"}"#);
+        let input = format!("{}.{}", header_b64, payload_b64);
+        let signature: k256::ecdsa::Signature = key.sign(input.as_bytes());
+        let token = format!("{}.{}", input, URL_SAFE_NO_PAD.encode(signature.to_bytes()));
+
+        let jws = parse_compact(&token, DPOP_ALLOWED).unwrap();
+        let verifying = JwsVerifyingKey::Es256K(*key.verifying_key());
+        assert!(jws.signature_matches_key(&verifying));
+        let claims: serde_json::Value = jws.verify_key(&verifying).unwrap().claims().unwrap();
+        assert_eq!(claims["sub"], "did:k");
+    }
+
+    #[test]
+    fn ed25519_round_trip_verifies() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[11u8; 32]);
+        let header_b64 = URL_SAFE_NO_PAD.encode(r#"{"alg":"EdDSA"}"#);
+        let payload_b64 = URL_SAFE_NO_PAD.encode(r#"{"sub":"did:e"}"#);
+        let input = format!("{}.{}", header_b64, payload_b64);
+        let signature = key.sign(input.as_bytes());
+        let token = format!("{}.{}", input, URL_SAFE_NO_PAD.encode(signature.to_bytes()));
+
+        let jws = parse_compact(&token, DPOP_ALLOWED).unwrap();
+        let verifying = JwsVerifyingKey::Ed25519(key.verifying_key());
+        assert!(jws.signature_matches_key(&verifying));
+        let claims: serde_json::Value = jws.verify_key(&verifying).unwrap().claims().unwrap();
+        assert_eq!(claims["sub"], "did:e");
+    }
+
+    #[test]
+    fn key_algorithm_mismatch_never_verifies() {
+        // An ES256-signed token must not verify against a key of a
+        // different algorithm, regardless of the signature bytes
+        let key = test_key();
+        let token = sign(&key, r#"{"alg":"ES256"}"#, "{}");
+        let jws = parse_compact(&token, DPOP_ALLOWED).unwrap();
+        let k256_key = k256::ecdsa::SigningKey::from_slice(&[9u8; 32]).unwrap();
+        assert!(!jws.signature_matches_key(&JwsVerifyingKey::Es256K(*k256_key.verifying_key())));
+        let ed_key = ed25519_dalek::SigningKey::from_bytes(&[11u8; 32]);
+        assert!(!jws.signature_matches_key(&JwsVerifyingKey::Ed25519(ed_key.verifying_key())));
+    }
+
+    #[test]
+    fn rejects_disallowed_algorithms() {
+        let key = test_key();
+        for alg in ["none", "HS256", "RS256", "ES384", ""] {
+            let token = sign(&key, &format!(r#"{{"alg":"{}"}}"#, alg), "{}");
+            assert!(parse_compact(&token, ALLOWED).is_err(), "accepted {alg:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_mistyped_header_fields() {
+        let key = test_key();
+        // alg must be a string, jwk must be an object
+        for header in [r#"{"alg":256}"#, r#"{"alg":"ES256","jwk":"x"}"#, "[]"] {
+            let token = sign(&key, header, "{}");
+            assert!(parse_compact(&token, ALLOWED).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_wrong_segment_counts() {
+        for token in ["", "a", "a.b", "a.b.c.d", "..", "a..c", ".b.c", "a.b."] {
+            assert!(parse_compact(token, ALLOWED).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_oversized_tokens() {
+        let token = format!("a.{}.c", "x".repeat(MAX_TOKEN_LENGTH));
+        assert!(parse_compact(&token, ALLOWED).is_err());
+    }
+
+    #[test]
+    fn expiry_leeway() {
+        let now = chrono::Utc::now().timestamp();
+        // Just expired but inside leeway
+        assert!(validate_timestamps(now - 10, None, None, 30).is_ok());
+        // Expired past leeway
+        assert!(validate_timestamps(now - 60, None, None, 30).is_err());
+        // Issued slightly in the future is tolerated, far in the future isn't
+        assert!(validate_timestamps(now + 600, Some(now + 10), None, 30).is_ok());
+        assert!(validate_timestamps(now + 600, Some(now + 120), None, 30).is_err());
+        // nbf gets the same leeway
+        assert!(validate_timestamps(now + 600, None, Some(now + 10), 30).is_ok());
+        assert!(validate_timestamps(now + 600, None, Some(now + 120), 30).is_err());
+    }
+
+    proptest! {
+        /// Arbitrary input never panics, only errors
+        #[test]
+        fn arbitrary_input_never_panics(token in ".*") {
+            let _ = parse_compact(&token, ALLOWED);
+        }
+
+        /// Arbitrary base64url segments never verify against our key
+        #[test]
+        fn garbage_segments_never_verify(
+            header in proptest::collection::vec(any::<u8>(), 0..128),
+            payload in proptest::collection::vec(any::<u8>(), 0..128),
+            signature in proptest::collection::vec(any::<u8>(), 0..128),
+        ) {
+            let token = format!(
+                "{}.{}.{}",
+                URL_SAFE_NO_PAD.encode(&header),
+                URL_SAFE_NO_PAD.encode(&payload),
+                URL_SAFE_NO_PAD.encode(&signature),
+            );
+            let key = test_key();
+            if let Ok(jws) = parse_compact(&token, ALLOWED) {
+                prop_assert!(jws.verify_es256(key.verifying_key()).is_err());
+            }
+        }
+
+        /// Any single corrupted signature byte fails verification
+        #[test]
+        fn corrupted_signature_never_verifies(index in 0usize..64, flip in 1u8..=255) {
+            let key = test_key();
+            let token = sign(&key, r#"{"alg":"ES256"}"#, r#"{"sub":"did:x"}"#);
+            let (input, signature_b64) = token.rsplit_once('.').unwrap();
+            let mut signature = URL_SAFE_NO_PAD.decode(signature_b64).unwrap();
+            signature[index] ^= flip;
+            let corrupted = format!("{}.{}", input, URL_SAFE_NO_PAD.encode(&signature));
+            let result = parse_compact(&corrupted, ALLOWED)
+                .and_then(|jws| jws.verify_es256(key.verifying_key()).map(|_| ()));
+            prop_assert!(result.is_err());
+        }
+
+        /// A payload swapped under a valid signature fails verification
+        #[test]
+        fn swapped_payload_never_verifies(payload in proptest::collection::vec(any::<u8>(), 1..128)) {
+            let key = test_key();
+            let token = sign(&key, r#"{"alg":"ES256"}"#, r#"{"sub":"did:x"}"#);
+            let parts: Vec<&str> = token.split('.').collect();
+            let swapped = format!(
+                "{}.{}.{}",
+                parts[0],
+                URL_SAFE_NO_PAD.encode(&payload),
+                parts[2],
+            );
+            let result = parse_compact(&swapped, ALLOWED)
+                .and_then(|jws| jws.verify_es256(key.verifying_key()).map(|_| ()));
+            // The only way this passes is the astronomically unlikely case
+            // of generating the original payload
+            if payload != br#"{"sub":"did:x"}"# {
+                prop_assert!(result.is_err());
+            }
+        }
+    }
+}
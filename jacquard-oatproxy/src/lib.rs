@@ -26,29 +26,73 @@
 //!     .session_store(my_session_store)
 //!     .key_store(my_key_store)
 //!     .nonce_store(my_nonce_store)
-//!     .build()?;
+//!     .build_async()
+//!     .await?;
 //!
 //! let app = proxy.router();
 //! # Ok(())
 //! # }
 //! ```
 
+pub mod audit;
 pub mod auth;
+pub mod cache;
+pub mod client;
+pub mod codec;
 pub mod config;
+pub mod deploy;
 pub mod error;
 pub mod handlers;
+pub mod jose;
+pub mod limit;
+pub mod migrate;
+pub mod pages;
+pub mod resolution;
 pub mod server;
 pub mod session;
 pub mod store;
+#[cfg(feature = "axum")]
+pub mod tenant;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
 pub mod token;
+pub mod upstream;
 
-pub use auth::{ProxyJwtClaims, extract_bearer_token, validate_proxy_jwt};
-pub use config::ProxyConfig;
+pub use audit::{AuditEvent, AuditRecord, AuditSink, TracingAuditSink};
+pub use client::ProxyClient;
+pub use auth::{
+    ClientAssertionClaims, ConfirmationClaim, ProxyJwtClaims, SecretBytes, SecretString,
+    constant_time_eq, extract_bearer_token, token_digest, validate_proxy_jwt,
+    verify_client_assertion,
+};
+pub use codec::{CODEC_ENVELOPE_VERSION, StoreCodec};
+pub use config::{
+    ClientTokenPolicy, EndpointPaths, ProxyConfig, ProxyConfigFile, RefreshKeyPolicy,
+    ServiceClient, ServiceClientEntry,
+};
+pub use deploy::{CheckStatus, DeploymentCheck, DeploymentReport};
 pub use error::{Error, Result};
-pub use server::{OAuthProxyServer, OAuthProxyServerBuilder};
+pub use migrate::{
+    EXPORT_VERSION, ExportEntry, ExportHeader, ExportableStore, PlaintextCipher, StoreCipher,
+    export_sessions, import_sessions,
+};
+pub use pages::{DefaultPageRenderer, ErrorPage, PageRenderer};
+pub use resolution::{CachedResolution, MemoryResolutionCache, ResolutionCache, ResolvedIdentity};
+pub use server::{
+    AuthenticatedSession, Missing, OAuthProxyServer, OAuthProxyServerBuilder,
+    OAuthProxyServerTypedBuilder,
+};
 pub use session::{OAuthSession, SessionState};
-pub use store::{KeyStore, OAuthSessionStore};
-pub use token::{DownstreamTokenClaims, TokenManager};
+pub use store::{KeyMaterial, KeyStore, OAuthSessionStore};
+#[cfg(feature = "axum")]
+pub use tenant::{HostTenantResolver, MultiTenantProxy, TenantResolver};
+pub use token::{
+    DownstreamTokenClaims, JwtTokenIssuer, MIN_TOKEN_ENTROPY_BYTES, SecureToken, TokenIssuer,
+    TokenManager, generate_token,
+};
 
 #[cfg(feature = "axum")]
-pub use auth::axum_extractors::{AuthState, AuthenticatedClaims, AuthenticatedUser};
+pub use auth::axum_extractors::{
+    Atproto, AuthState, AuthenticatedClaims, AuthenticatedUser, RequireScope, RequiredScope,
+    ScopedUser, TransitionChatBsky, TransitionGeneric, scope_granted,
+};
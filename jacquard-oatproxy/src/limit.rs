@@ -0,0 +1,131 @@
+//! Per-principal concurrency limiting for the XRPC proxy.
+//!
+//! Without limits, one misbehaving client can hold every upstream
+//! connection and starve the rest. [`XrpcConcurrencyLimits`] keys a
+//! semaphore per account DID and per downstream DPoP key: a principal at
+//! its limit queues up to the configured depth, and anything beyond that
+//! is answered with 429 immediately instead of piling onto the upstream.
+
+use crate::config::ProxyConfig;
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Idle entries are pruned once the map grows past this many keys
+const PRUNE_THRESHOLD: usize = 1024;
+
+/// A semaphore-per-key concurrency limiter with a bounded wait queue
+struct ConcurrencyLimiter {
+    /// Concurrent requests allowed per key; 0 disables the limiter
+    max_concurrent: usize,
+    /// Requests allowed to wait for a permit per key; beyond this, 429
+    queue_depth: usize,
+    entries: Mutex<HashMap<String, Arc<LimiterEntry>>>,
+}
+
+struct LimiterEntry {
+    semaphore: Arc<Semaphore>,
+    waiting: AtomicUsize,
+}
+
+impl ConcurrencyLimiter {
+    fn new(max_concurrent: usize, queue_depth: usize) -> Self {
+        Self {
+            max_concurrent,
+            queue_depth,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Acquire a permit for `key`, waiting in the bounded queue if the
+    /// key is at its concurrency limit
+    async fn acquire(&self, key: &str, what: &str) -> Result<Option<OwnedSemaphorePermit>> {
+        if self.max_concurrent == 0 {
+            return Ok(None);
+        }
+
+        let entry = {
+            let mut entries = self.entries.lock().expect("limiter lock");
+            // Keep the map from growing with every principal ever seen:
+            // drop entries that are idle (all permits free, nobody waiting)
+            if entries.len() > PRUNE_THRESHOLD {
+                let max = self.max_concurrent;
+                entries.retain(|_, e| {
+                    e.semaphore.available_permits() < max || e.waiting.load(Ordering::Relaxed) > 0
+                });
+            }
+            entries
+                .entry(key.to_string())
+                .or_insert_with(|| {
+                    Arc::new(LimiterEntry {
+                        semaphore: Arc::new(Semaphore::new(self.max_concurrent)),
+                        waiting: AtomicUsize::new(0),
+                    })
+                })
+                .clone()
+        };
+
+        // Fast path: a permit is free right now
+        if let Ok(permit) = entry.semaphore.clone().try_acquire_owned() {
+            return Ok(Some(permit));
+        }
+
+        // Queue for a permit, but only up to the configured depth
+        if entry.waiting.fetch_add(1, Ordering::AcqRel) >= self.queue_depth {
+            entry.waiting.fetch_sub(1, Ordering::AcqRel);
+            tracing::warn!("{} {} exceeded concurrency limit and queue depth", what, key);
+            return Err(Error::TooManyRequests(format!(
+                "too many concurrent requests for this {}",
+                what
+            )));
+        }
+
+        let permit = entry.semaphore.clone().acquire_owned().await;
+        entry.waiting.fetch_sub(1, Ordering::AcqRel);
+        permit
+            .map(Some)
+            .map_err(|_| Error::Internal("concurrency semaphore closed".to_string()))
+    }
+}
+
+/// Permits held for the duration of one proxied XRPC request; dropping
+/// them releases the caller's concurrency slots
+pub struct XrpcPermits {
+    _did: Option<OwnedSemaphorePermit>,
+    _client: Option<OwnedSemaphorePermit>,
+}
+
+/// Combined per-DID and per-client limits for the XRPC proxy
+pub struct XrpcConcurrencyLimits {
+    per_did: ConcurrencyLimiter,
+    per_client: ConcurrencyLimiter,
+}
+
+impl XrpcConcurrencyLimits {
+    pub fn from_config(config: &ProxyConfig) -> Self {
+        Self {
+            per_did: ConcurrencyLimiter::new(
+                config.xrpc_max_concurrent_per_did,
+                config.xrpc_queue_depth,
+            ),
+            per_client: ConcurrencyLimiter::new(
+                config.xrpc_max_concurrent_per_client,
+                config.xrpc_queue_depth,
+            ),
+        }
+    }
+
+    /// Acquire both permits for a request from `did` using the downstream
+    /// key `dpop_jkt`; returns 429 via [`Error::TooManyRequests`] when the
+    /// queue for either principal is full
+    pub async fn acquire(&self, did: &str, dpop_jkt: &str) -> Result<XrpcPermits> {
+        let did_permit = self.per_did.acquire(did, "account").await?;
+        let client_permit = self.per_client.acquire(dpop_jkt, "client").await?;
+        Ok(XrpcPermits {
+            _did: did_permit,
+            _client: client_permit,
+        })
+    }
+}
@@ -0,0 +1,358 @@
+//! Session export/import for migrating between storage backends.
+//!
+//! Moving a deployment to a different store (e.g. sqlite → Postgres or Redis)
+//! shouldn't log every user out. This module defines a versioned, line-delimited
+//! export format: a header line followed by one JSON entry per record, so large
+//! stores can be streamed through a pipe or file without buffering everything.
+//!
+//! The export side is driven by [`ExportableStore`], which stores implement to
+//! enumerate their durable state. The import side writes through the normal
+//! [`OAuthSessionStore`] and `ClientAuthStore` traits, so any store that works
+//! with the proxy can be an import target.
+//!
+//! Stores that encrypt secrets at rest can supply a [`StoreCipher`] for each
+//! side; [`import_sessions`] re-encrypts token material for the target store's
+//! cipher and refuses streams whose recorded cipher doesn't match the source.
+//! Upstream session blobs and DPoP keys are structured data owned by the store
+//! and pass through unchanged.
+
+use crate::error::{Error, Result};
+use crate::session::OAuthSession;
+use crate::store::OAuthSessionStore;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use jacquard_oauth::authstore::ClientAuthStore;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+
+/// Current version of the export stream format
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Cipher hook for stores that encrypt secrets at rest
+///
+/// The proxy's storage traits deal in plaintext tokens, so encryption is a
+/// store implementation detail. During migration the importer needs to move
+/// secrets from the source store's cipher to the target's; implement this for
+/// both sides and pass them to [`import_sessions`].
+pub trait StoreCipher: Send + Sync {
+    /// Stable identifier recorded in the export header (e.g. "plaintext",
+    /// "aes-gcm-v1"). Re-encryption is skipped when source and target match.
+    fn id(&self) -> &str;
+
+    /// Encrypt a secret for storage
+    fn encrypt(&self, plaintext: &str) -> Result<String>;
+
+    /// Decrypt a stored secret
+    fn decrypt(&self, ciphertext: &str) -> Result<String>;
+}
+
+/// No-op cipher for stores that persist secrets unencrypted
+pub struct PlaintextCipher;
+
+impl StoreCipher for PlaintextCipher {
+    fn id(&self) -> &str {
+        "plaintext"
+    }
+
+    fn encrypt(&self, plaintext: &str) -> Result<String> {
+        Ok(plaintext.to_string())
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> Result<String> {
+        Ok(ciphertext.to_string())
+    }
+}
+
+/// First line of an export stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportHeader {
+    /// Format version, see [`EXPORT_VERSION`]
+    pub version: u32,
+    /// When the export was taken
+    pub exported_at: DateTime<Utc>,
+    /// [`StoreCipher::id`] of the cipher protecting secrets in this stream
+    pub cipher: String,
+}
+
+/// A single durable record in an export stream
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExportEntry {
+    /// A full OAuth session
+    Session {
+        /// The session record
+        session: OAuthSession,
+    },
+    /// Upstream session data managed via `ClientAuthStore` (serialized
+    /// `ClientSessionData` JSON)
+    UpstreamSession {
+        /// Serialized session data
+        data: String,
+    },
+    /// Per-session DPoP key used for upstream PDS requests
+    DpopKey {
+        /// Session the key belongs to
+        session_id: String,
+        /// JWK thumbprint of the key
+        dpop_jkt: String,
+        /// The key itself, including private material
+        key: jose_jwk::Jwk,
+    },
+    /// Last DPoP nonce received from the PDS for a session
+    DpopNonce {
+        /// Session the nonce belongs to
+        session_id: String,
+        /// The nonce value
+        nonce: String,
+    },
+    /// How the downstream client authenticated for a session
+    AuthMethod {
+        /// Session the record belongs to
+        session_id: String,
+        /// "none" or "private_key_jwt"
+        auth_method: String,
+    },
+    /// Refresh token mapping (refresh_token → account_did + session_id)
+    RefreshToken {
+        /// The refresh token
+        refresh_token: String,
+        /// Account the token belongs to
+        account_did: String,
+        /// Session the token refreshes
+        session_id: String,
+        /// Client the token was issued to (absent in older exports)
+        #[serde(default)]
+        client_id: Option<String>,
+        /// Hard expiry from the original grant (absent in older exports)
+        #[serde(default)]
+        absolute_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+        /// Idle expiry refreshed on use (absent in older exports)
+        #[serde(default)]
+        idle_expires_at: Option<chrono::DateTime<chrono::Utc>>,
+        /// DPoP key thumbprint the token was issued to (absent in older
+        /// exports)
+        #[serde(default)]
+        dpop_jkt: Option<String>,
+    },
+    /// Active session mapping (DID → session_id)
+    ActiveSession {
+        /// Account DID
+        did: String,
+        /// The active session for that account
+        session_id: String,
+    },
+}
+
+/// Enumeration side of store migration
+///
+/// Stores implement this to dump every durable record as [`ExportEntry`]
+/// values. Ephemeral state (PAR data, pending auths, used JTIs) is
+/// intentionally excluded — it expires within minutes and doesn't survive a
+/// migration window anyway.
+#[async_trait]
+pub trait ExportableStore: Send + Sync {
+    /// Enumerate all durable entries in this store
+    async fn export_entries(&self) -> Result<Vec<ExportEntry>>;
+}
+
+/// Export all durable session state from a store as a versioned stream
+///
+/// Writes a header line followed by one JSON entry per line. `cipher` is
+/// recorded in the header so the importer can verify it decrypts secrets with
+/// the right key; pass [`PlaintextCipher`] for stores that don't encrypt.
+/// Returns the number of entries written.
+pub async fn export_sessions<S, W>(store: &S, cipher: &dyn StoreCipher, mut writer: W) -> Result<u64>
+where
+    S: ExportableStore,
+    W: Write,
+{
+    let header = ExportHeader {
+        version: EXPORT_VERSION,
+        exported_at: Utc::now(),
+        cipher: cipher.id().to_string(),
+    };
+    let header_json = serde_json::to_string(&header)
+        .map_err(|e| Error::Internal(format!("failed to serialize export header: {}", e)))?;
+    writeln!(writer, "{}", header_json)
+        .map_err(|e| Error::Internal(format!("failed to write export stream: {}", e)))?;
+
+    let mut count = 0u64;
+    for entry in store.export_entries().await? {
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| Error::Internal(format!("failed to serialize export entry: {}", e)))?;
+        writeln!(writer, "{}", line)
+            .map_err(|e| Error::Internal(format!("failed to write export stream: {}", e)))?;
+        count += 1;
+    }
+
+    writer
+        .flush()
+        .map_err(|e| Error::Internal(format!("failed to flush export stream: {}", e)))?;
+    Ok(count)
+}
+
+/// Import an export stream into a store, re-encrypting secrets as needed
+///
+/// Reads the stream line by line, so arbitrarily large exports can be piped
+/// in. The header is validated first: unknown format versions are rejected,
+/// and `source_cipher` must match the cipher recorded at export time — this
+/// guards against importing secrets the target can't actually decrypt. When
+/// `source_cipher` and `target_cipher` differ, token material is decrypted
+/// with the source cipher and re-encrypted for the target before being
+/// written. Returns the number of entries imported.
+pub async fn import_sessions<S, R>(
+    store: &S,
+    source_cipher: &dyn StoreCipher,
+    target_cipher: &dyn StoreCipher,
+    reader: R,
+) -> Result<u64>
+where
+    S: OAuthSessionStore + ClientAuthStore,
+    R: BufRead,
+{
+    let mut lines = reader.lines();
+
+    let header_line = lines
+        .next()
+        .ok_or_else(|| Error::InvalidRequest("export stream is empty".to_string()))?
+        .map_err(|e| Error::Internal(format!("failed to read export stream: {}", e)))?;
+    let header: ExportHeader = serde_json::from_str(&header_line)
+        .map_err(|e| Error::InvalidRequest(format!("invalid export header: {}", e)))?;
+
+    if header.version != EXPORT_VERSION {
+        return Err(Error::InvalidRequest(format!(
+            "unsupported export version {} (expected {})",
+            header.version, EXPORT_VERSION
+        )));
+    }
+    if header.cipher != source_cipher.id() {
+        return Err(Error::InvalidRequest(format!(
+            "export was taken with cipher '{}' but source cipher is '{}'",
+            header.cipher,
+            source_cipher.id()
+        )));
+    }
+
+    let reencrypt = header.cipher != target_cipher.id();
+    let mut count = 0u64;
+
+    for line in lines {
+        let line = line.map_err(|e| Error::Internal(format!("failed to read export stream: {}", e)))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut entry: ExportEntry = serde_json::from_str(&line)
+            .map_err(|e| Error::InvalidRequest(format!("invalid export entry: {}", e)))?;
+
+        if reencrypt {
+            reencrypt_entry(&mut entry, source_cipher, target_cipher)?;
+        }
+
+        import_entry(store, entry).await?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Re-encrypt token material in an entry for the target store's cipher
+fn reencrypt_entry(
+    entry: &mut ExportEntry,
+    source: &dyn StoreCipher,
+    target: &dyn StoreCipher,
+) -> Result<()> {
+    let reseal = |value: &mut String| -> Result<()> {
+        *value = target.encrypt(&source.decrypt(value)?)?;
+        Ok(())
+    };
+
+    match entry {
+        ExportEntry::Session { session } => {
+            reseal(&mut session.upstream_access_token)?;
+            if let Some(token) = session.upstream_refresh_token.as_mut() {
+                reseal(token)?;
+            }
+            if let Some(token) = session.downstream_refresh_token.as_mut() {
+                reseal(token)?;
+            }
+            if let Some(code) = session.downstream_auth_code.as_mut() {
+                reseal(code)?;
+            }
+        }
+        ExportEntry::RefreshToken { refresh_token, .. } => reseal(refresh_token)?,
+        // Structured store-owned data; stores that encrypt these do so on write
+        ExportEntry::UpstreamSession { .. }
+        | ExportEntry::DpopKey { .. }
+        | ExportEntry::DpopNonce { .. }
+        | ExportEntry::AuthMethod { .. }
+        | ExportEntry::ActiveSession { .. } => {}
+    }
+
+    Ok(())
+}
+
+/// Write a single entry into the target store via the normal storage traits
+async fn import_entry<S>(store: &S, entry: ExportEntry) -> Result<()>
+where
+    S: OAuthSessionStore + ClientAuthStore,
+{
+    match entry {
+        ExportEntry::Session { session } => {
+            OAuthSessionStore::update_session(store, &session).await?;
+        }
+        ExportEntry::UpstreamSession { data } => {
+            let session_data: jacquard_oauth::session::ClientSessionData<'_> =
+                serde_json::from_str(&data).map_err(|e| {
+                    Error::InvalidRequest(format!("invalid upstream session data: {}", e))
+                })?;
+            ClientAuthStore::upsert_session(store, session_data)
+                .await
+                .map_err(|e| Error::StorageError(e.to_string()))?;
+        }
+        ExportEntry::DpopKey {
+            session_id,
+            dpop_jkt,
+            key,
+        } => {
+            store.store_session_dpop_key(&session_id, dpop_jkt, key).await?;
+        }
+        ExportEntry::DpopNonce { session_id, nonce } => {
+            store.update_session_dpop_nonce(&session_id, nonce).await?;
+        }
+        ExportEntry::AuthMethod {
+            session_id,
+            auth_method,
+        } => {
+            store.store_session_auth_method(&session_id, auth_method).await?;
+        }
+        ExportEntry::RefreshToken {
+            refresh_token,
+            account_did,
+            session_id,
+            client_id,
+            absolute_expires_at,
+            idle_expires_at,
+            dpop_jkt,
+        } => {
+            store
+                .store_refresh_token_mapping(
+                    &refresh_token,
+                    crate::store::RefreshTokenData {
+                        account_did,
+                        session_id,
+                        client_id,
+                        absolute_expires_at,
+                        idle_expires_at,
+                        dpop_jkt,
+                    },
+                )
+                .await?;
+        }
+        ExportEntry::ActiveSession { did, session_id } => {
+            store.store_active_session(&did, session_id).await?;
+        }
+    }
+
+    Ok(())
+}
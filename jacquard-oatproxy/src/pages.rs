@@ -0,0 +1,74 @@
+//! Human-facing page rendering for browser-mediated flow steps.
+//!
+//! The authorize and callback handlers run in the user's browser, where a
+//! JSON error body or a bare-text 400 dead-ends the flow with no
+//! explanation. Failures there render through a [`PageRenderer`] instead,
+//! so host applications can brand the pages; [`DefaultPageRenderer`]
+//! provides a minimal unstyled fallback. The trait is the extension point
+//! for every page the proxy shows a human — login and consent pages, if a
+//! host adds them, belong here too.
+
+/// What a failed browser-mediated step needs to show the user.
+pub struct ErrorPage<'a> {
+    /// HTTP status the page is served with
+    pub status: u16,
+    /// Human-readable description of what went wrong
+    pub message: &'a str,
+    /// Link that restarts the failed step, when retrying can help;
+    /// `None` for dead ends like a consumed authorization code
+    pub retry_uri: Option<&'a str>,
+}
+
+/// Renders the HTML pages the proxy serves directly to users.
+///
+/// Implementations get structured page data and return a complete HTML
+/// document. They are responsible for escaping any values they
+/// interpolate beyond the ones provided here, which arrive unescaped.
+pub trait PageRenderer: Send + Sync {
+    /// Render a flow failure; see [`ErrorPage`] for the available context
+    fn error_page(&self, page: &ErrorPage<'_>) -> String;
+}
+
+/// Minimal unstyled [`PageRenderer`] used when the host doesn't supply
+/// one.
+pub struct DefaultPageRenderer;
+
+impl PageRenderer for DefaultPageRenderer {
+    fn error_page(&self, page: &ErrorPage<'_>) -> String {
+        let retry = page
+            .retry_uri
+            .map(|uri| {
+                format!(
+                    "<p><a href=\"{}\">Try again</a></p>",
+                    escape_html(uri)
+                )
+            })
+            .unwrap_or_else(|| {
+                "<p>Return to the application you came from and start over.</p>".to_string()
+            });
+
+        format!(
+            "<!DOCTYPE html>\
+             <html><head><meta charset=\"utf-8\">\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\
+             <title>Sign-in failed</title></head>\
+             <body>\
+             <h1>Sign-in failed</h1>\
+             <p>{}</p>\
+             {}\
+             </body></html>",
+            escape_html(page.message),
+            retry
+        )
+    }
+}
+
+/// Escape a value for interpolation into HTML text or attributes.
+pub fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
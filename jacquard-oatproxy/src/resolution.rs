@@ -0,0 +1,228 @@
+//! Identity and PDS resolution caching.
+//!
+//! `start_auth` resolves handle → DID → PDS from scratch on every login, so
+//! login latency is dominated by plc.directory and handle lookups. The proxy
+//! keeps a TTL cache in front of that resolution: cache hits hand the
+//! upstream client a DID directly, skipping the handle lookup entirely.
+//! The default cache is in-memory; embedders can provide a store-backed
+//! implementation via `OAuthProxyServerBuilder::resolution_cache`.
+
+use crate::error::{Error, Result};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+/// A successfully resolved identity.
+#[derive(Debug, Clone)]
+pub struct ResolvedIdentity {
+    /// The account DID
+    pub did: String,
+    /// The account handle, if known
+    pub handle: Option<String>,
+    /// The account's PDS endpoint
+    pub pds_url: String,
+}
+
+/// A cache entry: either a resolved identity or a remembered miss
+/// (negative caching, so repeated bad identifiers don't hammer upstream).
+#[derive(Debug, Clone)]
+pub enum CachedResolution {
+    Resolved(ResolvedIdentity),
+    NotFound,
+}
+
+/// Pluggable cache for identity/PDS resolution results.
+#[async_trait]
+pub trait ResolutionCache: Send + Sync {
+    /// Get a cached entry if present and not expired
+    async fn get(&self, identifier: &str) -> Result<Option<CachedResolution>>;
+
+    /// Store an entry for the identifier
+    async fn put(&self, identifier: &str, entry: CachedResolution) -> Result<()>;
+
+    /// Drop a cached entry, e.g. after a handle change or PDS migration
+    async fn invalidate(&self, identifier: &str) -> Result<()>;
+}
+
+/// In-memory TTL cache used by default.
+pub struct MemoryResolutionCache {
+    entries: RwLock<HashMap<String, (CachedResolution, Instant)>>,
+    ttl: Duration,
+    negative_ttl: Duration,
+}
+
+impl MemoryResolutionCache {
+    pub fn new(ttl: Duration, negative_ttl: Duration) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            ttl,
+            negative_ttl,
+        }
+    }
+}
+
+#[async_trait]
+impl ResolutionCache for MemoryResolutionCache {
+    async fn get(&self, identifier: &str) -> Result<Option<CachedResolution>> {
+        let entries = self.entries.read().unwrap();
+        if let Some((entry, stored_at)) = entries.get(identifier) {
+            let ttl = match entry {
+                CachedResolution::NotFound => self.negative_ttl,
+                CachedResolution::Resolved(_) => self.ttl,
+            };
+            if stored_at.elapsed() < ttl {
+                return Ok(Some(entry.clone()));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn put(&self, identifier: &str, entry: CachedResolution) -> Result<()> {
+        self.entries
+            .write()
+            .unwrap()
+            .insert(identifier.to_string(), (entry, Instant::now()));
+        Ok(())
+    }
+
+    async fn invalidate(&self, identifier: &str) -> Result<()> {
+        self.entries.write().unwrap().remove(identifier);
+        Ok(())
+    }
+}
+
+/// Resolve an identifier (handle or DID) to its DID and PDS endpoint,
+/// consulting and populating the cache.
+///
+/// A definitive miss (unknown handle, DID without a PDS) is negative-cached
+/// and surfaced as an invalid_request; transient network failures are not
+/// cached so the caller can fall back to the upstream resolver.
+pub async fn resolve_identity(
+    cache: &dyn ResolutionCache,
+    identifier: &str,
+) -> Result<ResolvedIdentity> {
+    if let Some(cached) = cache.get(identifier).await? {
+        return match cached {
+            CachedResolution::Resolved(resolved) => {
+                tracing::debug!("resolution cache hit for {}", identifier);
+                Ok(resolved)
+            }
+            CachedResolution::NotFound => Err(Error::InvalidRequest(format!(
+                "unknown identifier: {}",
+                identifier
+            ))),
+        };
+    }
+
+    match resolve_uncached(identifier).await? {
+        Some(resolved) => {
+            cache
+                .put(identifier, CachedResolution::Resolved(resolved.clone()))
+                .await?;
+            Ok(resolved)
+        }
+        None => {
+            cache.put(identifier, CachedResolution::NotFound).await?;
+            Err(Error::InvalidRequest(format!(
+                "unknown identifier: {}",
+                identifier
+            )))
+        }
+    }
+}
+
+/// Perform the actual resolution. Returns `Ok(None)` only for definitive
+/// misses; network failures are errors.
+async fn resolve_uncached(identifier: &str) -> Result<Option<ResolvedIdentity>> {
+    // Resolve handle → DID via the well-known HTTP fallback
+    let (did, mut handle) = if identifier.starts_with("did:") {
+        (identifier.to_string(), None)
+    } else {
+        let url = format!("https://{}/.well-known/atproto-did", identifier);
+        let resp = reqwest::get(&url)
+            .await
+            .map_err(|e| Error::NetworkError(format!("handle resolution failed: {}", e)))?;
+
+        if resp.status() == 404 {
+            return Ok(None);
+        }
+        if !resp.status().is_success() {
+            return Err(Error::NetworkError(format!(
+                "handle resolution returned {}",
+                resp.status()
+            )));
+        }
+
+        let did = resp
+            .text()
+            .await
+            .map_err(|e| Error::NetworkError(format!("handle resolution failed: {}", e)))?
+            .trim()
+            .to_string();
+
+        if !did.starts_with("did:") {
+            return Ok(None);
+        }
+
+        (did, Some(identifier.to_string()))
+    };
+
+    // Resolve DID → DID document
+    let doc_url = if let Some(host) = did.strip_prefix("did:web:") {
+        format!("https://{}/.well-known/did.json", host)
+    } else if did.starts_with("did:plc:") {
+        format!("https://plc.directory/{}", did)
+    } else {
+        return Ok(None);
+    };
+
+    let resp = reqwest::get(&doc_url)
+        .await
+        .map_err(|e| Error::NetworkError(format!("DID resolution failed: {}", e)))?;
+
+    if resp.status() == 404 {
+        return Ok(None);
+    }
+    if !resp.status().is_success() {
+        return Err(Error::NetworkError(format!(
+            "DID resolution returned {}",
+            resp.status()
+        )));
+    }
+
+    let doc: serde_json::Value = resp
+        .json()
+        .await
+        .map_err(|e| Error::NetworkError(format!("invalid DID document: {}", e)))?;
+
+    if handle.is_none() {
+        handle = doc
+            .get("alsoKnownAs")
+            .and_then(|v| v.as_array())
+            .and_then(|arr| arr.first())
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.strip_prefix("at://"))
+            .map(|s| s.to_string());
+    }
+
+    let pds_url = doc
+        .get("service")
+        .and_then(|s| s.as_array())
+        .and_then(|services| {
+            services
+                .iter()
+                .find(|svc| svc.get("id").and_then(|id| id.as_str()) == Some("#atproto_pds"))
+        })
+        .and_then(|svc| svc.get("serviceEndpoint"))
+        .and_then(|e| e.as_str());
+
+    match pds_url {
+        Some(pds_url) => Ok(Some(ResolvedIdentity {
+            did,
+            handle,
+            pds_url: pds_url.trim_end_matches('/').to_string(),
+        })),
+        None => Ok(None),
+    }
+}
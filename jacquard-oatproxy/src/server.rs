@@ -1,14 +1,17 @@
 use crate::{
+    auth::{constant_time_eq, token_digest},
     config::ProxyConfig,
     error::{Error, Result},
-    store::{KeyStore, OAuthSessionStore},
-    token::TokenManager,
+    resolution::{MemoryResolutionCache, ResolutionCache},
+    store::{AccessTokenData, KeyMaterial, KeyStore, OAuthSessionStore},
+    token::{DownstreamTokenClaims, JwtTokenIssuer, SecureToken, TokenIssuer, TokenManager},
+    upstream::UpstreamTransport,
 };
 use axum::{
     Json, Router,
-    extract::{Query, State},
+    extract::{DefaultBodyLimit, OriginalUri, Query, State},
     http::{HeaderMap, Method, StatusCode},
-    response::{IntoResponse, Redirect, Response},
+    response::{Html, IntoResponse, Redirect, Response},
     routing::{any, get, post},
 };
 use jacquard_identity::JacquardResolver;
@@ -17,6 +20,7 @@ use jacquard_oauth::client::OAuthClient;
 use jacquard_oauth::session::ClientData;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
 
 /// Main OAuth proxy server that handles both downstream (client ↔ proxy)
 /// and upstream (proxy ↔ PDS) OAuth flows.
@@ -29,8 +33,19 @@ where
     config: ProxyConfig,
     session_store: Arc<S>,
     key_store: Arc<K>,
+    key_material: Arc<KeyMaterial<K>>,
     token_manager: Arc<TokenManager>,
+    token_issuer: Arc<dyn TokenIssuer>,
     oauth_client: Arc<OAuthClient<JacquardResolver, S>>,
+    resolution_cache: Arc<dyn ResolutionCache>,
+    upstream: Arc<UpstreamTransport>,
+    xrpc_limits: Arc<crate::limit::XrpcConcurrencyLimits>,
+    response_cache: Arc<dyn crate::cache::ResponseCache>,
+    audit: Arc<dyn crate::audit::AuditSink>,
+    pages: Arc<dyn crate::pages::PageRenderer>,
+    // Runtime maintenance window; `None` or a past instant means normal
+    // operation
+    maintenance_until: Arc<std::sync::Mutex<Option<chrono::DateTime<chrono::Utc>>>>,
 }
 
 impl<S, K> OAuthProxyServer<S, K>
@@ -44,8 +59,36 @@ where
     }
 
     /// Create the axum router with all OAuth endpoints.
+    ///
+    /// Routes are mounted at the paths in [`ProxyConfig::endpoints`], so
+    /// they always match what the metadata documents advertise. Request
+    /// bodies are capped before buffering: OAuth endpoints at
+    /// [`ProxyConfig::oauth_max_body_bytes`], the XRPC proxy at the larger
+    /// of the XRPC and uploadBlob limits (the per-NSID check in the
+    /// handler enforces the tighter bound). Prefixes in
+    /// [`ProxyConfig::extra_proxy_paths`] are proxied with the same
+    /// authentication, limits, and CORS policy as `/xrpc`.
+    ///
+    /// CORS is applied per endpoint group inside the router: metadata and
+    /// JWKS documents are public and answer any origin, while everything
+    /// that touches credentials follows
+    /// [`ProxyConfig::cors_allowed_origins`]. Hosts should not wrap this
+    /// router in their own `CorsLayer` — an outer layer would overwrite
+    /// these headers.
     pub fn router(&self) -> Router {
-        Router::new()
+        let endpoints = &self.config.endpoints;
+        let xrpc_body_limit = self
+            .config
+            .xrpc_max_body_bytes
+            .max(self.config.xrpc_upload_max_body_bytes);
+        // Metadata and JWKS are public documents; browsers fetch them
+        // cross-origin during discovery, so any origin may read them
+        let metadata_cors = CorsLayer::new()
+            .allow_origin(tower_http::cors::Any)
+            .allow_methods(AllowMethods::mirror_request())
+            .allow_headers(AllowHeaders::mirror_request());
+        let sensitive_cors = sensitive_cors_layer(&self.config);
+        let metadata_routes = Router::new()
             .route(
                 "/.well-known/oauth-authorization-server",
                 get(handle_oauth_metadata),
@@ -54,16 +97,461 @@ where
                 "/.well-known/oauth-protected-resource",
                 get(handle_protected_resource_metadata),
             )
-            .route("/oauth-client-metadata.json", get(handle_client_metadata))
-            .route("/oauth/jwks.json", get(handle_jwks))
-            .route("/oauth/par", post(handle_par))
-            .route("/oauth/authorize", get(handle_authorize))
-            .route("/oauth/return", get(handle_return))
-            .route("/oauth/token", post(handle_token))
-            .route("/oauth/revoke", post(handle_revoke))
-            .route("/xrpc/{*path}", any(handle_xrpc_proxy))
+            .route(&endpoints.client_metadata, get(handle_client_metadata))
+            .route(&endpoints.jwks, get(handle_jwks))
+            .layer(metadata_cors);
+        let mut oauth_routes = Router::new()
+            .route(&endpoints.par, post(handle_par))
+            .route(&endpoints.authorize, get(handle_authorize))
+            // The callback follows the override when one is configured, so
+            // the route matches the redirect URI the metadata registers
+            .route(self.config.return_path(), get(handle_return))
+            .route(&endpoints.token, post(handle_token))
+            .route(&endpoints.revoke, post(handle_revoke))
+            .route(&endpoints.introspect, post(handle_introspect))
+            .route(&endpoints.logout, any(handle_logout))
+            // Token-authenticated, so it sits with the sensitive group
+            .route(
+                &endpoints.grants,
+                get(handle_list_grants).delete(handle_revoke_grant),
+            );
+        // Operator endpoint for toggling maintenance mode; only registered
+        // when an admin token is configured
+        if self.config.admin_token.is_some() {
+            oauth_routes =
+                oauth_routes.route("/oauth/admin/maintenance", post(handle_maintenance));
+        }
+        let oauth_routes = oauth_routes
+            .layer(DefaultBodyLimit::max(self.config.oauth_max_body_bytes))
+            .layer(sensitive_cors.clone());
+        let mut xrpc_routes = Router::new().route("/xrpc/{*path}", any(handle_xrpc_proxy));
+        // Hosts can proxy authenticated PDS paths that live outside XRPC
+        // (blob routes, etc.) through the same token and DPoP handling
+        for prefix in &self.config.extra_proxy_paths {
+            let prefix = prefix.trim_end_matches('/');
+            xrpc_routes =
+                xrpc_routes.route(&format!("{}/{{*path}}", prefix), any(handle_xrpc_proxy));
+        }
+        let xrpc_routes = xrpc_routes
+            .layer(DefaultBodyLimit::max(xrpc_body_limit))
+            .layer(sensitive_cors);
+        metadata_routes
+            .merge(oauth_routes)
+            .merge(xrpc_routes)
+            // Outermost layer so every response — error responses included —
+            // carries an X-Request-Id the client can quote back
+            .layer(axum::middleware::from_fn(request_id_middleware))
             .with_state(self.clone())
     }
+
+    /// Authenticate a request from its headers and return the session
+    /// identity.
+    ///
+    /// For host applications that mount their own routes next to the proxy
+    /// and need to answer "who is this request" without reaching into
+    /// [`TokenManager`] or the stores. Validates the downstream access
+    /// token (JWT or opaque) exactly as the XRPC proxy does, then resolves
+    /// the active upstream session for the DID. Note this does not check
+    /// the DPoP proof on the request; callers proxying upstream should go
+    /// through the XRPC handler instead.
+    pub async fn authenticate(&self, headers: &HeaderMap) -> Result<AuthenticatedSession> {
+        let auth_header = headers
+            .get("Authorization")
+            .and_then(|v| v.to_str().ok())
+            .ok_or(Error::Unauthorized)?;
+
+        let token = auth_header
+            .strip_prefix("DPoP ")
+            .or_else(|| auth_header.strip_prefix("Bearer "))
+            .ok_or(Error::Unauthorized)?;
+
+        let claims = validate_downstream_token(self, token).await?;
+
+        // JWTs carry the session ID since it became a claim; older tokens
+        // and opaque tokens fall back to the active-session lookup
+        let session_id = match claims.session_id.clone() {
+            Some(session_id) => Some(session_id),
+            None => self.session_store.get_active_session(&claims.sub).await?,
+        };
+
+        // The downstream session record carries the client_id and activity
+        // timestamp; keyed by the client's DPoP thumbprint, which the
+        // token's cnf claim pins
+        let session = self.session_store.get_by_dpop_jkt(&claims.cnf.jkt).await?;
+        let client_id = session
+            .as_ref()
+            .and_then(|s| s.downstream_client_id.clone());
+        let last_used_at = session.as_ref().map(|s| s.last_used_at);
+
+        Ok(AuthenticatedSession {
+            did: claims.sub,
+            scope: claims.scope,
+            client_id,
+            session_id,
+            last_used_at,
+            expires_at: chrono::DateTime::from_timestamp(claims.exp, 0)
+                .unwrap_or_else(chrono::Utc::now),
+        })
+    }
+
+    /// Rotate the upstream DPoP key recorded for a session, returning the
+    /// new key's thumbprint.
+    ///
+    /// A fresh P-256 keypair is created and persisted through
+    /// [`KeyStore::create_dpop_key`], the session's key mapping is
+    /// repointed at it, and the old key is removed from the key store.
+    /// Upstream tokens already bound to the old key stay usable only
+    /// until their next refresh; PDSes that bind refresh tokens to the
+    /// DPoP key will then require re-authorization, so rotate when the
+    /// old key may be compromised, not on a routine schedule.
+    pub async fn rotate_session_dpop_key(&self, session_id: &str) -> Result<String> {
+        let old = self.session_store.get_session_dpop_key(session_id).await?;
+
+        let (new_jkt, new_key) = self.key_store.create_dpop_key().await?;
+        self.session_store
+            .store_session_dpop_key(session_id, new_jkt.clone(), new_key)
+            .await?;
+
+        if let Some((old_jkt, _)) = old {
+            self.key_store.delete_dpop_key(&old_jkt).await?;
+        }
+
+        tracing::info!("rotated upstream DPoP key for session {}", session_id);
+        Ok(new_jkt)
+    }
+
+    /// Drop the cached signing-key material so the next request re-reads
+    /// the key store. Call after rotating the proxy's signing key; without
+    /// it the old key stays trusted until the cache TTL elapses.
+    pub fn invalidate_key_material(&self) {
+        self.key_material.invalidate();
+    }
+
+    /// Enter maintenance mode until `until`.
+    ///
+    /// While the window is open, new authorizations (PAR, authorize) and
+    /// token grants answer 503 with a `Retry-After` for the remainder of
+    /// the window, and the XRPC proxy rejects everything but read-only
+    /// requests — so store migrations can run without racing writes. The
+    /// window expires on its own; there is nothing to clean up after a
+    /// crashed maintenance job.
+    pub fn set_maintenance_until(&self, until: chrono::DateTime<chrono::Utc>) {
+        *self.maintenance_until.lock().unwrap() = Some(until);
+        tracing::warn!("entering maintenance mode until {}", until);
+    }
+
+    /// Leave maintenance mode before the window expires.
+    pub fn clear_maintenance(&self) {
+        *self.maintenance_until.lock().unwrap() = None;
+        tracing::warn!("leaving maintenance mode");
+    }
+
+    /// End of the current maintenance window, if one is open.
+    pub fn maintenance_until(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        (*self.maintenance_until.lock().unwrap())
+            .filter(|until| *until > chrono::Utc::now())
+    }
+
+    /// Fail with a 503 carrying the remaining window as `Retry-After`
+    /// when maintenance mode is on.
+    fn check_maintenance(&self) -> Result<()> {
+        if let Some(until) = self.maintenance_until() {
+            let remaining = (until - chrono::Utc::now()).num_seconds().max(1) as u64;
+            return Err(Error::Maintenance(remaining));
+        }
+        Ok(())
+    }
+
+    /// Run one garbage-collection pass, returning how many records were
+    /// removed.
+    ///
+    /// Expired refresh token mappings are always swept. When
+    /// [`ProxyConfig::session_retention_seconds`] is set, upstream
+    /// sessions idle longer than the retention window are deleted through
+    /// [`OAuthSessionStore::delete_idle_sessions_before`], and each purge
+    /// is reported as a [`SessionPurged`](crate::audit::AuditEvent)
+    /// record so hosts can mirror the cleanup in their own tables.
+    /// [`spawn_cleanup_worker`](Self::spawn_cleanup_worker) calls this on
+    /// an interval; call it directly to run cleanup on your own schedule.
+    pub async fn run_cleanup(&self) -> Result<u64> {
+        let now = chrono::Utc::now();
+        let mut removed = self.session_store.delete_expired_refresh_tokens(now).await?;
+
+        if self.config.session_retention_seconds > 0 {
+            let cutoff = now - chrono::Duration::seconds(self.config.session_retention_seconds);
+            let purged = self
+                .session_store
+                .delete_idle_sessions_before(cutoff)
+                .await?;
+            for (did, session_id) in &purged {
+                self.audit
+                    .record(
+                        crate::audit::AuditRecord::new(crate::audit::AuditEvent::SessionPurged)
+                            .with_did(did.clone())
+                            .with_detail(format!(
+                                "session {} idle past retention window",
+                                session_id
+                            )),
+                    )
+                    .await;
+            }
+            removed += purged.len() as u64;
+        }
+
+        if removed > 0 {
+            tracing::info!("cleanup pass removed {} stale records", removed);
+        }
+        Ok(removed)
+    }
+
+    /// Probe the public deployment the way a client would and report
+    /// what was found.
+    ///
+    /// Checks that the public host is HTTPS, the authorization-server
+    /// metadata is served there with the configured issuer, the
+    /// published JWKS contains the active signing key, the registered
+    /// redirect URIs are well-formed, the DPoP nonce secret isn't the
+    /// insecure default, and the local clock agrees with whatever is
+    /// fronting the deployment. Run it at startup or from an operator
+    /// CLI before taking traffic; a report that fails
+    /// [`passed`](crate::deploy::DeploymentReport::passed) means clients
+    /// will not be able to complete flows against this deployment.
+    pub async fn validate_deployment(&self) -> crate::deploy::DeploymentReport {
+        use base64::Engine;
+        use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+
+        let mut report = crate::deploy::DeploymentReport::new();
+        let config = &self.config;
+
+        // Public host scheme: clients refuse plain-http issuers
+        let host_name = config.host.host_str().unwrap_or("");
+        let local_host = matches!(host_name, "localhost" | "127.0.0.1" | "::1");
+        match config.host.scheme() {
+            "https" => report.pass(
+                "host_scheme",
+                format!("public host {} uses https", config.host),
+            ),
+            "http" if local_host => report.warn(
+                "host_scheme",
+                "public host is plain http; acceptable only for local development",
+            ),
+            other => report.fail(
+                "host_scheme",
+                format!("public host scheme is {:?}; clients require https", other),
+            ),
+        }
+
+        // Metadata document: reachable at the public host, valid JSON,
+        // and advertising the configured issuer. The response's Date
+        // header doubles as the clock-sanity reference below
+        let metadata_url = config.endpoint_url("/.well-known/oauth-authorization-server");
+        let mut observed_date = None;
+        match reqwest::get(&metadata_url).await {
+            Ok(response) => {
+                observed_date = response
+                    .headers()
+                    .get(http::header::DATE)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| chrono::DateTime::parse_from_rfc2822(v).ok())
+                    .map(|t| t.with_timezone(&chrono::Utc));
+                if !response.status().is_success() {
+                    report.fail(
+                        "metadata_document",
+                        format!("{} answered {}", metadata_url, response.status()),
+                    );
+                } else {
+                    match response.json::<serde_json::Value>().await {
+                        Ok(doc) => {
+                            let published =
+                                doc.get("issuer").and_then(|v| v.as_str()).unwrap_or("");
+                            if published == config.issuer() {
+                                report.pass(
+                                    "metadata_document",
+                                    format!("served at {} with matching issuer", metadata_url),
+                                );
+                            } else {
+                                report.fail(
+                                    "metadata_document",
+                                    format!(
+                                        "published issuer {:?} does not match configured \
+                                         {:?}; is a stale deployment answering?",
+                                        published,
+                                        config.issuer()
+                                    ),
+                                );
+                            }
+                        }
+                        Err(e) => report.fail(
+                            "metadata_document",
+                            format!("metadata document is not valid JSON: {}", e),
+                        ),
+                    }
+                }
+            }
+            Err(e) => report.fail(
+                "metadata_document",
+                format!("fetch of {} failed: {}", metadata_url, e),
+            ),
+        }
+
+        // JWKS: the published document must carry the signing key the
+        // proxy is actually using, or every issued token fails validation
+        match self.key_material.verifying_key().await {
+            Ok(verifying_key) => {
+                let point = verifying_key.to_encoded_point(false);
+                let expected_x = point.x().map(|x| URL_SAFE_NO_PAD.encode(x.as_slice()));
+                let expected_y = point.y().map(|y| URL_SAFE_NO_PAD.encode(y.as_slice()));
+                let jwks_url = config.jwks_url();
+                match fetch_json(&jwks_url).await {
+                    Ok(doc) => {
+                        let published = doc
+                            .get("keys")
+                            .and_then(|v| v.as_array())
+                            .is_some_and(|keys| {
+                                keys.iter().any(|key| {
+                                    key.get("x").and_then(|v| v.as_str())
+                                        == expected_x.as_deref()
+                                        && key.get("y").and_then(|v| v.as_str())
+                                            == expected_y.as_deref()
+                                })
+                            });
+                        if published {
+                            report.pass(
+                                "jwks_document",
+                                format!("{} carries the active signing key", jwks_url),
+                            );
+                        } else {
+                            report.fail(
+                                "jwks_document",
+                                format!(
+                                    "{} does not carry the active signing key; issued \
+                                     tokens will fail validation",
+                                    jwks_url
+                                ),
+                            );
+                        }
+                    }
+                    Err(e) => report.fail(
+                        "jwks_document",
+                        format!("fetch of {} failed: {}", jwks_url, e),
+                    ),
+                }
+            }
+            Err(e) => report.fail(
+                "jwks_document",
+                format!("signing key unavailable from the key store: {}", e),
+            ),
+        }
+
+        // Redirect URIs: every registered URI must parse, or the
+        // upstream PDS rejects the client metadata outright
+        let mut bad_redirects = Vec::new();
+        for uri in config.client_metadata.redirect_uris.iter() {
+            let uri = uri.to_string();
+            if url::Url::parse(&uri).is_err() {
+                bad_redirects.push(uri);
+            }
+        }
+        if bad_redirects.is_empty() {
+            report.pass(
+                "redirect_uris",
+                format!(
+                    "{} registered redirect URI(s) parse",
+                    config.client_metadata.redirect_uris.len()
+                ),
+            );
+        } else {
+            report.fail(
+                "redirect_uris",
+                format!("unparseable redirect URI(s): {}", bad_redirects.join(", ")),
+            );
+        }
+
+        // Nonce secret: the compiled-in default is public knowledge
+        let secret: &[u8] = config.dpop_nonce_hmac_secret.as_ref();
+        if secret == b"insecure-default-dpop-nonce-secret".as_slice() {
+            report.fail(
+                "dpop_nonce_secret",
+                "DPoP nonce secret is the insecure compiled-in default; \
+                 set `dpop_nonce_hmac_secret`",
+            );
+        } else if secret.len() < 32 {
+            report.warn(
+                "dpop_nonce_secret",
+                format!("DPoP nonce secret is {} bytes; 32+ recommended", secret.len()),
+            );
+        } else {
+            report.pass("dpop_nonce_secret", "DPoP nonce secret is set");
+        }
+
+        // Clock sanity: proofs and tokens carry timestamps, so a skewed
+        // clock rejects valid traffic. Compared against the Date header
+        // whatever fronts the deployment stamped on the metadata fetch
+        match observed_date {
+            Some(server_time) => {
+                let skew = (chrono::Utc::now() - server_time).num_seconds().abs();
+                if skew > 60 {
+                    report.warn(
+                        "clock",
+                        format!(
+                            "local clock is {}s from the deployment edge; DPoP \
+                             proofs may be rejected",
+                            skew
+                        ),
+                    );
+                } else {
+                    report.pass("clock", format!("local clock within {}s of the edge", skew));
+                }
+            }
+            None => report.warn(
+                "clock",
+                "no Date header observed on the metadata fetch; skew not assessed",
+            ),
+        }
+
+        report
+    }
+
+    /// Spawn a background task that calls [`run_cleanup`](Self::run_cleanup)
+    /// every [`ProxyConfig::cleanup_interval_seconds`]. Errors from a pass
+    /// are logged and the worker keeps its schedule; drop or abort the
+    /// returned handle to stop it.
+    pub fn spawn_cleanup_worker(&self) -> tokio::task::JoinHandle<()> {
+        let server = self.clone();
+        let period =
+            std::time::Duration::from_secs(server.config.cleanup_interval_seconds.max(1) as u64);
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            // The first tick fires immediately; skip it so startup isn't
+            // slowed by a sweep of a store that was just swept
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(e) = server.run_cleanup().await {
+                    tracing::warn!("session cleanup pass failed: {}", e);
+                }
+            }
+        })
+    }
+}
+
+/// The identity behind an authenticated request, as returned by
+/// [`OAuthProxyServer::authenticate`].
+#[derive(Debug, Clone)]
+pub struct AuthenticatedSession {
+    /// Account DID the token authenticates
+    pub did: String,
+    /// Scope granted to the token
+    pub scope: String,
+    /// Downstream client the session belongs to, when known
+    pub client_id: Option<String>,
+    /// Active upstream session ID for the DID, if one exists
+    pub session_id: Option<String>,
+    /// When the session last saw a proxied request or token refresh, if
+    /// a session record exists (coarse: persisted at most once a minute)
+    pub last_used_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// When the presented access token expires
+    pub expires_at: chrono::DateTime<chrono::Utc>,
 }
 
 // OAuth handler functions
@@ -76,45 +564,41 @@ where
     S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
     K: KeyStore + Clone + 'static,
 {
-    let base_url = server.config.host.as_str().trim_end_matches('/');
+    let config = &server.config;
 
     let metadata = serde_json::json!({
-        "issuer": base_url,
-        "request_parameter_supported": true,
+        "issuer": config.issuer(),
+        // JAR request objects are not implemented; request_uri comes from PAR
+        "request_parameter_supported": false,
         "request_uri_parameter_supported": true,
         "require_request_uri_registration": true,
         "scopes_supported": ["atproto", "transition:generic", "transition:chat.bsky"],
         "subject_types_supported": ["public"],
         "response_types_supported": ["code"],
         "response_modes_supported": ["query", "fragment", "form_post"],
-        "grant_types_supported": ["authorization_code", "refresh_token"],
+        "grant_types_supported": grant_types_supported(&server.config),
         "code_challenge_methods_supported": ["S256"],
         "ui_locales_supported": ["en-US"],
         "display_values_supported": ["page", "popup", "touch"],
-        "authorization_response_iss_parameter_supported": true,
+        "authorization_response_iss_parameter_supported": config.authorization_response_iss,
         "request_object_encryption_alg_values_supported": [],
         "request_object_encryption_enc_values_supported": [],
-        "jwks_uri": format!("{}/oauth/jwks", base_url),
-        "authorization_endpoint": format!("{}/oauth/authorize", base_url),
-        "token_endpoint": format!("{}/oauth/token", base_url),
-        "token_endpoint_auth_methods_supported": ["none", "private_key_jwt"],
-        "revocation_endpoint": format!("{}/oauth/revoke", base_url),
-        "introspection_endpoint": format!("{}/oauth/introspect", base_url),
-        "pushed_authorization_request_endpoint": format!("{}/oauth/par", base_url),
-        "require_pushed_authorization_requests": true,
+        "jwks_uri": config.jwks_url(),
+        "authorization_endpoint": config.authorize_url(),
+        "token_endpoint": config.token_url(),
+        // Advertised capabilities come from the config and match what the
+        // endpoints actually enforce; client assertions are only ever
+        // verified with ES256 keys
+        "token_endpoint_auth_methods_supported": config.token_endpoint_auth_methods,
+        "revocation_endpoint": config.revoke_url(),
+        "end_session_endpoint": config.logout_url(),
+        "introspection_endpoint": config.introspect_url(),
+        "pushed_authorization_request_endpoint": config.par_url(),
+        "require_pushed_authorization_requests": config.require_par,
         "client_id_metadata_document_supported": true,
-        "request_object_signing_alg_values_supported": [
-            "RS256", "RS384", "RS512", "PS256", "PS384", "PS512",
-            "ES256", "ES256K", "ES384", "ES512", "none"
-        ],
-        "token_endpoint_auth_signing_alg_values_supported": [
-            "RS256", "RS384", "RS512", "PS256", "PS384", "PS512",
-            "ES256", "ES256K", "ES384", "ES512"
-        ],
-        "dpop_signing_alg_values_supported": [
-            "RS256", "RS384", "RS512", "PS256", "PS384", "PS512",
-            "ES256", "ES256K", "ES384", "ES512"
-        ],
+        "request_object_signing_alg_values_supported": [],
+        "token_endpoint_auth_signing_alg_values_supported": ["ES256"],
+        "dpop_signing_alg_values_supported": config.dpop_signing_algs,
     });
 
     Ok((StatusCode::OK, Json(metadata)).into_response())
@@ -128,14 +612,14 @@ where
     S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
     K: KeyStore + Clone + 'static,
 {
-    let base_url = server.config.host.as_str().trim_end_matches('/');
+    let issuer = server.config.issuer();
 
     let metadata = serde_json::json!({
-        "resource": base_url,
-        "authorization_servers": [base_url],
+        "resource": issuer,
+        "authorization_servers": [issuer],
         "scopes_supported": [],
         "bearer_methods_supported": ["header"],
-        "resource_documentation": format!("{}/xrpc", base_url),
+        "resource_documentation": server.config.endpoint_url("/xrpc"),
     });
 
     Ok((StatusCode::OK, Json(metadata)).into_response())
@@ -236,8 +720,7 @@ where
 {
     use base64::Engine;
 
-    let signing_key = server.key_store.get_signing_key().await?;
-    let verifying_key = signing_key.verifying_key();
+    let verifying_key = server.key_material.verifying_key().await?;
     let encoded_point = verifying_key.to_encoded_point(false);
 
     let x = encoded_point
@@ -274,6 +757,7 @@ where
     K: KeyStore + Clone + 'static,
 {
     tracing::info!("handling PAR request");
+    server.check_maintenance()?;
 
     // Extract and parse DPoP proof
     let dpop_proof_str = headers
@@ -283,7 +767,7 @@ where
 
     // Get HTTP method and URL for DPoP validation
     let http_method = "POST";
-    let http_uri = format!("{}/oauth/par", server.config.host);
+    let http_uri = server.config.par_url();
 
     // Parse the PAR parameters - try JSON first, then form-encoded
     let params: PARRequest = if let Some(content_type) = headers.get("content-type") {
@@ -320,46 +804,90 @@ where
             "only S256 code_challenge_method supported".to_string(),
         ));
     }
+    let response_mode = validate_response_mode(params.response_mode.as_deref())?;
+
+    // Confidential clients authenticate with a private_key_jwt assertion;
+    // public clients rely on DPoP binding alone
+    let auth_method = match client_auth_method_from_assertion(
+        &server.config,
+        &params.client_id,
+        params.client_assertion_type.as_deref(),
+        params.client_assertion.as_deref(),
+    )
+    .await
+    {
+        Ok(method) => method,
+        Err(e) => {
+            // Covers both bad assertions and metadata documents that
+            // fail to fetch or validate
+            server
+                .audit
+                .record(
+                    crate::audit::AuditRecord::new(
+                        crate::audit::AuditEvent::ClientAuthFailure,
+                    )
+                    .with_request_context(&headers)
+                    .with_client_id(params.client_id.as_str())
+                    .with_detail(e.to_string()),
+                )
+                .await;
+            return Err(e);
+        }
+    };
 
-    // Configure DPoP verification with HMAC-based nonces
-    // The nonces are stateless and bound to the client
-    let hmac_config = dpop_verifier::HmacConfig::new(
-        &server.config.dpop_nonce_hmac_secret,
-        300,  // 5 minute max age
-        true, // bind to HTU/HTM
-        true, // bind to JKT
-        true, // bind to client
-    );
-
-    // Create a simple in-memory replay store for this request
-    let mut replay_store = SimpleReplayStore::new(server.session_store.clone());
+    if auth_method == "private_key_jwt" {
+        tracing::info!(
+            "verified private_key_jwt assertion for client_id: {}",
+            params.client_id
+        );
+    }
 
-    // Verify the DPoP proof using builder pattern
-    let verifier = dpop_verifier::DpopVerifier::new()
-        .with_max_age_seconds(300)
-        .with_future_skew_seconds(5)
-        .with_nonce_mode(dpop_verifier::NonceMode::Hmac(hmac_config))
-        .with_client_binding(params.client_id.clone());
+    // dpop-verifier only handles P-256 keys; ES256K and EdDSA proofs
+    // (also advertised in the metadata) take the manual path below
+    let proof_alg =
+        crate::jose::parse_compact(dpop_proof_str, &configured_dpop_algs(&server.config))?
+            .algorithm();
 
-    let verified = verifier
-        .verify(
-            &mut replay_store,
+    let downstream_dpop_jkt = if proof_alg == crate::jose::JwsAlgorithm::Es256 {
+        let primary = verify_es256_dpop_proof(
+            &server,
+            &server.config.dpop_nonce_hmac_secret,
             dpop_proof_str,
             &http_uri,
             http_method,
-            None, // no access token for PAR
+            &params.client_id,
         )
-        .await
-        .map_err(|e| match e {
-            dpop_verifier::DpopError::UseDpopNonce { nonce } => {
-                // Return a special error that includes the nonce
-                // The caller will need to return this as a DPoP-Nonce header
-                Error::DpopNonceRequired(nonce)
+        .await;
+
+        match primary {
+            Ok(jkt) => jkt,
+            Err(Error::DpopNonceRequired(nonce)) => {
+                // During secret rotation a nonce minted under the previous
+                // secret should still validate; fresh nonces always come
+                // from the primary, so a client that fails both just
+                // retries with the re-issued nonce
+                let previous = match &server.config.dpop_nonce_hmac_secret_previous {
+                    Some(prev) => {
+                        verify_es256_dpop_proof(
+                            &server,
+                            prev,
+                            dpop_proof_str,
+                            &http_uri,
+                            http_method,
+                            &params.client_id,
+                        )
+                        .await
+                        .ok()
+                    }
+                    None => None,
+                };
+                previous.ok_or(Error::DpopNonceRequired(nonce))?
             }
-            _ => Error::InvalidRequest(format!("invalid DPoP proof: {}", e)),
-        })?;
-
-    let downstream_dpop_jkt = verified.jkt;
+            Err(e) => return Err(e),
+        }
+    } else {
+        verify_non_es256_dpop_proof(&server, dpop_proof_str, &http_uri, http_method).await?
+    };
 
     tracing::info!("validated DPoP proof with JKT: {}", downstream_dpop_jkt);
     tracing::info!("PAR request state: {:?}", params.state);
@@ -374,7 +902,7 @@ where
         session.id
     } else {
         tracing::info!("no existing session found, creating new session for JKT");
-        let session_id = generate_random_string(32);
+        let session_id = SecureToken::with_entropy(server.config.token_entropy_bytes).into_string();
         tracing::info!("created new session: {}", session_id);
         session_id
     };
@@ -384,10 +912,40 @@ where
         params.client_id
     );
 
+    // Cap outstanding PARs so one client can't fill the store; entries
+    // expire after 90 seconds, so a legitimate client drains quickly
+    let max_pending = server.config.max_pending_par_per_client;
+    if max_pending > 0 {
+        let active = server
+            .session_store
+            .count_active_par_requests(&params.client_id, chrono::Utc::now())
+            .await?;
+        if active >= max_pending {
+            tracing::warn!(
+                "client {} has {} outstanding pushed authorization requests, rejecting",
+                params.client_id,
+                active
+            );
+            return Err(Error::InvalidRequest(
+                "too many pending authorization requests".to_string(),
+            ));
+        }
+    }
+
     // Generate request_uri
     let request_uri = format!(
         "urn:ietf:params:oauth:request_uri:{}",
-        generate_random_string(32)
+        SecureToken::with_entropy(server.config.token_entropy_bytes)
+    );
+
+    // Every flow gets a correlation ID here; it rides through
+    // PARData/DownstreamClientInfo/PendingAuth so the authorize,
+    // callback, and token handlers can tag their log lines with it
+    let flow_id = new_flow_id();
+    tracing::info!(
+        flow_id = %flow_id,
+        client_id = %params.client_id,
+        "pushed authorization request accepted"
     );
 
     // Store PAR data with 90 second expiry (per spec)
@@ -395,12 +953,15 @@ where
         client_id: params.client_id,
         redirect_uri: params.redirect_uri,
         response_type: params.response_type,
+        response_mode,
         state: params.state,
         scope: params.scope,
         code_challenge: params.code_challenge,
         code_challenge_method: params.code_challenge_method,
         login_hint: params.login_hint,
         downstream_dpop_jkt: downstream_dpop_jkt.clone(),
+        auth_method: auth_method.to_string(),
+        flow_id: Some(flow_id),
         expires_at: chrono::Utc::now() + chrono::Duration::seconds(90),
     };
 
@@ -412,10 +973,16 @@ where
     // Store downstream client info keyed by JKT
     // This will be retrieved in the callback after we look up the session
     let downstream_info = crate::store::DownstreamClientInfo {
+        client_id: Some(par_data.client_id),
         redirect_uri: par_data.redirect_uri,
         state: par_data.state,
         response_type: par_data.response_type,
+        response_mode: par_data.response_mode,
         scope: par_data.scope,
+        auth_method: par_data.auth_method,
+        // The upstream flow hasn't started yet, so there's no issuer to pin
+        upstream_issuer: None,
+        flow_id: par_data.flow_id,
         expires_at: par_data.expires_at,
     };
 
@@ -441,72 +1008,135 @@ where
 }
 
 /// Handle authorization request - redirect to upstream PDS.
+///
+/// Browser-facing: failures render as an HTML error page through the
+/// configured page renderer, with the original authorize URL offered as
+/// a retry link.
 async fn handle_authorize<S, K>(
     State(server): State<OAuthProxyServer<S, K>>,
+    OriginalUri(uri): OriginalUri,
     Query(params): Query<AuthorizeParams>,
+) -> Response
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    match authorize_inner(server.clone(), params).await {
+        Ok(response) => response,
+        Err(error) => render_error_page(&server, error, Some(&uri.to_string())),
+    }
+}
+
+async fn authorize_inner<S, K>(
+    server: OAuthProxyServer<S, K>,
+    params: AuthorizeParams,
 ) -> Result<Response>
 where
     S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
     K: KeyStore + Clone + 'static,
 {
     tracing::info!("handling authorize request");
+    server.check_maintenance()?;
 
     // If request_uri is provided, retrieve PAR data
-    let (client_id, redirect_uri, response_type, state, scope, login_hint, _downstream_dpop_jkt) =
-        if let Some(ref request_uri) = params.request_uri {
-            tracing::info!("using PAR request_uri: {}", request_uri);
-
-            let par_data = server
-                .session_store
-                .consume_par_data(request_uri)
-                .await?
-                .ok_or_else(|| {
-                    Error::InvalidRequest("invalid or expired request_uri".to_string())
-                })?;
-
-            // Check expiry
-            if par_data.expires_at < chrono::Utc::now() {
-                return Err(Error::InvalidRequest("request_uri expired".to_string()));
-            }
+    let (
+        client_id,
+        redirect_uri,
+        response_type,
+        response_mode,
+        state,
+        scope,
+        login_hint,
+        _downstream_dpop_jkt,
+        auth_method,
+        flow_id,
+    ) = if let Some(ref request_uri) = params.request_uri {
+        tracing::info!("using PAR request_uri: {}", request_uri);
+
+        // Single-use and auto-expiring: a replayed or stale request_uri
+        // reads as absent
+        let par_data = server
+            .session_store
+            .consume_valid_par_data(request_uri)
+            .await?
+            .ok_or_else(|| Error::InvalidRequest("invalid or expired request_uri".to_string()))?;
+
+        (
+            par_data.client_id,
+            par_data.redirect_uri,
+            par_data.response_type,
+            par_data.response_mode,
+            par_data.state,
+            par_data.scope,
+            par_data.login_hint,
+            Some(par_data.downstream_dpop_jkt),
+            par_data.auth_method,
+            // Flows without one (stored before the column existed) get a
+            // fresh ID so the rest of the flow still correlates
+            par_data.flow_id.unwrap_or_else(new_flow_id),
+        )
+    } else {
+        // Direct authorize is only allowed when PAR isn't mandated
+        if server.config.require_par {
+            return Err(Error::InvalidRequest(
+                "pushed authorization request required".to_string(),
+            ));
+        }
 
-            (
-                par_data.client_id,
-                par_data.redirect_uri,
-                par_data.response_type,
-                par_data.state,
-                par_data.scope,
-                par_data.login_hint,
-                Some(par_data.downstream_dpop_jkt),
-            )
-        } else {
-            // Use parameters from query string
-            (
-                params
-                    .client_id
-                    .ok_or_else(|| Error::InvalidRequest("missing client_id".to_string()))?,
-                params
-                    .redirect_uri
-                    .ok_or_else(|| Error::InvalidRequest("missing redirect_uri".to_string()))?,
-                params
-                    .response_type
-                    .ok_or_else(|| Error::InvalidRequest("missing response_type".to_string()))?,
-                params.state,
-                params.scope,
-                None, // no login_hint in direct authorize
-                None, // no JKT in direct authorize
-            )
-        };
+        // Use parameters from query string
+        (
+            params
+                .client_id
+                .ok_or_else(|| Error::InvalidRequest("missing client_id".to_string()))?,
+            params
+                .redirect_uri
+                .ok_or_else(|| Error::InvalidRequest("missing redirect_uri".to_string()))?,
+            params
+                .response_type
+                .ok_or_else(|| Error::InvalidRequest("missing response_type".to_string()))?,
+            validate_response_mode(params.response_mode.as_deref())?,
+            params.state,
+            params.scope,
+            None,               // no login_hint in direct authorize
+            None,               // no JKT in direct authorize
+            "none".to_string(), // direct authorize cannot carry a client assertion
+            // Direct authorize is where this flow starts, so mint the ID here
+            new_flow_id(),
+        )
+    };
 
-    tracing::info!("handling authorize request for client_id: {}", client_id);
+    tracing::info!(flow_id = %flow_id, "handling authorize request for client_id: {}", client_id);
 
     // Get the user identifier from login_hint
     let user_identifier =
         login_hint.ok_or_else(|| Error::InvalidRequest("missing login_hint".to_string()))?;
 
+    // Resolve through the TTL cache so repeated logins don't pay for
+    // plc.directory and handle lookups every time. Cache hits hand
+    // start_auth the DID directly; transient failures fall back to the
+    // upstream resolver with the raw identifier.
+    let user_identifier =
+        match crate::resolution::resolve_identity(&*server.resolution_cache, &user_identifier)
+            .await
+        {
+            Ok(resolved) => {
+                tracing::debug!("resolved {} to {}", user_identifier, resolved.did);
+                resolved.did
+            }
+            Err(e @ Error::InvalidRequest(_)) => return Err(e),
+            Err(e) => {
+                tracing::warn!(
+                    "cached resolution failed ({}), falling back to upstream resolver",
+                    e
+                );
+                user_identifier
+            }
+        };
+
     // Use jacquard OAuth client to start upstream auth flow
     // This will resolve the PDS, create PAR, and return the authorization URL
     // Generate our own state to link upstream and downstream flows
-    let proxy_state = generate_random_string(32);
+    let proxy_state = SecureToken::with_entropy(server.config.token_entropy_bytes).into_string();
 
     // Parse the scope from the client request
     let requested_scopes: Vec<jacquard_oauth::scopes::Scope> = scope
@@ -540,13 +1170,24 @@ where
             Error::InvalidRequest(format!("failed to start auth: {}", e))
         })?;
 
+    // Pin the authorization server we're sending the user to, so the
+    // callback can reject an `iss` from anywhere else (mix-up protection)
+    let upstream_issuer = url::Url::parse(&auth_url)
+        .ok()
+        .map(|u| u.origin().ascii_serialization());
+
     // Store downstream client info by proxy_state
     // When callback returns with this state, we can retrieve the client info directly
     let downstream_info = crate::store::DownstreamClientInfo {
+        client_id: Some(client_id.clone()),
         redirect_uri: redirect_uri.clone(),
         state: state.clone(),
         response_type: response_type.clone(),
+        response_mode: response_mode.clone(),
         scope: scope.clone(),
+        auth_method: auth_method.clone(),
+        upstream_issuer,
+        flow_id: Some(flow_id.clone()),
         expires_at: chrono::Utc::now() + chrono::Duration::minutes(10),
     };
 
@@ -559,14 +1200,32 @@ where
         "stored downstream client info for proxy_state: {}",
         proxy_state
     );
-    tracing::info!("redirecting to upstream PDS auth: {}", auth_url);
+    tracing::info!(flow_id = %flow_id, "redirecting to upstream PDS auth: {}", auth_url);
     Ok(Redirect::to(&auth_url).into_response())
 }
 
 /// Handle OAuth callback from upstream PDS.
+///
+/// Browser-facing like [`handle_authorize`]: failures render as an HTML
+/// error page. No retry link is offered — states and codes are single
+/// use, so replaying the callback cannot succeed.
 async fn handle_return<S, K>(
     State(server): State<OAuthProxyServer<S, K>>,
     Query(params): Query<CallbackParams>,
+) -> Response
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    match return_inner(server.clone(), params).await {
+        Ok(response) => response,
+        Err(error) => render_error_page(&server, error, None),
+    }
+}
+
+async fn return_inner<S, K>(
+    server: OAuthProxyServer<S, K>,
+    params: CallbackParams,
 ) -> Result<Response>
 where
     S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
@@ -593,6 +1252,48 @@ where
         Error::InvalidRequest("missing state".to_string())
     })?;
 
+    // Consume the state before touching the code: each state answers exactly
+    // one callback, even if the exchange below fails
+    let downstream_client_info = server
+        .session_store
+        .consume_downstream_client_info(state)
+        .await?
+        .ok_or_else(|| {
+            tracing::error!("no client info found for state: {}", state);
+            Error::InvalidRequest("session not found".to_string())
+        })?;
+
+    // Pick the flow's correlation ID back up for the rest of the callback
+    let flow_id = downstream_client_info
+        .flow_id
+        .clone()
+        .unwrap_or_else(new_flow_id);
+    tracing::info!(flow_id = %flow_id, "resumed flow at upstream callback");
+
+    if downstream_client_info.expires_at < chrono::Utc::now() {
+        tracing::warn!("callback arrived after the authorization request expired");
+        return Err(Error::InvalidRequest(
+            "authorization request expired".to_string(),
+        ));
+    }
+
+    // Mix-up protection: the iss must name the authorization server we
+    // actually sent the user to, compared in constant time
+    if let Some(expected_issuer) = downstream_client_info.upstream_issuer.as_deref() {
+        let iss = params.iss.as_deref().ok_or_else(|| {
+            tracing::error!("missing iss in callback, expected {}", expected_issuer);
+            Error::InvalidRequest("missing iss".to_string())
+        })?;
+        if !constant_time_eq(iss.as_bytes(), expected_issuer.as_bytes()) {
+            tracing::error!(
+                "callback iss {} does not match expected issuer {}",
+                iss,
+                expected_issuer
+            );
+            return Err(Error::InvalidRequest("issuer mismatch".to_string()));
+        }
+    }
+
     // Exchange authorization code for upstream tokens using jacquard-oauth
     let callback_params = jacquard_oauth::types::CallbackParams {
         code: code.into(),
@@ -614,12 +1315,39 @@ where
     let account_did = session_data.account_did.to_string();
     let _pds_url = session_data.host_url.to_string();
     let upstream_session_id = session_data.session_id.to_string();
+    let granted_scope = session_data.token_set.scope.as_ref().map(|s| s.to_string());
 
     // Get the DPoP key from dpop_data
     let dpop_key = session_data.dpop_data.dpop_key.clone();
     drop(session_data); // release the read lock
 
+    // Reconcile granted vs requested: the PDS may lawfully grant less
+    // than was asked for, and everything issued downstream reflects the
+    // granted set (token responses read it back off the upstream session)
+    if let Some(granted) = granted_scope.as_deref() {
+        let requested = downstream_client_info.scope.clone().unwrap_or_else(|| {
+            server
+                .config
+                .scope
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        });
+        let missing: Vec<&str> = requested
+            .split_whitespace()
+            .filter(|scope| !granted.split_whitespace().any(|g| g == *scope))
+            .collect();
+        if !missing.is_empty() {
+            tracing::warn!(
+                "upstream downgraded the grant; requested scopes not granted: {}",
+                missing.join(" ")
+            );
+        }
+    }
+
     tracing::info!(
+        flow_id = %flow_id,
         "successfully exchanged code for upstream tokens, DID: {}, session_id: {}",
         account_did,
         upstream_session_id
@@ -635,59 +1363,100 @@ where
     let dpop_jkt = compute_jwk_thumbprint(&dpop_jwk)?;
     server
         .session_store
-        .store_session_dpop_key(&upstream_session_id, dpop_jkt, dpop_jwk)
+        .store_session_dpop_key(&upstream_session_id, dpop_jkt.clone(), dpop_jwk.clone())
         .await?;
 
-    tracing::info!("stored upstream DPoP key for session");
+    // Also record it in the key store under its thumbprint, so
+    // thumbprint-based lookups (token refresh, rotation) resolve without
+    // the store having to guess which session a key belongs to
+    server.key_store.store_dpop_key(&dpop_jkt, dpop_jwk).await?;
 
-    // Retrieve downstream client info using the proxy_state
-    let downstream_client_info = server
-        .session_store
-        .consume_downstream_client_info(state)
-        .await?
-        .ok_or_else(|| {
-            tracing::error!("no client info found for state: {}", state);
-            Error::InvalidRequest("session not found".to_string())
-        })?;
+    tracing::info!("stored upstream DPoP key for session");
 
     tracing::info!(
-        "retrieved downstream client info, redirecting to: {}",
+        "redirecting to downstream client at: {}",
         downstream_client_info.redirect_uri
     );
 
     // Generate a downstream authorization code for the client
-    let downstream_code = generate_random_string(32);
+    let downstream_code =
+        SecureToken::with_entropy(server.config.token_entropy_bytes).into_string();
 
     // Store the pending auth so we can exchange it for tokens later
     let pending_auth = crate::store::PendingAuth {
         account_did,
         upstream_session_id,
+        client_id: downstream_client_info.client_id.clone(),
         redirect_uri: downstream_client_info.redirect_uri.clone(),
         state: downstream_client_info.state.clone(),
+        auth_method: downstream_client_info.auth_method.clone(),
+        flow_id: Some(flow_id),
         expires_at: chrono::Utc::now() + chrono::Duration::minutes(10),
     };
 
+    // Codes are stored hashed so a store compromise can't replay them
+    server
+        .session_store
+        .store_pending_auth(&token_digest(&downstream_code), pending_auth.clone())
+        .await?;
+
+    // Record how the downstream client authenticated so confidential
+    // sessions can be distinguished from public ones later
     server
         .session_store
-        .store_pending_auth(&downstream_code, pending_auth.clone())
+        .store_session_auth_method(
+            &pending_auth.upstream_session_id,
+            downstream_client_info.auth_method.clone(),
+        )
         .await?;
 
-    // Redirect back to the client with the downstream authorization code
-    // Use hash fragment instead of query params (OAuth implicit flow style)
-    // Include iss (issuer) parameter for security
-    let issuer = server.config.host.to_string();
-    let issuer = issuer.trim_end_matches('/');
-    let redirect_url = format!(
-        "{}#code={}&state={}&iss={}",
-        pending_auth.redirect_uri,
+    // Deliver the downstream authorization code per the requested
+    // response_mode, with the RFC 9207 iss parameter included for mix-up
+    // protection unless the deployment turned it off
+    let issuer = server
+        .config
+        .authorization_response_iss
+        .then(|| server.config.issuer());
+    let mut response_params = format!(
+        "code={}&state={}",
         urlencoding::encode(&downstream_code),
-        urlencoding::encode(&pending_auth.state.as_deref().unwrap_or("")),
-        urlencoding::encode(issuer)
+        urlencoding::encode(&pending_auth.state.as_deref().unwrap_or(""))
     );
+    if let Some(issuer) = &issuer {
+        response_params.push_str(&format!("&iss={}", urlencoding::encode(issuer)));
+    }
 
-    tracing::info!("redirecting client to: {}", redirect_url);
-
-    Ok(Redirect::to(&redirect_url).into_response())
+    match downstream_client_info.response_mode.as_str() {
+        "fragment" => {
+            let redirect_url = format!("{}#{}", pending_auth.redirect_uri, response_params);
+            tracing::info!("redirecting client to: {}", redirect_url);
+            Ok(Redirect::to(&redirect_url).into_response())
+        }
+        "form_post" => {
+            tracing::info!("posting code to client at: {}", pending_auth.redirect_uri);
+            Ok(render_form_post(
+                &pending_auth.redirect_uri,
+                &downstream_code,
+                pending_auth.state.as_deref(),
+                issuer.as_deref(),
+            )
+            .into_response())
+        }
+        // Query is the OAuth 2.1 default for the code flow
+        _ => {
+            let separator = if pending_auth.redirect_uri.contains('?') {
+                '&'
+            } else {
+                '?'
+            };
+            let redirect_url = format!(
+                "{}{}{}",
+                pending_auth.redirect_uri, separator, response_params
+            );
+            tracing::info!("redirecting client to: {}", redirect_url);
+            Ok(Redirect::to(&redirect_url).into_response())
+        }
+    }
 }
 
 /// Handle token request (exchange code for tokens or refresh tokens).
@@ -701,6 +1470,7 @@ where
     K: KeyStore + Clone + 'static,
 {
     tracing::info!("handling token request");
+    server.check_maintenance()?;
 
     // Parse token request - try JSON first, then form-encoded
     let params: TokenRequest = if let Some(content_type) = headers.get("content-type") {
@@ -721,6 +1491,18 @@ where
             .map_err(|e| Error::InvalidRequest(format!("invalid request body: {}", e)))?
     };
 
+    // Opportunistic GC: expired mappings are rejected on use anyway, this
+    // just keeps the store from accumulating dead rows
+    if let Ok(removed) = server
+        .session_store
+        .delete_expired_refresh_tokens(chrono::Utc::now())
+        .await
+    {
+        if removed > 0 {
+            tracing::debug!("garbage-collected {} expired refresh token mappings", removed);
+        }
+    }
+
     match params.grant_type.as_str() {
         "authorization_code" => {
             let code = params
@@ -728,20 +1510,71 @@ where
                 .ok_or_else(|| Error::InvalidRequest("missing code".to_string()))?;
 
             // Extract client's DPoP JKT
-            let dpop_jkt = extract_dpop_jkt(&headers)?;
+            let dpop_jkt = extract_dpop_jkt(&server.config, &headers)?;
 
-            // Look up and consume the pending auth
-            let pending_auth = server
+            // Look up and consume the pending auth; codes are stored hashed,
+            // with a plaintext fallback for rows written before hashing
+            let pending_auth = match server
                 .session_store
-                .consume_pending_auth(&code)
+                .consume_pending_auth(&token_digest(&code))
                 .await?
-                .ok_or_else(|| Error::InvalidGrant)?;
+            {
+                Some(auth) => auth,
+                None => server
+                    .session_store
+                    .consume_pending_auth(&code)
+                    .await?
+                    .ok_or_else(|| Error::InvalidGrant)?,
+            };
 
+            let flow_id = pending_auth.flow_id.clone().unwrap_or_else(new_flow_id);
             tracing::info!(
+                flow_id = %flow_id,
                 "exchanging downstream code for DID: {}",
                 pending_auth.account_did
             );
 
+            // Bind the exchange to the flow that produced the code: a
+            // client_id or redirect_uri differing from authorization time
+            // signals a mixed-up or injected code
+            if let (Some(bound), Some(asserted)) =
+                (pending_auth.client_id.as_deref(), params.client_id.as_deref())
+            {
+                if bound != asserted {
+                    tracing::warn!(
+                        "code exchange client_id mismatch: bound to {}, asserted {}",
+                        bound,
+                        asserted
+                    );
+                    return Err(Error::InvalidGrant);
+                }
+            }
+            if let Some(redirect_uri) = params.redirect_uri.as_deref() {
+                if redirect_uri != pending_auth.redirect_uri {
+                    tracing::warn!("code exchange redirect_uri mismatch");
+                    return Err(Error::InvalidGrant);
+                }
+            }
+
+            // Confidential clients must authenticate again at the token endpoint
+            if pending_auth.auth_method == "private_key_jwt" {
+                let client_id = params.client_id.as_deref().ok_or(Error::InvalidClient)?;
+                if params.client_assertion.is_none() {
+                    tracing::warn!(
+                        "confidential client {} omitted client_assertion at token endpoint",
+                        client_id
+                    );
+                    return Err(Error::InvalidClient);
+                }
+                client_auth_method_from_assertion(
+                    &server.config,
+                    client_id,
+                    params.client_assertion_type.as_deref(),
+                    params.client_assertion.as_deref(),
+                )
+                .await?;
+            }
+
             // Get the upstream session from jacquard-oauth store
             let did = jacquard_common::types::did::Did::new_owned(&pending_auth.account_did)
                 .map_err(|e| Error::InvalidRequest(format!("invalid DID: {}", e)))?;
@@ -774,36 +1607,68 @@ where
                 scope_str
             );
 
-            // Issue downstream JWT bound to client's DPoP key
-            let access_token = server
-                .token_manager
-                .issue_downstream_jwt(
-                    &pending_auth.account_did,
-                    &dpop_jkt,
-                    &scope_str,
-                    server.config.downstream_token_expiry_seconds,
-                    &*server.key_store,
-                )
-                .await?;
+            // The client bound at authorization time wins; fall back to the
+            // asserted client_id for codes stored before binding existed
+            let policy_client_id = pending_auth
+                .client_id
+                .as_deref()
+                .or(params.client_id.as_deref());
+            let expires_in = server.config.downstream_token_expiry_for(policy_client_id);
+
+            // Issue downstream access token bound to client's DPoP key
+            let binding = issuance_binding(&server, &headers);
+            let access_token = issue_downstream_access_token(
+                &server,
+                &pending_auth.account_did,
+                &dpop_jkt,
+                &scope_str,
+                Some(&pending_auth.upstream_session_id),
+                binding.as_deref(),
+                expires_in,
+            )
+            .await?;
 
             // Generate downstream refresh token (separate from upstream)
-            let downstream_refresh_token = generate_random_string(64);
-
-            // Store mapping: downstream_refresh_token → (account_did, upstream_session_id)
+            let downstream_refresh_token =
+                SecureToken::with_entropy(server.config.token_entropy_bytes).into_string();
+
+            // Store mapping: downstream_refresh_token → (account_did, upstream_session_id),
+            // keyed by digest so the store never holds usable credentials
+            let now = chrono::Utc::now();
+            let absolute =
+                server.config.refresh_token_absolute_lifetime_for(policy_client_id);
+            let idle = server.config.refresh_token_idle_timeout_for(policy_client_id);
             server
                 .session_store
                 .store_refresh_token_mapping(
-                    &downstream_refresh_token,
-                    pending_auth.account_did.clone(),
-                    pending_auth.upstream_session_id.clone(),
+                    &token_digest(&downstream_refresh_token),
+                    crate::store::RefreshTokenData {
+                        account_did: pending_auth.account_did.clone(),
+                        session_id: pending_auth.upstream_session_id.clone(),
+                        client_id: policy_client_id.map(|s| s.to_string()),
+                        absolute_expires_at: (absolute > 0)
+                            .then(|| now + chrono::Duration::seconds(absolute)),
+                        idle_expires_at: (idle > 0)
+                            .then(|| now + chrono::Duration::seconds(idle)),
+                        dpop_jkt: Some(dpop_jkt.clone()),
+                    },
                 )
                 .await?;
 
             tracing::info!(
+                flow_id = %flow_id,
                 "issued downstream JWT and refresh token for DID: {}",
                 pending_auth.account_did
             );
 
+            // Record the grant so the user can see and revoke it later
+            if let Some(client_id) = policy_client_id {
+                server
+                    .session_store
+                    .record_grant(&pending_auth.account_did, client_id, Some(scope_str.clone()))
+                    .await?;
+            }
+
             // Store the session so XRPC proxy can look it up
             // We already have the complete upstream_session_data, just store it
             ClientAuthStore::upsert_session(&*server.session_store, upstream_session_data.clone())
@@ -828,13 +1693,14 @@ where
             let response = TokenResponse {
                 access_token,
                 token_type: "DPoP".to_string(),
-                expires_in: server.config.downstream_token_expiry_seconds as u64,
+                expires_in: expires_in as u64,
                 refresh_token: Some(downstream_refresh_token),
                 scope: scope_str,
                 sub: pending_auth.account_did.clone(),
+                csrf_token: None,
             };
 
-            Ok(Json(response).into_response())
+            token_response_with_cookie(&server, response).await
         }
         "refresh_token" => {
             let refresh_token = params
@@ -842,16 +1708,109 @@ where
                 .ok_or_else(|| Error::InvalidRequest("missing refresh_token".to_string()))?;
 
             // Extract client's DPoP JKT (may have changed)
-            let dpop_jkt = extract_dpop_jkt(&headers)?;
+            let dpop_jkt = extract_dpop_jkt(&server.config, &headers)?;
 
             tracing::info!("handling refresh token request");
 
             // Look up the session by refresh token
-            let (account_did, session_id) = server
-                .session_store
-                .get_refresh_token_mapping(&refresh_token)
-                .await?
-                .ok_or_else(|| Error::InvalidGrant)?;
+            let mapping = match lookup_refresh_token(&server, &refresh_token).await? {
+                Some(mapping) => mapping,
+                None => {
+                    // An unknown token is indistinguishable from a
+                    // rotated-and-reused one, which is the case worth
+                    // flagging for forensics
+                    let mut record = crate::audit::AuditRecord::new(
+                        crate::audit::AuditEvent::RefreshTokenReused,
+                    )
+                    .with_request_context(&headers)
+                    .with_detail("unknown or already-rotated refresh token");
+                    if let Some(client_id) = params.client_id.as_deref() {
+                        record = record.with_client_id(client_id);
+                    }
+                    server.audit.record(record).await;
+                    return Err(Error::InvalidGrant);
+                }
+            };
+
+            // Enforce the lifetime policy before honoring the grant
+            let now = chrono::Utc::now();
+            if mapping.absolute_expires_at.is_some_and(|t| t < now)
+                || mapping.idle_expires_at.is_some_and(|t| t < now)
+            {
+                revoke_refresh_token(&server, &refresh_token).await?;
+                tracing::info!("rejecting refresh token expired by lifetime policy");
+                return Err(Error::InvalidGrant);
+            }
+
+            let account_did = mapping.account_did.clone();
+            let session_id = mapping.session_id.clone();
+            let policy_client_id = mapping
+                .client_id
+                .as_deref()
+                .or(params.client_id.as_deref());
+
+            // The token was bound to the client's DPoP key at issuance. A
+            // public client that lost its keypair (an SPA with cleared
+            // storage) arrives here with a valid token under a fresh key;
+            // refresh_key_policy decides whether the grant re-binds to it
+            // or dies, and either outcome is audited
+            if let Some(bound_jkt) = mapping.dpop_jkt.as_deref() {
+                if bound_jkt != dpop_jkt {
+                    let mut record = crate::audit::AuditRecord::new(
+                        crate::audit::AuditEvent::RefreshKeyMismatch,
+                    )
+                    .with_request_context(&headers)
+                    .with_did(&account_did);
+                    if let Some(client_id) = policy_client_id {
+                        record = record.with_client_id(client_id);
+                    }
+                    match server.config.refresh_key_policy {
+                        crate::config::RefreshKeyPolicy::Rebind => {
+                            record =
+                                record.with_detail("re-bound refresh grant to the new key");
+                            server.audit.record(record).await;
+                            // Re-key the downstream session record so
+                            // jkt-keyed lookups keep finding it
+                            if let Some(mut session) =
+                                server.session_store.get_by_dpop_jkt(bound_jkt).await?
+                            {
+                                if session.id == session_id {
+                                    session.downstream_dpop_key_thumbprint =
+                                        dpop_jkt.clone();
+                                    server.session_store.update_session(&session).await?;
+                                }
+                            }
+                            tracing::info!("re-binding refresh grant to a new DPoP key");
+                        }
+                        crate::config::RefreshKeyPolicy::Reject => {
+                            record =
+                                record.with_detail("rejected grant and revoked the token");
+                            server.audit.record(record).await;
+                            revoke_refresh_token(&server, &refresh_token).await?;
+                            tracing::info!("rejecting refresh grant under a new DPoP key");
+                            return Err(Error::InvalidGrant);
+                        }
+                    }
+                }
+            }
+
+            // A session that idled out is revoked rather than quietly
+            // resurrected by the refresh grant; otherwise the refresh
+            // itself counts as activity
+            if let Some(mut session) = server.session_store.get_by_dpop_jkt(&dpop_jkt).await? {
+                if session.id == session_id {
+                    if session_idle_expired(&server.config, &session) {
+                        revoke_idle_session(&server, &session).await?;
+                        revoke_refresh_token(&server, &refresh_token).await?;
+                        tracing::info!("rejecting refresh for idle-expired session");
+                        return Err(Error::InvalidGrant);
+                    }
+                    touch_session(&server, &mut session).await?;
+                }
+            }
+
+            // Rotate: the presented token is spent either way
+            revoke_refresh_token(&server, &refresh_token).await?;
 
             tracing::info!("refreshing token for DID: {}", account_did);
 
@@ -880,28 +1839,40 @@ where
                         .join(" ")
                 });
 
-            // Issue new downstream JWT
-            let access_token = server
-                .token_manager
-                .issue_downstream_jwt(
-                    &account_did,
-                    &dpop_jkt,
-                    &scope_str,
-                    server.config.downstream_token_expiry_seconds,
-                    &*server.key_store,
-                )
-                .await?;
+            // Issue new downstream access token
+            let expires_in = server.config.downstream_token_expiry_for(policy_client_id);
+            let binding = issuance_binding(&server, &headers);
+            let access_token = issue_downstream_access_token(
+                &server,
+                &account_did,
+                &dpop_jkt,
+                &scope_str,
+                Some(&session_id),
+                binding.as_deref(),
+                expires_in,
+            )
+            .await?;
 
             // Generate new downstream refresh token (token rotation)
-            let new_downstream_refresh = generate_random_string(64);
+            let new_downstream_refresh =
+                SecureToken::with_entropy(server.config.token_entropy_bytes).into_string();
 
-            // Update mapping
+            // Update mapping, keyed by digest. The absolute expiry from the
+            // original grant survives rotation; the idle expiry resets now
+            let idle = server.config.refresh_token_idle_timeout_for(policy_client_id);
             server
                 .session_store
                 .store_refresh_token_mapping(
-                    &new_downstream_refresh,
-                    account_did.clone(),
-                    session_id.clone(),
+                    &token_digest(&new_downstream_refresh),
+                    crate::store::RefreshTokenData {
+                        account_did: account_did.clone(),
+                        session_id: session_id.clone(),
+                        client_id: policy_client_id.map(|s| s.to_string()),
+                        absolute_expires_at: mapping.absolute_expires_at,
+                        idle_expires_at: (idle > 0)
+                            .then(|| now + chrono::Duration::seconds(idle)),
+                        dpop_jkt: Some(dpop_jkt.clone()),
+                    },
                 )
                 .await?;
 
@@ -910,6 +1881,14 @@ where
                 account_did
             );
 
+            // Keep the grant record's activity timestamp current
+            if let Some(client_id) = policy_client_id {
+                server
+                    .session_store
+                    .record_grant(&account_did, client_id, Some(scope_str.clone()))
+                    .await?;
+            }
+
             // Store/update the session (we already have the complete upstream_session_data)
             ClientAuthStore::upsert_session(&*server.session_store, upstream_session_data.clone())
                 .await
@@ -924,10 +1903,114 @@ where
             let response = TokenResponse {
                 access_token,
                 token_type: "DPoP".to_string(),
-                expires_in: server.config.downstream_token_expiry_seconds as u64,
+                expires_in: expires_in as u64,
                 refresh_token: Some(new_downstream_refresh),
                 scope: scope_str,
                 sub: account_did,
+                csrf_token: None,
+            };
+
+            token_response_with_cookie(&server, response).await
+        }
+        "client_credentials" => {
+            // Service-account grant for backend jobs: restricted to configured
+            // confidential clients, bound to a pre-provisioned upstream session.
+            let client_id = params
+                .client_id
+                .ok_or_else(|| Error::InvalidRequest("missing client_id".to_string()))?;
+            let client_secret = params
+                .client_secret
+                .ok_or_else(|| Error::InvalidRequest("missing client_secret".to_string()))?;
+
+            let service_client = server
+                .config
+                .service_clients
+                .iter()
+                .find(|c| c.client_id == client_id)
+                .ok_or(Error::InvalidClient)?;
+
+            if !constant_time_eq(
+                service_client.client_secret.as_bytes(),
+                client_secret.as_bytes(),
+            ) {
+                tracing::warn!(
+                    "client_credentials auth failed for client_id: {}",
+                    client_id
+                );
+                return Err(Error::InvalidClient);
+            }
+
+            // The client's DPoP key binds the issued JWT, same as the other grants
+            let dpop_jkt = extract_dpop_jkt(&server.config, &headers)?;
+
+            tracing::info!(
+                "client_credentials grant for client_id: {}, DID: {}",
+                client_id,
+                service_client.did
+            );
+
+            // The upstream session must have been provisioned ahead of time
+            // (e.g. via a one-off interactive flow for the service account)
+            let session_id = server
+                .session_store
+                .get_active_session(&service_client.did)
+                .await?
+                .ok_or(Error::SessionNotFound)?;
+
+            let did = jacquard_common::types::did::Did::new_owned(&service_client.did)
+                .map_err(|e| Error::InvalidRequest(format!("invalid DID: {}", e)))?;
+            let upstream_session_data =
+                ClientAuthStore::get_session(&*server.session_store, &did, &session_id)
+                    .await
+                    .map_err(|e| Error::InvalidRequest(format!("failed to get session: {}", e)))?
+                    .ok_or(Error::SessionNotFound)?;
+
+            let scope_str = upstream_session_data
+                .token_set
+                .scope
+                .as_ref()
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| {
+                    server
+                        .config
+                        .scope
+                        .iter()
+                        .map(|s| s.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                });
+
+            // Issue downstream access token bound to the service client's
+            // DPoP key. No refresh token: the client can simply repeat the
+            // grant.
+            let expires_in = server
+                .config
+                .downstream_token_expiry_for(Some(&service_client.client_id));
+            let binding = issuance_binding(&server, &headers);
+            let access_token = issue_downstream_access_token(
+                &server,
+                &service_client.did,
+                &dpop_jkt,
+                &scope_str,
+                None,
+                binding.as_deref(),
+                expires_in,
+            )
+            .await?;
+
+            tracing::info!(
+                "issued downstream JWT via client_credentials for DID: {}",
+                service_client.did
+            );
+
+            let response = TokenResponse {
+                access_token,
+                token_type: "DPoP".to_string(),
+                expires_in: expires_in as u64,
+                refresh_token: None,
+                scope: scope_str,
+                sub: service_client.did.clone(),
+                csrf_token: None,
             };
 
             Ok(Json(response).into_response())
@@ -936,11 +2019,309 @@ where
     }
 }
 
+/// Issue a downstream access token in whichever mode the proxy is
+/// configured for: whatever the configured [`TokenIssuer`] produces (ES256
+/// JWTs by default), or an opaque random token persisted in the store when
+/// `opaque_access_tokens` is set.
+async fn issue_downstream_access_token<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    sub: &str,
+    dpop_jkt: &str,
+    scope: &str,
+    session_id: Option<&str>,
+    binding: Option<&str>,
+    expires_in: i64,
+) -> Result<String>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    if server.config.opaque_access_tokens {
+        server
+            .token_manager
+            .issue_opaque_token(
+                sub,
+                dpop_jkt,
+                scope,
+                expires_in,
+                server.config.token_entropy_bytes,
+                &*server.session_store,
+            )
+            .await
+    } else {
+        server
+            .token_issuer
+            .issue(sub, dpop_jkt, scope, session_id, binding, expires_in)
+            .await
+    }
+}
+
+/// The network-context fingerprint to pin a freshly issued token to, or
+/// `None` when session binding is off or the request carried neither an
+/// `X-Forwarded-For` IP nor a `User-Agent` to fingerprint.
+fn issuance_binding<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    headers: &axum::http::HeaderMap,
+) -> Option<String>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    (server.config.session_binding_mode != "off")
+        .then(|| {
+            crate::auth::session_binding_fingerprint(
+                headers,
+                server.config.session_binding_ipv4_prefix_bits,
+                server.config.session_binding_ipv6_prefix_bits,
+            )
+        })
+        .flatten()
+}
+
+/// JKT sentinel recorded on cookie-session tokens, which have no client
+/// DPoP key; cookie-authenticated requests are CSRF-checked instead.
+const COOKIE_SESSION_JKT: &str = "cookie";
+
+/// Finish a token response, attaching an HttpOnly session cookie when
+/// cookie session mode is on.
+///
+/// The cookie holds an opaque store-backed token (so it can be revoked),
+/// and the response body gains the derived CSRF token the client must
+/// echo in `X-CSRF-Token` on mutating requests.
+async fn token_response_with_cookie<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    mut response: TokenResponse,
+) -> Result<Response>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    if !server.config.cookie_sessions {
+        return Ok(Json(response).into_response());
+    }
+
+    let cookie_token = server
+        .token_manager
+        .issue_opaque_token(
+            &response.sub,
+            COOKIE_SESSION_JKT,
+            &response.scope,
+            server.config.cookie_max_age_seconds,
+            server.config.token_entropy_bytes,
+            &*server.session_store,
+        )
+        .await?;
+
+    response.csrf_token = Some(crate::auth::cookie_csrf_token(&cookie_token));
+
+    let cookie = format!(
+        "{}={}; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age={}",
+        server.config.cookie_name, cookie_token, server.config.cookie_max_age_seconds
+    );
+
+    Ok((
+        [(axum::http::header::SET_COOKIE, cookie)],
+        Json(response),
+    )
+        .into_response())
+}
+
+/// Pull the session cookie's value out of the Cookie header.
+fn session_cookie_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get(axum::http::header::COOKIE)?
+        .to_str()
+        .ok()?
+        .split(';')
+        .find_map(|pair| {
+            let (key, value) = pair.trim().split_once('=')?;
+            (key == name).then_some(value)
+        })
+}
+
+/// Minimum gap between persisted `last_used_at` updates; activity within
+/// the window is coalesced so busy sessions don't rewrite the session
+/// record on every proxied request.
+const SESSION_TOUCH_GRANULARITY_SECONDS: i64 = 60;
+
+/// Whether a session has sat idle past the configured timeout.
+fn session_idle_expired(config: &ProxyConfig, session: &crate::session::OAuthSession) -> bool {
+    config.session_idle_timeout_seconds > 0
+        && session.last_used_at
+            + chrono::Duration::seconds(config.session_idle_timeout_seconds)
+            < chrono::Utc::now()
+}
+
+/// Record activity on a session, persisting `last_used_at` at most once
+/// per [`SESSION_TOUCH_GRANULARITY_SECONDS`].
+async fn touch_session<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    session: &mut crate::session::OAuthSession,
+) -> Result<()>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    let now = chrono::Utc::now();
+    if now - session.last_used_at
+        >= chrono::Duration::seconds(SESSION_TOUCH_GRANULARITY_SECONDS)
+    {
+        session.last_used_at = now;
+        server.session_store.update_session(session).await?;
+    }
+    Ok(())
+}
+
+/// Revoke a session that idled out: mark it revoked and drop the
+/// active-session mapping so later lookups don't resurrect it.
+async fn revoke_idle_session<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    session: &crate::session::OAuthSession,
+) -> Result<()>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    tracing::info!(
+        "revoking session {} idle since {}",
+        session.id,
+        session.last_used_at
+    );
+    let mut revoked = session.clone();
+    revoked.state = crate::session::SessionState::Revoked;
+    server.session_store.update_session(&revoked).await?;
+    server
+        .session_store
+        .clear_active_session(&session.did.to_string())
+        .await?;
+    Ok(())
+}
+
+/// Validate a downstream access token: first against the configured
+/// [`TokenIssuer`], then against the opaque token store, so that tokens
+/// issued before an `opaque_access_tokens` mode switch stay valid.
+async fn validate_downstream_token<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    token: &str,
+) -> Result<DownstreamTokenClaims>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    match server.token_issuer.validate(token).await {
+        Ok(claims) => Ok(claims),
+        Err(err) => {
+            // Opaque store-backed tokens don't parse as issuer tokens
+            match server
+                .token_manager
+                .validate_opaque_token(token, &*server.session_store)
+                .await
+            {
+                Ok(claims) => Ok(claims),
+                Err(_) => Err(err),
+            }
+        }
+    }
+}
+
+/// Look up a refresh token mapping by its SHA-256 digest, falling back to
+/// legacy plaintext rows and upgrading them in place.
+async fn lookup_refresh_token<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    refresh_token: &str,
+) -> Result<Option<crate::store::RefreshTokenData>>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    let digest = token_digest(refresh_token);
+    if let Some(mapping) = server
+        .session_store
+        .get_refresh_token_mapping(&digest)
+        .await?
+    {
+        return Ok(Some(mapping));
+    }
+
+    // Migration path: rows written before hashing are keyed by plaintext
+    if let Some(mapping) = server
+        .session_store
+        .get_refresh_token_mapping(refresh_token)
+        .await?
+    {
+        server
+            .session_store
+            .delete_refresh_token_mapping(refresh_token)
+            .await?;
+        server
+            .session_store
+            .store_refresh_token_mapping(&digest, mapping.clone())
+            .await?;
+        return Ok(Some(mapping));
+    }
+
+    Ok(None)
+}
+
+/// Delete a refresh token mapping in both hashed and legacy plaintext form.
+async fn revoke_refresh_token<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    refresh_token: &str,
+) -> Result<()>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    server
+        .session_store
+        .delete_refresh_token_mapping(&token_digest(refresh_token))
+        .await?;
+    server
+        .session_store
+        .delete_refresh_token_mapping(refresh_token)
+        .await?;
+    Ok(())
+}
+
+/// Look up and delete an opaque access token, in hashed or legacy form.
+async fn revoke_access_token<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    access_token: &str,
+) -> Result<Option<AccessTokenData>>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    let digest = token_digest(access_token);
+    let data = match server.session_store.get_access_token(&digest).await? {
+        Some(data) => Some(data),
+        None => server.session_store.get_access_token(access_token).await?,
+    };
+    if data.is_some() {
+        server.session_store.delete_access_token(&digest).await?;
+        server.session_store.delete_access_token(access_token).await?;
+    }
+    Ok(data)
+}
+
+/// Parameters for the revocation endpoint (RFC 7009 form body).
+#[derive(Debug, Default, Deserialize)]
+struct RevokeParams {
+    /// Downstream token to revoke: an access JWT or a refresh token
+    token: Option<String>,
+}
+
 /// Handle token revocation.
+///
+/// Deletes the local session and forwards the revocation to the PDS using
+/// the session's stored DPoP key, so the long-lived upstream tokens don't
+/// stay valid until they expire. The upstream call is best-effort — not
+/// every PDS implements RFC 7009, so failures are logged and the local
+/// revocation still succeeds.
 async fn handle_revoke<S, K>(
     State(server): State<OAuthProxyServer<S, K>>,
     headers: HeaderMap,
-    _body: String,
+    body: String,
 ) -> Result<Response>
 where
     S: OAuthSessionStore + ClientAuthStore + Clone,
@@ -948,59 +2329,821 @@ where
 {
     tracing::info!("handling revoke request");
 
-    // Extract DPoP JKT
-    let dpop_jkt = extract_dpop_jkt(&headers)?;
+    // RFC 7009 puts the token in the form body; older clients only send a
+    // DPoP proof, so fall back to the JKT lookup when it's absent
+    let params: RevokeParams = serde_urlencoded::from_str(&body).unwrap_or_default();
 
-    // Look up and delete the session
-    let session = server
-        .session_store
-        .get_by_dpop_jkt(&dpop_jkt)
-        .await?
-        .ok_or(Error::SessionNotFound)?;
+    let (account_did, session_id) = if let Some(token) = params.token.as_deref() {
+        if let Ok(claims) = validate_downstream_token(&server, token).await {
+            // Access token in whatever format the issuer produced; opaque
+            // tokens get deleted from the store, a no-op for the rest
+            revoke_access_token(&server, token).await?;
+            let session_id = server
+                .session_store
+                .get_active_session(&claims.sub)
+                .await?
+                .ok_or(Error::SessionNotFound)?;
+            (claims.sub, session_id)
+        } else if let Some(mapping) = lookup_refresh_token(&server, token).await? {
+            // Revoke the presented refresh token immediately
+            revoke_refresh_token(&server, token).await?;
+            (mapping.account_did, mapping.session_id)
+        } else {
+            return Err(Error::SessionNotFound);
+        }
+    } else {
+        let dpop_jkt = extract_dpop_jkt(&server.config, &headers)?;
+        let session = server
+            .session_store
+            .get_by_dpop_jkt(&dpop_jkt)
+            .await?
+            .ok_or(Error::SessionNotFound)?;
+
+        OAuthSessionStore::delete_session(&*server.session_store, &session.id).await?;
 
-    OAuthSessionStore::delete_session(&*server.session_store, &session.id).await?;
+        let account_did = session.did.to_string();
+        let session_id = server
+            .session_store
+            .get_active_session(&account_did)
+            .await?
+            .unwrap_or(session.id);
+        (account_did, session_id)
+    };
+
+    // Forward the revocation upstream so the PDS drops its tokens too
+    let did = jacquard_common::types::did::Did::new_owned(&account_did)
+        .map_err(|e| Error::InvalidRequest(format!("invalid DID: {}", e)))?;
+    if let Ok(Some(upstream_session_data)) =
+        ClientAuthStore::get_session(&*server.session_store, &did, &session_id).await
+    {
+        let host_url = upstream_session_data
+            .host_url
+            .as_str()
+            .trim_end_matches('/');
+        let revoke_url = format!("{}/oauth/revoke", host_url);
+        let upstream_token = upstream_session_data
+            .token_set
+            .refresh_token
+            .as_ref()
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| upstream_session_data.token_set.access_token.to_string());
+
+        match server.session_store.get_session_dpop_key(&session_id).await? {
+            Some((_jkt, upstream_dpop_key)) => {
+                let mut dpop_nonce = server
+                    .session_store
+                    .get_session_dpop_nonce(&session_id)
+                    .await?;
+                let mut retried = false;
+
+                loop {
+                    // The revocation endpoint authenticates via the token
+                    // itself, so no ath claim is bound into the proof
+                    let dpop_proof = server
+                        .token_manager
+                        .create_upstream_dpop_proof(
+                            "POST",
+                            &revoke_url,
+                            None,
+                            dpop_nonce.as_deref(),
+                            &upstream_dpop_key,
+                        )
+                        .await?;
+
+                    let result = server
+                        .upstream
+                        .client()
+                        .post(&revoke_url)
+                        .header("DPoP", &dpop_proof)
+                        .form(&[("token", upstream_token.as_str())])
+                        .send()
+                        .await;
+
+                    match result {
+                        Ok(resp) => {
+                            // Retry once if the PDS demands a fresh DPoP nonce
+                            if (resp.status() == 400 || resp.status() == 401) && !retried {
+                                if let Some(new_nonce) = resp.headers().get("DPoP-Nonce") {
+                                    if let Ok(nonce_str) = new_nonce.to_str() {
+                                        server
+                                            .session_store
+                                            .update_session_dpop_nonce(
+                                                &session_id,
+                                                nonce_str.to_string(),
+                                            )
+                                            .await?;
+                                        dpop_nonce = Some(nonce_str.to_string());
+                                        retried = true;
+                                        continue;
+                                    }
+                                }
+                            }
+
+                            if resp.status().is_success() {
+                                tracing::info!("revoked upstream tokens for DID: {}", account_did);
+                            } else {
+                                tracing::warn!("upstream revocation returned {}", resp.status());
+                            }
+                        }
+                        Err(e) => {
+                            tracing::warn!("upstream revocation failed: {}", e);
+                        }
+                    }
+                    break;
+                }
+            }
+            None => {
+                tracing::warn!(
+                    "no stored DPoP key for session {}, skipping upstream revocation",
+                    session_id
+                );
+            }
+        }
+
+        ClientAuthStore::delete_session(&*server.session_store, &did, &session_id)
+            .await
+            .map_err(|e| Error::InvalidRequest(format!("failed to delete session: {}", e)))?;
+    }
+
+    server.session_store.clear_active_session(&account_did).await?;
 
     Ok(StatusCode::NO_CONTENT.into_response())
 }
 
-/// Proxy XRPC requests to the user's PDS with authenticated context.
-async fn handle_xrpc_proxy<S, K>(
+/// Parameters for the logout endpoint (query string or form encoded).
+#[derive(Debug, Deserialize)]
+struct LogoutParams {
+    /// Downstream token to log out: an access JWT or a refresh token
+    token: Option<String>,
+    /// OIDC-style alias for `token`, sent by RP-initiated logout libraries
+    id_token_hint: Option<String>,
+    /// Client requesting the logout; required to validate the redirect
+    client_id: Option<String>,
+    /// Where to send the user agent after logout
+    post_logout_redirect_uri: Option<String>,
+    /// Opaque state echoed back on the redirect
+    state: Option<String>,
+}
+
+/// Parameters for the introspection endpoint (RFC 7662 form body).
+#[derive(Debug, Default, Deserialize)]
+struct IntrospectParams {
+    /// Token to introspect: an access token (JWT or opaque) or a refresh token
+    token: Option<String>,
+}
+
+/// Handle token introspection (RFC 7662).
+///
+/// Reports whether a downstream token is still active, which is the only
+/// way to check an opaque access token without presenting it. Per the RFC,
+/// unknown or expired tokens yield `{"active": false}` rather than an error.
+async fn handle_introspect<S, K>(
+    State(server): State<OAuthProxyServer<S, K>>,
+    body: String,
+) -> Result<Response>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    tracing::info!("handling introspection request");
+
+    let params: IntrospectParams = serde_urlencoded::from_str(&body).unwrap_or_default();
+    let Some(token) = params.token.as_deref() else {
+        return Ok(Json(serde_json::json!({ "active": false })).into_response());
+    };
+
+    if let Ok(claims) = validate_downstream_token(&server, token).await {
+        let mut body = serde_json::json!({
+            "active": true,
+            "token_type": "DPoP",
+            "iss": claims.iss,
+            "sub": claims.sub,
+            "aud": claims.aud,
+            "exp": claims.exp,
+            "iat": claims.iat,
+            "scope": claims.scope,
+            "cnf": { "jkt": claims.cnf.jkt },
+        });
+        if let Some(session_id) = &claims.session_id {
+            body["session_id"] = serde_json::json!(session_id);
+        }
+        if let Some(session) = server.session_store.get_by_dpop_jkt(&claims.cnf.jkt).await? {
+            body["last_used_at"] = serde_json::json!(session.last_used_at.to_rfc3339());
+        }
+        return Ok(Json(body).into_response());
+    }
+
+    // Refresh tokens introspect too, but only report liveness
+    if let Some(mapping) = lookup_refresh_token(&server, token).await? {
+        let now = chrono::Utc::now();
+        if mapping.absolute_expires_at.is_some_and(|t| t < now)
+            || mapping.idle_expires_at.is_some_and(|t| t < now)
+        {
+            return Ok(Json(serde_json::json!({ "active": false })).into_response());
+        }
+        return Ok(Json(serde_json::json!({
+            "active": true,
+            "token_type": "refresh_token",
+        }))
+        .into_response());
+    }
+
+    Ok(Json(serde_json::json!({ "active": false })).into_response())
+}
+
+/// Handle single logout (front-channel GET or POST).
+///
+/// Revokes the downstream refresh token, deletes the upstream session,
+/// best-effort revokes the upstream tokens at the PDS, and clears the
+/// active-session mapping. If the client supplied a
+/// `post_logout_redirect_uri` that its metadata document lists, the user
+/// agent is redirected there; otherwise the endpoint returns 204.
+async fn handle_logout<S, K>(
     State(server): State<OAuthProxyServer<S, K>>,
     method: Method,
     uri: http::Uri,
     headers: HeaderMap,
-    body: axum::body::Bytes,
+    body: String,
 ) -> Result<Response>
 where
     S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
     K: KeyStore + Clone + 'static,
 {
-    tracing::info!("proxying XRPC request: {} {}", method, uri.path());
+    tracing::info!("handling logout request");
+
+    let params: LogoutParams = if method == Method::GET {
+        serde_urlencoded::from_str(uri.query().unwrap_or(""))
+            .map_err(|e| Error::InvalidRequest(format!("invalid query string: {}", e)))?
+    } else {
+        serde_urlencoded::from_str(&body)
+            .map_err(|e| Error::InvalidRequest(format!("invalid form data: {}", e)))?
+    };
+
+    let token = params
+        .token
+        .as_deref()
+        .or(params.id_token_hint.as_deref())
+        .ok_or_else(|| Error::InvalidRequest("missing token".to_string()))?;
+
+    // The token is either a downstream access token (in whatever format
+    // the issuer produces) or a downstream refresh token; resolve it to
+    // (DID, upstream session id)
+    let (account_did, session_id) = if let Ok(claims) =
+        validate_downstream_token(&server, token).await
+    {
+        // Opaque access tokens get deleted from the store, a no-op for
+        // the rest
+        revoke_access_token(&server, token).await?;
+        let session_id = server
+            .session_store
+            .get_active_session(&claims.sub)
+            .await?
+            .ok_or(Error::SessionNotFound)?;
+        (claims.sub, session_id)
+    } else if let Some(mapping) = lookup_refresh_token(&server, token).await? {
+        // Revoke the presented refresh token immediately
+        revoke_refresh_token(&server, token).await?;
+        (mapping.account_did, mapping.session_id)
+    } else {
+        return Err(Error::SessionNotFound);
+    };
+
+    tracing::info!("logging out DID: {}", account_did);
+
+    // Best-effort upstream revocation: tell the PDS to drop its tokens so
+    // the long-lived upstream session doesn't outlive the logout
+    let did = jacquard_common::types::did::Did::new_owned(&account_did)
+        .map_err(|e| Error::InvalidRequest(format!("invalid DID: {}", e)))?;
+    if let Ok(Some(upstream_session_data)) =
+        ClientAuthStore::get_session(&*server.session_store, &did, &session_id).await
+    {
+        let host_url = upstream_session_data
+            .host_url
+            .as_str()
+            .trim_end_matches('/');
+        let revoke_url = format!("{}/oauth/revoke", host_url);
+        let upstream_token = upstream_session_data
+            .token_set
+            .refresh_token
+            .as_ref()
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| upstream_session_data.token_set.access_token.to_string());
+
+        let result = server
+            .upstream
+            .client()
+            .post(&revoke_url)
+            .form(&[("token", upstream_token.as_str())])
+            .send()
+            .await;
+        match result {
+            Ok(resp) if resp.status().is_success() => {
+                tracing::info!("revoked upstream tokens for DID: {}", account_did);
+            }
+            Ok(resp) => {
+                tracing::warn!("upstream revocation returned {}", resp.status());
+            }
+            Err(e) => {
+                tracing::warn!("upstream revocation failed: {}", e);
+            }
+        }
+
+        ClientAuthStore::delete_session(&*server.session_store, &did, &session_id)
+            .await
+            .map_err(|e| Error::InvalidRequest(format!("failed to delete session: {}", e)))?;
+    }
+
+    server.session_store.clear_active_session(&account_did).await?;
+
+    // Cookie session mode: revoke the cookie's backing token and tell the
+    // browser to drop the cookie itself
+    let mut response_headers = HeaderMap::new();
+    if server.config.cookie_sessions {
+        if let Some(cookie_token) = session_cookie_value(&headers, &server.config.cookie_name) {
+            revoke_access_token(&server, cookie_token).await?;
+        }
+        let clear_cookie = format!(
+            "{}=; Path=/; HttpOnly; Secure; SameSite=Lax; Max-Age=0",
+            server.config.cookie_name
+        );
+        if let Ok(value) = clear_cookie.parse() {
+            response_headers.insert(axum::http::header::SET_COOKIE, value);
+        }
+    }
+
+    // Front-channel redirect, only to a URI the client's metadata declares
+    if let Some(redirect_uri) = params.post_logout_redirect_uri.as_deref() {
+        let client_id = params
+            .client_id
+            .as_deref()
+            .ok_or_else(|| Error::InvalidRequest("missing client_id".to_string()))?;
+        if !post_logout_redirect_allowed(client_id, redirect_uri).await? {
+            return Err(Error::InvalidRequest(
+                "post_logout_redirect_uri not registered for client".to_string(),
+            ));
+        }
 
-    // 1. Extract and validate downstream JWT from Authorization header
-    let auth_header = headers
+        let redirect_url = match params.state.as_deref() {
+            Some(state) => {
+                let sep = if redirect_uri.contains('?') { '&' } else { '?' };
+                format!("{}{}state={}", redirect_uri, sep, urlencoding::encode(state))
+            }
+            None => redirect_uri.to_string(),
+        };
+        return Ok((response_headers, Redirect::to(&redirect_url)).into_response());
+    }
+
+    Ok((response_headers, StatusCode::NO_CONTENT).into_response())
+}
+
+/// Body of the operator maintenance endpoint.
+#[derive(Debug, Deserialize)]
+struct MaintenanceRequest {
+    /// `true` opens (or extends) the window, `false` closes it
+    enabled: bool,
+    /// Window length in seconds when enabling (default: 300)
+    retry_after_seconds: Option<i64>,
+}
+
+/// Toggle maintenance mode at runtime.
+///
+/// Registered only when [`ProxyConfig::admin_token`] is set;
+/// authenticated by that token as a bearer credential. The window is
+/// time-boxed, so an operator script that dies mid-migration leaves the
+/// proxy serving again once the window lapses.
+async fn handle_maintenance<S, K>(
+    State(server): State<OAuthProxyServer<S, K>>,
+    headers: HeaderMap,
+    body: String,
+) -> Result<Response>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    let admin_token = server
+        .config
+        .admin_token
+        .as_ref()
+        .ok_or(Error::Unauthorized)?;
+    let presented = headers
         .get("Authorization")
         .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
         .ok_or(Error::Unauthorized)?;
+    if !constant_time_eq(presented.as_bytes(), admin_token.as_bytes()) {
+        return Err(Error::Unauthorized);
+    }
 
-    let token = auth_header
-        .strip_prefix("DPoP ")
-        .or_else(|| auth_header.strip_prefix("Bearer "))
-        .ok_or(Error::Unauthorized)?;
+    let params: MaintenanceRequest = serde_json::from_str(&body)
+        .map_err(|e| Error::InvalidRequest(format!("invalid JSON: {}", e)))?;
 
-    let claims = server
-        .token_manager
-        .validate_downstream_jwt(token, &*server.key_store)
+    if params.enabled {
+        let window = params.retry_after_seconds.unwrap_or(300);
+        if window <= 0 {
+            return Err(Error::InvalidRequest(
+                "retry_after_seconds must be positive".to_string(),
+            ));
+        }
+        server.set_maintenance_until(chrono::Utc::now() + chrono::Duration::seconds(window));
+    } else {
+        server.clear_maintenance();
+    }
+
+    Ok(Json(serde_json::json!({
+        "maintenance": params.enabled,
+        "until": server.maintenance_until().map(|t| t.to_rfc3339()),
+    }))
+    .into_response())
+}
+
+/// List the calling user's authorized clients.
+///
+/// Authenticated by the downstream access token, so users can only see
+/// their own grants. Each entry carries the client_id, the scope from
+/// the most recent authorization, and created/last-used timestamps.
+async fn handle_list_grants<S, K>(
+    State(server): State<OAuthProxyServer<S, K>>,
+    headers: HeaderMap,
+) -> Result<Response>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    tracing::info!("handling grant listing request");
+
+    let session = server.authenticate(&headers).await?;
+    let grants = server.session_store.list_grants(&session.did).await?;
+
+    let grants: Vec<serde_json::Value> = grants
+        .iter()
+        .map(|grant| {
+            serde_json::json!({
+                "client_id": grant.client_id,
+                "scope": grant.scope,
+                "created_at": grant.created_at.to_rfc3339(),
+                "last_used_at": grant.last_used_at.to_rfc3339(),
+            })
+        })
+        .collect();
+
+    Ok(Json(serde_json::json!({ "grants": grants })).into_response())
+}
+
+/// Parameters for revoking one grant, carried in the query string
+/// because client IDs are URLs and don't survive a path segment.
+#[derive(Debug, Deserialize)]
+struct RevokeGrantParams {
+    client_id: String,
+}
+
+/// Revoke one of the calling user's grants.
+///
+/// Deletes the grant record and every refresh token the client holds
+/// for the DID; access tokens already issued run out on their own short
+/// lifetime, the same semantics as the revocation endpoint.
+async fn handle_revoke_grant<S, K>(
+    State(server): State<OAuthProxyServer<S, K>>,
+    Query(params): Query<RevokeGrantParams>,
+    headers: HeaderMap,
+) -> Result<Response>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    tracing::info!("handling grant revocation request");
+
+    let session = server.authenticate(&headers).await?;
+    let revoked = server
+        .session_store
+        .delete_grant(&session.did, &params.client_id)
         .await?;
 
-    tracing::info!("validated JWT for DID: {}", claims.sub);
+    tracing::info!(
+        "revoked grant for client {} ({} refresh tokens)",
+        params.client_id,
+        revoked
+    );
 
-    // 2. Verify DPoP binding
-    let dpop_jkt = extract_dpop_jkt(&headers)?;
-    if dpop_jkt != claims.cnf.jkt {
-        return Err(Error::InvalidRequest("DPoP key mismatch".to_string()));
+    Ok(Json(serde_json::json!({
+        "client_id": params.client_id,
+        "revoked_tokens": revoked,
+    }))
+    .into_response())
+}
+
+/// GET a URL and parse the body as JSON, for deployment probes.
+async fn fetch_json(url: &str) -> Result<serde_json::Value> {
+    let response = reqwest::get(url)
+        .await
+        .map_err(|e| Error::NetworkError(format!("request failed: {}", e)))?;
+    if !response.status().is_success() {
+        return Err(Error::NetworkError(format!(
+            "answered {}",
+            response.status()
+        )));
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| Error::NetworkError(format!("invalid JSON: {}", e)))
+}
+
+/// Check whether a client's metadata document registers the given
+/// post-logout redirect URI, falling back to its regular redirect URIs.
+async fn post_logout_redirect_allowed(client_id: &str, redirect_uri: &str) -> Result<bool> {
+    let metadata: serde_json::Value = reqwest::get(client_id)
+        .await
+        .map_err(|e| Error::NetworkError(format!("failed to fetch client metadata: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| Error::NetworkError(format!("invalid client metadata: {}", e)))?;
+
+    let registered = metadata
+        .get("post_logout_redirect_uris")
+        .or_else(|| metadata.get("redirect_uris"))
+        .and_then(|v| v.as_array());
+
+    Ok(registered.is_some_and(|uris| {
+        uris.iter()
+            .any(|uri| uri.as_str() == Some(redirect_uri))
+    }))
+}
+
+/// Scope a proxied NSID requires beyond the base `atproto` grant, if
+/// any. Used to fail calls at the proxy when the upstream grant was
+/// downgraded below what the endpoint needs.
+fn nsid_required_scope(nsid: &str) -> Option<&'static str> {
+    if nsid.starts_with("chat.bsky.") {
+        Some("transition:chat.bsky")
     } else {
-        tracing::info!("DPoP key binding verified");
+        None
+    }
+}
+
+/// Proxy XRPC requests to the user's PDS with authenticated context.
+async fn handle_xrpc_proxy<S, K>(
+    State(server): State<OAuthProxyServer<S, K>>,
+    method: Method,
+    uri: http::Uri,
+    headers: HeaderMap,
+    parts: http::request::Parts,
+    body: axum::body::Bytes,
+) -> Result<Response>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    tracing::info!("proxying XRPC request: {} {}", method, uri.path());
+
+    // Maintenance mode drains writes but keeps reads flowing, so clients
+    // stay usable while the session store is migrated
+    if method != Method::GET && method != Method::HEAD {
+        server.check_maintenance()?;
+    }
+
+    // Cheap size checks before any crypto or upstream work. The router's
+    // body-limit layer already capped the body at the larger allowance;
+    // this applies the tighter per-NSID bound (uploadBlob gets the big one)
+    let url_len = uri.path_and_query().map(|pq| pq.as_str().len()).unwrap_or(0);
+    if url_len > server.config.max_request_url_bytes {
+        return Err(Error::UriTooLong);
+    }
+    let body_limit = if crate::cache::nsid_from_path(uri.path())
+        == Some("com.atproto.repo.uploadBlob")
+    {
+        server.config.xrpc_upload_max_body_bytes
+    } else {
+        server.config.xrpc_max_body_bytes
+    };
+    if body.len() > body_limit {
+        return Err(Error::PayloadTooLarge(format!(
+            "request body exceeds {} bytes",
+            body_limit
+        )));
+    }
+
+    // Only available when the app is served with connect info; used for
+    // the injected X-Forwarded-For chain
+    let client_ip = parts
+        .extensions
+        .get::<axum::extract::ConnectInfo<std::net::SocketAddr>>()
+        .map(|ci| ci.0.ip());
+
+    // 1. Authenticate the request: an Authorization header with a
+    // DPoP-bound token, or in cookie session mode the HttpOnly session
+    // cookie with a CSRF double-submit check standing in for DPoP
+    let (claims, dpop_jkt) = if let Some(auth_header) =
+        headers.get("Authorization").and_then(|v| v.to_str().ok())
+    {
+        let token = auth_header
+            .strip_prefix("DPoP ")
+            .or_else(|| auth_header.strip_prefix("Bearer "))
+            .ok_or(Error::Unauthorized)?;
+
+        let claims = match validate_downstream_token(&server, token).await {
+            Ok(claims) => claims,
+            Err(e) => {
+                server
+                    .audit
+                    .record(
+                        crate::audit::AuditRecord::new(
+                            crate::audit::AuditEvent::SignatureFailure,
+                        )
+                        .with_request_context(&headers)
+                        .with_detail(format!("downstream token rejected: {}", e)),
+                    )
+                    .await;
+                return Err(e);
+            }
+        };
+
+        tracing::info!("validated token for DID: {}", claims.sub);
+
+        // 2. Verify DPoP binding
+        let dpop_jkt = extract_dpop_jkt(&server.config, &headers)?;
+        if dpop_jkt != claims.cnf.jkt {
+            return Err(Error::InvalidRequest("DPoP key mismatch".to_string()));
+        } else {
+            tracing::info!("DPoP key binding verified");
+        }
+
+        (claims, dpop_jkt)
+    } else if server.config.cookie_sessions {
+        let cookie_token = session_cookie_value(&headers, &server.config.cookie_name)
+            .ok_or(Error::Unauthorized)?;
+
+        let claims = server
+            .token_manager
+            .validate_opaque_token(cookie_token, &*server.session_store)
+            .await
+            .map_err(|_| Error::Unauthorized)?;
+
+        tracing::info!("validated session cookie for DID: {}", claims.sub);
+
+        // 2. CSRF double-submit check on anything that can mutate state;
+        // the cookie is SameSite=Lax, but the header requirement also
+        // covers same-site subdomains and older browsers
+        if method != Method::GET && method != Method::HEAD {
+            let presented = headers
+                .get("x-csrf-token")
+                .and_then(|v| v.to_str().ok())
+                .ok_or(Error::Unauthorized)?;
+            let expected = crate::auth::cookie_csrf_token(cookie_token);
+            if !constant_time_eq(presented.as_bytes(), expected.as_bytes()) {
+                return Err(Error::Unauthorized);
+            }
+        }
+
+        (claims, COOKIE_SESSION_JKT.to_string())
+    } else {
+        return Err(Error::Unauthorized);
+    };
+
+    // Fair queuing: a principal at its concurrency limit waits for a slot
+    // (up to the configured queue depth) instead of saturating the
+    // upstream for everyone; past the queue it gets a 429. The permits are
+    // held until this handler returns.
+    let _xrpc_permits = server.xrpc_limits.acquire(&claims.sub, &dpop_jkt).await?;
+
+    // A token whose grant was downgraded upstream must not reach
+    // endpoints the missing scope gates
+    if let Some(required) = crate::cache::nsid_from_path(uri.path()).and_then(nsid_required_scope)
+    {
+        if !crate::auth::axum_extractors::scope_granted(&claims.scope, required) {
+            return Err(Error::InsufficientScope(required.to_string()));
+        }
+    }
+
+    // Session binding: a token carrying a fingerprint must be presented
+    // from the same network context it was issued to. Tokens without a
+    // `bind` claim (issued while binding was off, or from requests with
+    // nothing to fingerprint) pass — turning the knob on only pins tokens
+    // issued after that point.
+    if server.config.session_binding_mode != "off" {
+        if let Some(bound) = claims.bind.as_deref() {
+            let current = crate::auth::session_binding_fingerprint(
+                &headers,
+                server.config.session_binding_ipv4_prefix_bits,
+                server.config.session_binding_ipv6_prefix_bits,
+            );
+            if current.as_deref() != Some(bound) {
+                if server.config.session_binding_mode == "enforce" {
+                    server
+                        .audit
+                        .record(
+                            crate::audit::AuditRecord::new(
+                                crate::audit::AuditEvent::SessionBindingMismatch,
+                            )
+                            .with_request_context(&headers)
+                            .with_did(claims.sub.as_str())
+                            .with_detail("token used from a different network context"),
+                        )
+                        .await;
+                    return Err(Error::Unauthorized);
+                }
+                tracing::warn!(
+                    did = %claims.sub,
+                    "session binding mismatch (warn mode), allowing request"
+                );
+            }
+        }
+    }
+
+    // Idle-expiry enforcement and last-use bookkeeping on the proxy
+    // session; cookie-mode requests have no per-JKT session record
+    if let Some(mut session) = server.session_store.get_by_dpop_jkt(&dpop_jkt).await? {
+        if session_idle_expired(&server.config, &session) {
+            revoke_idle_session(&server, &session).await?;
+            return Err(Error::Unauthorized);
+        }
+        touch_session(&server, &mut session).await?;
+    }
+
+    // Service auth minting is gated so downstream apps can only obtain
+    // tokens for audiences/methods this deployment has approved
+    if uri.path() == "/xrpc/com.atproto.server.getServiceAuth" {
+        check_service_auth_request(&server.config, &uri)?;
+    }
+
+    // Direct service routing: honor atproto-proxy (did#service) for
+    // allowlisted services instead of hopping through the PDS
+    if let Some(endpoint) = resolve_atproto_proxy_target(&server.config, &headers).await? {
+        let path = uri.path_and_query().map(|pq| pq.as_str()).unwrap_or("");
+        let target_url = format!("{}/{}", endpoint, path.trim_start_matches('/'));
+        tracing::info!("routing XRPC request directly to service: {}", target_url);
+
+        let target_host = url::Url::parse(&target_url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+            .unwrap_or_else(|| endpoint.clone());
+
+        let mut request = server.upstream.client().request(method.clone(), &target_url);
+
+        // Copy headers allowed by the forwarding policy; the target is not
+        // the PDS, so the proxy directive itself is also stripped
+        for (name, value) in headers.iter() {
+            if name.as_str() != "atproto-proxy"
+                && should_forward_request_header(&server.config, name.as_str())
+            {
+                request = request.header(name, value);
+            }
+        }
+        for (name, value) in forwarding_headers(&headers, client_ip) {
+            request = request.header(name, value);
+        }
+
+        if !body.is_empty() {
+            request = request.body(body.clone());
+        }
+
+        let response = server
+            .upstream
+            .send(&target_host, request, method == Method::GET)
+            .await?;
+
+        let status = response.status();
+        let resp_headers = response.headers().clone();
+        let resp_body = response
+            .bytes()
+            .await
+            .map_err(|e| Error::NetworkError(e.to_string()))?;
+
+        let mut response_builder = axum::http::Response::builder().status(status);
+        for (name, value) in resp_headers.iter() {
+            if !should_return_response_header(&server.config, name.as_str()) {
+                continue;
+            }
+            response_builder = response_builder.header(name, value);
+        }
+        response_builder = response_builder.header("via", VIA_PSEUDONYM);
+
+        return Ok(response_builder
+            .body(resp_body.into())
+            .map_err(|e| Error::InvalidRequest(e.to_string()))?);
+    }
+
+    // Serve allowlisted read-only GETs from the response cache; entries
+    // are keyed per DID so responses never leak between users
+    let cacheable = method == Method::GET
+        && crate::cache::nsid_from_path(uri.path())
+            .map(|nsid| server.config.xrpc_cacheable_nsids.iter().any(|n| n == nsid))
+            .unwrap_or(false);
+    let cache_key = crate::cache::cache_key(
+        &claims.sub,
+        uri.path_and_query().map(|pq| pq.as_str()).unwrap_or(""),
+    );
+    if cacheable {
+        if let Some(cached) = server.response_cache.get(&cache_key).await? {
+            tracing::debug!("response cache hit for {} {}", claims.sub, uri.path());
+            let mut response_builder = axum::http::Response::builder().status(cached.status);
+            for (name, value) in &cached.headers {
+                response_builder = response_builder.header(name, value);
+            }
+            response_builder = response_builder.header("via", VIA_PSEUDONYM);
+            return Ok(response_builder
+                .body(cached.body.into())
+                .map_err(|e| Error::InvalidRequest(e.to_string()))?);
+        }
     }
 
     tracing::info!("Looking up active session for sub: {}", &claims.sub);
@@ -1019,7 +3162,7 @@ where
         &did,
         &session_id
     );
-    let upstream_session_data =
+    let mut upstream_session_data =
         ClientAuthStore::get_session(&*server.session_store, &did, &session_id)
             .await
             .map_err(|e| Error::InvalidRequest(format!("failed to get session: {}", e)))?
@@ -1035,6 +3178,10 @@ where
         .trim_end_matches('/');
     let path = path.trim_start_matches('/');
     let upstream_url = format!("{}/{}", host_url, path);
+    let upstream_host = url::Url::parse(&upstream_url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or_else(|| host_url.to_string());
 
     tracing::info!("upstream URL: {}", upstream_url);
 
@@ -1057,6 +3204,9 @@ where
     // Retry loop for DPoP nonce handling
     let mut retry_count = 0;
     let max_retries = 1;
+    // One forced upstream refresh per request, for tokens invalidated
+    // before their expiry
+    let mut refresh_attempted = false;
 
     loop {
         // 7. Create DPoP proof for upstream request
@@ -1077,8 +3227,9 @@ where
         );
 
         // 8. Forward request to PDS
-        let client = reqwest::Client::new();
-        let mut request = client
+        let mut request = server
+            .upstream
+            .client()
             .request(method.clone(), &upstream_url)
             .header(
                 "Authorization",
@@ -1086,22 +3237,26 @@ where
             )
             .header("DPoP", dpop_proof);
 
-        // Copy relevant headers (skip auth/dpop/host)
+        // Copy headers allowed by the forwarding policy
         for (name, value) in headers.iter() {
-            if !["host", "authorization", "dpop"].contains(&name.as_str()) {
+            if should_forward_request_header(&server.config, name.as_str()) {
                 request = request.header(name, value);
             }
         }
+        for (name, value) in forwarding_headers(&headers, client_ip) {
+            request = request.header(name, value);
+        }
 
         if !body.is_empty() {
             request = request.body(body.clone());
         }
 
-        // 9. Send request
-        let response = request
-            .send()
-            .await
-            .map_err(|e| Error::NetworkError(e.to_string()))?;
+        // 9. Send request through the shared transport (timeouts, GET
+        // retries, circuit breaker)
+        let response = server
+            .upstream
+            .send(&upstream_host, request, method == Method::GET)
+            .await?;
 
         tracing::info!("upstream response status: {}", response.status());
 
@@ -1135,6 +3290,69 @@ where
             }
         }
 
+        // 10b. A 401 that names the access token means it died before its
+        // expiry — a password change or an admin revoke. Force one
+        // refresh through jacquard-oauth and retry before relaying the
+        // failure to the client.
+        if response.status() == 401 && !refresh_attempted {
+            let status = response.status();
+            let resp_headers = response.headers().clone();
+            let resp_body = response
+                .bytes()
+                .await
+                .map_err(|e| Error::NetworkError(e.to_string()))?;
+
+            if upstream_reports_invalid_token(&resp_headers, &resp_body) {
+                refresh_attempted = true;
+                tracing::info!("upstream rejected the access token, forcing a refresh");
+                match force_upstream_refresh(&server, &did, &session_id).await {
+                    Ok(()) => {
+                        // jacquard-oauth persisted the refreshed token set;
+                        // re-read it so the retry carries the new token
+                        upstream_session_data = ClientAuthStore::get_session(
+                            &*server.session_store,
+                            &did,
+                            &session_id,
+                        )
+                        .await
+                        .map_err(|e| {
+                            Error::InvalidRequest(format!("failed to get session: {}", e))
+                        })?
+                        .ok_or(Error::SessionNotFound)?;
+                        // The new token gets the full nonce-retry budget
+                        retry_count = 0;
+                        continue;
+                    }
+                    Err(e) => {
+                        tracing::warn!("forced upstream refresh failed: {}", e);
+                    }
+                }
+            }
+
+            // Not a token problem, or the refresh didn't help: relay the
+            // 401 as-is (the body is already consumed, so this mirrors the
+            // tail of the loop without the cache write)
+            if let Some(new_nonce) = resp_headers.get("DPoP-Nonce") {
+                if let Ok(nonce_str) = new_nonce.to_str() {
+                    let _ = server
+                        .session_store
+                        .update_session_dpop_nonce(&session_id, nonce_str.to_string())
+                        .await;
+                }
+            }
+            let mut response_builder = axum::http::Response::builder().status(status);
+            for (name, value) in resp_headers.iter() {
+                if !should_return_response_header(&server.config, name.as_str()) {
+                    continue;
+                }
+                response_builder = response_builder.header(name, value);
+            }
+            response_builder = response_builder.header("via", VIA_PSEUDONYM);
+            return Ok(response_builder
+                .body(resp_body.into())
+                .map_err(|e| Error::InvalidRequest(e.to_string()))?);
+        }
+
         // 11. Handle successful DPoP nonce updates
         if let Some(new_nonce) = response.headers().get("DPoP-Nonce") {
             if let Ok(nonce_str) = new_nonce.to_str() {
@@ -1163,13 +3381,39 @@ where
         );
 
         let mut response_builder = axum::http::Response::builder().status(status);
+        let mut returned_headers = Vec::new();
         for (name, value) in resp_headers.iter() {
-            // Skip transfer-encoding since we've already consumed the body
-            if name == "transfer-encoding" {
+            // The body is already consumed, so hop-by-hop headers like
+            // transfer-encoding must not be echoed
+            if !should_return_response_header(&server.config, name.as_str()) {
                 continue;
             }
+            if let Ok(value_str) = value.to_str() {
+                returned_headers.push((name.as_str().to_string(), value_str.to_string()));
+            }
             response_builder = response_builder.header(name, value);
         }
+        response_builder = response_builder.header("via", VIA_PSEUDONYM);
+
+        // Cache successful responses the upstream marked reusable, within
+        // the configured size bound
+        if cacheable && status.is_success() && body.len() <= server.config.xrpc_cache_max_entry_bytes
+        {
+            if let Some(ttl) = crate::cache::cache_ttl_from_headers(&resp_headers) {
+                let _ = server
+                    .response_cache
+                    .put(
+                        &cache_key,
+                        crate::cache::CachedResponse {
+                            status: status.as_u16(),
+                            headers: returned_headers,
+                            body: body.to_vec(),
+                        },
+                        ttl,
+                    )
+                    .await;
+            }
+        }
 
         return Ok(response_builder
             .body(body.into())
@@ -1177,6 +3421,50 @@ where
     }
 }
 
+/// Whether an upstream 401 names the access token itself as the problem,
+/// as opposed to a DPoP or nonce issue. Checks the `WWW-Authenticate`
+/// challenge per RFC 6750 first, then falls back to the XRPC error body,
+/// which PDSes use for `InvalidToken`/`ExpiredToken`.
+fn upstream_reports_invalid_token(headers: &HeaderMap, body: &[u8]) -> bool {
+    if let Some(challenge) = headers
+        .get("www-authenticate")
+        .and_then(|v| v.to_str().ok())
+    {
+        if challenge.contains("invalid_token") {
+            return true;
+        }
+    }
+    serde_json::from_slice::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| v.get("error").and_then(|e| e.as_str().map(|e| e.to_string())))
+        .is_some_and(|e| matches!(e.as_str(), "InvalidToken" | "ExpiredToken" | "invalid_token"))
+}
+
+/// Force a refresh of the upstream token set for a session, for tokens
+/// the PDS invalidated before their recorded expiry. jacquard-oauth
+/// persists the refreshed tokens through the session store; callers
+/// re-read the session afterwards.
+async fn force_upstream_refresh<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    did: &jacquard_common::types::did::Did<'static>,
+    session_id: &str,
+) -> Result<()>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    let oauth_session = server
+        .oauth_client
+        .restore(did, session_id)
+        .await
+        .map_err(|e| Error::InvalidRequest(format!("failed to restore session: {}", e)))?;
+    oauth_session
+        .refresh()
+        .await
+        .map_err(|e| Error::InvalidRequest(format!("failed to refresh session: {}", e)))?;
+    Ok(())
+}
+
 // Builder for OAuthProxyServer.
 pub struct OAuthProxyServerBuilder<S, K>
 where
@@ -1186,6 +3474,11 @@ where
     config: Option<ProxyConfig>,
     session_store: Option<Arc<S>>,
     key_store: Option<Arc<K>>,
+    resolution_cache: Option<Arc<dyn ResolutionCache>>,
+    token_issuer: Option<Arc<dyn TokenIssuer>>,
+    response_cache: Option<Arc<dyn crate::cache::ResponseCache>>,
+    audit: Option<Arc<dyn crate::audit::AuditSink>>,
+    pages: Option<Arc<dyn crate::pages::PageRenderer>>,
 }
 
 impl<S, K> Default for OAuthProxyServerBuilder<S, K>
@@ -1198,6 +3491,11 @@ where
             config: None,
             session_store: None,
             key_store: None,
+            resolution_cache: None,
+            token_issuer: None,
+            response_cache: None,
+            audit: None,
+            pages: None,
         }
     }
 }
@@ -1222,7 +3520,56 @@ where
         self
     }
 
+    /// Use a custom identity/PDS resolution cache instead of the in-memory
+    /// default (e.g. a store-backed cache shared across instances).
+    pub fn resolution_cache(mut self, cache: Arc<dyn ResolutionCache>) -> Self {
+        self.resolution_cache = Some(cache);
+        self
+    }
+
+    /// Use a custom downstream token format instead of the default ES256
+    /// JWTs (e.g. extra claims, EdDSA, or an external token service).
+    pub fn token_issuer(mut self, issuer: Arc<dyn TokenIssuer>) -> Self {
+        self.token_issuer = Some(issuer);
+        self
+    }
+
+    /// Use a custom XRPC response cache instead of the in-memory default
+    /// (e.g. a shared cache across instances).
+    pub fn response_cache(mut self, cache: Arc<dyn crate::cache::ResponseCache>) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Send security audit events to a custom sink instead of the default
+    /// tracing-based one (e.g. a database or SIEM forwarder).
+    pub fn audit_sink(mut self, sink: Arc<dyn crate::audit::AuditSink>) -> Self {
+        self.audit = Some(sink);
+        self
+    }
+
+    /// Render human-facing pages (browser-mediated flow errors) through a
+    /// custom [`PageRenderer`](crate::pages::PageRenderer) instead of the
+    /// minimal built-in template.
+    pub fn page_renderer(mut self, pages: Arc<dyn crate::pages::PageRenderer>) -> Self {
+        self.pages = Some(pages);
+        self
+    }
+
+    /// Build the server, blocking the current thread while the signing key
+    /// is fetched from the key store.
+    ///
+    /// `block_in_place` panics on current-thread runtimes and inside other
+    /// blocking sections, so prefer [`build_async`](Self::build_async).
+    #[deprecated(note = "use build_async; block_in_place panics on current-thread runtimes")]
     pub fn build(self) -> Result<OAuthProxyServer<S, K>> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(self.build_async())
+        })
+    }
+
+    /// Build the server, fetching the signing key from the key store.
+    pub async fn build_async(self) -> Result<OAuthProxyServer<S, K>> {
         let config = self
             .config
             .ok_or_else(|| Error::InvalidRequest("config required".to_string()))?;
@@ -1233,12 +3580,25 @@ where
             .key_store
             .ok_or_else(|| Error::InvalidRequest("key_store required".to_string()))?;
 
-        let token_manager = Arc::new(TokenManager::new(config.host.to_string()));
+        let host_str = config.host.as_str();
+        if config.dpop_nonce_hmac_secret.as_ref() == b"insecure-default-dpop-nonce-secret"
+            && !host_str.contains("localhost")
+            && !host_str.contains("127.0.0.1")
+        {
+            tracing::warn!(
+                "dpop_nonce_hmac_secret is the insecure built-in default; set \
+                 OATPROXY_DPOP_NONCE_HMAC_SECRET (or `dpop_nonce_hmac_secret` in the \
+                 config file) for non-localhost deployments"
+            );
+        }
+
+        let token_manager = Arc::new(
+            TokenManager::new(config.issuer())
+                .with_clock_skew_leeway(config.clock_skew_leeway_seconds),
+        );
 
         // Get the signing key for client authentication
-        let signing_key = tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current().block_on(key_store.get_signing_key())
-        })?;
+        let signing_key = key_store.get_signing_key().await?;
 
         // Convert p256 signing key to jose_jwk::Jwk format
         let verifying_key = signing_key.verifying_key();
@@ -1279,14 +3639,217 @@ where
         };
         let oauth_client = Arc::new(OAuthClient::new((*session_store).clone(), client_data));
 
+        let resolution_cache = self.resolution_cache.unwrap_or_else(|| {
+            Arc::new(MemoryResolutionCache::new(
+                std::time::Duration::from_secs(config.resolution_cache_ttl_seconds),
+                std::time::Duration::from_secs(config.resolution_negative_cache_ttl_seconds),
+            ))
+        });
+
+        let upstream = Arc::new(UpstreamTransport::new(&config));
+
+        let token_issuer = self.token_issuer.unwrap_or_else(|| {
+            Arc::new(JwtTokenIssuer::new(token_manager.clone(), key_store.clone()))
+        });
+
+        let xrpc_limits = Arc::new(crate::limit::XrpcConcurrencyLimits::from_config(&config));
+
+        let response_cache = self
+            .response_cache
+            .unwrap_or_else(|| Arc::new(crate::cache::MemoryResponseCache::new(1024)));
+
+        let audit = self
+            .audit
+            .unwrap_or_else(|| Arc::new(crate::audit::TracingAuditSink));
+
+        let pages = self
+            .pages
+            .unwrap_or_else(|| Arc::new(crate::pages::DefaultPageRenderer));
+
+        let key_material = Arc::new(KeyMaterial::new(key_store.clone()));
+
         Ok(OAuthProxyServer {
             config,
             session_store,
             key_store,
+            key_material,
             token_manager,
+            token_issuer,
             oauth_client,
+            resolution_cache,
+            upstream,
+            xrpc_limits,
+            response_cache,
+            audit,
+            pages,
+            maintenance_until: Arc::new(std::sync::Mutex::new(None)),
+        })
+    }
+}
+
+/// Marker for a typestate builder slot that has not been filled yet.
+pub struct Missing;
+
+/// Typestate variant of [`OAuthProxyServerBuilder`].
+///
+/// The config, session store, and key store slots are tracked in the type
+/// parameters, so [`build`](OAuthProxyServerTypedBuilder::build) only exists
+/// once all three have been provided — forgetting one is a compile error
+/// instead of a runtime `config required`. The optional resolution cache and
+/// token issuer slots stay runtime `Option`s as in the plain builder.
+///
+/// ```rust,ignore
+/// let proxy = OAuthProxyServerTypedBuilder::new()
+///     .config(config)
+///     .session_store(store.clone())
+///     .key_store(store)
+///     .build()?;
+/// ```
+pub struct OAuthProxyServerTypedBuilder<C, Sst, Kst> {
+    config: C,
+    session_store: Sst,
+    key_store: Kst,
+    resolution_cache: Option<Arc<dyn ResolutionCache>>,
+    token_issuer: Option<Arc<dyn TokenIssuer>>,
+    response_cache: Option<Arc<dyn crate::cache::ResponseCache>>,
+    audit: Option<Arc<dyn crate::audit::AuditSink>>,
+    pages: Option<Arc<dyn crate::pages::PageRenderer>>,
+}
+
+impl OAuthProxyServerTypedBuilder<Missing, Missing, Missing> {
+    pub fn new() -> Self {
+        Self {
+            config: Missing,
+            session_store: Missing,
+            key_store: Missing,
+            resolution_cache: None,
+            token_issuer: None,
+            response_cache: None,
+            audit: None,
+            pages: None,
+        }
+    }
+}
+
+impl Default for OAuthProxyServerTypedBuilder<Missing, Missing, Missing> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, Sst, Kst> OAuthProxyServerTypedBuilder<C, Sst, Kst> {
+    pub fn config(self, config: ProxyConfig) -> OAuthProxyServerTypedBuilder<ProxyConfig, Sst, Kst> {
+        OAuthProxyServerTypedBuilder {
+            config,
+            session_store: self.session_store,
+            key_store: self.key_store,
+            resolution_cache: self.resolution_cache,
+            token_issuer: self.token_issuer,
+            response_cache: self.response_cache,
+            audit: self.audit,
+            pages: self.pages,
+        }
+    }
+
+    pub fn session_store<S>(self, store: Arc<S>) -> OAuthProxyServerTypedBuilder<C, Arc<S>, Kst>
+    where
+        S: OAuthSessionStore + ClientAuthStore + Clone,
+    {
+        OAuthProxyServerTypedBuilder {
+            config: self.config,
+            session_store: store,
+            key_store: self.key_store,
+            resolution_cache: self.resolution_cache,
+            token_issuer: self.token_issuer,
+            response_cache: self.response_cache,
+            audit: self.audit,
+            pages: self.pages,
+        }
+    }
+
+    pub fn key_store<K>(self, store: Arc<K>) -> OAuthProxyServerTypedBuilder<C, Sst, Arc<K>>
+    where
+        K: KeyStore + Clone,
+    {
+        OAuthProxyServerTypedBuilder {
+            config: self.config,
+            session_store: self.session_store,
+            key_store: store,
+            resolution_cache: self.resolution_cache,
+            token_issuer: self.token_issuer,
+            response_cache: self.response_cache,
+            audit: self.audit,
+            pages: self.pages,
+        }
+    }
+
+    /// Use a custom identity/PDS resolution cache instead of the in-memory
+    /// default (e.g. a store-backed cache shared across instances).
+    pub fn resolution_cache(mut self, cache: Arc<dyn ResolutionCache>) -> Self {
+        self.resolution_cache = Some(cache);
+        self
+    }
+
+    /// Use a custom downstream token format instead of the default ES256
+    /// JWTs (e.g. extra claims, EdDSA, or an external token service).
+    pub fn token_issuer(mut self, issuer: Arc<dyn TokenIssuer>) -> Self {
+        self.token_issuer = Some(issuer);
+        self
+    }
+
+    /// Use a custom XRPC response cache instead of the in-memory default
+    /// (e.g. a shared cache across instances).
+    pub fn response_cache(mut self, cache: Arc<dyn crate::cache::ResponseCache>) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Send security audit events to a custom sink instead of the default
+    /// tracing-based one (e.g. a database or SIEM forwarder).
+    pub fn audit_sink(mut self, sink: Arc<dyn crate::audit::AuditSink>) -> Self {
+        self.audit = Some(sink);
+        self
+    }
+
+    /// Render human-facing pages (browser-mediated flow errors) through a
+    /// custom [`PageRenderer`](crate::pages::PageRenderer) instead of the
+    /// minimal built-in template.
+    pub fn page_renderer(mut self, pages: Arc<dyn crate::pages::PageRenderer>) -> Self {
+        self.pages = Some(pages);
+        self
+    }
+}
+
+impl<S, K> OAuthProxyServerTypedBuilder<ProxyConfig, Arc<S>, Arc<K>>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    /// Build the server, blocking the current thread while the signing key
+    /// is fetched. Prefer [`build_async`](Self::build_async).
+    #[deprecated(note = "use build_async; block_in_place panics on current-thread runtimes")]
+    pub fn build(self) -> Result<OAuthProxyServer<S, K>> {
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(self.build_async())
         })
     }
+
+    /// Build the server. Still fallible: the signing key fetch and keyset
+    /// construction can fail even with all parts present.
+    pub async fn build_async(self) -> Result<OAuthProxyServer<S, K>> {
+        OAuthProxyServerBuilder {
+            config: Some(self.config),
+            session_store: Some(self.session_store),
+            key_store: Some(self.key_store),
+            resolution_cache: self.resolution_cache,
+            token_issuer: self.token_issuer,
+            response_cache: self.response_cache,
+            audit: self.audit,
+            pages: self.pages,
+        }
+        .build_async()
+        .await
+    }
 }
 
 // Request/response types
@@ -1296,11 +3859,14 @@ struct PARRequest {
     client_id: String,
     redirect_uri: String,
     response_type: String,
+    response_mode: Option<String>,
     state: Option<String>,
     scope: Option<String>,
     code_challenge: Option<String>,
     code_challenge_method: Option<String>,
     login_hint: Option<String>,
+    client_assertion_type: Option<String>,
+    client_assertion: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1308,6 +3874,7 @@ struct AuthorizeParams {
     client_id: Option<String>,
     redirect_uri: Option<String>,
     response_type: Option<String>,
+    response_mode: Option<String>,
     state: Option<String>,
     scope: Option<String>,
     request_uri: Option<String>,
@@ -1327,7 +3894,10 @@ struct TokenRequest {
     code: Option<String>,
     refresh_token: Option<String>,
     client_id: Option<String>,
+    client_secret: Option<String>,
     redirect_uri: Option<String>,
+    client_assertion_type: Option<String>,
+    client_assertion: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1338,53 +3908,531 @@ struct TokenResponse {
     refresh_token: Option<String>,
     scope: String,
     sub: String,
+    /// Present in cookie session mode; the client echoes it in
+    /// `X-CSRF-Token` on mutating XRPC requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    csrf_token: Option<String>,
 }
 
 // Helper functions
 
-fn extract_dpop_jkt_and_key(headers: &HeaderMap) -> Result<(String, jose_jwk::Jwk)> {
-    use base64::prelude::*;
+/// Normalize the requested response_mode, defaulting to "query" per
+/// OAuth 2.1 when the client didn't ask for one.
+fn validate_response_mode(mode: Option<&str>) -> Result<String> {
+    match mode {
+        None => Ok("query".to_string()),
+        Some(mode @ ("query" | "fragment" | "form_post")) => Ok(mode.to_string()),
+        Some(other) => Err(Error::InvalidRequest(format!(
+            "unsupported response_mode: {}",
+            other
+        ))),
+    }
+}
 
-    // Get the DPoP header
-    let dpop_proof = headers
-        .get("DPoP")
+/// Render a browser-mediated flow failure as an HTML page through the
+/// server's [`PageRenderer`](crate::pages::PageRenderer), reusing the
+/// status the JSON error path would have chosen.
+fn render_error_page<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    error: Error,
+    retry_uri: Option<&str>,
+) -> Response
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    tracing::warn!("browser-facing flow step failed: {}", error);
+    let message = error.to_string();
+    let status = error.into_response().status();
+    let html = server.pages.error_page(&crate::pages::ErrorPage {
+        status: status.as_u16(),
+        message: &message,
+        retry_uri,
+    });
+    (status, Html(html)).into_response()
+}
+
+/// Render the auto-submitting form used for `response_mode=form_post`
+/// (OAuth 2.0 Form Post Response Mode).
+fn render_form_post(
+    redirect_uri: &str,
+    code: &str,
+    state: Option<&str>,
+    issuer: Option<&str>,
+) -> Html<String> {
+    // The state is client-supplied, so everything going into an attribute
+    // gets escaped
+    fn escape(value: &str) -> String {
+        value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&#39;")
+    }
+
+    let state_input = state
+        .map(|s| {
+            format!(
+                "<input type=\"hidden\" name=\"state\" value=\"{}\">",
+                escape(s)
+            )
+        })
+        .unwrap_or_default();
+
+    let iss_input = issuer
+        .map(|i| {
+            format!(
+                "<input type=\"hidden\" name=\"iss\" value=\"{}\">",
+                escape(i)
+            )
+        })
+        .unwrap_or_default();
+
+    Html(format!(
+        "<!DOCTYPE html>\
+         <html><head><title>Submitting...</title></head>\
+         <body onload=\"document.forms[0].submit()\">\
+         <form method=\"post\" action=\"{}\">\
+         <input type=\"hidden\" name=\"code\" value=\"{}\">\
+         {}\
+         {}\
+         <noscript><button type=\"submit\">Continue</button></noscript>\
+         </form></body></html>",
+        escape(redirect_uri),
+        escape(code),
+        state_input,
+        iss_input
+    ))
+}
+
+/// Grant types advertised in the authorization server metadata.
+/// `client_credentials` is only advertised when service clients are configured.
+fn grant_types_supported(config: &ProxyConfig) -> Vec<&'static str> {
+    let mut grant_types = vec!["authorization_code", "refresh_token"];
+    if !config.service_clients.is_empty() {
+        grant_types.push("client_credentials");
+    }
+    grant_types
+}
+
+/// Request headers never forwarded upstream: credentials, cookies,
+/// hop-by-hop headers, and client IP headers (the proxy injects its own
+/// X-Forwarded-For/Via).
+const REQUEST_HEADER_DENYLIST: &[&str] = &[
+    "host",
+    "authorization",
+    "dpop",
+    "cookie",
+    "connection",
+    "content-length",
+    "forwarded",
+    "proxy-authorization",
+    "transfer-encoding",
+    "via",
+    "x-forwarded-for",
+    "x-forwarded-host",
+    "x-forwarded-proto",
+    "x-real-ip",
+];
+
+/// Response headers never passed back to the client.
+const RESPONSE_HEADER_DENYLIST: &[&str] = &[
+    "connection",
+    "content-length",
+    "set-cookie",
+    "transfer-encoding",
+    "via",
+];
+
+fn should_forward_request_header(config: &ProxyConfig, name: &str) -> bool {
+    if REQUEST_HEADER_DENYLIST.contains(&name)
+        || config
+            .request_header_denylist
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case(name))
+    {
+        return false;
+    }
+    if !config.request_header_allowlist.is_empty() {
+        return config
+            .request_header_allowlist
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case(name));
+    }
+    true
+}
+
+fn should_return_response_header(config: &ProxyConfig, name: &str) -> bool {
+    if RESPONSE_HEADER_DENYLIST.contains(&name)
+        || config
+            .response_header_denylist
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case(name))
+    {
+        return false;
+    }
+    if !config.response_header_allowlist.is_empty() {
+        return config
+            .response_header_allowlist
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case(name));
+    }
+    true
+}
+
+/// Correlation ID stamped on an OAuth flow at PAR (or direct authorize)
+/// time and persisted through `PARData`/`DownstreamClientInfo`/
+/// `PendingAuth`, so one grep over the logs reconstructs a flow across
+/// PAR -> authorize -> callback -> token exchange.
+fn new_flow_id() -> String {
+    use rand::RngCore;
+    let mut bytes = [0u8; 8];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+/// Per-request correlation: honor an inbound `X-Request-Id` (or mint one),
+/// wrap the handler in a tracing span carrying it, and echo it on the
+/// response — including error responses, so a client-reported failure can
+/// be matched to its log lines. The header survives into the request the
+/// XRPC proxy forwards upstream, since the forwarding filter passes it.
+async fn request_id_middleware(
+    mut request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    use tracing::Instrument;
+
+    let request_id = request
+        .headers()
+        .get("x-request-id")
         .and_then(|v| v.to_str().ok())
-        .ok_or(Error::DpopProofRequired)?;
+        .filter(|v| !v.is_empty() && v.len() <= 128)
+        .map(|v| v.to_string())
+        .unwrap_or_else(new_flow_id);
+
+    if let Ok(value) = axum::http::HeaderValue::from_str(&request_id) {
+        request.headers_mut().insert("x-request-id", value.clone());
+        let span = tracing::info_span!("request", request_id = %request_id);
+        let mut response = next.run(request).instrument(span).await;
+        response.headers_mut().insert("x-request-id", value);
+        response
+    } else {
+        // Unrepresentable header values only happen with a hostile inbound
+        // ID; proceed without correlation rather than failing the request
+        next.run(request).await
+    }
+}
+
+/// CORS policy for the endpoints that touch credentials: PAR, authorize,
+/// token, revoke, introspect, logout, and the XRPC proxy.
+///
+/// Driven by [`ProxyConfig::cors_allowed_origins`]: a `"*"` entry answers
+/// any origin, listed origins are allowed exactly, and the empty default
+/// sends no CORS headers at all — cross-origin browser access to token
+/// endpoints is opt-in. Methods and headers mirror the preflight request,
+/// which stays valid when credentials are allowed (a wildcard there would
+/// not be).
+fn sensitive_cors_layer(config: &ProxyConfig) -> CorsLayer {
+    let allow_origin = if config.cors_allowed_origins.iter().any(|o| o == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(
+            config
+                .cors_allowed_origins
+                .iter()
+                .filter_map(|origin| origin.parse().ok()),
+        )
+    };
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods(AllowMethods::mirror_request())
+        .allow_headers(AllowHeaders::mirror_request())
+        .allow_credentials(config.cors_allow_credentials)
+}
+
+/// Build the X-Forwarded-For/Via headers to inject on upstream requests,
+/// extending any chain the client (or an outer reverse proxy) sent.
+fn forwarding_headers(
+    headers: &HeaderMap,
+    client_ip: Option<std::net::IpAddr>,
+) -> Vec<(&'static str, String)> {
+    let mut injected = Vec::new();
+
+    let existing_xff = headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    let xff = match (existing_xff.is_empty(), client_ip) {
+        (true, Some(ip)) => ip.to_string(),
+        (false, Some(ip)) => format!("{}, {}", existing_xff, ip),
+        (false, None) => existing_xff.to_string(),
+        (true, None) => String::new(),
+    };
+    if !xff.is_empty() {
+        injected.push(("x-forwarded-for", xff));
+    }
+
+    let existing_via = headers
+        .get("via")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    let via = if existing_via.is_empty() {
+        VIA_PSEUDONYM.to_string()
+    } else {
+        format!("{}, {}", existing_via, VIA_PSEUDONYM)
+    };
+    injected.push(("via", via));
+
+    injected
+}
+
+const VIA_PSEUDONYM: &str = "1.1 jacquard-oatproxy";
+
+/// Resolve the `atproto-proxy` header (`did#service`) to a service endpoint
+/// if direct routing is enabled for that DID.
+///
+/// Returns `None` when the header is absent or the DID is not allowlisted;
+/// in the latter case the request is forwarded to the PDS unchanged, which
+/// performs the service proxying itself.
+async fn resolve_atproto_proxy_target(
+    config: &ProxyConfig,
+    headers: &HeaderMap,
+) -> Result<Option<String>> {
+    let Some(header) = headers.get("atproto-proxy").and_then(|v| v.to_str().ok()) else {
+        return Ok(None);
+    };
+
+    let (did, service_fragment) = header
+        .split_once('#')
+        .ok_or_else(|| Error::InvalidRequest("invalid atproto-proxy header".to_string()))?;
+
+    if !config.atproto_proxy_allowed_dids.iter().any(|d| d == did) {
+        return Ok(None);
+    }
+
+    let doc_url = if let Some(host) = did.strip_prefix("did:web:") {
+        format!("https://{}/.well-known/did.json", host)
+    } else if did.starts_with("did:plc:") {
+        format!("https://plc.directory/{}", did)
+    } else {
+        return Err(Error::InvalidRequest(format!(
+            "unsupported DID method: {}",
+            did
+        )));
+    };
+
+    let doc: serde_json::Value = reqwest::get(&doc_url)
+        .await
+        .map_err(|e| Error::NetworkError(format!("failed to resolve service DID: {}", e)))?
+        .json()
+        .await
+        .map_err(|e| Error::NetworkError(format!("invalid DID document: {}", e)))?;
+
+    let service_id = format!("#{}", service_fragment);
+    let endpoint = doc
+        .get("service")
+        .and_then(|s| s.as_array())
+        .and_then(|services| {
+            services
+                .iter()
+                .find(|svc| svc.get("id").and_then(|id| id.as_str()) == Some(service_id.as_str()))
+        })
+        .and_then(|svc| svc.get("serviceEndpoint"))
+        .and_then(|e| e.as_str())
+        .ok_or_else(|| {
+            Error::InvalidRequest(format!("service {} not found in DID document", header))
+        })?;
+
+    Ok(Some(endpoint.trim_end_matches('/').to_string()))
+}
+
+/// Enforce the configured aud/lxm allowlists for
+/// `com.atproto.server.getServiceAuth` requests.
+fn check_service_auth_request(config: &ProxyConfig, uri: &http::Uri) -> Result<()> {
+    let params: Vec<(String, String)> = serde_urlencoded::from_str(uri.query().unwrap_or(""))
+        .map_err(|e| Error::InvalidRequest(format!("invalid query string: {}", e)))?;
+
+    let aud = params
+        .iter()
+        .find(|(k, _)| k == "aud")
+        .map(|(_, v)| v.as_str())
+        .ok_or_else(|| Error::InvalidRequest("missing aud".to_string()))?;
+
+    if !config.service_auth_allowed_auds.is_empty()
+        && !config.service_auth_allowed_auds.iter().any(|a| a == aud)
+    {
+        tracing::warn!("getServiceAuth aud not in allowlist: {}", aud);
+        return Err(Error::UnauthorizedClient);
+    }
+
+    if let Some(lxm) = params
+        .iter()
+        .find(|(k, _)| k == "lxm")
+        .map(|(_, v)| v.as_str())
+    {
+        if !config.service_auth_allowed_lxms.is_empty()
+            && !config.service_auth_allowed_lxms.iter().any(|l| l == lxm)
+        {
+            tracing::warn!("getServiceAuth lxm not in allowlist: {}", lxm);
+            return Err(Error::UnauthorizedClient);
+        }
+    }
+
+    Ok(())
+}
+
+const CLIENT_ASSERTION_TYPE_JWT_BEARER: &str =
+    "urn:ietf:params:oauth:client-assertion-type:jwt-bearer";
+
+/// Validate an optional `private_key_jwt` client assertion and return the
+/// effective token endpoint auth method for the request.
+///
+/// The method must be on the configured
+/// [`token_endpoint_auth_methods`](ProxyConfig::token_endpoint_auth_methods)
+/// list, so the endpoints enforce exactly what the metadata advertises.
+async fn client_auth_method_from_assertion(
+    config: &ProxyConfig,
+    client_id: &str,
+    assertion_type: Option<&str>,
+    assertion: Option<&str>,
+) -> Result<&'static str> {
+    let allowed = |method: &str| config.token_endpoint_auth_methods.iter().any(|m| m == method);
+
+    let Some(assertion) = assertion else {
+        if !allowed("none") {
+            return Err(Error::InvalidClient);
+        }
+        return Ok("none");
+    };
 
-    // DPoP proof is a JWT - parse the header to get the JWK
-    // JWT format: header.payload.signature
-    let parts: Vec<&str> = dpop_proof.split('.').collect();
-    if parts.len() != 3 {
+    if !allowed("private_key_jwt") {
+        return Err(Error::InvalidClient);
+    }
+
+    if assertion_type != Some(CLIENT_ASSERTION_TYPE_JWT_BEARER) {
         return Err(Error::InvalidRequest(
-            "invalid DPoP proof format".to_string(),
+            "unsupported client_assertion_type".to_string(),
         ));
     }
 
-    // Decode the header (first part)
-    let header_json = BASE64_URL_SAFE_NO_PAD
-        .decode(parts[0])
-        .map_err(|e| Error::InvalidRequest(format!("invalid DPoP header encoding: {}", e)))?;
+    let issuer = config.issuer();
+    crate::auth::verify_client_assertion(assertion, client_id, &issuer).await?;
 
-    let header: serde_json::Value = serde_json::from_slice(&header_json)
-        .map_err(|e| Error::InvalidRequest(format!("invalid DPoP header JSON: {}", e)))?;
+    Ok("private_key_jwt")
+}
 
-    // Extract the JWK from the header
-    let jwk_value = header
-        .get("jwk")
-        .ok_or_else(|| Error::InvalidRequest("DPoP proof missing jwk in header".to_string()))?;
+/// Every DPoP proof algorithm the proxy is able to verify; the effective
+/// allowlist is the intersection of this with the configured
+/// `dpop_signing_algs`, which is what the metadata advertises.
+const DPOP_PROOF_ALGS: &[crate::jose::JwsAlgorithm] = &[
+    crate::jose::JwsAlgorithm::Es256,
+    crate::jose::JwsAlgorithm::Es256K,
+    crate::jose::JwsAlgorithm::Ed25519,
+];
+
+/// The DPoP algorithm allowlist for this deployment. Configured names the
+/// proxy can't verify are ignored.
+fn configured_dpop_algs(config: &ProxyConfig) -> Vec<crate::jose::JwsAlgorithm> {
+    config
+        .dpop_signing_algs
+        .iter()
+        .filter_map(|name| DPOP_PROOF_ALGS.iter().copied().find(|a| a.name() == name))
+        .collect()
+}
+
+fn extract_dpop_jkt_and_key(
+    config: &ProxyConfig,
+    headers: &HeaderMap,
+) -> Result<(String, serde_json::Value)> {
+    // Get the DPoP header
+    let dpop_proof = headers
+        .get("DPoP")
+        .and_then(|v| v.to_str().ok())
+        .ok_or(Error::DpopProofRequired)?;
 
-    // Parse JWK
-    let jwk: jose_jwk::Jwk = serde_json::from_value(jwk_value.clone())
-        .map_err(|e| Error::InvalidRequest(format!("invalid JWK: {}", e)))?;
+    // Strict structural parse with the DPoP algorithm allowlist; the
+    // proof's signature itself is checked by the DPoP verifier, this only
+    // lifts the embedded key out of the header
+    let jws = crate::jose::parse_compact(dpop_proof, &configured_dpop_algs(config))?;
+    let jwk_value = jws
+        .header()
+        .jwk
+        .as_ref()
+        .map(|map| serde_json::Value::Object(map.clone()))
+        .ok_or_else(|| Error::InvalidRequest("DPoP proof missing jwk in header".to_string()))?;
 
     // Compute the JWK thumbprint (JKT) according to RFC 7638
-    let jkt = compute_jwk_thumbprint_from_json(jwk_value)?;
+    let jkt = compute_jwk_thumbprint_from_json(&jwk_value)?;
+
+    Ok((jkt, jwk_value))
+}
 
-    Ok((jkt, jwk))
+fn extract_dpop_jkt(config: &ProxyConfig, headers: &HeaderMap) -> Result<String> {
+    extract_dpop_jkt_and_key(config, headers).map(|(jkt, _)| jkt)
 }
 
-fn extract_dpop_jkt(headers: &HeaderMap) -> Result<String> {
-    extract_dpop_jkt_and_key(headers).map(|(jkt, _)| jkt)
+/// Build a typed verifying key from a DPoP proof's embedded JWK.
+///
+/// Accepts the key shapes matching [`DPOP_PROOF_ALGS`]: P-256 and
+/// secp256k1 EC keys, and Ed25519 OKP keys. Coordinate lengths are checked
+/// before conversion so malformed keys error instead of panicking.
+fn dpop_verifying_key(jwk: &serde_json::Value) -> Result<crate::jose::JwsVerifyingKey> {
+    use base64::Engine;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use crate::jose::JwsVerifyingKey;
+
+    let coordinate = |name: &str| -> Result<Vec<u8>> {
+        let value = jwk
+            .get(name)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| Error::InvalidRequest(format!("JWK missing {}", name)))?;
+        let bytes = URL_SAFE_NO_PAD
+            .decode(value)
+            .map_err(|e| Error::InvalidRequest(format!("invalid JWK {}: {}", name, e)))?;
+        if bytes.len() != 32 {
+            return Err(Error::InvalidRequest(format!("invalid JWK {} length", name)));
+        }
+        Ok(bytes)
+    };
+
+    let kty = jwk.get("kty").and_then(|v| v.as_str()).unwrap_or("");
+    let crv = jwk.get("crv").and_then(|v| v.as_str()).unwrap_or("");
+    match (kty, crv) {
+        ("EC", "P-256") => {
+            let (x, y) = (coordinate("x")?, coordinate("y")?);
+            let point = p256::EncodedPoint::from_affine_coordinates(
+                x.as_slice().into(),
+                y.as_slice().into(),
+                false,
+            );
+            let key = p256::ecdsa::VerifyingKey::from_encoded_point(&point)
+                .map_err(|_| Error::InvalidRequest("invalid P-256 public key".to_string()))?;
+            Ok(JwsVerifyingKey::Es256(key))
+        }
+        ("EC", "secp256k1") => {
+            let (x, y) = (coordinate("x")?, coordinate("y")?);
+            let point = k256::EncodedPoint::from_affine_coordinates(
+                x.as_slice().into(),
+                y.as_slice().into(),
+                false,
+            );
+            let key = k256::ecdsa::VerifyingKey::from_encoded_point(&point)
+                .map_err(|_| Error::InvalidRequest("invalid secp256k1 public key".to_string()))?;
+            Ok(JwsVerifyingKey::Es256K(key))
+        }
+        ("OKP", "Ed25519") => {
+            let x = coordinate("x")?;
+            let bytes: [u8; 32] = x.as_slice().try_into().expect("length checked above");
+            let key = ed25519_dalek::VerifyingKey::from_bytes(&bytes)
+                .map_err(|_| Error::InvalidRequest("invalid Ed25519 public key".to_string()))?;
+            Ok(JwsVerifyingKey::Ed25519(key))
+        }
+        _ => Err(Error::InvalidRequest(format!(
+            "unsupported DPoP key type: {}/{}",
+            kty, crv
+        ))),
+    }
 }
 
 fn compute_jwk_thumbprint(jwk: &jose_jwk::Jwk) -> Result<String> {
@@ -1476,16 +4524,143 @@ fn compute_jwk_thumbprint_from_json(jwk: &serde_json::Value) -> Result<String> {
     Ok(BASE64_URL_SAFE_NO_PAD.encode(&hash))
 }
 
-fn generate_random_string(len: usize) -> String {
-    use rand::Rng;
-    const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
-    let mut rng = rand::thread_rng();
-    (0..len)
-        .map(|_| {
-            let idx = rng.gen_range(0..CHARSET.len());
-            CHARSET[idx] as char
-        })
-        .collect()
+
+/// Verify an ES256 DPoP proof with HMAC nonces derived from `secret`.
+///
+/// The nonces are stateless and bound to the client, HTU/HTM, and JKT;
+/// future skew follows the configured clock-skew tolerance so drifting
+/// client clocks don't 401. Called once with the primary secret and, on
+/// a nonce failure during rotation, again with the previous one.
+async fn verify_es256_dpop_proof<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    secret: &[u8],
+    proof: &str,
+    http_uri: &str,
+    http_method: &str,
+    client_id: &str,
+) -> Result<String>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    let hmac_config = dpop_verifier::HmacConfig::new(
+        secret, 300,  // 5 minute max age
+        true, // bind to HTU/HTM
+        true, // bind to JKT
+        true, // bind to client
+    );
+
+    // Create a simple in-memory replay store for this request
+    let mut replay_store = SimpleReplayStore::new(server.session_store.clone());
+
+    let verifier = dpop_verifier::DpopVerifier::new()
+        .with_max_age_seconds(300)
+        .with_future_skew_seconds(server.config.clock_skew_leeway_seconds.max(0) as u64)
+        .with_nonce_mode(dpop_verifier::NonceMode::Hmac(hmac_config))
+        .with_client_binding(client_id.to_string());
+
+    let verified = verifier
+        .verify(
+            &mut replay_store,
+            proof,
+            http_uri,
+            http_method,
+            None, // no access token for PAR
+        )
+        .await
+        .map_err(|e| match e {
+            dpop_verifier::DpopError::UseDpopNonce { nonce } => {
+                // Return a special error that includes the nonce
+                // The caller will need to return this as a DPoP-Nonce header
+                Error::DpopNonceRequired(nonce)
+            }
+            _ => Error::InvalidRequest(format!("invalid DPoP proof: {}", e)),
+        })?;
+
+    Ok(verified.jkt)
+}
+
+/// Verify an ES256K or EdDSA DPoP proof.
+///
+/// `dpop-verifier` only supports P-256 keys, so the other algorithms the
+/// metadata advertises are checked here: signature against the embedded
+/// key, `typ`, `htm`/`htu` binding, `iat` freshness with the configured
+/// clock-skew leeway, and single-use `jti` through the same nonce store
+/// the ES256 path uses for replay protection. The stateless HMAC nonce
+/// layer remains ES256-only.
+async fn verify_non_es256_dpop_proof<S, K>(
+    server: &OAuthProxyServer<S, K>,
+    proof: &str,
+    http_uri: &str,
+    http_method: &str,
+) -> Result<String>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone,
+    K: KeyStore + Clone,
+{
+    use crate::jose::{self, JwsAlgorithm};
+
+    let non_es256: Vec<JwsAlgorithm> = configured_dpop_algs(&server.config)
+        .into_iter()
+        .filter(|a| *a != JwsAlgorithm::Es256)
+        .collect();
+    let jws = jose::parse_compact(proof, &non_es256)?;
+    if jws.header().typ.as_deref() != Some("dpop+jwt") {
+        return Err(Error::DpopInvalid);
+    }
+    let jwk_value = jws
+        .header()
+        .jwk
+        .as_ref()
+        .map(|map| serde_json::Value::Object(map.clone()))
+        .ok_or_else(|| Error::InvalidRequest("DPoP proof missing jwk in header".to_string()))?;
+
+    let key = dpop_verifying_key(&jwk_value)?;
+    let claims: serde_json::Value = jws.verify_key(&key)?.claims()?;
+
+    if claims.get("htm").and_then(|v| v.as_str()) != Some(http_method) {
+        return Err(Error::DpopMethodMismatch);
+    }
+    if claims.get("htu").and_then(|v| v.as_str()) != Some(http_uri) {
+        return Err(Error::DpopUrlMismatch);
+    }
+
+    // Same freshness window as the dpop-verifier path: 5 minutes back,
+    // clock-skew leeway forward
+    let iat = claims
+        .get("iat")
+        .and_then(|v| v.as_i64())
+        .ok_or(Error::DpopInvalid)?;
+    let now = chrono::Utc::now().timestamp();
+    let leeway = server.config.clock_skew_leeway_seconds.max(0);
+    if iat < now - 300 - leeway {
+        return Err(Error::DpopExpired);
+    }
+    if iat > now + leeway {
+        return Err(Error::DpopInvalid);
+    }
+
+    // Single-use jti, digested like SimpleReplayStore digests its hashes
+    let jti = claims
+        .get("jti")
+        .and_then(|v| v.as_str())
+        .ok_or(Error::DpopInvalid)?;
+    let jti_digest = {
+        use sha2::{Digest, Sha256};
+        hex::encode(Sha256::digest(jti.as_bytes()))
+    };
+    if !server.session_store.check_and_consume_nonce(&jti_digest).await? {
+        server
+            .audit
+            .record(
+                crate::audit::AuditRecord::new(crate::audit::AuditEvent::DpopReplayDetected)
+                    .with_detail(format!("jti {} presented twice", jti)),
+            )
+            .await;
+        return Err(Error::DpopNonceReused);
+    }
+
+    compute_jwk_thumbprint_from_json(&jwk_value)
 }
 
 // Simple ReplayStore implementation that wraps our OAuthSessionStore
@@ -23,7 +23,11 @@ pub enum SessionState {
 }
 
 /// OAuth session containing both upstream (proxy↔PDS) and downstream (client↔proxy) state
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// `Debug` is implemented by hand so token material never reaches logs;
+/// `Serialize` keeps the real values, since stores persist sessions
+/// through it.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct OAuthSession {
     /// Unique session identifier
     pub id: SessionId,
@@ -105,6 +109,54 @@ pub struct OAuthSession {
     pub last_used_at: DateTime<Utc>,
 }
 
+impl std::fmt::Debug for OAuthSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Tokens, codes, and the PKCE verifier are redacted down to
+        // presence; everything else prints as derive(Debug) would
+        fn redact<T>(value: &Option<T>) -> &'static str {
+            if value.is_some() { "Some([redacted])" } else { "None" }
+        }
+        f.debug_struct("OAuthSession")
+            .field("id", &self.id)
+            .field("state", &self.state)
+            .field("did", &self.did)
+            .field("handle", &self.handle)
+            .field("pds_url", &self.pds_url)
+            .field("upstream_access_token", &"[redacted]")
+            .field(
+                "upstream_refresh_token",
+                &redact(&self.upstream_refresh_token),
+            )
+            .field(
+                "upstream_dpop_key_thumbprint",
+                &self.upstream_dpop_key_thumbprint,
+            )
+            .field("upstream_expires_at", &self.upstream_expires_at)
+            .field("upstream_scope", &self.upstream_scope)
+            .field("upstream_dpop_nonce", &self.upstream_dpop_nonce)
+            .field("downstream_auth_code", &redact(&self.downstream_auth_code))
+            .field(
+                "downstream_refresh_token",
+                &redact(&self.downstream_refresh_token),
+            )
+            .field(
+                "downstream_dpop_key_thumbprint",
+                &self.downstream_dpop_key_thumbprint,
+            )
+            .field("downstream_expires_at", &self.downstream_expires_at)
+            .field("downstream_dpop_nonce_pad", &"[redacted]")
+            .field("jti_cache", &self.jti_cache)
+            .field("request_uri", &self.request_uri)
+            .field("pkce_verifier", &redact(&self.pkce_verifier))
+            .field("downstream_redirect_uri", &self.downstream_redirect_uri)
+            .field("downstream_client_id", &self.downstream_client_id)
+            .field("downstream_state", &self.downstream_state)
+            .field("created_at", &self.created_at)
+            .field("last_used_at", &self.last_used_at)
+            .finish()
+    }
+}
+
 impl OAuthSession {
     /// Create a new session in pending state
     pub fn new(did: Did<'static>, pds_url: Url, client_id: String, redirect_uri: String) -> Self {
@@ -153,15 +205,15 @@ impl OAuthSession {
 }
 
 fn generate_session_id() -> String {
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
-    let bytes: [u8; 32] = rng.r#gen();
+    use rand::RngCore;
+    let mut bytes = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
     hex::encode(bytes)
 }
 
 fn generate_nonce_pad() -> String {
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
-    let bytes: [u8; 16] = rng.r#gen();
+    use rand::RngCore;
+    let mut bytes = [0u8; 16];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
     hex::encode(bytes)
 }
@@ -2,6 +2,7 @@ use crate::error::Result;
 use crate::session::{OAuthSession, SessionId};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
+use std::sync::Arc;
 
 /// Information about a pending downstream authorization
 #[derive(Debug, Clone)]
@@ -10,10 +11,17 @@ pub struct PendingAuth {
     pub account_did: String,
     /// Session ID from upstream (the state parameter)
     pub upstream_session_id: String,
+    /// Downstream client the code was issued to, when known
+    pub client_id: Option<String>,
     /// Downstream client's redirect URI
     pub redirect_uri: String,
     /// Downstream client's state parameter
     pub state: Option<String>,
+    /// How the client authenticated ("none" or "private_key_jwt")
+    pub auth_method: String,
+    /// Correlation ID minted when the flow started, carried through from
+    /// [`PARData`]; `None` on legacy rows
+    pub flow_id: Option<String>,
     /// When this authorization expires
     pub expires_at: DateTime<Utc>,
 }
@@ -21,14 +29,27 @@ pub struct PendingAuth {
 /// Downstream client metadata for an authorization flow
 #[derive(Debug, Clone)]
 pub struct DownstreamClientInfo {
+    /// Client's client_id, when known
+    pub client_id: Option<String>,
     /// Client's redirect URI
     pub redirect_uri: String,
     /// Client's state parameter
     pub state: Option<String>,
     /// Client's response type
     pub response_type: String,
+    /// How the authorization response is delivered
+    /// ("query", "fragment", or "form_post")
+    pub response_mode: String,
     /// Requested scope
     pub scope: Option<String>,
+    /// How the client authenticated ("none" or "private_key_jwt")
+    pub auth_method: String,
+    /// Upstream authorization server expected to answer the callback,
+    /// recorded for mix-up protection; `None` before the upstream flow
+    /// starts or on legacy rows
+    pub upstream_issuer: Option<String>,
+    /// Correlation ID minted when the flow started; `None` on legacy rows
+    pub flow_id: Option<String>,
     /// When this info expires
     pub expires_at: DateTime<Utc>,
 }
@@ -42,6 +63,9 @@ pub struct PARData {
     pub redirect_uri: String,
     /// Response type
     pub response_type: String,
+    /// How the authorization response is delivered
+    /// ("query", "fragment", or "form_post")
+    pub response_mode: String,
     /// State parameter
     pub state: Option<String>,
     /// Requested scope
@@ -54,10 +78,66 @@ pub struct PARData {
     pub login_hint: Option<String>,
     /// Downstream client's DPoP JKT
     pub downstream_dpop_jkt: String,
+    /// How the client authenticated ("none" or "private_key_jwt")
+    pub auth_method: String,
+    /// Correlation ID minted when the PAR was pushed; every log line the
+    /// flow produces through authorize, callback, and token exchange
+    /// carries it, so one grep reconstructs the whole flow. `None` on
+    /// legacy rows
+    pub flow_id: Option<String>,
     /// When this PAR expires (typically 90 seconds)
     pub expires_at: DateTime<Utc>,
 }
 
+/// A downstream refresh token mapping with its lifetime bounds
+#[derive(Debug, Clone)]
+pub struct RefreshTokenData {
+    /// Account DID the token belongs to
+    pub account_did: String,
+    /// Upstream session the token refreshes
+    pub session_id: String,
+    /// Downstream client the token was issued to, when known
+    pub client_id: Option<String>,
+    /// Hard expiry set at the original grant and carried through
+    /// rotation; `None` means no absolute lifetime is enforced
+    pub absolute_expires_at: Option<DateTime<Utc>>,
+    /// Expiry refreshed on every use; `None` means no idle timeout
+    pub idle_expires_at: Option<DateTime<Utc>>,
+    /// Thumbprint of the client DPoP key the token was issued to;
+    /// `None` on rows from before the binding was recorded, which the
+    /// refresh grant treats as matching any key
+    pub dpop_jkt: Option<String>,
+}
+
+/// An opaque downstream access token issued in opaque token mode
+#[derive(Debug, Clone)]
+pub struct AccessTokenData {
+    /// Account DID the token authenticates
+    pub account_did: String,
+    /// DPoP JKT the token is bound to (same cnf semantics as JWTs)
+    pub dpop_jkt: String,
+    /// Scope granted to the token
+    pub scope: String,
+    /// When the token was issued
+    pub issued_at: DateTime<Utc>,
+    /// When the token expires
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A downstream client's standing authorization for an account, shown to
+/// the user by the grant listing endpoint
+#[derive(Debug, Clone)]
+pub struct GrantData {
+    /// Downstream client the grant belongs to
+    pub client_id: String,
+    /// Scope granted at the most recent authorization, when known
+    pub scope: Option<String>,
+    /// When the client was first authorized
+    pub created_at: DateTime<Utc>,
+    /// When the client last exchanged or refreshed a token
+    pub last_used_at: DateTime<Utc>,
+}
+
 /// Storage abstraction for OAuth sessions
 #[async_trait]
 pub trait OAuthSessionStore: Send + Sync {
@@ -93,21 +173,92 @@ pub trait OAuthSessionStore: Send + Sync {
     async fn store_par_data(&self, request_uri: &str, data: PARData) -> Result<()>;
 
     /// Get and remove PAR data by request_uri
+    ///
+    /// Implementations MUST make this an atomic get-and-delete: when
+    /// concurrent authorize calls race on the same request_uri, at most
+    /// one may observe the data. Callers should go through
+    /// [`consume_valid_par_data`](Self::consume_valid_par_data), which
+    /// additionally drops expired entries.
     async fn consume_par_data(&self, request_uri: &str) -> Result<Option<PARData>>;
 
-    /// Store refresh token mapping (refresh_token → account_did + session_id)
+    /// Consume PAR data, treating expired entries as absent
+    ///
+    /// Provided on top of [`consume_par_data`](Self::consume_par_data) so
+    /// every caller gets the same single-use, auto-expiring semantics.
+    async fn consume_valid_par_data(&self, request_uri: &str) -> Result<Option<PARData>> {
+        match self.consume_par_data(request_uri).await? {
+            Some(data) if data.expires_at > Utc::now() => Ok(Some(data)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Count stored PAR entries for a client that haven't expired yet,
+    /// used to cap outstanding requests per client
+    async fn count_active_par_requests(
+        &self,
+        client_id: &str,
+        now: DateTime<Utc>,
+    ) -> Result<u64>;
+
+    /// Store refresh token mapping (refresh_token → session + lifetimes)
     async fn store_refresh_token_mapping(
         &self,
         refresh_token: &str,
-        account_did: String,
-        session_id: String,
+        data: RefreshTokenData,
     ) -> Result<()>;
 
     /// Get refresh token mapping by refresh token
     async fn get_refresh_token_mapping(
         &self,
         refresh_token: &str,
-    ) -> Result<Option<(String, String)>>;
+    ) -> Result<Option<RefreshTokenData>>;
+
+    /// Delete a refresh token mapping, revoking the refresh token
+    async fn delete_refresh_token_mapping(&self, refresh_token: &str) -> Result<()>;
+
+    /// Garbage-collect refresh token mappings whose absolute or idle
+    /// expiry is in the past, returning how many were removed
+    async fn delete_expired_refresh_tokens(&self, now: DateTime<Utc>) -> Result<u64>;
+
+    /// Delete upstream session records that haven't been used since
+    /// `cutoff`, along with whatever per-session state the store keys to
+    /// them, returning the `(did, session_id)` pairs purged so callers
+    /// can emit events or mirror the cleanup in their own tables.
+    ///
+    /// The default does nothing; stores that track session activity
+    /// should override it so the retention policy in
+    /// [`run_cleanup`](crate::server::OAuthProxyServer::run_cleanup) has
+    /// something to act on.
+    async fn delete_idle_sessions_before(
+        &self,
+        _cutoff: DateTime<Utc>,
+    ) -> Result<Vec<(String, String)>> {
+        Ok(Vec::new())
+    }
+
+    /// Record that `client_id` holds a grant for `did`, creating the
+    /// record on first authorization and bumping `last_used_at` (and the
+    /// scope, when one is given) on every token exchange after that
+    async fn record_grant(&self, did: &str, client_id: &str, scope: Option<String>) -> Result<()>;
+
+    /// List the grants recorded for a DID, for the user-facing grant
+    /// listing endpoint
+    async fn list_grants(&self, did: &str) -> Result<Vec<GrantData>>;
+
+    /// Delete the grant record for `(did, client_id)` and revoke every
+    /// refresh token that client holds for the DID, returning how many
+    /// tokens were revoked. Access tokens already issued run out on
+    /// their own short lifetime, matching revocation semantics elsewhere
+    async fn delete_grant(&self, did: &str, client_id: &str) -> Result<u64>;
+
+    /// Store an opaque downstream access token (opaque token mode)
+    async fn store_access_token(&self, access_token: &str, data: AccessTokenData) -> Result<()>;
+
+    /// Look up an opaque downstream access token
+    async fn get_access_token(&self, access_token: &str) -> Result<Option<AccessTokenData>>;
+
+    /// Delete an opaque downstream access token, revoking it
+    async fn delete_access_token(&self, access_token: &str) -> Result<()>;
 
     /// Store active session mapping (DID → session_id)
     async fn store_active_session(&self, did: &str, session_id: String) -> Result<()>;
@@ -115,6 +266,9 @@ pub trait OAuthSessionStore: Send + Sync {
     /// Get active session for a DID
     async fn get_active_session(&self, did: &str) -> Result<Option<String>>;
 
+    /// Clear the active session mapping for a DID (logout)
+    async fn clear_active_session(&self, did: &str) -> Result<()>;
+
     /// Store DPoP key for a session
     async fn store_session_dpop_key(
         &self,
@@ -129,6 +283,13 @@ pub trait OAuthSessionStore: Send + Sync {
         session_id: &str,
     ) -> Result<Option<(String, jose_jwk::Jwk)>>;
 
+    /// Record how the downstream client authenticated for a session
+    /// ("none" or "private_key_jwt")
+    async fn store_session_auth_method(&self, session_id: &str, auth_method: String) -> Result<()>;
+
+    /// Get the recorded client auth method for a session
+    async fn get_session_auth_method(&self, session_id: &str) -> Result<Option<String>>;
+
     /// Store DPoP nonce for a session
     async fn update_session_dpop_nonce(&self, session_id: &str, nonce: String) -> Result<()>;
 
@@ -145,8 +306,107 @@ pub trait OAuthSessionStore: Send + Sync {
 pub trait KeyStore: Send + Sync {
     /// Get the proxy's JWT signing key for issuing downstream tokens
     /// Returns a P256 ECDSA signing key
+    ///
+    /// `SigningKey` zeroizes its scalar on drop, so handing out owned
+    /// copies per call doesn't leave key material behind; implementations
+    /// should avoid logging or Debug-formatting the key regardless
     async fn get_signing_key(&self) -> Result<p256::ecdsa::SigningKey>;
 
     /// Get a DPoP key by its thumbprint
     async fn get_dpop_key(&self, thumbprint: &str) -> Result<Option<jose_jwk::Jwk>>;
+
+    /// Persist a DPoP key under its thumbprint so
+    /// [`get_dpop_key`](Self::get_dpop_key) can find it later
+    async fn store_dpop_key(&self, thumbprint: &str, key: jose_jwk::Jwk) -> Result<()>;
+
+    /// Delete a DPoP key by its thumbprint (no-op if absent)
+    async fn delete_dpop_key(&self, thumbprint: &str) -> Result<()>;
+
+    /// Generate and persist a fresh P-256 DPoP keypair, returning the
+    /// thumbprint and the full JWK (private `d` included)
+    ///
+    /// The default implementation generates via
+    /// [`generate_p256_dpop_jwk`](crate::jose::generate_p256_dpop_jwk) and
+    /// persists through [`store_dpop_key`](Self::store_dpop_key); override
+    /// it for stores backed by an HSM or external key service
+    async fn create_dpop_key(&self) -> Result<(String, jose_jwk::Jwk)> {
+        let (thumbprint, jwk) = crate::jose::generate_p256_dpop_jwk();
+        self.store_dpop_key(&thumbprint, jwk.clone()).await?;
+        Ok((thumbprint, jwk))
+    }
+}
+
+/// How long [`KeyMaterial`] trusts cached keys before re-reading them
+/// from the key store
+pub const DEFAULT_KEY_MATERIAL_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// In-process cache over a [`KeyStore`]'s signing key.
+///
+/// Every JWT issued or validated needs the proxy's signing key, and for
+/// SQL-backed stores `get_signing_key` is a database read — paid once per
+/// request without caching, which benchmarks showed dwarfs the signature
+/// check itself. `KeyMaterial` holds the parsed signing and verifying
+/// keys, refreshing them after a TTL so out-of-band signing-key rotation
+/// still takes effect; call [`invalidate`](Self::invalidate) after an
+/// explicit rotation to pick the new key up immediately.
+pub struct KeyMaterial<K: KeyStore> {
+    key_store: Arc<K>,
+    ttl: std::time::Duration,
+    cached: std::sync::Mutex<Option<CachedKeys>>,
+}
+
+/// One fetched-and-parsed generation of the signing key
+struct CachedKeys {
+    signing_key: p256::ecdsa::SigningKey,
+    verifying_key: p256::ecdsa::VerifyingKey,
+    fetched_at: std::time::Instant,
+}
+
+impl<K: KeyStore> KeyMaterial<K> {
+    /// Cache keys from `key_store` with the default TTL
+    pub fn new(key_store: Arc<K>) -> Self {
+        Self {
+            key_store,
+            ttl: DEFAULT_KEY_MATERIAL_TTL,
+            cached: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Override how long cached keys are trusted
+    pub fn with_ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// The proxy's signing key, from cache when fresh
+    pub async fn signing_key(&self) -> Result<p256::ecdsa::SigningKey> {
+        Ok(self.fresh().await?.0)
+    }
+
+    /// The verifying key for the proxy's signing key, from cache when fresh
+    pub async fn verifying_key(&self) -> Result<p256::ecdsa::VerifyingKey> {
+        Ok(self.fresh().await?.1)
+    }
+
+    /// Drop the cached keys so the next use re-reads the key store;
+    /// call after rotating the signing key
+    pub fn invalidate(&self) {
+        *self.cached.lock().unwrap() = None;
+    }
+
+    async fn fresh(&self) -> Result<(p256::ecdsa::SigningKey, p256::ecdsa::VerifyingKey)> {
+        if let Some(cached) = self.cached.lock().unwrap().as_ref() {
+            if cached.fetched_at.elapsed() < self.ttl {
+                return Ok((cached.signing_key.clone(), cached.verifying_key));
+            }
+        }
+        let signing_key = self.key_store.get_signing_key().await?;
+        let verifying_key = *signing_key.verifying_key();
+        *self.cached.lock().unwrap() = Some(CachedKeys {
+            signing_key: signing_key.clone(),
+            verifying_key,
+            fetched_at: std::time::Instant::now(),
+        });
+        Ok((signing_key, verifying_key))
+    }
 }
@@ -0,0 +1,195 @@
+//! Multi-tenant dispatch: several virtual proxies behind one deployment.
+//!
+//! SaaS hosts running one server for many apps give each app its own fully
+//! built [`OAuthProxyServer`] — its own issuer host, client metadata, and
+//! signing key — and route requests to the right one through a
+//! [`TenantResolver`]. Because every tenant is a complete server value,
+//! all config and key lookups downstream of dispatch are tenant-aware
+//! without the handlers knowing tenants exist.
+//!
+//! The default [`HostTenantResolver`] keys tenants by the request's `Host`
+//! header. Custom resolvers can look tenants up anywhere (a database, a
+//! control plane) as long as they hand back a built server.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use axum::{
+    Json, Router,
+    body::Body,
+    http::{Request, StatusCode},
+    response::{IntoResponse, Response},
+};
+use jacquard_oauth::authstore::ClientAuthStore;
+use tower::ServiceExt;
+
+use crate::error::Result;
+use crate::server::OAuthProxyServer;
+use crate::store::{KeyStore, OAuthSessionStore};
+
+/// Resolves which tenant (virtual proxy) serves a request.
+#[async_trait]
+pub trait TenantResolver<S, K>: Send + Sync
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    /// Look up the tenant for a request's `Host` header value. `None`
+    /// means no tenant claims the host; the dispatcher falls back to the
+    /// default tenant, or answers 404 if there isn't one.
+    async fn resolve(&self, host: &str) -> Result<Option<OAuthProxyServer<S, K>>>;
+}
+
+/// The default resolver: a static map from hostname to tenant.
+///
+/// Hostnames are matched case-insensitively and with any `:port` suffix
+/// stripped, so `Example.com:8443` finds a tenant registered as
+/// `example.com`.
+pub struct HostTenantResolver<S, K>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    tenants: HashMap<String, OAuthProxyServer<S, K>>,
+}
+
+impl<S, K> HostTenantResolver<S, K>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            tenants: HashMap::new(),
+        }
+    }
+
+    /// Register a tenant under a hostname (no scheme, no port)
+    pub fn with_tenant(
+        mut self,
+        host: impl Into<String>,
+        server: OAuthProxyServer<S, K>,
+    ) -> Self {
+        self.tenants.insert(host.into().to_ascii_lowercase(), server);
+        self
+    }
+}
+
+impl<S, K> Default for HostTenantResolver<S, K>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl<S, K> TenantResolver<S, K> for HostTenantResolver<S, K>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    async fn resolve(&self, host: &str) -> Result<Option<OAuthProxyServer<S, K>>> {
+        let normalized = host
+            .rsplit_once(':')
+            .map(|(h, _port)| h)
+            .unwrap_or(host)
+            .to_ascii_lowercase();
+        Ok(self.tenants.get(&normalized).cloned())
+    }
+}
+
+/// Front door for a multi-tenant deployment.
+///
+/// Wraps a [`TenantResolver`] and exposes a single [`router`](Self::router)
+/// that dispatches every request to the resolved tenant's own router.
+pub struct MultiTenantProxy<S, K>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    resolver: Arc<dyn TenantResolver<S, K>>,
+    default_tenant: Option<OAuthProxyServer<S, K>>,
+}
+
+impl<S, K> Clone for MultiTenantProxy<S, K>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            resolver: self.resolver.clone(),
+            default_tenant: self.default_tenant.clone(),
+        }
+    }
+}
+
+impl<S, K> MultiTenantProxy<S, K>
+where
+    S: OAuthSessionStore + ClientAuthStore + Clone + 'static,
+    K: KeyStore + Clone + 'static,
+{
+    pub fn new(resolver: Arc<dyn TenantResolver<S, K>>) -> Self {
+        Self {
+            resolver,
+            default_tenant: None,
+        }
+    }
+
+    /// Tenant that serves requests whose host no tenant claims; without
+    /// one, those requests get a 404
+    pub fn with_default_tenant(mut self, server: OAuthProxyServer<S, K>) -> Self {
+        self.default_tenant = Some(server);
+        self
+    }
+
+    /// Build the dispatching router. Every request is matched to a tenant
+    /// by its `Host` header and handed to that tenant's
+    /// [`router`](OAuthProxyServer::router); body limits, endpoint paths,
+    /// and all other per-tenant config apply as configured on the tenant.
+    pub fn router(self) -> Router {
+        Router::new().fallback(move |req: Request<Body>| {
+            let proxy = self.clone();
+            async move { proxy.dispatch(req).await }
+        })
+    }
+
+    async fn dispatch(&self, req: Request<Body>) -> Response {
+        let host = req
+            .headers()
+            .get(axum::http::header::HOST)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("");
+
+        let tenant = match self.resolver.resolve(host).await {
+            Ok(Some(tenant)) => tenant,
+            Ok(None) => match &self.default_tenant {
+                Some(tenant) => tenant.clone(),
+                None => {
+                    tracing::debug!("no tenant for host: {}", host);
+                    return (
+                        StatusCode::NOT_FOUND,
+                        Json(serde_json::json!({
+                            "error": "invalid_request",
+                            "error_description": format!("no tenant for host: {}", host),
+                        })),
+                    )
+                        .into_response();
+                }
+            },
+            Err(e) => return e.into_response(),
+        };
+
+        // Router::oneshot is infallible; per-request router construction
+        // keeps dispatch correct for resolvers whose host → tenant mapping
+        // changes at runtime
+        match tenant.router().oneshot(req).await {
+            Ok(response) => response,
+            Err(infallible) => match infallible {},
+        }
+    }
+}
@@ -0,0 +1,559 @@
+//! Integration-test harness: a mock PDS authorization server and a
+//! scripted DPoP client.
+//!
+//! End-to-end coverage of the proxy normally needs a live PDS, which makes
+//! the interesting flows (nonce retries, token rotation, revocation)
+//! untestable in CI. This module, behind the `test-utils` feature, provides
+//! a [`MockPds`] — an in-process axum server speaking the PAR / authorize /
+//! token / revoke subset of ATProto OAuth with real DPoP nonce enforcement —
+//! and a [`ScriptedDpopClient`] that signs genuine ES256 DPoP proofs, so
+//! tests can walk the full login → token → XRPC → refresh → revoke
+//! lifecycle without any network access.
+//!
+//! Nothing here is hardened for production use: the mock auto-approves every
+//! authorization and keeps all state in memory.
+
+use axum::{
+    Json, Router,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Redirect, Response},
+    routing::{get, post},
+};
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use p256::ecdsa::signature::Signer;
+use p256::ecdsa::{SigningKey, VerifyingKey};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use crate::token::generate_token;
+
+/// A test client that signs real ES256 DPoP proofs
+///
+/// Holds a private P-256 key for the lifetime of the test and produces
+/// proofs in the same shape real ATProto clients send: `dpop+jwt` typ, the
+/// public JWK in the header, and `jti`/`htm`/`htu`/`iat` claims plus the
+/// optional `nonce` and `ath`. The last server-issued nonce is remembered
+/// so scripted flows can retry after a `use_dpop_nonce` rejection.
+pub struct ScriptedDpopClient {
+    key: SigningKey,
+    jkt: String,
+    nonce: Mutex<Option<String>>,
+}
+
+impl ScriptedDpopClient {
+    /// Create a client with a fresh random key
+    pub fn new() -> Self {
+        let key = SigningKey::random(&mut rand::rngs::OsRng);
+        let jkt = jwk_thumbprint(&public_jwk(&key));
+        Self {
+            key,
+            jkt,
+            nonce: Mutex::new(None),
+        }
+    }
+
+    /// RFC 7638 thumbprint of this client's public key
+    pub fn jkt(&self) -> &str {
+        &self.jkt
+    }
+
+    /// The public JWK embedded in proof headers
+    pub fn jwk(&self) -> serde_json::Value {
+        public_jwk(&self.key)
+    }
+
+    /// Remember a nonce from a `DPoP-Nonce` response header for the next proof
+    pub fn set_nonce(&self, nonce: impl Into<String>) {
+        *self.nonce.lock().unwrap() = Some(nonce.into());
+    }
+
+    /// The nonce the next proof will carry, if any
+    pub fn nonce(&self) -> Option<String> {
+        self.nonce.lock().unwrap().clone()
+    }
+
+    /// Sign a DPoP proof for a request
+    ///
+    /// Includes the remembered nonce when one is set, and an `ath` claim
+    /// when `access_token` is given (for resource requests).
+    pub fn proof(&self, method: &str, url: &str, access_token: Option<&str>) -> String {
+        let header = json!({
+            "typ": "dpop+jwt",
+            "alg": "ES256",
+            "jwk": self.jwk(),
+        });
+
+        let mut payload = json!({
+            "jti": generate_token(16),
+            "htm": method,
+            "htu": url,
+            "iat": chrono::Utc::now().timestamp(),
+        });
+        if let Some(nonce) = self.nonce() {
+            payload["nonce"] = json!(nonce);
+        }
+        if let Some(token) = access_token {
+            let hash = Sha256::digest(token.as_bytes());
+            payload["ath"] = json!(URL_SAFE_NO_PAD.encode(hash));
+        }
+
+        let header_b64 = URL_SAFE_NO_PAD.encode(header.to_string());
+        let payload_b64 = URL_SAFE_NO_PAD.encode(payload.to_string());
+        let signing_input = format!("{}.{}", header_b64, payload_b64);
+        let signature: p256::ecdsa::Signature = self.key.sign(signing_input.as_bytes());
+        let signature_b64 = URL_SAFE_NO_PAD.encode(signature.to_bytes());
+
+        format!("{}.{}.{}", header_b64, payload_b64, signature_b64)
+    }
+}
+
+impl Default for ScriptedDpopClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The DID the mock issues tokens for
+pub const MOCK_DID: &str = "did:plc:mockpdsuser";
+
+/// A pushed authorization request the mock has accepted
+struct StoredPar {
+    client_id: String,
+    redirect_uri: String,
+    state: Option<String>,
+    dpop_jkt: String,
+}
+
+/// An authorization code the mock has issued
+struct StoredCode {
+    dpop_jkt: String,
+}
+
+#[derive(Default)]
+struct MockPdsState {
+    /// Current DPoP nonce; proofs carrying anything else are rejected
+    nonce: String,
+    nonce_counter: u64,
+    pars: HashMap<String, StoredPar>,
+    codes: HashMap<String, StoredCode>,
+    /// access_token -> bound JKT
+    access_tokens: HashMap<String, String>,
+    /// refresh_token -> bound JKT
+    refresh_tokens: HashMap<String, String>,
+    token_counter: u64,
+}
+
+impl MockPdsState {
+    fn rotate_nonce(&mut self) -> String {
+        self.nonce_counter += 1;
+        self.nonce = format!("mock-nonce-{}", self.nonce_counter);
+        self.nonce.clone()
+    }
+}
+
+/// An in-process mock PDS authorization server
+///
+/// Spawn one with [`MockPds::spawn`], point a client (or the proxy's
+/// upstream configuration) at [`MockPds::url`], and drive the flow with a
+/// [`ScriptedDpopClient`]. The mock enforces DPoP on PAR, token, revoke,
+/// and XRPC requests — including the `use_dpop_nonce` retry dance — binds
+/// codes and tokens to the proving key's JKT, and rotates refresh tokens
+/// on use. Authorization is auto-approved for [`MOCK_DID`].
+pub struct MockPds {
+    addr: SocketAddr,
+    handle: tokio::task::JoinHandle<()>,
+}
+
+impl MockPds {
+    /// Bind an ephemeral port on localhost and start serving
+    pub async fn spawn() -> Self {
+        let state = SharedState::default();
+        let router = Router::new()
+            .route(
+                "/.well-known/oauth-authorization-server",
+                get(handle_metadata),
+            )
+            .route("/oauth/par", post(handle_par))
+            .route("/oauth/authorize", get(handle_authorize))
+            .route("/oauth/token", post(handle_token))
+            .route("/oauth/revoke", post(handle_revoke))
+            .route("/xrpc/com.atproto.server.getSession", get(handle_get_session))
+            .with_state(state);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock PDS listener");
+        let addr = listener.local_addr().expect("mock PDS local addr");
+        let handle = tokio::spawn(async move {
+            axum::serve(listener, router).await.expect("serve mock PDS");
+        });
+
+        Self { addr, handle }
+    }
+
+    /// Base URL of the mock, e.g. `http://127.0.0.1:49152`
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Issuer identifier the mock advertises and stamps into `iss`
+    pub fn issuer(&self) -> String {
+        self.url()
+    }
+}
+
+impl Drop for MockPds {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+type SharedState = Arc<Mutex<MockPdsState>>;
+
+/// Outcome of checking a DPoP proof against the mock's state
+enum ProofCheck {
+    /// Proof is valid; the proving key's JKT
+    Valid(String),
+    /// Proof is missing a fresh nonce; retry with this one
+    UseNonce(String),
+    /// Proof is structurally or cryptographically invalid
+    Invalid(&'static str),
+}
+
+/// Verify a DPoP proof header the way a real PDS would: decode the header
+/// JWK, verify the ES256 signature over the signing input, and require the
+/// current nonce. Returns the JKT on success.
+fn check_dpop(state: &SharedState, headers: &HeaderMap) -> ProofCheck {
+    let Some(proof) = headers.get("DPoP").and_then(|v| v.to_str().ok()) else {
+        return ProofCheck::Invalid("missing DPoP header");
+    };
+
+    let Ok(jws) = crate::jose::parse_compact(proof, &[crate::jose::JwsAlgorithm::Es256]) else {
+        return ProofCheck::Invalid("malformed DPoP proof");
+    };
+    let Some(jwk) = jws.header().jwk.clone().map(serde_json::Value::Object) else {
+        return ProofCheck::Invalid("missing jwk");
+    };
+
+    // Rebuild the verifying key from the header JWK
+    let (Some(x), Some(y)) = (
+        jwk.get("x").and_then(|v| v.as_str()),
+        jwk.get("y").and_then(|v| v.as_str()),
+    ) else {
+        return ProofCheck::Invalid("jwk missing coordinates");
+    };
+    let (Ok(x_bytes), Ok(y_bytes)) = (URL_SAFE_NO_PAD.decode(x), URL_SAFE_NO_PAD.decode(y)) else {
+        return ProofCheck::Invalid("bad jwk coordinate encoding");
+    };
+    let point = p256::EncodedPoint::from_affine_coordinates(
+        x_bytes.as_slice().into(),
+        y_bytes.as_slice().into(),
+        false,
+    );
+    let Ok(verifying_key) = VerifyingKey::from_encoded_point(&point) else {
+        return ProofCheck::Invalid("invalid public key");
+    };
+
+    if !jws.signature_matches(&verifying_key) {
+        return ProofCheck::Invalid("signature verification failed");
+    }
+    let Ok(payload) = jws.into_verified().claims::<serde_json::Value>() else {
+        return ProofCheck::Invalid("bad payload JSON");
+    };
+
+    // Nonce dance: no nonce or a stale one earns a fresh one and a retry
+    let mut state = state.lock().unwrap();
+    let presented = payload.get("nonce").and_then(|v| v.as_str());
+    if state.nonce.is_empty() || presented != Some(state.nonce.as_str()) {
+        let fresh = state.rotate_nonce();
+        return ProofCheck::UseNonce(fresh);
+    }
+
+    ProofCheck::Valid(jwk_thumbprint(&jwk))
+}
+
+/// Short-circuit a handler with the standard `use_dpop_nonce` error
+fn use_nonce_response(nonce: String) -> Response {
+    let mut response = (
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "error": "use_dpop_nonce" })),
+    )
+        .into_response();
+    response
+        .headers_mut()
+        .insert("DPoP-Nonce", nonce.parse().expect("valid nonce header"));
+    response
+}
+
+async fn handle_metadata(headers: HeaderMap) -> Response {
+    let host = headers
+        .get("host")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("127.0.0.1");
+    let base = format!("http://{}", host);
+    Json(json!({
+        "issuer": base,
+        "pushed_authorization_request_endpoint": format!("{}/oauth/par", base),
+        "authorization_endpoint": format!("{}/oauth/authorize", base),
+        "token_endpoint": format!("{}/oauth/token", base),
+        "revocation_endpoint": format!("{}/oauth/revoke", base),
+        "response_types_supported": ["code"],
+        "grant_types_supported": ["authorization_code", "refresh_token"],
+        "code_challenge_methods_supported": ["S256"],
+        "dpop_signing_alg_values_supported": ["ES256"],
+        "require_pushed_authorization_requests": true,
+    }))
+    .into_response()
+}
+
+async fn handle_par(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let jkt = match check_dpop(&state, &headers) {
+        ProofCheck::Valid(jkt) => jkt,
+        ProofCheck::UseNonce(nonce) => return use_nonce_response(nonce),
+        ProofCheck::Invalid(reason) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "invalid_dpop_proof", "error_description": reason })),
+            )
+                .into_response();
+        }
+    };
+
+    let params: HashMap<String, String> = serde_urlencoded::from_str(&body).unwrap_or_default();
+    let (Some(client_id), Some(redirect_uri)) =
+        (params.get("client_id"), params.get("redirect_uri"))
+    else {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "invalid_request" })),
+        )
+            .into_response();
+    };
+
+    let request_uri = format!("urn:ietf:params:oauth:request_uri:{}", generate_token(16));
+    state.lock().unwrap().pars.insert(
+        request_uri.clone(),
+        StoredPar {
+            client_id: client_id.clone(),
+            redirect_uri: redirect_uri.clone(),
+            state: params.get("state").cloned(),
+            dpop_jkt: jkt,
+        },
+    );
+
+    (
+        StatusCode::CREATED,
+        Json(json!({ "request_uri": request_uri, "expires_in": 90 })),
+    )
+        .into_response()
+}
+
+async fn handle_authorize(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let Some(request_uri) = params.get("request_uri") else {
+        return (StatusCode::BAD_REQUEST, "missing request_uri").into_response();
+    };
+
+    let mut state = state.lock().unwrap();
+    let Some(par) = state.pars.remove(request_uri) else {
+        return (StatusCode::BAD_REQUEST, "unknown request_uri").into_response();
+    };
+    if let Some(client_id) = params.get("client_id") {
+        if *client_id != par.client_id {
+            return (StatusCode::BAD_REQUEST, "client_id mismatch").into_response();
+        }
+    }
+
+    // Auto-approve: a real PDS shows a consent page here
+    let code = format!("mock-code-{}", generate_token(16));
+    state.codes.insert(
+        code.clone(),
+        StoredCode {
+            dpop_jkt: par.dpop_jkt,
+        },
+    );
+
+    let host = headers
+        .get("host")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("127.0.0.1");
+    let location = format!(
+        "{}?code={}&state={}&iss={}",
+        par.redirect_uri,
+        urlencoding::encode(&code),
+        urlencoding::encode(par.state.as_deref().unwrap_or("")),
+        urlencoding::encode(&format!("http://{}", host)),
+    );
+    Redirect::to(&location).into_response()
+}
+
+async fn handle_token(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let jkt = match check_dpop(&state, &headers) {
+        ProofCheck::Valid(jkt) => jkt,
+        ProofCheck::UseNonce(nonce) => return use_nonce_response(nonce),
+        ProofCheck::Invalid(reason) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "invalid_dpop_proof", "error_description": reason })),
+            )
+                .into_response();
+        }
+    };
+
+    let params: HashMap<String, String> = serde_urlencoded::from_str(&body).unwrap_or_default();
+    let mut state = state.lock().unwrap();
+
+    let bound_jkt = match params.get("grant_type").map(String::as_str) {
+        Some("authorization_code") => {
+            let Some(code) = params.get("code") else {
+                return invalid_grant();
+            };
+            let Some(stored) = state.codes.remove(code) else {
+                return invalid_grant();
+            };
+            stored.dpop_jkt
+        }
+        Some("refresh_token") => {
+            let Some(token) = params.get("refresh_token") else {
+                return invalid_grant();
+            };
+            // Rotation: the presented token is consumed either way
+            let Some(bound) = state.refresh_tokens.remove(token) else {
+                return invalid_grant();
+            };
+            bound
+        }
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "unsupported_grant_type" })),
+            )
+                .into_response();
+        }
+    };
+
+    if bound_jkt != jkt {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "invalid_dpop_proof", "error_description": "key mismatch" })),
+        )
+            .into_response();
+    }
+
+    state.token_counter += 1;
+    let access_token = format!("mock-access-{}", state.token_counter);
+    let refresh_token = format!("mock-refresh-{}", state.token_counter);
+    state.access_tokens.insert(access_token.clone(), jkt.clone());
+    state.refresh_tokens.insert(refresh_token.clone(), jkt);
+
+    Json(json!({
+        "access_token": access_token,
+        "refresh_token": refresh_token,
+        "token_type": "DPoP",
+        "expires_in": 3600,
+        "scope": "atproto transition:generic",
+        "sub": MOCK_DID,
+    }))
+    .into_response()
+}
+
+fn invalid_grant() -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "error": "invalid_grant" })),
+    )
+        .into_response()
+}
+
+async fn handle_revoke(
+    State(state): State<SharedState>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    match check_dpop(&state, &headers) {
+        ProofCheck::Valid(_) => {}
+        ProofCheck::UseNonce(nonce) => return use_nonce_response(nonce),
+        ProofCheck::Invalid(reason) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "invalid_dpop_proof", "error_description": reason })),
+            )
+                .into_response();
+        }
+    }
+
+    let params: HashMap<String, String> = serde_urlencoded::from_str(&body).unwrap_or_default();
+    if let Some(token) = params.get("token") {
+        let mut state = state.lock().unwrap();
+        state.access_tokens.remove(token);
+        state.refresh_tokens.remove(token);
+    }
+    // Per RFC 7009, revocation of unknown tokens still succeeds
+    Json(json!({})).into_response()
+}
+
+async fn handle_get_session(State(state): State<SharedState>, headers: HeaderMap) -> Response {
+    let Some(token) = headers
+        .get("authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("DPoP "))
+    else {
+        return (StatusCode::UNAUTHORIZED, "missing DPoP authorization").into_response();
+    };
+
+    let jkt = match check_dpop(&state, &headers) {
+        ProofCheck::Valid(jkt) => jkt,
+        ProofCheck::UseNonce(nonce) => return use_nonce_response(nonce),
+        ProofCheck::Invalid(reason) => return (StatusCode::UNAUTHORIZED, reason).into_response(),
+    };
+
+    let state = state.lock().unwrap();
+    match state.access_tokens.get(token) {
+        Some(bound) if *bound == jkt => Json(json!({
+            "did": MOCK_DID,
+            "handle": "mockuser.test",
+        }))
+        .into_response(),
+        Some(_) => (StatusCode::UNAUTHORIZED, "token bound to another key").into_response(),
+        None => (StatusCode::UNAUTHORIZED, "unknown access token").into_response(),
+    }
+}
+
+/// Public P-256 JWK for a signing key
+fn public_jwk(key: &SigningKey) -> serde_json::Value {
+    let point = key.verifying_key().to_encoded_point(false);
+    json!({
+        "kty": "EC",
+        "crv": "P-256",
+        "x": URL_SAFE_NO_PAD.encode(point.x().expect("affine x")),
+        "y": URL_SAFE_NO_PAD.encode(point.y().expect("affine y")),
+    })
+}
+
+/// RFC 7638 thumbprint of an EC JWK (crv, kty, x, y in lexicographic order)
+fn jwk_thumbprint(jwk: &serde_json::Value) -> String {
+    let canonical = json!({
+        "crv": jwk.get("crv"),
+        "kty": jwk.get("kty"),
+        "x": jwk.get("x"),
+        "y": jwk.get("y"),
+    });
+    let hash = Sha256::digest(canonical.to_string().as_bytes());
+    URL_SAFE_NO_PAD.encode(hash)
+}
@@ -1,20 +1,116 @@
 use crate::error::Result;
 use crate::session::OAuthSession;
-use crate::store::{KeyStore, OAuthSessionStore};
+use crate::store::{KeyMaterial, KeyStore, OAuthSessionStore};
+use async_trait::async_trait;
 use chrono::{Duration, Utc};
 use http::Method;
-use serde_json::json;
+use std::sync::Arc;
 use url::Url;
 
+/// Pluggable downstream access token format.
+///
+/// [`TokenManager`] hard-codes ES256 JWTs with a fixed claim layout. Wire a
+/// custom implementation into `OAuthProxyServerBuilder::token_issuer` to add
+/// claims (roles, tenant IDs), switch signature algorithms, or delegate to an
+/// external token service. Implementations must preserve the cnf/JKT binding:
+/// `validate` has to return the JKT the token was issued for so the proxy can
+/// enforce DPoP on every request.
+#[async_trait]
+pub trait TokenIssuer: Send + Sync {
+    /// Issue a downstream access token bound to a DPoP key, recording the
+    /// upstream session it was issued against when one exists.
+    ///
+    /// `binding` is the network-context fingerprint to pin the token to when
+    /// session binding is enabled, `None` otherwise.
+    async fn issue(
+        &self,
+        sub: &str,
+        dpop_jkt: &str,
+        scope: &str,
+        session_id: Option<&str>,
+        binding: Option<&str>,
+        expires_in_seconds: i64,
+    ) -> Result<String>;
+
+    /// Validate a downstream access token and return its claims
+    async fn validate(&self, token: &str) -> Result<DownstreamTokenClaims>;
+}
+
+/// Default [`TokenIssuer`]: self-contained ES256 JWTs signed with the
+/// proxy's key, exactly as [`TokenManager`] has always issued them.
+///
+/// Issues and validates through a [`KeyMaterial`] cache so neither path
+/// pays a key-store round-trip per call.
+pub struct JwtTokenIssuer<K: KeyStore> {
+    token_manager: Arc<TokenManager>,
+    key_material: KeyMaterial<K>,
+}
+
+impl<K: KeyStore> JwtTokenIssuer<K> {
+    pub fn new(token_manager: Arc<TokenManager>, key_store: Arc<K>) -> Self {
+        Self {
+            token_manager,
+            key_material: KeyMaterial::new(key_store),
+        }
+    }
+
+    /// Drop cached key material so the next call re-reads the key store;
+    /// call after rotating the signing key
+    pub fn invalidate_key_material(&self) {
+        self.key_material.invalidate();
+    }
+}
+
+#[async_trait]
+impl<K: KeyStore> TokenIssuer for JwtTokenIssuer<K> {
+    async fn issue(
+        &self,
+        sub: &str,
+        dpop_jkt: &str,
+        scope: &str,
+        session_id: Option<&str>,
+        binding: Option<&str>,
+        expires_in_seconds: i64,
+    ) -> Result<String> {
+        let signing_key = self.key_material.signing_key().await?;
+        self.token_manager.issue_downstream_jwt_with_key(
+            sub,
+            dpop_jkt,
+            scope,
+            session_id,
+            binding,
+            expires_in_seconds,
+            &signing_key,
+        )
+    }
+
+    async fn validate(&self, token: &str) -> Result<DownstreamTokenClaims> {
+        let verifying_key = self.key_material.verifying_key().await?;
+        self.token_manager
+            .validate_downstream_jwt_with_key(token, &verifying_key)
+    }
+}
+
 /// Manages token issuance and refresh
 pub struct TokenManager {
     // For issuing downstream JWTs
     issuer: String,
+    // Clock-skew leeway applied when validating exp/iat
+    clock_skew_leeway_seconds: i64,
 }
 
 impl TokenManager {
     pub fn new(issuer: String) -> Self {
-        Self { issuer }
+        Self {
+            issuer,
+            clock_skew_leeway_seconds: crate::jose::DEFAULT_CLOCK_SKEW_LEEWAY_SECONDS,
+        }
+    }
+
+    /// Override the clock-skew leeway used for exp/iat validation
+    pub fn with_clock_skew_leeway(mut self, seconds: i64) -> Self {
+        self.clock_skew_leeway_seconds = seconds;
+        self
     }
 
     /// Issue a downstream JWT access token for the client
@@ -23,31 +119,61 @@ impl TokenManager {
         sub: &str,
         dpop_jkt: &str,
         scope: &str,
+        session_id: Option<&str>,
+        binding: Option<&str>,
         expires_in_seconds: i64,
         key_store: &impl KeyStore,
+    ) -> Result<String> {
+        let signing_key = key_store.get_signing_key().await?;
+        self.issue_downstream_jwt_with_key(
+            sub,
+            dpop_jkt,
+            scope,
+            session_id,
+            binding,
+            expires_in_seconds,
+            &signing_key,
+        )
+    }
+
+    /// Issue a downstream JWT with an already-resolved signing key
+    ///
+    /// Split out from [`Self::issue_downstream_jwt`] so callers holding a
+    /// [`KeyMaterial`] cache skip the key-store read.
+    pub fn issue_downstream_jwt_with_key(
+        &self,
+        sub: &str,
+        dpop_jkt: &str,
+        scope: &str,
+        session_id: Option<&str>,
+        binding: Option<&str>,
+        expires_in_seconds: i64,
+        signing_key: &p256::ecdsa::SigningKey,
     ) -> Result<String> {
         use jacquard_oauth::jose::jws::RegisteredHeader;
         use jose_jwk::jose_jwa::{Algorithm, Signing};
 
-        let signing_key = key_store.get_signing_key().await?;
-
         let now = Utc::now().timestamp();
         let exp = now + expires_in_seconds;
 
-        // Create claims JSON with custom fields
-        let claims_json = json!({
-            "iss": self.issuer,
-            "sub": sub,
-            "aud": self.issuer,
-            "exp": exp,
-            "iat": now,
-            "scope": scope,
-            "cnf": {
-                "jkt": dpop_jkt,
+        // The same struct validation deserializes into, so issuance can't
+        // drift from what consumers expect
+        let claims = DownstreamTokenClaims {
+            iss: self.issuer.clone(),
+            sub: sub.to_string(),
+            aud: self.issuer.clone(),
+            exp,
+            iat: now,
+            nbf: None,
+            scope: scope.to_string(),
+            cnf: ConfirmationClaim {
+                jkt: dpop_jkt.to_string(),
             },
-        });
+            session_id: session_id.map(|s| s.to_string()),
+            bind: binding.map(|b| b.to_string()),
+        };
 
-        let claims_str = serde_json::to_string(&claims_json).map_err(|e| {
+        let claims_str = serde_json::to_string(&claims).map_err(|e| {
             crate::error::Error::InvalidRequest(format!("failed to serialize claims: {}", e))
         })?;
 
@@ -76,6 +202,88 @@ impl TokenManager {
         Ok(jwt)
     }
 
+    /// Issue an opaque downstream access token persisted in the store
+    ///
+    /// Used in opaque token mode instead of [`Self::issue_downstream_jwt`];
+    /// the token carries no claims itself and is validated by lookup, so it
+    /// can be revoked before expiry. The cnf/JKT binding is recorded in the
+    /// store and enforced the same way as for JWTs.
+    pub async fn issue_opaque_token<S: OAuthSessionStore>(
+        &self,
+        sub: &str,
+        dpop_jkt: &str,
+        scope: &str,
+        expires_in_seconds: i64,
+        entropy_bytes: usize,
+        session_store: &S,
+    ) -> Result<String> {
+        let token = generate_token(entropy_bytes);
+        let now = Utc::now();
+
+        // Stored by digest so a store compromise doesn't leak usable tokens
+        session_store
+            .store_access_token(
+                &crate::auth::token_digest(&token),
+                crate::store::AccessTokenData {
+                    account_did: sub.to_string(),
+                    dpop_jkt: dpop_jkt.to_string(),
+                    scope: scope.to_string(),
+                    issued_at: now,
+                    expires_at: now + Duration::seconds(expires_in_seconds),
+                },
+            )
+            .await?;
+
+        Ok(token)
+    }
+
+    /// Validate an opaque downstream access token via store lookup
+    ///
+    /// Returns the same claims shape as [`Self::validate_downstream_jwt`]
+    /// so callers don't care which mode issued the token.
+    pub async fn validate_opaque_token<S: OAuthSessionStore>(
+        &self,
+        token: &str,
+        session_store: &S,
+    ) -> Result<DownstreamTokenClaims> {
+        let digest = crate::auth::token_digest(token);
+        let data = match session_store.get_access_token(&digest).await? {
+            Some(data) => Some(data),
+            // Rows written before hashing are keyed by plaintext
+            None => session_store.get_access_token(token).await?,
+        };
+        let data = data.ok_or_else(|| {
+            crate::error::Error::InvalidRequest("unknown access token".to_string())
+        })?;
+
+        if data.expires_at < Utc::now() {
+            // Expired rows are dead weight, drop them on sight
+            let _ = session_store.delete_access_token(&digest).await;
+            let _ = session_store.delete_access_token(token).await;
+            return Err(crate::error::Error::InvalidRequest(
+                "token expired".to_string(),
+            ));
+        }
+
+        Ok(DownstreamTokenClaims {
+            iss: self.issuer.clone(),
+            sub: data.account_did,
+            aud: self.issuer.clone(),
+            exp: data.expires_at.timestamp(),
+            iat: data.issued_at.timestamp(),
+            nbf: None,
+            scope: data.scope,
+            cnf: ConfirmationClaim {
+                jkt: data.dpop_jkt,
+            },
+            // Opaque token rows don't record the upstream session; callers
+            // fall back to the active-session lookup
+            session_id: None,
+            // ... nor a binding fingerprint, so opaque tokens are never pinned
+            bind: None,
+        })
+    }
+
     /// Issue a downstream JWT access token for the client (legacy method for OAuthSession)
     pub async fn issue_downstream_access_token(
         &self,
@@ -86,6 +294,8 @@ impl TokenManager {
             session.did.as_str(),
             &session.downstream_dpop_key_thumbprint,
             &session.upstream_scope,
+            Some(&session.id),
+            None,
             24 * 3600, // 24 hours
             key_store,
         )
@@ -98,69 +308,26 @@ impl TokenManager {
         jwt: &str,
         key_store: &impl KeyStore,
     ) -> Result<DownstreamTokenClaims> {
-        use base64::Engine;
-        use base64::engine::general_purpose::URL_SAFE_NO_PAD;
-        use p256::ecdsa::signature::Verifier;
-
-        // Parse JWT (header.payload.signature)
-        let parts: Vec<&str> = jwt.split('.').collect();
-        if parts.len() != 3 {
-            return Err(crate::error::Error::InvalidRequest(
-                "invalid JWT format".to_string(),
-            ));
-        }
-
-        // Decode header to verify it's the right algorithm
-        let header_json = URL_SAFE_NO_PAD.decode(parts[0]).map_err(|e| {
-            crate::error::Error::InvalidRequest(format!("invalid header encoding: {}", e))
-        })?;
-
-        let header: serde_json::Value = serde_json::from_slice(&header_json).map_err(|e| {
-            crate::error::Error::InvalidRequest(format!("invalid header JSON: {}", e))
-        })?;
-
-        // Verify algorithm
-        let alg = header.get("alg").and_then(|v| v.as_str()).ok_or_else(|| {
-            crate::error::Error::InvalidRequest("missing alg in header".to_string())
-        })?;
-
-        if alg != "ES256" {
-            return Err(crate::error::Error::InvalidRequest(format!(
-                "unsupported algorithm: {}",
-                alg
-            )));
-        }
-
-        // Decode payload
-        let payload_json = URL_SAFE_NO_PAD.decode(parts[1]).map_err(|e| {
-            crate::error::Error::InvalidRequest(format!("invalid payload encoding: {}", e))
-        })?;
-
-        // Decode signature
-        let signature_bytes = URL_SAFE_NO_PAD.decode(parts[2]).map_err(|e| {
-            crate::error::Error::InvalidRequest(format!("invalid signature encoding: {}", e))
-        })?;
-
-        // Get signing key for validation
         let signing_key = key_store.get_signing_key().await?;
-        let verifying_key = signing_key.verifying_key();
-
-        // Verify signature
-        let signature_input = format!("{}.{}", parts[0], parts[1]);
-        let signature = p256::ecdsa::Signature::from_bytes(signature_bytes.as_slice().into())
-            .map_err(|e| {
-                crate::error::Error::InvalidRequest(format!("invalid signature format: {}", e))
-            })?;
+        self.validate_downstream_jwt_with_key(jwt, signing_key.verifying_key())
+    }
 
-        verifying_key
-            .verify(signature_input.as_bytes(), &signature)
-            .map_err(|_| {
-                crate::error::Error::InvalidRequest("signature verification failed".to_string())
-            })?;
+    /// Validate a downstream JWT against an already-resolved verifying key
+    ///
+    /// Split out from [`Self::validate_downstream_jwt`] so callers on the
+    /// per-request path (see [`JwtTokenIssuer`]) can cache the key instead
+    /// of hitting the key store for every XRPC request.
+    pub fn validate_downstream_jwt_with_key(
+        &self,
+        jwt: &str,
+        verifying_key: &p256::ecdsa::VerifyingKey,
+    ) -> Result<DownstreamTokenClaims> {
+        use crate::jose::{self, JwsAlgorithm};
 
-        // Parse claims
-        let claims: DownstreamTokenClaims = serde_json::from_slice(&payload_json)
-            .map_err(|e| crate::error::Error::InvalidRequest(format!("invalid claims: {}", e)))?;
+        // Strict parse and signature check before any claims are read
+        let claims: DownstreamTokenClaims = jose::parse_compact(jwt, &[JwsAlgorithm::Es256])?
+            .verify_es256(verifying_key)?
+            .claims()?;
 
         // Verify issuer
         if claims.iss != self.issuer {
@@ -169,13 +336,12 @@ impl TokenManager {
             ));
         }
 
-        // Check expiry
-        let now = Utc::now().timestamp();
-        if claims.exp < now {
-            return Err(crate::error::Error::InvalidRequest(
-                "token expired".to_string(),
-            ));
-        }
+        jose::validate_timestamps(
+            claims.exp,
+            Some(claims.iat),
+            claims.nbf,
+            self.clock_skew_leeway_seconds,
+        )?;
 
         Ok(claims)
     }
@@ -297,7 +463,7 @@ impl TokenManager {
         use sha2::{Digest, Sha256};
 
         let now = Utc::now().timestamp();
-        let jti = generate_random_string(32);
+        let jti = generate_token(16);
 
         // Hash access token if provided (for token-bound requests)
         let ath = if let Some(token) = access_token {
@@ -334,22 +500,7 @@ impl TokenManager {
         header.typ = Some("dpop+jwt".into());
 
         // Create a public-only version of the JWK for the header
-        let public_jwk = jose_jwk::Jwk {
-            key: match &dpop_jwk.key {
-                jose_jwk::Key::Ec(ec) => {
-                    // Keep only public parameters (crv, x, y), strip private key (d)
-                    jose_jwk::Key::Ec(jose_jwk::Ec {
-                        crv: ec.crv.clone(),
-                        x: ec.x.clone(),
-                        y: ec.y.clone(),
-                        d: None, // Remove private key
-                    })
-                }
-                _ => dpop_jwk.key.clone(),
-            },
-            prm: dpop_jwk.prm.clone(),
-        };
-        header.jwk = Some(public_jwk);
+        header.jwk = Some(crate::jose::public_jwk(dpop_jwk));
 
         // Extract the secret key from the JWK for signing
         let signing_key = match jose_jwk::crypto::Key::try_from(&dpop_jwk.key)
@@ -380,32 +531,108 @@ struct TokenResponse {
     expires_in: Option<i64>,
 }
 
-/// Claims from a downstream JWT issued by the proxy
-#[derive(Debug, serde::Deserialize)]
-pub struct DownstreamTokenClaims {
-    pub iss: String,
-    pub sub: String, // account DID
-    pub aud: String,
-    pub exp: i64,
-    pub iat: i64,
-    pub scope: String,
-    pub cnf: ConfirmationClaim,
+/// Claims from a downstream token issued by the proxy.
+///
+/// Alias for [`ProxyJwtClaims`](crate::auth::ProxyJwtClaims): issuance,
+/// validation, the axum extractors, and introspection all share the one
+/// claims struct.
+pub type DownstreamTokenClaims = crate::auth::ProxyJwtClaims;
+
+pub use crate::auth::ConfirmationClaim;
+
+/// Minimum entropy for generated codes and tokens, in bytes (128 bits)
+pub const MIN_TOKEN_ENTROPY_BYTES: usize = 16;
+
+/// Generate a random secret with `num_bytes` of OS-sourced entropy,
+/// base64url-encoded without padding.
+///
+/// Used for authorization codes, refresh tokens, and opaque access tokens;
+/// the byte length comes from `ProxyConfig::token_entropy_bytes`. Lengths
+/// below [`MIN_TOKEN_ENTROPY_BYTES`] are raised to the minimum so a
+/// misconfiguration can't weaken issued credentials.
+pub fn generate_token(num_bytes: usize) -> String {
+    use base64::Engine;
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use rand::RngCore;
+
+    let mut bytes = vec![0u8; num_bytes.max(MIN_TOKEN_ENTROPY_BYTES)];
+    rand::rngs::OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(&bytes)
 }
 
-/// DPoP confirmation claim
-#[derive(Debug, serde::Deserialize)]
-pub struct ConfirmationClaim {
-    pub jkt: String, // DPoP JKT
+/// An unguessable credential string: OS-sourced entropy, constant-time
+/// equality, redacted `Debug`.
+///
+/// Wraps [`generate_token`] output so authorization codes, request_uris,
+/// states, and refresh tokens are generated and compared one way
+/// everywhere instead of each call site rolling its own. Comparison goes
+/// through [`constant_time_eq`](crate::auth::constant_time_eq), so a
+/// `SecureToken` can be matched against an attacker-supplied value
+/// without leaking a prefix through timing.
+#[derive(Clone)]
+pub struct SecureToken(String);
+
+impl SecureToken {
+    /// Generate a token with 256 bits of entropy
+    pub fn new() -> Self {
+        Self(generate_token(32))
+    }
+
+    /// Generate a token with `num_bytes` of entropy, raised to
+    /// [`MIN_TOKEN_ENTROPY_BYTES`] like [`generate_token`]
+    pub fn with_entropy(num_bytes: usize) -> Self {
+        Self(generate_token(num_bytes))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
 }
 
-const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
-fn generate_random_string(len: usize) -> String {
-    use rand::Rng;
-    let mut rng = rand::thread_rng();
-    (0..len)
-        .map(|_| {
-            let idx = rng.gen_range(0..CHARSET.len());
-            CHARSET[idx] as char
-        })
-        .collect()
+impl Default for SecureToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PartialEq for SecureToken {
+    fn eq(&self, other: &Self) -> bool {
+        crate::auth::constant_time_eq(self.0.as_bytes(), other.0.as_bytes())
+    }
+}
+
+impl Eq for SecureToken {}
+
+impl PartialEq<str> for SecureToken {
+    fn eq(&self, other: &str) -> bool {
+        crate::auth::constant_time_eq(self.0.as_bytes(), other.as_bytes())
+    }
+}
+
+impl PartialEq<&str> for SecureToken {
+    fn eq(&self, other: &&str) -> bool {
+        crate::auth::constant_time_eq(self.0.as_bytes(), other.as_bytes())
+    }
+}
+
+impl std::fmt::Display for SecureToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::fmt::Debug for SecureToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecureToken([redacted; {}])", self.0.len())
+    }
+}
+
+impl From<SecureToken> for String {
+    fn from(token: SecureToken) -> String {
+        token.0
+    }
 }
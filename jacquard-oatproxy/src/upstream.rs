@@ -0,0 +1,185 @@
+//! Upstream HTTP transport policy.
+//!
+//! Proxied requests previously used bare `reqwest::Client::new()` with
+//! default (unbounded) timeouts, so a hung PDS hung the downstream request
+//! with it. This module owns the shared upstream client with configured
+//! connect/read timeouts, bounded retries for idempotent GETs, and a
+//! per-host circuit breaker that fails fast with 502 while an upstream
+//! keeps failing.
+
+use crate::config::ProxyConfig;
+use crate::error::{Error, Result};
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+
+struct HostState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+/// Per-host circuit breaker. A host's circuit opens after a run of
+/// consecutive failures and stays open for the reset interval; the first
+/// request after that interval is let through as a probe.
+pub struct CircuitBreaker {
+    hosts: RwLock<HashMap<String, HostState>>,
+    failure_threshold: u32,
+    reset_after: Duration,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, reset_after: Duration) -> Self {
+        Self {
+            hosts: RwLock::new(HashMap::new()),
+            failure_threshold,
+            reset_after,
+        }
+    }
+
+    /// Fail fast if the host's circuit is open and the reset interval has
+    /// not elapsed yet.
+    pub fn check(&self, host: &str) -> Result<()> {
+        let hosts = self.hosts.read().unwrap();
+        if let Some(state) = hosts.get(host) {
+            if let Some(opened_at) = state.opened_at {
+                if opened_at.elapsed() < self.reset_after {
+                    return Err(Error::UpstreamUnavailable(host.to_string()));
+                }
+                // Past the reset interval: let this request through as a
+                // probe; record_success/record_failure decides what's next
+            }
+        }
+        Ok(())
+    }
+
+    pub fn record_success(&self, host: &str) {
+        self.hosts.write().unwrap().remove(host);
+    }
+
+    pub fn record_failure(&self, host: &str) {
+        let mut hosts = self.hosts.write().unwrap();
+        let state = hosts.entry(host.to_string()).or_insert(HostState {
+            consecutive_failures: 0,
+            opened_at: None,
+        });
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= self.failure_threshold && state.opened_at.is_none() {
+            tracing::warn!(
+                "circuit opened for {} after {} consecutive failures",
+                host,
+                state.consecutive_failures
+            );
+            state.opened_at = Some(Instant::now());
+        } else if state.opened_at.is_some() {
+            // A failed probe keeps the circuit open for another interval
+            state.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+/// Shared upstream client plus the retry/circuit-breaker policy around it.
+pub struct UpstreamTransport {
+    client: reqwest::Client,
+    breaker: CircuitBreaker,
+    get_retries: u32,
+}
+
+impl UpstreamTransport {
+    pub fn new(config: &ProxyConfig) -> Self {
+        let client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(config.upstream_connect_timeout_seconds))
+            .timeout(Duration::from_secs(config.upstream_read_timeout_seconds))
+            .build()
+            .expect("valid reqwest client");
+
+        Self {
+            client,
+            breaker: CircuitBreaker::new(
+                config.circuit_breaker_failure_threshold,
+                Duration::from_secs(config.circuit_breaker_reset_seconds),
+            ),
+            get_retries: config.upstream_get_retries,
+        }
+    }
+
+    /// The shared client; use this to build requests so the configured
+    /// timeouts apply.
+    pub fn client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
+    /// Send a request, consulting the host's circuit breaker first.
+    /// Idempotent requests get bounded retries on transport errors and
+    /// 502/503/504 responses; anything else is returned as-is.
+    pub async fn send(
+        &self,
+        host: &str,
+        request: reqwest::RequestBuilder,
+        idempotent: bool,
+    ) -> Result<reqwest::Response> {
+        self.breaker.check(host)?;
+
+        let max_attempts = if idempotent { self.get_retries + 1 } else { 1 };
+        let mut request = Some(request);
+
+        for attempt in 1..=max_attempts {
+            // Keep the original around for later attempts when the body is
+            // clonable; fall back to a single shot when it isn't
+            let this_request = if attempt == max_attempts {
+                request.take().expect("request builder available")
+            } else {
+                match request.as_ref().and_then(|r| r.try_clone()) {
+                    Some(clone) => clone,
+                    None => request.take().expect("request builder available"),
+                }
+            };
+
+            match this_request.send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    if status.is_server_error() {
+                        self.breaker.record_failure(host);
+                    } else {
+                        self.breaker.record_success(host);
+                    }
+
+                    let retriable_status = matches!(status.as_u16(), 502 | 503 | 504);
+                    if retriable_status && request.is_some() {
+                        tracing::warn!(
+                            "upstream {} returned {}, retrying (attempt {}/{})",
+                            host,
+                            status,
+                            attempt,
+                            max_attempts
+                        );
+                        tokio::time::sleep(Duration::from_millis(250 * attempt as u64)).await;
+                        continue;
+                    }
+
+                    return Ok(response);
+                }
+                Err(e) => {
+                    self.breaker.record_failure(host);
+
+                    let retriable = e.is_timeout() || e.is_connect();
+                    if !retriable || request.is_none() {
+                        return Err(Error::NetworkError(e.to_string()));
+                    }
+
+                    tracing::warn!(
+                        "upstream request to {} failed ({}), retrying (attempt {}/{})",
+                        host,
+                        e,
+                        attempt,
+                        max_attempts
+                    );
+                    tokio::time::sleep(Duration::from_millis(250 * attempt as u64)).await;
+                }
+            }
+        }
+
+        // Only reachable if the final attempt's builder was consumed by an
+        // earlier fallback, which record_failure already noted
+        Err(Error::UpstreamUnavailable(host.to_string()))
+    }
+}
@@ -0,0 +1,367 @@
+//! Full OAuth lifecycle against the mock PDS: login → token → XRPC →
+//! refresh → revoke. Run with `cargo test --features test-utils`.
+
+#![cfg(feature = "test-utils")]
+
+use jacquard_oatproxy::test_utils::{MOCK_DID, MockPds, ScriptedDpopClient};
+use reqwest::StatusCode;
+
+/// Capture a `DPoP-Nonce` response header into the client for its next proof
+fn remember_nonce(client: &ScriptedDpopClient, response: &reqwest::Response) {
+    if let Some(nonce) = response
+        .headers()
+        .get("DPoP-Nonce")
+        .and_then(|v| v.to_str().ok())
+    {
+        client.set_nonce(nonce);
+    }
+}
+
+/// Push an authorization request, handling the `use_dpop_nonce` retry
+async fn push_par(
+    http: &reqwest::Client,
+    pds: &MockPds,
+    dpop: &ScriptedDpopClient,
+    state: &str,
+) -> String {
+    let par_url = format!("{}/oauth/par", pds.url());
+    let body = format!(
+        "client_id=https%3A%2F%2Fclient.test%2Fmetadata.json\
+         &redirect_uri=https%3A%2F%2Fclient.test%2Fcallback\
+         &response_type=code&state={state}&scope=atproto"
+    );
+
+    let first = http
+        .post(&par_url)
+        .header("DPoP", dpop.proof("POST", &par_url, None))
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(body.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(first.status(), StatusCode::BAD_REQUEST);
+    remember_nonce(dpop, &first);
+    let error: serde_json::Value = first.json().await.unwrap();
+    assert_eq!(error["error"], "use_dpop_nonce");
+
+    let retry = http
+        .post(&par_url)
+        .header("DPoP", dpop.proof("POST", &par_url, None))
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(retry.status(), StatusCode::CREATED);
+    let body: serde_json::Value = retry.json().await.unwrap();
+    body["request_uri"].as_str().unwrap().to_string()
+}
+
+/// Exchange a grant at the token endpoint, retrying once for a fresh
+/// nonce; returns the final status and parsed JSON body
+async fn exchange(
+    http: &reqwest::Client,
+    pds: &MockPds,
+    dpop: &ScriptedDpopClient,
+    form: &str,
+) -> (StatusCode, serde_json::Value) {
+    let token_url = format!("{}/oauth/token", pds.url());
+    let first = http
+        .post(&token_url)
+        .header("DPoP", dpop.proof("POST", &token_url, None))
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(form.to_string())
+        .send()
+        .await
+        .unwrap();
+    let status = first.status();
+    remember_nonce(dpop, &first);
+    let body: serde_json::Value = first.json().await.unwrap();
+    if body["error"] != "use_dpop_nonce" {
+        return (status, body);
+    }
+    let retry = http
+        .post(&token_url)
+        .header("DPoP", dpop.proof("POST", &token_url, None))
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(form.to_string())
+        .send()
+        .await
+        .unwrap();
+    let status = retry.status();
+    (status, retry.json().await.unwrap())
+}
+
+async fn get_session(
+    http: &reqwest::Client,
+    pds: &MockPds,
+    dpop: &ScriptedDpopClient,
+    access_token: &str,
+) -> reqwest::Response {
+    let url = format!("{}/xrpc/com.atproto.server.getSession", pds.url());
+    http.get(&url)
+        .header("authorization", format!("DPoP {access_token}"))
+        .header("DPoP", dpop.proof("GET", &url, Some(access_token)))
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn full_lifecycle() {
+    let pds = MockPds::spawn().await;
+    let dpop = ScriptedDpopClient::new();
+    let http = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    // Metadata advertises the endpoints we're about to hit
+    let metadata: serde_json::Value = http
+        .get(format!(
+            "{}/.well-known/oauth-authorization-server",
+            pds.url()
+        ))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(metadata["issuer"], pds.issuer());
+    assert_eq!(
+        metadata["authorization_response_iss_parameter_supported"],
+        true
+    );
+
+    // Login: PAR (with nonce dance) then authorize
+    let request_uri = push_par(&http, &pds, &dpop, "teststate").await;
+
+    let authorize = http
+        .get(format!("{}/oauth/authorize", pds.url()))
+        .query(&[("request_uri", request_uri.as_str())])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(authorize.status(), StatusCode::SEE_OTHER);
+    let location = authorize
+        .headers()
+        .get("location")
+        .and_then(|v| v.to_str().ok())
+        .unwrap();
+    let redirect = url::Url::parse(location).unwrap();
+    let params: std::collections::HashMap<String, String> =
+        redirect.query_pairs().into_owned().collect();
+    assert_eq!(params["state"], "teststate");
+    assert_eq!(params["iss"], pds.issuer());
+    let code = params["code"].clone();
+
+    // A consumed request_uri is single-use
+    let replay = http
+        .get(format!("{}/oauth/authorize", pds.url()))
+        .query(&[("request_uri", request_uri.as_str())])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(replay.status(), StatusCode::BAD_REQUEST);
+
+    // Token: exchange the code for DPoP-bound tokens
+    let (status, tokens) = exchange(
+        &http,
+        &pds,
+        &dpop,
+        &format!("grant_type=authorization_code&code={code}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(tokens["token_type"], "DPoP");
+    assert_eq!(tokens["sub"], MOCK_DID);
+    let access_token = tokens["access_token"].as_str().unwrap().to_string();
+    let refresh_token = tokens["refresh_token"].as_str().unwrap().to_string();
+
+    // XRPC: an authenticated call succeeds with the right key...
+    let session = get_session(&http, &pds, &dpop, &access_token).await;
+    assert_eq!(session.status(), StatusCode::OK);
+    let session: serde_json::Value = session.json().await.unwrap();
+    assert_eq!(session["did"], MOCK_DID);
+
+    // ...and fails when the proof comes from someone else's key
+    let stranger = ScriptedDpopClient::new();
+    stranger.set_nonce(dpop.nonce().unwrap());
+    let hijack = get_session(&http, &pds, &stranger, &access_token).await;
+    assert_eq!(hijack.status(), StatusCode::UNAUTHORIZED);
+
+    // Refresh: tokens rotate and the old refresh token dies
+    let (status, rotated) = exchange(
+        &http,
+        &pds,
+        &dpop,
+        &format!("grant_type=refresh_token&refresh_token={refresh_token}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let new_access = rotated["access_token"].as_str().unwrap().to_string();
+    assert_ne!(new_access, access_token);
+
+    let (status, error) = exchange(
+        &http,
+        &pds,
+        &dpop,
+        &format!("grant_type=refresh_token&refresh_token={refresh_token}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"], "invalid_grant");
+
+    // Revoke: the access token stops working
+    let revoke_url = format!("{}/oauth/revoke", pds.url());
+    let revoked = http
+        .post(&revoke_url)
+        .header("DPoP", dpop.proof("POST", &revoke_url, None))
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(format!("token={new_access}"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(revoked.status(), StatusCode::OK);
+
+    let after_revoke = get_session(&http, &pds, &dpop, &new_access).await;
+    assert_eq!(after_revoke.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn token_endpoint_rejects_foreign_key() {
+    let pds = MockPds::spawn().await;
+    let owner = ScriptedDpopClient::new();
+    let thief = ScriptedDpopClient::new();
+    let http = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    let request_uri = push_par(&http, &pds, &owner, "s").await;
+    let authorize = http
+        .get(format!("{}/oauth/authorize", pds.url()))
+        .query(&[("request_uri", request_uri.as_str())])
+        .send()
+        .await
+        .unwrap();
+    let location = authorize
+        .headers()
+        .get("location")
+        .and_then(|v| v.to_str().ok())
+        .unwrap();
+    let redirect = url::Url::parse(location).unwrap();
+    let code = redirect
+        .query_pairs()
+        .find(|(k, _)| k == "code")
+        .map(|(_, v)| v.into_owned())
+        .unwrap();
+
+    // The code is bound to the owner's JKT; a stolen code plus a different
+    // key must not yield tokens
+    let (status, error) = exchange(
+        &http,
+        &pds,
+        &thief,
+        &format!("grant_type=authorization_code&code={code}"),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"], "invalid_dpop_proof");
+}
+
+/// Run PAR + authorize and pull the code out of the redirect
+async fn obtain_code(
+    http: &reqwest::Client,
+    pds: &MockPds,
+    dpop: &ScriptedDpopClient,
+) -> String {
+    let request_uri = push_par(http, pds, dpop, "s").await;
+    let authorize = http
+        .get(format!("{}/oauth/authorize", pds.url()))
+        .query(&[("request_uri", request_uri.as_str())])
+        .send()
+        .await
+        .unwrap();
+    let location = authorize
+        .headers()
+        .get("location")
+        .and_then(|v| v.to_str().ok())
+        .unwrap();
+    url::Url::parse(location)
+        .unwrap()
+        .query_pairs()
+        .find(|(k, _)| k == "code")
+        .map(|(_, v)| v.into_owned())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn token_exchange_rejects_mismatched_redirect_uri() {
+    let pds = MockPds::spawn().await;
+    let dpop = ScriptedDpopClient::new();
+    let http = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    let code = obtain_code(&http, &pds, &dpop).await;
+
+    // A redirect_uri from a different flow is the mix-up / code-injection
+    // shape: the code was authorized for client.test's callback
+    let (status, error) = exchange(
+        &http,
+        &pds,
+        &dpop,
+        &format!(
+            "grant_type=authorization_code&code={code}\
+             &redirect_uri=https%3A%2F%2Fevil.test%2Fcallback"
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"], "invalid_grant");
+}
+
+#[tokio::test]
+async fn token_exchange_rejects_mismatched_client_id() {
+    let pds = MockPds::spawn().await;
+    let dpop = ScriptedDpopClient::new();
+    let http = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    let code = obtain_code(&http, &pds, &dpop).await;
+
+    let (status, error) = exchange(
+        &http,
+        &pds,
+        &dpop,
+        &format!(
+            "grant_type=authorization_code&code={code}\
+             &client_id=https%3A%2F%2Fevil.test%2Fmetadata.json"
+        ),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(error["error"], "invalid_grant");
+}
+
+#[tokio::test]
+async fn par_requires_dpop_proof() {
+    let pds = MockPds::spawn().await;
+    let http = reqwest::Client::new();
+
+    let bare = http
+        .post(format!("{}/oauth/par", pds.url()))
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body("client_id=x&redirect_uri=y")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(bare.status(), StatusCode::BAD_REQUEST);
+    let error: serde_json::Value = bare.json().await.unwrap();
+    assert_eq!(error["error"], "invalid_dpop_proof");
+}
@@ -5,4 +5,6 @@
 
 pub mod delete_emoji;
 pub mod emoji;
-pub mod search_emoji;
\ No newline at end of file
+pub mod pack;
+pub mod search_emoji;
+pub mod set_emoji_category;
@@ -21,11 +21,19 @@ pub struct Emoji<'a> {
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[serde(borrow)]
     pub alt_text: Option<jacquard_common::CowStr<'a>>,
+    /// Picker category from the curated set; unknown values are ignored by indexers
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub category: Option<jacquard_common::CowStr<'a>>,
     #[serde(borrow)]
     pub emoji: jacquard_common::types::blob::BlobRef<'a>,
     /// Canonical name/identifier for the emoji (no spaces, e.g. 'POGGERS', 'Cinema')
     #[serde(borrow)]
     pub name: jacquard_common::CowStr<'a>,
+    /// Free-form tags for search and picker filtering
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub tags: Option<Vec<jacquard_common::CowStr<'a>>>,
 }
 
 pub mod emoji_state {
@@ -76,9 +84,11 @@ pub mod emoji_state {
 pub struct EmojiBuilder<'a, S: emoji_state::State> {
     _phantom_state: ::core::marker::PhantomData<fn() -> S>,
     __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::types::blob::BlobRef<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<Vec<jacquard_common::CowStr<'a>>>,
     ),
     _phantom: ::core::marker::PhantomData<&'a ()>,
 }
@@ -95,7 +105,7 @@ impl<'a> EmojiBuilder<'a, emoji_state::Empty> {
     pub fn new() -> Self {
         EmojiBuilder {
             _phantom_state: ::core::marker::PhantomData,
-            __unsafe_private_named: (None, None, None),
+            __unsafe_private_named: (None, None, None, None, None),
             _phantom: ::core::marker::PhantomData,
         }
     }
@@ -117,6 +127,22 @@ impl<'a, S: emoji_state::State> EmojiBuilder<'a, S> {
     }
 }
 
+impl<'a, S: emoji_state::State> EmojiBuilder<'a, S> {
+    /// Set the `category` field (optional)
+    pub fn category(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.1 = value.into();
+        self
+    }
+    /// Set the `category` field to an Option value (optional)
+    pub fn maybe_category(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
+        self.__unsafe_private_named.1 = value;
+        self
+    }
+}
+
 impl<'a, S> EmojiBuilder<'a, S>
 where
     S: emoji_state::State,
@@ -127,7 +153,7 @@ where
         mut self,
         value: impl Into<jacquard_common::types::blob::BlobRef<'a>>,
     ) -> EmojiBuilder<'a, emoji_state::SetEmoji<S>> {
-        self.__unsafe_private_named.1 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.2 = ::core::option::Option::Some(value.into());
         EmojiBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -146,7 +172,7 @@ where
         mut self,
         value: impl Into<jacquard_common::CowStr<'a>>,
     ) -> EmojiBuilder<'a, emoji_state::SetName<S>> {
-        self.__unsafe_private_named.2 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.3 = ::core::option::Option::Some(value.into());
         EmojiBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -155,6 +181,22 @@ where
     }
 }
 
+impl<'a, S: emoji_state::State> EmojiBuilder<'a, S> {
+    /// Set the `tags` field (optional)
+    pub fn tags(
+        mut self,
+        value: impl Into<Option<Vec<jacquard_common::CowStr<'a>>>>,
+    ) -> Self {
+        self.__unsafe_private_named.4 = value.into();
+        self
+    }
+    /// Set the `tags` field to an Option value (optional)
+    pub fn maybe_tags(mut self, value: Option<Vec<jacquard_common::CowStr<'a>>>) -> Self {
+        self.__unsafe_private_named.4 = value;
+        self
+    }
+}
+
 impl<'a, S> EmojiBuilder<'a, S>
 where
     S: emoji_state::State,
@@ -165,8 +207,10 @@ where
     pub fn build(self) -> Emoji<'a> {
         Emoji {
             alt_text: self.__unsafe_private_named.0,
-            emoji: self.__unsafe_private_named.1.unwrap(),
-            name: self.__unsafe_private_named.2.unwrap(),
+            category: self.__unsafe_private_named.1,
+            emoji: self.__unsafe_private_named.2.unwrap(),
+            name: self.__unsafe_private_named.3.unwrap(),
+            tags: self.__unsafe_private_named.4,
             extra_data: Default::default(),
         }
     }
@@ -180,8 +224,10 @@ where
     ) -> Emoji<'a> {
         Emoji {
             alt_text: self.__unsafe_private_named.0,
-            emoji: self.__unsafe_private_named.1.unwrap(),
-            name: self.__unsafe_private_named.2.unwrap(),
+            category: self.__unsafe_private_named.1,
+            emoji: self.__unsafe_private_named.2.unwrap(),
+            name: self.__unsafe_private_named.3.unwrap(),
+            tags: self.__unsafe_private_named.4,
             extra_data: Some(extra_data),
         }
     }
@@ -291,6 +337,18 @@ impl<'a> ::jacquard_lexicon::schema::LexiconSchema for Emoji<'a> {
                 }
             }
         }
+        if let Some(ref value) = self.category {
+            #[allow(unused_comparisons)]
+            if <str>::len(value.as_ref()) > 32usize {
+                return Err(::jacquard_lexicon::validation::ConstraintError::MaxLength {
+                    path: ::jacquard_lexicon::validation::ValidationPath::from_field(
+                        "category",
+                    ),
+                    max: 32usize,
+                    actual: <str>::len(value.as_ref()),
+                });
+            }
+        }
         {
             let value = &self.name;
             #[allow(unused_comparisons)]
@@ -304,6 +362,30 @@ impl<'a> ::jacquard_lexicon::schema::LexiconSchema for Emoji<'a> {
                 });
             }
         }
+        if let Some(ref value) = self.tags {
+            #[allow(unused_comparisons)]
+            if value.len() > 8usize {
+                return Err(::jacquard_lexicon::validation::ConstraintError::MaxLength {
+                    path: ::jacquard_lexicon::validation::ValidationPath::from_field(
+                        "tags",
+                    ),
+                    max: 8usize,
+                    actual: value.len(),
+                });
+            }
+            for value in value.iter() {
+                #[allow(unused_comparisons)]
+                if <str>::len(value.as_ref()) > 64usize {
+                    return Err(::jacquard_lexicon::validation::ConstraintError::MaxLength {
+                        path: ::jacquard_lexicon::validation::ValidationPath::from_field(
+                            "tags",
+                        ),
+                        max: 64usize,
+                        actual: <str>::len(value.as_ref()),
+                    });
+                }
+            }
+        }
         Ok(())
     }
 }
@@ -354,6 +436,38 @@ fn lexicon_doc_vg_nat_istat_moji_emoji() -> ::jacquard_lexicon::lexicon::Lexicon
                                     known_values: None,
                                 }),
                             );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static("category"),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                    description: Some(
+                                        ::jacquard_common::CowStr::new_static(
+                                            "Picker category from the curated set; unknown values are ignored by indexers",
+                                        ),
+                                    ),
+                                    format: None,
+                                    default: None,
+                                    min_length: None,
+                                    max_length: Some(32usize),
+                                    min_graphemes: None,
+                                    max_graphemes: None,
+                                    r#enum: None,
+                                    r#const: None,
+                                    known_values: Some(
+                                        vec![
+                                            ::jacquard_common::CowStr::new_static("smileys"),
+                                            ::jacquard_common::CowStr::new_static("people"),
+                                            ::jacquard_common::CowStr::new_static("animals"),
+                                            ::jacquard_common::CowStr::new_static("food"),
+                                            ::jacquard_common::CowStr::new_static("activities"),
+                                            ::jacquard_common::CowStr::new_static("travel"),
+                                            ::jacquard_common::CowStr::new_static("objects"),
+                                            ::jacquard_common::CowStr::new_static("symbols"),
+                                            ::jacquard_common::CowStr::new_static("flags"),
+                                            ::jacquard_common::CowStr::new_static("memes")
+                                        ],
+                                    ),
+                                }),
+                            );
                             map.insert(
                                 ::jacquard_common::smol_str::SmolStr::new_static("emoji"),
                                 ::jacquard_lexicon::lexicon::LexObjectProperty::Blob(::jacquard_lexicon::lexicon::LexBlob {
@@ -381,6 +495,30 @@ fn lexicon_doc_vg_nat_istat_moji_emoji() -> ::jacquard_lexicon::lexicon::Lexicon
                                     known_values: None,
                                 }),
                             );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static("tags"),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::Array(::jacquard_lexicon::lexicon::LexArray {
+                                    description: Some(
+                                        ::jacquard_common::CowStr::new_static(
+                                            "Free-form tags for search and picker filtering",
+                                        ),
+                                    ),
+                                    items: ::jacquard_lexicon::lexicon::LexArrayItem::String(::jacquard_lexicon::lexicon::LexString {
+                                        description: None,
+                                        format: None,
+                                        default: None,
+                                        min_length: None,
+                                        max_length: Some(64usize),
+                                        min_graphemes: None,
+                                        max_graphemes: None,
+                                        r#enum: None,
+                                        r#const: None,
+                                        known_values: None,
+                                    }),
+                                    min_length: None,
+                                    max_length: Some(8usize),
+                                }),
+                            );
                             map
                         },
                     }),
@@ -24,6 +24,10 @@ pub struct EmojiView<'a> {
     /// CID of the emoji blob
     #[serde(borrow)]
     pub blob_cid: jacquard_common::CowStr<'a>,
+    /// Effective picker category (curated override or the record value)
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub category: Option<jacquard_common::CowStr<'a>>,
     /// DID of the user who created this emoji
     #[serde(borrow)]
     pub created_by: jacquard_common::types::string::Did<'a>,
@@ -31,9 +35,21 @@ pub struct EmojiView<'a> {
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[serde(borrow)]
     pub created_by_handle: Option<jacquard_common::types::string::Handle<'a>>,
+    /// Canonical emoji sharing this blob, when this record is a duplicate
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub duplicate_of: Option<jacquard_common::types::string::AtUri<'a>>,
     /// Canonical name of the emoji
     #[serde(borrow)]
     pub name: jacquard_common::CowStr<'a>,
+    /// AT-URIs of packs that include this emoji
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub packs: Option<Vec<jacquard_common::types::string::AtUri<'a>>>,
+    /// Tags attached to the emoji
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub tags: Option<Vec<jacquard_common::CowStr<'a>>>,
     /// AT-URI of the emoji record
     #[serde(borrow)]
     pub uri: jacquard_common::types::string::AtUri<'a>,
@@ -138,11 +154,15 @@ pub mod emoji_view_state {
 pub struct EmojiViewBuilder<'a, S: emoji_view_state::State> {
     _phantom_state: ::core::marker::PhantomData<fn() -> S>,
     __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::types::string::Did<'a>>,
         ::core::option::Option<jacquard_common::types::string::Handle<'a>>,
+        ::core::option::Option<jacquard_common::types::string::AtUri<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<Vec<jacquard_common::types::string::AtUri<'a>>>,
+        ::core::option::Option<Vec<jacquard_common::CowStr<'a>>>,
         ::core::option::Option<jacquard_common::types::string::AtUri<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
     ),
@@ -161,7 +181,19 @@ impl<'a> EmojiViewBuilder<'a, emoji_view_state::Empty> {
     pub fn new() -> Self {
         EmojiViewBuilder {
             _phantom_state: ::core::marker::PhantomData,
-            __unsafe_private_named: (None, None, None, None, None, None, None),
+            __unsafe_private_named: (
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            ),
             _phantom: ::core::marker::PhantomData,
         }
     }
@@ -202,6 +234,25 @@ where
     }
 }
 
+impl<'a, S: emoji_view_state::State> EmojiViewBuilder<'a, S> {
+    /// Set the `category` field (optional)
+    pub fn category(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.2 = value.into();
+        self
+    }
+    /// Set the `category` field to an Option value (optional)
+    pub fn maybe_category(
+        mut self,
+        value: Option<jacquard_common::CowStr<'a>>,
+    ) -> Self {
+        self.__unsafe_private_named.2 = value;
+        self
+    }
+}
+
 impl<'a, S> EmojiViewBuilder<'a, S>
 where
     S: emoji_view_state::State,
@@ -212,7 +263,7 @@ where
         mut self,
         value: impl Into<jacquard_common::types::string::Did<'a>>,
     ) -> EmojiViewBuilder<'a, emoji_view_state::SetCreatedBy<S>> {
-        self.__unsafe_private_named.2 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.3 = ::core::option::Option::Some(value.into());
         EmojiViewBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -227,7 +278,7 @@ impl<'a, S: emoji_view_state::State> EmojiViewBuilder<'a, S> {
         mut self,
         value: impl Into<Option<jacquard_common::types::string::Handle<'a>>>,
     ) -> Self {
-        self.__unsafe_private_named.3 = value.into();
+        self.__unsafe_private_named.4 = value.into();
         self
     }
     /// Set the `createdByHandle` field to an Option value (optional)
@@ -235,7 +286,26 @@ impl<'a, S: emoji_view_state::State> EmojiViewBuilder<'a, S> {
         mut self,
         value: Option<jacquard_common::types::string::Handle<'a>>,
     ) -> Self {
-        self.__unsafe_private_named.3 = value;
+        self.__unsafe_private_named.4 = value;
+        self
+    }
+}
+
+impl<'a, S: emoji_view_state::State> EmojiViewBuilder<'a, S> {
+    /// Set the `duplicateOf` field (optional)
+    pub fn duplicate_of(
+        mut self,
+        value: impl Into<Option<jacquard_common::types::string::AtUri<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.5 = value.into();
+        self
+    }
+    /// Set the `duplicateOf` field to an Option value (optional)
+    pub fn maybe_duplicate_of(
+        mut self,
+        value: Option<jacquard_common::types::string::AtUri<'a>>,
+    ) -> Self {
+        self.__unsafe_private_named.5 = value;
         self
     }
 }
@@ -250,7 +320,7 @@ where
         mut self,
         value: impl Into<jacquard_common::CowStr<'a>>,
     ) -> EmojiViewBuilder<'a, emoji_view_state::SetName<S>> {
-        self.__unsafe_private_named.4 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.6 = ::core::option::Option::Some(value.into());
         EmojiViewBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -259,6 +329,44 @@ where
     }
 }
 
+impl<'a, S: emoji_view_state::State> EmojiViewBuilder<'a, S> {
+    /// Set the `packs` field (optional)
+    pub fn packs(
+        mut self,
+        value: impl Into<Option<Vec<jacquard_common::types::string::AtUri<'a>>>>,
+    ) -> Self {
+        self.__unsafe_private_named.7 = value.into();
+        self
+    }
+    /// Set the `packs` field to an Option value (optional)
+    pub fn maybe_packs(
+        mut self,
+        value: Option<Vec<jacquard_common::types::string::AtUri<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.7 = value;
+        self
+    }
+}
+
+impl<'a, S: emoji_view_state::State> EmojiViewBuilder<'a, S> {
+    /// Set the `tags` field (optional)
+    pub fn tags(
+        mut self,
+        value: impl Into<Option<Vec<jacquard_common::CowStr<'a>>>>,
+    ) -> Self {
+        self.__unsafe_private_named.8 = value.into();
+        self
+    }
+    /// Set the `tags` field to an Option value (optional)
+    pub fn maybe_tags(
+        mut self,
+        value: Option<Vec<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.8 = value;
+        self
+    }
+}
+
 impl<'a, S> EmojiViewBuilder<'a, S>
 where
     S: emoji_view_state::State,
@@ -269,7 +377,7 @@ where
         mut self,
         value: impl Into<jacquard_common::types::string::AtUri<'a>>,
     ) -> EmojiViewBuilder<'a, emoji_view_state::SetUri<S>> {
-        self.__unsafe_private_named.5 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.9 = ::core::option::Option::Some(value.into());
         EmojiViewBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -288,7 +396,7 @@ where
         mut self,
         value: impl Into<jacquard_common::CowStr<'a>>,
     ) -> EmojiViewBuilder<'a, emoji_view_state::SetUrl<S>> {
-        self.__unsafe_private_named.6 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.10 = ::core::option::Option::Some(value.into());
         EmojiViewBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -311,11 +419,15 @@ where
         EmojiView {
             alt_text: self.__unsafe_private_named.0,
             blob_cid: self.__unsafe_private_named.1.unwrap(),
-            created_by: self.__unsafe_private_named.2.unwrap(),
-            created_by_handle: self.__unsafe_private_named.3,
-            name: self.__unsafe_private_named.4.unwrap(),
-            uri: self.__unsafe_private_named.5.unwrap(),
-            url: self.__unsafe_private_named.6.unwrap(),
+            category: self.__unsafe_private_named.2,
+            created_by: self.__unsafe_private_named.3.unwrap(),
+            created_by_handle: self.__unsafe_private_named.4,
+            duplicate_of: self.__unsafe_private_named.5,
+            name: self.__unsafe_private_named.6.unwrap(),
+            packs: self.__unsafe_private_named.7,
+            tags: self.__unsafe_private_named.8,
+            uri: self.__unsafe_private_named.9.unwrap(),
+            url: self.__unsafe_private_named.10.unwrap(),
             extra_data: Default::default(),
         }
     }
@@ -330,11 +442,15 @@ where
         EmojiView {
             alt_text: self.__unsafe_private_named.0,
             blob_cid: self.__unsafe_private_named.1.unwrap(),
-            created_by: self.__unsafe_private_named.2.unwrap(),
-            created_by_handle: self.__unsafe_private_named.3,
-            name: self.__unsafe_private_named.4.unwrap(),
-            uri: self.__unsafe_private_named.5.unwrap(),
-            url: self.__unsafe_private_named.6.unwrap(),
+            category: self.__unsafe_private_named.2,
+            created_by: self.__unsafe_private_named.3.unwrap(),
+            created_by_handle: self.__unsafe_private_named.4,
+            duplicate_of: self.__unsafe_private_named.5,
+            name: self.__unsafe_private_named.6.unwrap(),
+            packs: self.__unsafe_private_named.7,
+            tags: self.__unsafe_private_named.8,
+            uri: self.__unsafe_private_named.9.unwrap(),
+            url: self.__unsafe_private_named.10.unwrap(),
             extra_data: Some(extra_data),
         }
     }
@@ -405,6 +521,25 @@ fn lexicon_doc_vg_nat_istat_moji_searchEmoji() -> ::jacquard_lexicon::lexicon::L
                                 known_values: None,
                             }),
                         );
+                        map.insert(
+                            ::jacquard_common::smol_str::SmolStr::new_static("category"),
+                            ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                description: Some(
+                                    ::jacquard_common::CowStr::new_static(
+                                        "Effective picker category (curated override or the record value)",
+                                    ),
+                                ),
+                                format: None,
+                                default: None,
+                                min_length: None,
+                                max_length: None,
+                                min_graphemes: None,
+                                max_graphemes: None,
+                                r#enum: None,
+                                r#const: None,
+                                known_values: None,
+                            }),
+                        );
                         map.insert(
                             ::jacquard_common::smol_str::SmolStr::new_static(
                                 "createdBy",
@@ -451,6 +586,29 @@ fn lexicon_doc_vg_nat_istat_moji_searchEmoji() -> ::jacquard_lexicon::lexicon::L
                                 known_values: None,
                             }),
                         );
+                        map.insert(
+                            ::jacquard_common::smol_str::SmolStr::new_static(
+                                "duplicateOf",
+                            ),
+                            ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                description: Some(
+                                    ::jacquard_common::CowStr::new_static(
+                                        "Canonical emoji sharing this blob, when this record is a duplicate",
+                                    ),
+                                ),
+                                format: Some(
+                                    ::jacquard_lexicon::lexicon::LexStringFormat::AtUri,
+                                ),
+                                default: None,
+                                min_length: None,
+                                max_length: None,
+                                min_graphemes: None,
+                                max_graphemes: None,
+                                r#enum: None,
+                                r#const: None,
+                                known_values: None,
+                            }),
+                        );
                         map.insert(
                             ::jacquard_common::smol_str::SmolStr::new_static("name"),
                             ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
@@ -470,6 +628,56 @@ fn lexicon_doc_vg_nat_istat_moji_searchEmoji() -> ::jacquard_lexicon::lexicon::L
                                 known_values: None,
                             }),
                         );
+                        map.insert(
+                            ::jacquard_common::smol_str::SmolStr::new_static("packs"),
+                            ::jacquard_lexicon::lexicon::LexObjectProperty::Array(::jacquard_lexicon::lexicon::LexArray {
+                                description: Some(
+                                    ::jacquard_common::CowStr::new_static(
+                                        "AT-URIs of packs that include this emoji",
+                                    ),
+                                ),
+                                items: ::jacquard_lexicon::lexicon::LexArrayItem::String(::jacquard_lexicon::lexicon::LexString {
+                                    description: None,
+                                    format: Some(
+                                        ::jacquard_lexicon::lexicon::LexStringFormat::AtUri,
+                                    ),
+                                    default: None,
+                                    min_length: None,
+                                    max_length: None,
+                                    min_graphemes: None,
+                                    max_graphemes: None,
+                                    r#enum: None,
+                                    r#const: None,
+                                    known_values: None,
+                                }),
+                                min_length: None,
+                                max_length: None,
+                            }),
+                        );
+                        map.insert(
+                            ::jacquard_common::smol_str::SmolStr::new_static("tags"),
+                            ::jacquard_lexicon::lexicon::LexObjectProperty::Array(::jacquard_lexicon::lexicon::LexArray {
+                                description: Some(
+                                    ::jacquard_common::CowStr::new_static(
+                                        "Tags attached to the emoji",
+                                    ),
+                                ),
+                                items: ::jacquard_lexicon::lexicon::LexArrayItem::String(::jacquard_lexicon::lexicon::LexString {
+                                    description: None,
+                                    format: None,
+                                    default: None,
+                                    min_length: None,
+                                    max_length: None,
+                                    min_graphemes: None,
+                                    max_graphemes: None,
+                                    r#enum: None,
+                                    r#const: None,
+                                    known_values: None,
+                                }),
+                                min_length: None,
+                                max_length: None,
+                            }),
+                        );
                         map.insert(
                             ::jacquard_common::smol_str::SmolStr::new_static("uri"),
                             ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
@@ -529,6 +737,44 @@ fn lexicon_doc_vg_nat_istat_moji_searchEmoji() -> ::jacquard_lexicon::lexicon::L
                             properties: {
                                 #[allow(unused_mut)]
                                 let mut map = ::std::collections::BTreeMap::new();
+                                map.insert(
+                                    ::jacquard_common::smol_str::SmolStr::new_static("category"),
+                                    ::jacquard_lexicon::lexicon::LexXrpcParametersProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                        description: Some(
+                                            ::jacquard_common::CowStr::new_static(
+                                                "Only return emojis in this category (curated override wins over the record value)",
+                                            ),
+                                        ),
+                                        format: None,
+                                        default: None,
+                                        min_length: None,
+                                        max_length: None,
+                                        min_graphemes: None,
+                                        max_graphemes: None,
+                                        r#enum: None,
+                                        r#const: None,
+                                        known_values: None,
+                                    }),
+                                );
+                                map.insert(
+                                    ::jacquard_common::smol_str::SmolStr::new_static("cursor"),
+                                    ::jacquard_lexicon::lexicon::LexXrpcParametersProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                        description: Some(
+                                            ::jacquard_common::CowStr::new_static(
+                                                "Pagination cursor",
+                                            ),
+                                        ),
+                                        format: None,
+                                        default: None,
+                                        min_length: None,
+                                        max_length: None,
+                                        min_graphemes: None,
+                                        max_graphemes: None,
+                                        r#enum: None,
+                                        r#const: None,
+                                        known_values: None,
+                                    }),
+                                );
                                 map.insert(
                                     ::jacquard_common::smol_str::SmolStr::new_static("limit"),
                                     ::jacquard_lexicon::lexicon::LexXrpcParametersProperty::Integer(::jacquard_lexicon::lexicon::LexInteger {
@@ -545,7 +791,26 @@ fn lexicon_doc_vg_nat_istat_moji_searchEmoji() -> ::jacquard_lexicon::lexicon::L
                                     ::jacquard_lexicon::lexicon::LexXrpcParametersProperty::String(::jacquard_lexicon::lexicon::LexString {
                                         description: Some(
                                             ::jacquard_common::CowStr::new_static(
-                                                "Search query to match against emoji name and alt text",
+                                                "Search query to match against emoji name, alt text, and tags. An empty query matches everything, for category/tag browsing",
+                                            ),
+                                        ),
+                                        format: None,
+                                        default: None,
+                                        min_length: None,
+                                        max_length: None,
+                                        min_graphemes: None,
+                                        max_graphemes: None,
+                                        r#enum: None,
+                                        r#const: None,
+                                        known_values: None,
+                                    }),
+                                );
+                                map.insert(
+                                    ::jacquard_common::smol_str::SmolStr::new_static("tag"),
+                                    ::jacquard_lexicon::lexicon::LexXrpcParametersProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                        description: Some(
+                                            ::jacquard_common::CowStr::new_static(
+                                                "Only return emojis carrying this tag (case-insensitive)",
                                             ),
                                         ),
                                         format: None,
@@ -600,11 +865,23 @@ impl<'a> ::jacquard_lexicon::schema::LexiconSchema for EmojiView<'a> {
 )]
 #[serde(rename_all = "camelCase")]
 pub struct SearchEmoji<'a> {
+    /// Only return emojis in this category (curated override wins over the record value)
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub category: std::option::Option<jacquard_common::CowStr<'a>>,
+    /// Pagination cursor
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
     ///(default: 20, min: 1, max: 100)
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     pub limit: std::option::Option<i64>,
     #[serde(borrow)]
     pub query: jacquard_common::CowStr<'a>,
+    /// Only return emojis carrying this tag (case-insensitive)
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub tag: std::option::Option<jacquard_common::CowStr<'a>>,
 }
 
 pub mod search_emoji_state {
@@ -643,8 +920,11 @@ pub mod search_emoji_state {
 pub struct SearchEmojiBuilder<'a, S: search_emoji_state::State> {
     _phantom_state: ::core::marker::PhantomData<fn() -> S>,
     __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<i64>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
     ),
     _phantom: ::core::marker::PhantomData<&'a ()>,
 }
@@ -661,21 +941,59 @@ impl<'a> SearchEmojiBuilder<'a, search_emoji_state::Empty> {
     pub fn new() -> Self {
         SearchEmojiBuilder {
             _phantom_state: ::core::marker::PhantomData,
-            __unsafe_private_named: (None, None),
+            __unsafe_private_named: (None, None, None, None, None),
             _phantom: ::core::marker::PhantomData,
         }
     }
 }
 
+impl<'a, S: search_emoji_state::State> SearchEmojiBuilder<'a, S> {
+    /// Set the `category` field (optional)
+    pub fn category(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.0 = value.into();
+        self
+    }
+    /// Set the `category` field to an Option value (optional)
+    pub fn maybe_category(
+        mut self,
+        value: Option<jacquard_common::CowStr<'a>>,
+    ) -> Self {
+        self.__unsafe_private_named.0 = value;
+        self
+    }
+}
+
+impl<'a, S: search_emoji_state::State> SearchEmojiBuilder<'a, S> {
+    /// Set the `cursor` field (optional)
+    pub fn cursor(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.1 = value.into();
+        self
+    }
+    /// Set the `cursor` field to an Option value (optional)
+    pub fn maybe_cursor(
+        mut self,
+        value: Option<jacquard_common::CowStr<'a>>,
+    ) -> Self {
+        self.__unsafe_private_named.1 = value;
+        self
+    }
+}
+
 impl<'a, S: search_emoji_state::State> SearchEmojiBuilder<'a, S> {
     /// Set the `limit` field (optional)
     pub fn limit(mut self, value: impl Into<Option<i64>>) -> Self {
-        self.__unsafe_private_named.0 = value.into();
+        self.__unsafe_private_named.2 = value.into();
         self
     }
     /// Set the `limit` field to an Option value (optional)
     pub fn maybe_limit(mut self, value: Option<i64>) -> Self {
-        self.__unsafe_private_named.0 = value;
+        self.__unsafe_private_named.2 = value;
         self
     }
 }
@@ -690,7 +1008,7 @@ where
         mut self,
         value: impl Into<jacquard_common::CowStr<'a>>,
     ) -> SearchEmojiBuilder<'a, search_emoji_state::SetQuery<S>> {
-        self.__unsafe_private_named.1 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.3 = ::core::option::Option::Some(value.into());
         SearchEmojiBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -699,6 +1017,25 @@ where
     }
 }
 
+impl<'a, S: search_emoji_state::State> SearchEmojiBuilder<'a, S> {
+    /// Set the `tag` field (optional)
+    pub fn tag(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.4 = value.into();
+        self
+    }
+    /// Set the `tag` field to an Option value (optional)
+    pub fn maybe_tag(
+        mut self,
+        value: Option<jacquard_common::CowStr<'a>>,
+    ) -> Self {
+        self.__unsafe_private_named.4 = value;
+        self
+    }
+}
+
 impl<'a, S> SearchEmojiBuilder<'a, S>
 where
     S: search_emoji_state::State,
@@ -707,8 +1044,11 @@ where
     /// Build the final struct
     pub fn build(self) -> SearchEmoji<'a> {
         SearchEmoji {
-            limit: self.__unsafe_private_named.0,
-            query: self.__unsafe_private_named.1.unwrap(),
+            category: self.__unsafe_private_named.0,
+            cursor: self.__unsafe_private_named.1,
+            limit: self.__unsafe_private_named.2,
+            query: self.__unsafe_private_named.3.unwrap(),
+            tag: self.__unsafe_private_named.4,
         }
     }
 }
@@ -725,6 +1065,10 @@ where
 )]
 #[serde(rename_all = "camelCase")]
 pub struct SearchEmojiOutput<'a> {
+    /// Pagination cursor for next page
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
     #[serde(borrow)]
     pub emojis: Vec<crate::vg_nat::istat::moji::search_emoji::EmojiView<'a>>,
 }
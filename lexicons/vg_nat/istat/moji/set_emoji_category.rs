@@ -0,0 +1,254 @@
+// @generated by jacquard-lexicon. DO NOT EDIT.
+//
+// Lexicon: vg.nat.istat.moji.setEmojiCategory
+//
+// This file was automatically generated from Lexicon schemas.
+// Any manual changes will be overwritten on the next regeneration.
+
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SetEmojiCategory<'a> {
+    /// Category from the curated set; omit to clear the override
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub category: Option<jacquard_common::CowStr<'a>>,
+    /// AT-URI of the emoji to curate
+    #[serde(borrow)]
+    pub uri: jacquard_common::types::string::AtUri<'a>,
+}
+
+pub mod set_emoji_category_state {
+
+    pub use crate::builder_types::{Set, Unset, IsSet, IsUnset};
+    #[allow(unused)]
+    use ::core::marker::PhantomData;
+    mod sealed {
+        pub trait Sealed {}
+    }
+    /// State trait tracking which required fields have been set
+    pub trait State: sealed::Sealed {
+        type Uri;
+    }
+    /// Empty state - all required fields are unset
+    pub struct Empty(());
+    impl sealed::Sealed for Empty {}
+    impl State for Empty {
+        type Uri = Unset;
+    }
+    ///State transition - sets the `uri` field to Set
+    pub struct SetUri<S: State = Empty>(PhantomData<fn() -> S>);
+    impl<S: State> sealed::Sealed for SetUri<S> {}
+    impl<S: State> State for SetUri<S> {
+        type Uri = Set<members::uri>;
+    }
+    /// Marker types for field names
+    #[allow(non_camel_case_types)]
+    pub mod members {
+        ///Marker type for the `uri` field
+        pub struct uri(());
+    }
+}
+
+/// Builder for constructing an instance of this type
+pub struct SetEmojiCategoryBuilder<'a, S: set_emoji_category_state::State> {
+    _phantom_state: ::core::marker::PhantomData<fn() -> S>,
+    __unsafe_private_named: (
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<jacquard_common::types::string::AtUri<'a>>,
+    ),
+    _phantom: ::core::marker::PhantomData<&'a ()>,
+}
+
+impl<'a> SetEmojiCategory<'a> {
+    /// Create a new builder for this type
+    pub fn new() -> SetEmojiCategoryBuilder<'a, set_emoji_category_state::Empty> {
+        SetEmojiCategoryBuilder::new()
+    }
+}
+
+impl<'a> SetEmojiCategoryBuilder<'a, set_emoji_category_state::Empty> {
+    /// Create a new builder with all fields unset
+    pub fn new() -> Self {
+        SetEmojiCategoryBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: (None, None),
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S: set_emoji_category_state::State> SetEmojiCategoryBuilder<'a, S> {
+    /// Set the `category` field (optional)
+    pub fn category(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.0 = value.into();
+        self
+    }
+    /// Set the `category` field to an Option value (optional)
+    pub fn maybe_category(
+        mut self,
+        value: Option<jacquard_common::CowStr<'a>>,
+    ) -> Self {
+        self.__unsafe_private_named.0 = value;
+        self
+    }
+}
+
+impl<'a, S> SetEmojiCategoryBuilder<'a, S>
+where
+    S: set_emoji_category_state::State,
+    S::Uri: set_emoji_category_state::IsUnset,
+{
+    /// Set the `uri` field (required)
+    pub fn uri(
+        mut self,
+        value: impl Into<jacquard_common::types::string::AtUri<'a>>,
+    ) -> SetEmojiCategoryBuilder<'a, set_emoji_category_state::SetUri<S>> {
+        self.__unsafe_private_named.1 = ::core::option::Option::Some(value.into());
+        SetEmojiCategoryBuilder {
+            _phantom_state: ::core::marker::PhantomData,
+            __unsafe_private_named: self.__unsafe_private_named,
+            _phantom: ::core::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, S> SetEmojiCategoryBuilder<'a, S>
+where
+    S: set_emoji_category_state::State,
+    S::Uri: set_emoji_category_state::IsSet,
+{
+    /// Build the final struct
+    pub fn build(self) -> SetEmojiCategory<'a> {
+        SetEmojiCategory {
+            category: self.__unsafe_private_named.0,
+            uri: self.__unsafe_private_named.1.unwrap(),
+            extra_data: Default::default(),
+        }
+    }
+    /// Build the final struct with custom extra_data
+    pub fn build_with_data(
+        self,
+        extra_data: std::collections::BTreeMap<
+            jacquard_common::smol_str::SmolStr,
+            jacquard_common::types::value::Data<'a>,
+        >,
+    ) -> SetEmojiCategory<'a> {
+        SetEmojiCategory {
+            category: self.__unsafe_private_named.0,
+            uri: self.__unsafe_private_named.1.unwrap(),
+            extra_data: Some(extra_data),
+        }
+    }
+}
+
+#[jacquard_derive::lexicon]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    jacquard_derive::IntoStatic
+)]
+#[serde(rename_all = "camelCase")]
+pub struct SetEmojiCategoryOutput<'a> {
+    pub success: bool,
+}
+
+#[jacquard_derive::open_union]
+#[derive(
+    serde::Serialize,
+    serde::Deserialize,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    thiserror::Error,
+    miette::Diagnostic,
+    jacquard_derive::IntoStatic
+)]
+#[serde(tag = "error", content = "message")]
+#[serde(bound(deserialize = "'de: 'a"))]
+pub enum SetEmojiCategoryError<'a> {
+    /// User is not an admin
+    #[serde(rename = "Unauthorized")]
+    Unauthorized(std::option::Option<String>),
+    /// Emoji not found
+    #[serde(rename = "NotFound")]
+    NotFound(std::option::Option<String>),
+    /// Category is not in the curated set
+    #[serde(rename = "InvalidCategory")]
+    InvalidCategory(std::option::Option<String>),
+}
+
+impl std::fmt::Display for SetEmojiCategoryError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unauthorized(msg) => {
+                write!(f, "Unauthorized")?;
+                if let Some(msg) = msg {
+                    write!(f, ": {}", msg)?;
+                }
+                Ok(())
+            }
+            Self::NotFound(msg) => {
+                write!(f, "NotFound")?;
+                if let Some(msg) = msg {
+                    write!(f, ": {}", msg)?;
+                }
+                Ok(())
+            }
+            Self::InvalidCategory(msg) => {
+                write!(f, "InvalidCategory")?;
+                if let Some(msg) = msg {
+                    write!(f, ": {}", msg)?;
+                }
+                Ok(())
+            }
+            Self::Unknown(err) => write!(f, "Unknown error: {:?}", err),
+        }
+    }
+}
+
+/// Response type for
+///vg.nat.istat.moji.setEmojiCategory
+pub struct SetEmojiCategoryResponse;
+impl jacquard_common::xrpc::XrpcResp for SetEmojiCategoryResponse {
+    const NSID: &'static str = "vg.nat.istat.moji.setEmojiCategory";
+    const ENCODING: &'static str = "application/json";
+    type Output<'de> = SetEmojiCategoryOutput<'de>;
+    type Err<'de> = SetEmojiCategoryError<'de>;
+}
+
+impl<'a> jacquard_common::xrpc::XrpcRequest for SetEmojiCategory<'a> {
+    const NSID: &'static str = "vg.nat.istat.moji.setEmojiCategory";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Procedure(
+        "application/json",
+    );
+    type Response = SetEmojiCategoryResponse;
+}
+
+/// Endpoint type for
+///vg.nat.istat.moji.setEmojiCategory
+pub struct SetEmojiCategoryRequest;
+impl jacquard_common::xrpc::XrpcEndpoint for SetEmojiCategoryRequest {
+    const PATH: &'static str = "/xrpc/vg.nat.istat.moji.setEmojiCategory";
+    const METHOD: jacquard_common::xrpc::XrpcMethod = jacquard_common::xrpc::XrpcMethod::Procedure(
+        "application/json",
+    );
+    type Request<'de> = SetEmojiCategory<'de>;
+    type Response = SetEmojiCategoryResponse;
+}
@@ -5,6 +5,7 @@
 
 pub mod delete_status;
 pub mod get_status;
+pub mod list_replies;
 pub mod list_statuses;
 pub mod list_user_statuses;
 pub mod record;
\ No newline at end of file
@@ -171,6 +171,10 @@ pub struct GetStatusOutput<'a> {
     /// Optional expiration timestamp for this status
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     pub expires: std::option::Option<jacquard_common::types::string::Datetime>,
+    /// IANA timezone identifier for the author, for rendering expiry in local time
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub timezone: std::option::Option<jacquard_common::CowStr<'a>>,
     /// Optional status text title
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[serde(borrow)]
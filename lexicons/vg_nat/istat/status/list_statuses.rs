@@ -213,9 +213,20 @@ pub struct StatusView<'a> {
     /// The user's handle
     #[serde(borrow)]
     pub handle: jacquard_common::types::string::Handle<'a>,
+    /// Number of replies to this status
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub reply_count: Option<i64>,
+    /// AT-URI of the status this status replies to
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub reply_to: Option<jacquard_common::types::string::AtUri<'a>>,
     /// The record key
     #[serde(borrow)]
     pub rkey: jacquard_common::CowStr<'a>,
+    /// IANA timezone identifier for the author, for rendering expiry in local time
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub timezone: Option<jacquard_common::CowStr<'a>>,
     /// Optional status text title
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[serde(borrow)]
@@ -330,6 +341,9 @@ pub struct StatusViewBuilder<'a, S: status_view_state::State> {
         ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::types::string::Datetime>,
         ::core::option::Option<jacquard_common::types::string::Handle<'a>>,
+        ::core::option::Option<i64>,
+        ::core::option::Option<jacquard_common::types::string::AtUri<'a>>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
     ),
@@ -363,6 +377,9 @@ impl<'a> StatusViewBuilder<'a, status_view_state::Empty> {
                 None,
                 None,
                 None,
+                None,
+                None,
+                None,
             ),
             _phantom: ::core::marker::PhantomData,
         }
@@ -597,6 +614,38 @@ where
     }
 }
 
+impl<'a, S: status_view_state::State> StatusViewBuilder<'a, S> {
+    /// Set the `replyCount` field (optional)
+    pub fn reply_count(mut self, value: impl Into<Option<i64>>) -> Self {
+        self.__unsafe_private_named.12 = value.into();
+        self
+    }
+    /// Set the `replyCount` field to an Option value (optional)
+    pub fn maybe_reply_count(mut self, value: Option<i64>) -> Self {
+        self.__unsafe_private_named.12 = value;
+        self
+    }
+}
+
+impl<'a, S: status_view_state::State> StatusViewBuilder<'a, S> {
+    /// Set the `replyTo` field (optional)
+    pub fn reply_to(
+        mut self,
+        value: impl Into<Option<jacquard_common::types::string::AtUri<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.13 = value.into();
+        self
+    }
+    /// Set the `replyTo` field to an Option value (optional)
+    pub fn maybe_reply_to(
+        mut self,
+        value: Option<jacquard_common::types::string::AtUri<'a>>,
+    ) -> Self {
+        self.__unsafe_private_named.13 = value;
+        self
+    }
+}
+
 impl<'a, S> StatusViewBuilder<'a, S>
 where
     S: status_view_state::State,
@@ -607,7 +656,7 @@ where
         mut self,
         value: impl Into<jacquard_common::CowStr<'a>>,
     ) -> StatusViewBuilder<'a, status_view_state::SetRkey<S>> {
-        self.__unsafe_private_named.12 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.14 = ::core::option::Option::Some(value.into());
         StatusViewBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -616,18 +665,37 @@ where
     }
 }
 
+impl<'a, S: status_view_state::State> StatusViewBuilder<'a, S> {
+    /// Set the `timezone` field (optional)
+    pub fn timezone(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.15 = value.into();
+        self
+    }
+    /// Set the `timezone` field to an Option value (optional)
+    pub fn maybe_timezone(
+        mut self,
+        value: Option<jacquard_common::CowStr<'a>>,
+    ) -> Self {
+        self.__unsafe_private_named.15 = value;
+        self
+    }
+}
+
 impl<'a, S: status_view_state::State> StatusViewBuilder<'a, S> {
     /// Set the `title` field (optional)
     pub fn title(
         mut self,
         value: impl Into<Option<jacquard_common::CowStr<'a>>>,
     ) -> Self {
-        self.__unsafe_private_named.13 = value.into();
+        self.__unsafe_private_named.16 = value.into();
         self
     }
     /// Set the `title` field to an Option value (optional)
     pub fn maybe_title(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
-        self.__unsafe_private_named.13 = value;
+        self.__unsafe_private_named.16 = value;
         self
     }
 }
@@ -656,8 +724,11 @@ where
             emoji_url: self.__unsafe_private_named.9.unwrap(),
             expires: self.__unsafe_private_named.10,
             handle: self.__unsafe_private_named.11.unwrap(),
-            rkey: self.__unsafe_private_named.12.unwrap(),
-            title: self.__unsafe_private_named.13,
+            reply_count: self.__unsafe_private_named.12,
+            reply_to: self.__unsafe_private_named.13,
+            rkey: self.__unsafe_private_named.14.unwrap(),
+            timezone: self.__unsafe_private_named.15,
+            title: self.__unsafe_private_named.16,
             extra_data: Default::default(),
         }
     }
@@ -682,8 +753,11 @@ where
             emoji_url: self.__unsafe_private_named.9.unwrap(),
             expires: self.__unsafe_private_named.10,
             handle: self.__unsafe_private_named.11.unwrap(),
-            rkey: self.__unsafe_private_named.12.unwrap(),
-            title: self.__unsafe_private_named.13,
+            reply_count: self.__unsafe_private_named.12,
+            reply_to: self.__unsafe_private_named.13,
+            rkey: self.__unsafe_private_named.14.unwrap(),
+            timezone: self.__unsafe_private_named.15,
+            title: self.__unsafe_private_named.16,
             extra_data: Some(extra_data),
         }
     }
@@ -1005,6 +1079,44 @@ fn lexicon_doc_vg_nat_istat_status_listStatuses() -> ::jacquard_lexicon::lexicon
                                 known_values: None,
                             }),
                         );
+                        map.insert(
+                            ::jacquard_common::smol_str::SmolStr::new_static(
+                                "replyCount",
+                            ),
+                            ::jacquard_lexicon::lexicon::LexObjectProperty::Integer(::jacquard_lexicon::lexicon::LexInteger {
+                                description: Some(
+                                    ::jacquard_common::CowStr::new_static(
+                                        "Number of replies to this status",
+                                    ),
+                                ),
+                                default: None,
+                                minimum: None,
+                                maximum: None,
+                                r#enum: None,
+                                r#const: None,
+                            }),
+                        );
+                        map.insert(
+                            ::jacquard_common::smol_str::SmolStr::new_static("replyTo"),
+                            ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                description: Some(
+                                    ::jacquard_common::CowStr::new_static(
+                                        "AT-URI of the status this status replies to",
+                                    ),
+                                ),
+                                format: Some(
+                                    ::jacquard_lexicon::lexicon::LexStringFormat::AtUri,
+                                ),
+                                default: None,
+                                min_length: None,
+                                max_length: None,
+                                min_graphemes: None,
+                                max_graphemes: None,
+                                r#enum: None,
+                                r#const: None,
+                                known_values: None,
+                            }),
+                        );
                         map.insert(
                             ::jacquard_common::smol_str::SmolStr::new_static("rkey"),
                             ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
@@ -1022,6 +1134,27 @@ fn lexicon_doc_vg_nat_istat_status_listStatuses() -> ::jacquard_lexicon::lexicon
                                 known_values: None,
                             }),
                         );
+                        map.insert(
+                            ::jacquard_common::smol_str::SmolStr::new_static(
+                                "timezone",
+                            ),
+                            ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                description: Some(
+                                    ::jacquard_common::CowStr::new_static(
+                                        "IANA timezone identifier for the author, for rendering expiry in local time",
+                                    ),
+                                ),
+                                format: None,
+                                default: None,
+                                min_length: None,
+                                max_length: Some(64usize),
+                                min_graphemes: None,
+                                max_graphemes: None,
+                                r#enum: None,
+                                r#const: None,
+                                known_values: None,
+                            }),
+                        );
                         map.insert(
                             ::jacquard_common::smol_str::SmolStr::new_static("title"),
                             ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
@@ -1123,6 +1256,18 @@ impl<'a> ::jacquard_lexicon::schema::LexiconSchema for StatusView<'a> {
                 }
             }
         }
+        if let Some(ref value) = self.timezone {
+            #[allow(unused_comparisons)]
+            if <str>::len(value.as_ref()) > 64usize {
+                return Err(::jacquard_lexicon::validation::ConstraintError::MaxLength {
+                    path: ::jacquard_lexicon::validation::ValidationPath::from_field(
+                        "timezone",
+                    ),
+                    max: 64usize,
+                    actual: <str>::len(value.as_ref()),
+                });
+            }
+        }
         if let Some(ref value) = self.title {
             #[allow(unused_comparisons)]
             if <str>::len(value.as_ref()) > 2560usize {
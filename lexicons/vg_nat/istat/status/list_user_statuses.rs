@@ -19,11 +19,20 @@ pub struct ListUserStatuses<'a> {
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[serde(borrow)]
     pub cursor: std::option::Option<jacquard_common::CowStr<'a>>,
+    ///Only statuses created at or after this time
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub from: std::option::Option<jacquard_common::types::string::Datetime>,
     #[serde(borrow)]
     pub handle: jacquard_common::types::string::Handle<'a>,
+    ///Include expired statuses. Only the owner or an admin may set this. (default: false)
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub include_expired: std::option::Option<bool>,
     ///(default: 50, min: 1, max: 100)
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     pub limit: std::option::Option<i64>,
+    ///Only statuses created at or before this time
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    pub until: std::option::Option<jacquard_common::types::string::Datetime>,
 }
 
 pub mod list_user_statuses_state {
@@ -63,8 +72,11 @@ pub struct ListUserStatusesBuilder<'a, S: list_user_statuses_state::State> {
     _phantom_state: ::core::marker::PhantomData<fn() -> S>,
     __unsafe_private_named: (
         ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<jacquard_common::types::string::Datetime>,
         ::core::option::Option<jacquard_common::types::string::Handle<'a>>,
+        ::core::option::Option<bool>,
         ::core::option::Option<i64>,
+        ::core::option::Option<jacquard_common::types::string::Datetime>,
     ),
     _phantom: ::core::marker::PhantomData<&'a ()>,
 }
@@ -81,7 +93,7 @@ impl<'a> ListUserStatusesBuilder<'a, list_user_statuses_state::Empty> {
     pub fn new() -> Self {
         ListUserStatusesBuilder {
             _phantom_state: ::core::marker::PhantomData,
-            __unsafe_private_named: (None, None, None),
+            __unsafe_private_named: (None, None, None, None, None, None),
             _phantom: ::core::marker::PhantomData,
         }
     }
@@ -103,6 +115,25 @@ impl<'a, S: list_user_statuses_state::State> ListUserStatusesBuilder<'a, S> {
     }
 }
 
+impl<'a, S: list_user_statuses_state::State> ListUserStatusesBuilder<'a, S> {
+    /// Set the `from` field (optional)
+    pub fn from(
+        mut self,
+        value: impl Into<Option<jacquard_common::types::string::Datetime>>,
+    ) -> Self {
+        self.__unsafe_private_named.1 = value.into();
+        self
+    }
+    /// Set the `from` field to an Option value (optional)
+    pub fn maybe_from(
+        mut self,
+        value: Option<jacquard_common::types::string::Datetime>,
+    ) -> Self {
+        self.__unsafe_private_named.1 = value;
+        self
+    }
+}
+
 impl<'a, S> ListUserStatusesBuilder<'a, S>
 where
     S: list_user_statuses_state::State,
@@ -113,7 +144,7 @@ where
         mut self,
         value: impl Into<jacquard_common::types::string::Handle<'a>>,
     ) -> ListUserStatusesBuilder<'a, list_user_statuses_state::SetHandle<S>> {
-        self.__unsafe_private_named.1 = ::core::option::Option::Some(value.into());
+        self.__unsafe_private_named.2 = ::core::option::Option::Some(value.into());
         ListUserStatusesBuilder {
             _phantom_state: ::core::marker::PhantomData,
             __unsafe_private_named: self.__unsafe_private_named,
@@ -122,15 +153,47 @@ where
     }
 }
 
+impl<'a, S: list_user_statuses_state::State> ListUserStatusesBuilder<'a, S> {
+    /// Set the `includeExpired` field (optional)
+    pub fn include_expired(mut self, value: impl Into<Option<bool>>) -> Self {
+        self.__unsafe_private_named.3 = value.into();
+        self
+    }
+    /// Set the `includeExpired` field to an Option value (optional)
+    pub fn maybe_include_expired(mut self, value: Option<bool>) -> Self {
+        self.__unsafe_private_named.3 = value;
+        self
+    }
+}
+
 impl<'a, S: list_user_statuses_state::State> ListUserStatusesBuilder<'a, S> {
     /// Set the `limit` field (optional)
     pub fn limit(mut self, value: impl Into<Option<i64>>) -> Self {
-        self.__unsafe_private_named.2 = value.into();
+        self.__unsafe_private_named.4 = value.into();
         self
     }
     /// Set the `limit` field to an Option value (optional)
     pub fn maybe_limit(mut self, value: Option<i64>) -> Self {
-        self.__unsafe_private_named.2 = value;
+        self.__unsafe_private_named.4 = value;
+        self
+    }
+}
+
+impl<'a, S: list_user_statuses_state::State> ListUserStatusesBuilder<'a, S> {
+    /// Set the `until` field (optional)
+    pub fn until(
+        mut self,
+        value: impl Into<Option<jacquard_common::types::string::Datetime>>,
+    ) -> Self {
+        self.__unsafe_private_named.5 = value.into();
+        self
+    }
+    /// Set the `until` field to an Option value (optional)
+    pub fn maybe_until(
+        mut self,
+        value: Option<jacquard_common::types::string::Datetime>,
+    ) -> Self {
+        self.__unsafe_private_named.5 = value;
         self
     }
 }
@@ -144,8 +207,11 @@ where
     pub fn build(self) -> ListUserStatuses<'a> {
         ListUserStatuses {
             cursor: self.__unsafe_private_named.0,
-            handle: self.__unsafe_private_named.1.unwrap(),
-            limit: self.__unsafe_private_named.2,
+            from: self.__unsafe_private_named.1,
+            handle: self.__unsafe_private_named.2.unwrap(),
+            include_expired: self.__unsafe_private_named.3,
+            limit: self.__unsafe_private_named.4,
+            until: self.__unsafe_private_named.5,
         }
     }
 }
@@ -245,6 +311,10 @@ pub struct UserStatusView<'a> {
     /// The record key
     #[serde(borrow)]
     pub rkey: jacquard_common::CowStr<'a>,
+    /// IANA timezone identifier for the author, for rendering expiry in local time
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub timezone: Option<jacquard_common::CowStr<'a>>,
     /// Optional status text title
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[serde(borrow)]
@@ -324,6 +394,7 @@ pub struct UserStatusViewBuilder<'a, S: user_status_view_state::State> {
         ::core::option::Option<jacquard_common::types::string::Handle<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
     ),
     _phantom: ::core::marker::PhantomData<&'a ()>,
 }
@@ -352,6 +423,7 @@ impl<'a> UserStatusViewBuilder<'a, user_status_view_state::Empty> {
                 None,
                 None,
                 None,
+                None,
             ),
             _phantom: ::core::marker::PhantomData,
         }
@@ -548,18 +620,37 @@ where
     }
 }
 
+impl<'a, S: user_status_view_state::State> UserStatusViewBuilder<'a, S> {
+    /// Set the `timezone` field (optional)
+    pub fn timezone(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.10 = value.into();
+        self
+    }
+    /// Set the `timezone` field to an Option value (optional)
+    pub fn maybe_timezone(
+        mut self,
+        value: Option<jacquard_common::CowStr<'a>>,
+    ) -> Self {
+        self.__unsafe_private_named.10 = value;
+        self
+    }
+}
+
 impl<'a, S: user_status_view_state::State> UserStatusViewBuilder<'a, S> {
     /// Set the `title` field (optional)
     pub fn title(
         mut self,
         value: impl Into<Option<jacquard_common::CowStr<'a>>>,
     ) -> Self {
-        self.__unsafe_private_named.10 = value.into();
+        self.__unsafe_private_named.11 = value.into();
         self
     }
     /// Set the `title` field to an Option value (optional)
     pub fn maybe_title(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
-        self.__unsafe_private_named.10 = value;
+        self.__unsafe_private_named.11 = value;
         self
     }
 }
@@ -584,7 +675,8 @@ where
             expires: self.__unsafe_private_named.7,
             handle: self.__unsafe_private_named.8,
             rkey: self.__unsafe_private_named.9.unwrap(),
-            title: self.__unsafe_private_named.10,
+            timezone: self.__unsafe_private_named.10,
+            title: self.__unsafe_private_named.11,
             extra_data: Default::default(),
         }
     }
@@ -607,7 +699,8 @@ where
             expires: self.__unsafe_private_named.7,
             handle: self.__unsafe_private_named.8,
             rkey: self.__unsafe_private_named.9.unwrap(),
-            title: self.__unsafe_private_named.10,
+            timezone: self.__unsafe_private_named.10,
+            title: self.__unsafe_private_named.11,
             extra_data: Some(extra_data),
         }
     }
@@ -657,6 +750,27 @@ fn lexicon_doc_vg_nat_istat_status_listUserStatuses() -> ::jacquard_lexicon::lex
                                         known_values: None,
                                     }),
                                 );
+                                map.insert(
+                                    ::jacquard_common::smol_str::SmolStr::new_static("from"),
+                                    ::jacquard_lexicon::lexicon::LexXrpcParametersProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                        description: Some(
+                                            ::jacquard_common::CowStr::new_static(
+                                                "Only statuses created at or after this time",
+                                            ),
+                                        ),
+                                        format: Some(
+                                            ::jacquard_lexicon::lexicon::LexStringFormat::Datetime,
+                                        ),
+                                        default: None,
+                                        min_length: None,
+                                        max_length: None,
+                                        min_graphemes: None,
+                                        max_graphemes: None,
+                                        r#enum: None,
+                                        r#const: None,
+                                        known_values: None,
+                                    }),
+                                );
                                 map.insert(
                                     ::jacquard_common::smol_str::SmolStr::new_static("handle"),
                                     ::jacquard_lexicon::lexicon::LexXrpcParametersProperty::String(::jacquard_lexicon::lexicon::LexString {
@@ -678,6 +792,20 @@ fn lexicon_doc_vg_nat_istat_status_listUserStatuses() -> ::jacquard_lexicon::lex
                                         known_values: None,
                                     }),
                                 );
+                                map.insert(
+                                    ::jacquard_common::smol_str::SmolStr::new_static(
+                                        "includeExpired",
+                                    ),
+                                    ::jacquard_lexicon::lexicon::LexXrpcParametersProperty::Boolean(::jacquard_lexicon::lexicon::LexBoolean {
+                                        description: Some(
+                                            ::jacquard_common::CowStr::new_static(
+                                                "Include expired statuses. Only the owner or an admin may set this.",
+                                            ),
+                                        ),
+                                        default: None,
+                                        r#const: None,
+                                    }),
+                                );
                                 map.insert(
                                     ::jacquard_common::smol_str::SmolStr::new_static("limit"),
                                     ::jacquard_lexicon::lexicon::LexXrpcParametersProperty::Integer(::jacquard_lexicon::lexicon::LexInteger {
@@ -689,6 +817,27 @@ fn lexicon_doc_vg_nat_istat_status_listUserStatuses() -> ::jacquard_lexicon::lex
                                         r#const: None,
                                     }),
                                 );
+                                map.insert(
+                                    ::jacquard_common::smol_str::SmolStr::new_static("until"),
+                                    ::jacquard_lexicon::lexicon::LexXrpcParametersProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                        description: Some(
+                                            ::jacquard_common::CowStr::new_static(
+                                                "Only statuses created at or before this time",
+                                            ),
+                                        ),
+                                        format: Some(
+                                            ::jacquard_lexicon::lexicon::LexStringFormat::Datetime,
+                                        ),
+                                        default: None,
+                                        min_length: None,
+                                        max_length: None,
+                                        min_graphemes: None,
+                                        max_graphemes: None,
+                                        r#enum: None,
+                                        r#const: None,
+                                        known_values: None,
+                                    }),
+                                );
                                 map
                             },
                         }),
@@ -912,6 +1061,25 @@ fn lexicon_doc_vg_nat_istat_status_listUserStatuses() -> ::jacquard_lexicon::lex
                                 known_values: None,
                             }),
                         );
+                        map.insert(
+                            ::jacquard_common::smol_str::SmolStr::new_static("timezone"),
+                            ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                description: Some(
+                                    ::jacquard_common::CowStr::new_static(
+                                        "IANA timezone identifier for the author, for rendering expiry in local time",
+                                    ),
+                                ),
+                                format: None,
+                                default: None,
+                                min_length: None,
+                                max_length: Some(64usize),
+                                min_graphemes: None,
+                                max_graphemes: None,
+                                r#enum: None,
+                                r#const: None,
+                                known_values: None,
+                            }),
+                        );
                         map.insert(
                             ::jacquard_common::smol_str::SmolStr::new_static("title"),
                             ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
@@ -1013,6 +1181,18 @@ impl<'a> ::jacquard_lexicon::schema::LexiconSchema for UserStatusView<'a> {
                 }
             }
         }
+        if let Some(ref value) = self.timezone {
+            #[allow(unused_comparisons)]
+            if <str>::len(value.as_ref()) > 64usize {
+                return Err(::jacquard_lexicon::validation::ConstraintError::MaxLength {
+                    path: ::jacquard_lexicon::validation::ValidationPath::from_field(
+                        "timezone",
+                    ),
+                    max: 64usize,
+                    actual: <str>::len(value.as_ref()),
+                });
+            }
+        }
         if let Some(ref value) = self.title {
             #[allow(unused_comparisons)]
             if <str>::len(value.as_ref()) > 2560usize {
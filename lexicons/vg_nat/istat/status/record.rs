@@ -29,6 +29,14 @@ pub struct Record<'a> {
     /// Optional expiration timestamp for this status
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     pub expires: Option<jacquard_common::types::string::Datetime>,
+    /// Optional AT-URI of the status record this status replies to
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub reply_to: Option<jacquard_common::types::string::AtUri<'a>>,
+    /// Optional IANA timezone identifier for the author (e.g. 'America/Chicago'), used to render expiry times in the author's local time
+    #[serde(skip_serializing_if = "std::option::Option::is_none")]
+    #[serde(borrow)]
+    pub timezone: Option<jacquard_common::CowStr<'a>>,
     /// Optional status text title
     #[serde(skip_serializing_if = "std::option::Option::is_none")]
     #[serde(borrow)]
@@ -87,6 +95,8 @@ pub struct RecordBuilder<'a, S: record_state::State> {
         ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::types::value::Data<'a>>,
         ::core::option::Option<jacquard_common::types::string::Datetime>,
+        ::core::option::Option<jacquard_common::types::string::AtUri<'a>>,
+        ::core::option::Option<jacquard_common::CowStr<'a>>,
         ::core::option::Option<jacquard_common::CowStr<'a>>,
     ),
     _phantom: ::core::marker::PhantomData<&'a ()>,
@@ -104,7 +114,7 @@ impl<'a> RecordBuilder<'a, record_state::Empty> {
     pub fn new() -> Self {
         RecordBuilder {
             _phantom_state: ::core::marker::PhantomData,
-            __unsafe_private_named: (None, None, None, None, None),
+            __unsafe_private_named: (None, None, None, None, None, None, None),
             _phantom: ::core::marker::PhantomData,
         }
     }
@@ -186,18 +196,56 @@ impl<'a, S: record_state::State> RecordBuilder<'a, S> {
     }
 }
 
+impl<'a, S: record_state::State> RecordBuilder<'a, S> {
+    /// Set the `replyTo` field (optional)
+    pub fn reply_to(
+        mut self,
+        value: impl Into<Option<jacquard_common::types::string::AtUri<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.4 = value.into();
+        self
+    }
+    /// Set the `replyTo` field to an Option value (optional)
+    pub fn maybe_reply_to(
+        mut self,
+        value: Option<jacquard_common::types::string::AtUri<'a>>,
+    ) -> Self {
+        self.__unsafe_private_named.4 = value;
+        self
+    }
+}
+
+impl<'a, S: record_state::State> RecordBuilder<'a, S> {
+    /// Set the `timezone` field (optional)
+    pub fn timezone(
+        mut self,
+        value: impl Into<Option<jacquard_common::CowStr<'a>>>,
+    ) -> Self {
+        self.__unsafe_private_named.5 = value.into();
+        self
+    }
+    /// Set the `timezone` field to an Option value (optional)
+    pub fn maybe_timezone(
+        mut self,
+        value: Option<jacquard_common::CowStr<'a>>,
+    ) -> Self {
+        self.__unsafe_private_named.5 = value;
+        self
+    }
+}
+
 impl<'a, S: record_state::State> RecordBuilder<'a, S> {
     /// Set the `title` field (optional)
     pub fn title(
         mut self,
         value: impl Into<Option<jacquard_common::CowStr<'a>>>,
     ) -> Self {
-        self.__unsafe_private_named.4 = value.into();
+        self.__unsafe_private_named.6 = value.into();
         self
     }
     /// Set the `title` field to an Option value (optional)
     pub fn maybe_title(mut self, value: Option<jacquard_common::CowStr<'a>>) -> Self {
-        self.__unsafe_private_named.4 = value;
+        self.__unsafe_private_named.6 = value;
         self
     }
 }
@@ -215,7 +263,9 @@ where
             description: self.__unsafe_private_named.1,
             emoji: self.__unsafe_private_named.2.unwrap(),
             expires: self.__unsafe_private_named.3,
-            title: self.__unsafe_private_named.4,
+            reply_to: self.__unsafe_private_named.4,
+            timezone: self.__unsafe_private_named.5,
+            title: self.__unsafe_private_named.6,
             extra_data: Default::default(),
         }
     }
@@ -232,7 +282,9 @@ where
             description: self.__unsafe_private_named.1,
             emoji: self.__unsafe_private_named.2.unwrap(),
             expires: self.__unsafe_private_named.3,
-            title: self.__unsafe_private_named.4,
+            reply_to: self.__unsafe_private_named.4,
+            timezone: self.__unsafe_private_named.5,
+            title: self.__unsafe_private_named.6,
             extra_data: Some(extra_data),
         }
     }
@@ -342,6 +394,18 @@ impl<'a> ::jacquard_lexicon::schema::LexiconSchema for Record<'a> {
                 }
             }
         }
+        if let Some(ref value) = self.timezone {
+            #[allow(unused_comparisons)]
+            if <str>::len(value.as_ref()) > 64usize {
+                return Err(::jacquard_lexicon::validation::ConstraintError::MaxLength {
+                    path: ::jacquard_lexicon::validation::ValidationPath::from_field(
+                        "timezone",
+                    ),
+                    max: 64usize,
+                    actual: <str>::len(value.as_ref()),
+                });
+            }
+        }
         if let Some(ref value) = self.title {
             #[allow(unused_comparisons)]
             if <str>::len(value.as_ref()) > 2560usize {
@@ -477,6 +541,50 @@ fn lexicon_doc_vg_nat_istat_status_record() -> ::jacquard_lexicon::lexicon::Lexi
                                     known_values: None,
                                 }),
                             );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static(
+                                    "replyTo",
+                                ),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                    description: Some(
+                                        ::jacquard_common::CowStr::new_static(
+                                            "Optional AT-URI of the status record this status replies to",
+                                        ),
+                                    ),
+                                    format: Some(
+                                        ::jacquard_lexicon::lexicon::LexStringFormat::AtUri,
+                                    ),
+                                    default: None,
+                                    min_length: None,
+                                    max_length: None,
+                                    min_graphemes: None,
+                                    max_graphemes: None,
+                                    r#enum: None,
+                                    r#const: None,
+                                    known_values: None,
+                                }),
+                            );
+                            map.insert(
+                                ::jacquard_common::smol_str::SmolStr::new_static(
+                                    "timezone",
+                                ),
+                                ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
+                                    description: Some(
+                                        ::jacquard_common::CowStr::new_static(
+                                            "Optional IANA timezone identifier for the author (e.g. 'America/Chicago'), used to render expiry times in the author's local time",
+                                        ),
+                                    ),
+                                    format: None,
+                                    default: None,
+                                    min_length: None,
+                                    max_length: Some(64usize),
+                                    min_graphemes: None,
+                                    max_graphemes: None,
+                                    r#enum: None,
+                                    r#const: None,
+                                    known_values: None,
+                                }),
+                            );
                             map.insert(
                                 ::jacquard_common::smol_str::SmolStr::new_static("title"),
                                 ::jacquard_lexicon::lexicon::LexObjectProperty::String(::jacquard_lexicon::lexicon::LexString {
@@ -0,0 +1,5 @@
+-- Add timezone column to statuses (IANA identifier from the status record)
+ALTER TABLE statuses ADD COLUMN timezone TEXT;
+
+-- Cache the most recent timezone per author for profile views
+ALTER TABLE profiles ADD COLUMN timezone TEXT;
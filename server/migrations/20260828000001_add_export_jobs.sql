@@ -0,0 +1,13 @@
+-- Personal data export jobs (vg.nat.istat.actor.exportMyData)
+CREATE TABLE export_jobs (
+    id TEXT PRIMARY KEY,
+    did TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK(status IN ('pending', 'processing', 'complete', 'failed')),
+    download_token TEXT NOT NULL,
+    file_path TEXT,
+    error TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    completed_at DATETIME
+);
+
+CREATE INDEX idx_export_jobs_did ON export_jobs(did);
@@ -0,0 +1,39 @@
+-- Trusted peer instances we subscribe to for moderation signals
+CREATE TABLE moderation_peers (
+    url TEXT PRIMARY KEY,
+    name TEXT,
+    enabled INTEGER NOT NULL DEFAULT 1,
+    added_by TEXT NOT NULL,
+    last_synced_at DATETIME,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Hashed blacklist entries fetched from peers, with provenance
+CREATE TABLE peer_blacklist_hashes (
+    hash TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    reason TEXT,
+    source_url TEXT NOT NULL REFERENCES moderation_peers(url) ON DELETE CASCADE,
+    fetched_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (hash, source_url)
+);
+
+-- Local CIDs that matched a peer's hashed blacklist
+CREATE TABLE peer_blacklisted_cids (
+    cid TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    reason TEXT,
+    source_url TEXT NOT NULL REFERENCES moderation_peers(url) ON DELETE CASCADE,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (cid, source_url)
+);
+
+-- Union of locally blacklisted CIDs and peer-sourced matches,
+-- respecting the per-peer enabled flag
+CREATE VIEW effective_blacklisted_cids AS
+    SELECT cid, content_type FROM blacklisted_cids
+    UNION
+    SELECT pb.cid, pb.content_type
+    FROM peer_blacklisted_cids pb
+    JOIN moderation_peers mp ON pb.source_url = mp.url
+    WHERE mp.enabled = 1;
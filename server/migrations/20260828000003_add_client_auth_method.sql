@@ -0,0 +1,12 @@
+-- Track how downstream clients authenticated ("none" or "private_key_jwt")
+-- so confidential clients can be distinguished from public ones.
+
+ALTER TABLE oatproxy_pending_auths ADD COLUMN auth_method TEXT NOT NULL DEFAULT 'none';
+ALTER TABLE oatproxy_downstream_clients ADD COLUMN auth_method TEXT NOT NULL DEFAULT 'none';
+ALTER TABLE oatproxy_par_data ADD COLUMN auth_method TEXT NOT NULL DEFAULT 'none';
+
+-- Per-session record of the client auth method, keyed by upstream session
+CREATE TABLE IF NOT EXISTS oatproxy_session_auth_methods (
+    session_id TEXT PRIMARY KEY,
+    auth_method TEXT NOT NULL
+);
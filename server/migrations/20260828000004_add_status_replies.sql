@@ -0,0 +1,11 @@
+-- Optional parent status for threaded check-ins
+ALTER TABLE statuses ADD COLUMN reply_to TEXT;
+
+-- Reply edges, maintained by the ingestor for fast per-status reply counts
+CREATE TABLE IF NOT EXISTS status_replies (
+    at TEXT PRIMARY KEY NOT NULL,
+    parent_at TEXT NOT NULL,
+    created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_status_replies_parent ON status_replies(parent_at);
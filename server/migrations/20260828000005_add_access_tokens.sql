@@ -0,0 +1,13 @@
+-- Opaque downstream access tokens, issued when the proxy runs in opaque
+-- token mode instead of self-contained JWTs
+CREATE TABLE IF NOT EXISTS oatproxy_access_tokens (
+    access_token TEXT PRIMARY KEY,
+    account_did TEXT NOT NULL,
+    dpop_jkt TEXT NOT NULL,
+    scope TEXT NOT NULL,
+    issued_at TEXT NOT NULL,
+    expires_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_oatproxy_access_tokens_expires
+    ON oatproxy_access_tokens(expires_at);
@@ -0,0 +1,15 @@
+-- Picker category from the emoji record (validated against the curated set)
+ALTER TABLE emojis ADD COLUMN category TEXT;
+
+-- Admin-curated category override; wins over the record value when set
+ALTER TABLE emojis ADD COLUMN curated_category TEXT;
+
+-- Free-form tags, one row per tag, maintained by the ingestor
+CREATE TABLE IF NOT EXISTS emoji_tags (
+    emoji_at TEXT NOT NULL,
+    tag TEXT NOT NULL,
+    PRIMARY KEY (emoji_at, tag)
+);
+
+CREATE INDEX IF NOT EXISTS idx_emoji_tags_tag ON emoji_tags(tag);
+CREATE INDEX IF NOT EXISTS idx_emojis_category ON emojis(category);
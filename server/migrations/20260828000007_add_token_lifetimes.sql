@@ -0,0 +1,9 @@
+-- Record which downstream client an authorization and refresh token belong to,
+-- and give refresh tokens optional absolute/idle expiries so per-client
+-- lifetime policies can be enforced at the token endpoint.
+
+ALTER TABLE oatproxy_pending_auths ADD COLUMN client_id TEXT;
+ALTER TABLE oatproxy_downstream_clients ADD COLUMN client_id TEXT;
+ALTER TABLE oatproxy_refresh_tokens ADD COLUMN client_id TEXT;
+ALTER TABLE oatproxy_refresh_tokens ADD COLUMN absolute_expires_at TEXT;
+ALTER TABLE oatproxy_refresh_tokens ADD COLUMN idle_expires_at TEXT;
@@ -0,0 +1,6 @@
+-- Remember how each authorization's response should be delivered back to the
+-- client. Flows recorded before this column existed used the old fragment
+-- behavior, which the store preserves as the fallback.
+
+ALTER TABLE oatproxy_par_data ADD COLUMN response_mode TEXT NOT NULL DEFAULT 'fragment';
+ALTER TABLE oatproxy_downstream_clients ADD COLUMN response_mode TEXT NOT NULL DEFAULT 'fragment';
@@ -0,0 +1,5 @@
+-- Pin the upstream authorization server for each pending authorization so the
+-- callback can reject an iss from anywhere else (mix-up protection). Legacy
+-- rows stay NULL and skip the check.
+
+ALTER TABLE oatproxy_downstream_clients ADD COLUMN upstream_issuer TEXT;
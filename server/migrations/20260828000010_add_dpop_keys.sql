@@ -0,0 +1,6 @@
+-- DPoP keys stored by thumbprint, so key lookups (token refresh, key
+-- rotation) don't depend on knowing which session a key belongs to
+CREATE TABLE IF NOT EXISTS oatproxy_dpop_keys (
+    thumbprint TEXT PRIMARY KEY,
+    key_json TEXT NOT NULL
+);
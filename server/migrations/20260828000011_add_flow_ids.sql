@@ -0,0 +1,5 @@
+-- Correlation ID minted at PAR time and carried through the whole OAuth
+-- flow; NULL on rows written before the column existed
+ALTER TABLE oatproxy_par_data ADD COLUMN flow_id TEXT;
+ALTER TABLE oatproxy_downstream_clients ADD COLUMN flow_id TEXT;
+ALTER TABLE oatproxy_pending_auths ADD COLUMN flow_id TEXT;
@@ -0,0 +1,4 @@
+-- DPoP key thumbprint a refresh token was issued to, checked by the
+-- refresh grant's key-rebinding policy; NULL on rows written before the
+-- binding was recorded
+ALTER TABLE oatproxy_refresh_tokens ADD COLUMN dpop_jkt TEXT;
@@ -0,0 +1,6 @@
+-- When an upstream session row was last written, read by the retention
+-- sweep in the cleanup worker. Existing rows are backfilled to now so a
+-- freshly enabled retention policy doesn't purge sessions whose age it
+-- never observed.
+ALTER TABLE oatproxy_oauth_sessions ADD COLUMN last_used_at TEXT;
+UPDATE oatproxy_oauth_sessions SET last_used_at = datetime('now');
@@ -0,0 +1,10 @@
+-- Downstream grants per (DID, client), backing the user-facing grant
+-- listing and revocation endpoint
+CREATE TABLE IF NOT EXISTS oatproxy_grants (
+    did TEXT NOT NULL,
+    client_id TEXT NOT NULL,
+    scope TEXT,
+    created_at TEXT NOT NULL,
+    last_used_at TEXT NOT NULL,
+    PRIMARY KEY (did, client_id)
+);
@@ -0,0 +1,11 @@
+-- Progress checkpoints for the relay backfill. One row per (did,
+-- collection); cursor is the last listRecords page cursor, completed_at
+-- marks the repo as fully indexed so re-runs skip it.
+CREATE TABLE IF NOT EXISTS backfill_checkpoints (
+    did TEXT NOT NULL,
+    collection TEXT NOT NULL,
+    cursor TEXT,
+    completed_at TEXT,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (did, collection)
+);
@@ -0,0 +1,10 @@
+-- Cache of verified handle <-> DID resolutions. One row per DID;
+-- resolved_at drives the TTL, so stale rows are simply re-resolved
+-- rather than evicted.
+CREATE TABLE IF NOT EXISTS identity_cache (
+    did TEXT PRIMARY KEY,
+    handle TEXT NOT NULL,
+    resolved_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_identity_cache_handle ON identity_cache (handle);
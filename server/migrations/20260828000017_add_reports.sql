@@ -0,0 +1,18 @@
+-- User-submitted content reports. Subject is an AT-URI (emoji or status
+-- record) or a bare CID (blob). Reports move through a small lifecycle:
+-- open -> acknowledged (triaged) -> resolved or dismissed.
+CREATE TABLE reports (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    subject TEXT NOT NULL,
+    reason TEXT NOT NULL CHECK(reason IN ('nudity', 'gore', 'harassment', 'spam', 'copyright', 'other')),
+    reason_details TEXT,
+    reporter_did TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'open' CHECK(status IN ('open', 'acknowledged', 'resolved', 'dismissed')),
+    resolved_by TEXT REFERENCES admins(did),
+    resolution_notes TEXT,
+    created_at DATETIME DEFAULT CURRENT_TIMESTAMP,
+    resolved_at DATETIME
+);
+
+CREATE INDEX idx_reports_status ON reports(status);
+CREATE INDEX idx_reports_subject ON reports(subject);
@@ -0,0 +1,11 @@
+-- Local moderation takedowns of whole actors. Separate from
+-- profiles.account_status, which mirrors upstream PDS account events and
+-- gets overwritten by them; a row here hides everything the DID authored
+-- and blocks re-indexing until the actor is reinstated.
+CREATE TABLE actor_takedowns (
+    did TEXT PRIMARY KEY,
+    reason TEXT NOT NULL CHECK(reason IN ('nudity', 'gore', 'harassment', 'spam', 'copyright', 'other')),
+    reason_details TEXT,
+    moderator_did TEXT NOT NULL REFERENCES admins(did),
+    taken_down_at DATETIME DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,3 @@
+-- When the sweeper last confirmed a status still exists on its PDS.
+-- NULL means never checked; the sweeper picks those up first.
+ALTER TABLE statuses ADD COLUMN last_checked_at DATETIME;
@@ -0,0 +1,13 @@
+-- Cached follow graph for the timeline endpoint. Rows are the DIDs a
+-- user follows, refreshed wholesale from the AppView on a TTL tracked
+-- in follow_cache_meta.
+CREATE TABLE IF NOT EXISTS follow_cache (
+    did TEXT NOT NULL,
+    subject_did TEXT NOT NULL,
+    PRIMARY KEY (did, subject_did)
+);
+
+CREATE TABLE IF NOT EXISTS follow_cache_meta (
+    did TEXT PRIMARY KEY,
+    refreshed_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
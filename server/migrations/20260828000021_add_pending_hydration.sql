@@ -0,0 +1,11 @@
+-- Retry queue for profile hydration. DIDs land here when an inline
+-- hydration attempt fails, and when a profile is old enough to be
+-- re-fetched; the hydration worker drains due rows with backoff.
+CREATE TABLE IF NOT EXISTS pending_hydration (
+    did TEXT PRIMARY KEY,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    enqueued_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_pending_hydration_next ON pending_hydration(next_attempt_at);
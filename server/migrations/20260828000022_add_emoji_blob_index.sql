@@ -0,0 +1,4 @@
+-- Blob CID lookups power duplicate grouping: search collapses emojis
+-- sharing a blob under one canonical entry, and detail views surface a
+-- duplicateOf reference to it.
+CREATE INDEX IF NOT EXISTS idx_emojis_blob_cid ON emojis(blob_cid);
@@ -0,0 +1,29 @@
+-- Emoji packs: curated sets of emoji published as vg.nat.istat.moji.pack
+-- records. Membership lives in a separate table so search can answer
+-- "which packs include this emoji" with one join.
+
+CREATE TABLE IF NOT EXISTS emoji_packs (
+    at TEXT PRIMARY KEY NOT NULL,
+    did TEXT NOT NULL,
+    rkey TEXT NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    created_at TEXT NOT NULL,
+    deleted_at DATETIME,
+    deleted_by TEXT,
+    UNIQUE(did, rkey)
+);
+
+CREATE INDEX IF NOT EXISTS idx_emoji_packs_did ON emoji_packs(did);
+CREATE INDEX IF NOT EXISTS idx_emoji_packs_created_at ON emoji_packs(created_at);
+
+-- Membership rows; emoji_ref carries the at:// prefix like statuses.emoji_ref,
+-- position preserves the record's display order
+CREATE TABLE IF NOT EXISTS emoji_pack_items (
+    pack_at TEXT NOT NULL,
+    emoji_ref TEXT NOT NULL,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (pack_at, emoji_ref)
+);
+
+CREATE INDEX IF NOT EXISTS idx_emoji_pack_items_emoji ON emoji_pack_items(emoji_ref);
@@ -0,0 +1,216 @@
+//! Relay backfill: index `vg.nat.istat.*` records that predate this
+//! instance.
+//!
+//! A fresh instance only sees records created after it first connects to
+//! jetstream. The backfill walks `com.atproto.repo.listRecords` for each
+//! known DID and feeds every record through the same indexing paths the
+//! jetstream ingestors use. Progress is checkpointed per (did,
+//! collection) in `backfill_checkpoints`, so an interrupted run resumes
+//! from its last page and completed repos are skipped on re-runs.
+//!
+//! Triggered by the admin `moderation.backfill` endpoint, or at startup
+//! with `ISTAT_BACKFILL_ON_START=true`.
+
+use anyhow::Result;
+use sqlx::SqlitePool;
+
+/// Collections the backfill indexes, in order
+fn backfill_collections() -> [&'static str; 3] {
+    let registry = crate::collections::registry();
+    [
+        registry.emoji.as_str(),
+        registry.pack.as_str(),
+        registry.status.as_str(),
+    ]
+}
+
+/// DIDs worth backfilling: everyone the index has seen in any table
+async fn known_dids(db: &SqlitePool) -> Result<Vec<String>> {
+    let dids = sqlx::query_scalar(
+        "SELECT did FROM profiles UNION SELECT did FROM emojis UNION SELECT did FROM statuses",
+    )
+    .fetch_all(db)
+    .await?;
+    Ok(dids)
+}
+
+/// Page through listRecords for one DID and collection, indexing every
+/// record and checkpointing the cursor after each page. Returns the
+/// number of records indexed.
+async fn backfill_collection(
+    db: &SqlitePool,
+    did: &str,
+    pds: &str,
+    collection: &str,
+) -> Result<u64> {
+    let checkpoint: Option<(Option<String>, Option<String>)> = sqlx::query_as(
+        "SELECT cursor, completed_at FROM backfill_checkpoints WHERE did = ? AND collection = ?",
+    )
+    .bind(did)
+    .bind(collection)
+    .fetch_optional(db)
+    .await?;
+
+    let mut cursor = match checkpoint {
+        Some((_, Some(_))) => return Ok(0),
+        Some((cursor, None)) => cursor,
+        None => None,
+    };
+
+    let mut indexed = 0u64;
+    loop {
+        let mut url = format!(
+            "{}/xrpc/com.atproto.repo.listRecords?repo={}&collection={}&limit=100",
+            pds, did, collection
+        );
+        if let Some(ref c) = cursor {
+            url.push_str("&cursor=");
+            url.push_str(c);
+        }
+
+        let resp = crate::outbound::get(&url).await?;
+        if !resp.status().is_success() {
+            anyhow::bail!("listRecords for {} answered {}", did, resp.status());
+        }
+        let page: serde_json::Value = resp.json().await?;
+
+        let records = page
+            .get("records")
+            .and_then(|r| r.as_array())
+            .cloned()
+            .unwrap_or_default();
+        // One transaction per page collapses per-row commit overhead
+        let mut tx = db.begin().await?;
+        for rec in &records {
+            let Some(uri) = rec.get("uri").and_then(|u| u.as_str()) else {
+                continue;
+            };
+            let Some(rkey) = uri.rsplit('/').next() else {
+                continue;
+            };
+            let Some(value) = rec.get("value").cloned() else {
+                continue;
+            };
+
+            let result = if collection == crate::collections::registry().emoji {
+                crate::jetstream::index_emoji(&mut tx, did, rkey, value).await
+            } else if collection == crate::collections::registry().pack {
+                crate::jetstream::index_pack(&mut tx, did, rkey, value).await
+            } else {
+                crate::jetstream::index_status(&mut tx, did, rkey, value).await
+            };
+            match result {
+                Ok(()) => indexed += 1,
+                Err(e) => tracing::warn!("Backfill: failed to index {}: {}", uri, e),
+            }
+        }
+        tx.commit().await?;
+
+        cursor = page
+            .get("cursor")
+            .and_then(|c| c.as_str())
+            .map(|s| s.to_string());
+
+        sqlx::query(
+            r#"
+            INSERT INTO backfill_checkpoints (did, collection, cursor, updated_at)
+            VALUES (?, ?, ?, datetime('now'))
+            ON CONFLICT(did, collection)
+            DO UPDATE SET cursor = excluded.cursor, updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(did)
+        .bind(collection)
+        .bind(&cursor)
+        .execute(db)
+        .await?;
+
+        if cursor.is_none() || records.is_empty() {
+            break;
+        }
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO backfill_checkpoints (did, collection, cursor, completed_at, updated_at)
+        VALUES (?, ?, NULL, datetime('now'), datetime('now'))
+        ON CONFLICT(did, collection)
+        DO UPDATE SET cursor = NULL, completed_at = excluded.completed_at,
+                      updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(did)
+    .bind(collection)
+    .execute(db)
+    .await?;
+
+    Ok(indexed)
+}
+
+/// Backfill every collection for one DID
+pub(crate) async fn backfill_did(db: &SqlitePool, did: &str) -> Result<u64> {
+    let pds = crate::img::resolve_pds(did)
+        .await
+        .ok_or_else(|| anyhow::anyhow!("no PDS endpoint found for {}", did))?;
+
+    let _ = crate::jetstream::hydrate_profile(db, did).await;
+
+    let mut total = 0u64;
+    for collection in backfill_collections() {
+        total += backfill_collection(db, did, &pds, collection).await?;
+    }
+    Ok(total)
+}
+
+/// Clear checkpoints so the next run re-walks repos from the beginning
+pub(crate) async fn reset_checkpoints(db: &SqlitePool, did: Option<&str>) -> Result<()> {
+    match did {
+        Some(did) => {
+            sqlx::query("DELETE FROM backfill_checkpoints WHERE did = ?")
+                .bind(did)
+                .execute(db)
+                .await?;
+        }
+        None => {
+            sqlx::query("DELETE FROM backfill_checkpoints")
+                .execute(db)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Run a full backfill pass. `dids` limits the run to specific repos;
+/// None walks every DID the index knows about. Errors per repo are
+/// logged and don't stop the pass.
+pub async fn run_backfill(db: SqlitePool, dids: Option<Vec<String>>) {
+    let dids = match dids {
+        Some(dids) => dids,
+        None => match known_dids(&db).await {
+            Ok(dids) => dids,
+            Err(e) => {
+                tracing::warn!("Backfill: failed to list known DIDs: {}", e);
+                return;
+            }
+        },
+    };
+
+    tracing::info!("Backfill: starting pass over {} repos", dids.len());
+    let mut total = 0u64;
+    for did in &dids {
+        match backfill_did(&db, did).await {
+            Ok(n) => {
+                total += n;
+                if n > 0 {
+                    tracing::info!("Backfill: indexed {} records for {}", n, did);
+                }
+            }
+            Err(e) => tracing::warn!("Backfill: {} failed: {}", did, e),
+        }
+    }
+    tracing::info!(
+        "Backfill: pass complete, {} records indexed across {} repos",
+        total,
+        dids.len()
+    );
+}
@@ -0,0 +1,75 @@
+//! Typed registry for the record collections this instance indexes.
+//!
+//! The NSIDs default to the `vg.nat.istat` namespace; a fork can rename
+//! the whole namespace with `ISTAT_NAMESPACE` instead of chasing string
+//! literals through the ingestors and XRPC handlers. Extra lexicons
+//! (future reaction or bookmark collections, say) can be registered with
+//! [`register_ingestor`] before the jetstream starts, without editing
+//! `start_jetstream`.
+
+use rocketman::ingestion::LexiconIngestor;
+use std::sync::{Mutex, OnceLock};
+
+/// Full NSIDs of the collections the jetstream subscribes to
+pub struct CollectionRegistry {
+    pub emoji: String,
+    pub pack: String,
+    pub status: String,
+    pub profile: String,
+}
+
+impl CollectionRegistry {
+    fn from_env() -> Self {
+        let ns = std::env::var("ISTAT_NAMESPACE").unwrap_or_else(|_| "vg.nat.istat".to_string());
+        Self {
+            emoji: format!("{}.moji.emoji", ns),
+            pack: format!("{}.moji.pack", ns),
+            status: format!("{}.status.record", ns),
+            // Profiles come from Bluesky regardless of the app namespace
+            profile: "app.bsky.actor.profile".to_string(),
+        }
+    }
+
+    /// `did/collection/rkey` for an emoji, as stored in the `at` columns
+    /// (no `at://` prefix)
+    pub fn emoji_at(&self, did: &str, rkey: &str) -> String {
+        format!("{}/{}/{}", did, self.emoji, rkey)
+    }
+
+    /// `did/collection/rkey` for an emoji pack, as stored in the `at`
+    /// columns (no `at://` prefix)
+    pub fn pack_at(&self, did: &str, rkey: &str) -> String {
+        format!("{}/{}/{}", did, self.pack, rkey)
+    }
+
+    /// `did/collection/rkey` for a status, as stored in the `at` columns
+    /// (no `at://` prefix)
+    pub fn status_at(&self, did: &str, rkey: &str) -> String {
+        format!("{}/{}/{}", did, self.status, rkey)
+    }
+}
+
+/// The process-wide registry, resolved from the environment on first use
+pub fn registry() -> &'static CollectionRegistry {
+    static REGISTRY: OnceLock<CollectionRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(CollectionRegistry::from_env)
+}
+
+type BoxedIngestor = Box<dyn LexiconIngestor + Send + Sync>;
+
+/// Ingestors queued for collections beyond the built-in three
+fn extra_ingestors() -> &'static Mutex<Vec<(String, BoxedIngestor)>> {
+    static EXTRA: OnceLock<Mutex<Vec<(String, BoxedIngestor)>>> = OnceLock::new();
+    EXTRA.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// Queue an ingestor for an additional collection. Must run before the
+/// jetstream connects; registrations after that are never drained.
+pub fn register_ingestor(nsid: impl Into<String>, ingestor: BoxedIngestor) {
+    extra_ingestors().lock().unwrap().push((nsid.into(), ingestor));
+}
+
+/// Drained once by `start_jetstream` when wiring up the connection
+pub(crate) fn take_extra_ingestors() -> Vec<(String, BoxedIngestor)> {
+    std::mem::take(&mut *extra_ingestors().lock().unwrap())
+}
@@ -0,0 +1,104 @@
+//! Live status event stream.
+//!
+//! The write pipeline publishes every applied status change to a global
+//! broadcast channel; `/events/statuses` bridges that channel to SSE so
+//! the frontend can stop polling `listStatuses`. The broadcast buffer
+//! bounds per-connection memory: a consumer that falls more than
+//! `EVENT_BUFFER` events behind skips ahead instead of growing a queue.
+
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::sync::OnceLock;
+
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio_stream::{Stream, StreamExt, wrappers::BroadcastStream};
+
+use crate::AppState;
+
+/// Events buffered per connection before a slow consumer skips ahead
+const EVENT_BUFFER: usize = 256;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusEvent {
+    /// "create", "update", or "delete"
+    pub op: &'static str,
+    pub did: String,
+    pub rkey: String,
+    pub uri: String,
+}
+
+fn channel() -> &'static broadcast::Sender<StatusEvent> {
+    static CHANNEL: OnceLock<broadcast::Sender<StatusEvent>> = OnceLock::new();
+    CHANNEL.get_or_init(|| broadcast::channel(EVENT_BUFFER).0)
+}
+
+/// Publish an applied status change. A send with no subscribers is fine.
+pub fn publish(op: &'static str, did: &str, rkey: &str) {
+    let _ = channel().send(StatusEvent {
+        op,
+        did: did.to_string(),
+        rkey: rkey.to_string(),
+        uri: format!("at://{}", crate::collections::registry().status_at(did, rkey)),
+    });
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StatusEventsParams {
+    /// Only events from this DID
+    pub actor: Option<String>,
+    /// Only events from accounts the authenticated user follows
+    pub followed: Option<bool>,
+}
+
+pub async fn handle_status_events(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<StatusEventsParams>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, StatusCode> {
+    // followed-only needs auth and a fresh follow set to filter against
+    let follow_set: Option<HashSet<String>> = if params.followed.unwrap_or(false) {
+        let did = crate::xrpc::moderation::extract_authenticated_did(&headers, &state).await?;
+        crate::xrpc::timeline::ensure_follows_fresh(&state.db, &did).await?;
+
+        let follows: Vec<String> =
+            sqlx::query_scalar("SELECT subject_did FROM follow_cache WHERE did = ?")
+                .bind(&did)
+                .fetch_all(&state.db)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        Some(follows.into_iter().collect())
+    } else {
+        None
+    };
+
+    let actor = params.actor;
+    let rx = channel().subscribe();
+
+    let stream = BroadcastStream::new(rx).filter_map(move |result| {
+        // Lagged consumers just skip ahead; the next Ok resyncs them
+        let event = result.ok()?;
+
+        if let Some(ref actor) = actor {
+            if event.did != *actor {
+                return None;
+            }
+        }
+        if let Some(ref follows) = follow_set {
+            if !follows.contains(&event.did) {
+                return None;
+            }
+        }
+
+        let data = serde_json::to_string(&event).ok()?;
+        Some(Ok(Event::default().event("status").data(data)))
+    });
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
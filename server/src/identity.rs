@@ -0,0 +1,159 @@
+//! Cached handle <-> DID resolution.
+//!
+//! Read handlers used to call the public AppView on every request just to
+//! turn a handle into a DID. This module fronts resolution with a
+//! DB-backed cache (`identity_cache`) so repeat lookups stay local, and
+//! verifies resolutions bidirectionally: a handle is only accepted for a
+//! DID when that DID's document lists the handle back in `alsoKnownAs`,
+//! and a handle read from a DID document is only accepted when it
+//! resolves back to the same DID. Supports `did:plc` via plc.directory
+//! and `did:web` via `.well-known/did.json`.
+
+use sqlx::SqlitePool;
+use std::collections::BTreeMap;
+
+/// How long a cached resolution stays fresh (default: 24 hours)
+fn cache_ttl_secs() -> i64 {
+    std::env::var("ISTAT_IDENTITY_TTL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(86_400)
+}
+
+/// URL of the DID document for a `did:plc` or `did:web` DID
+fn did_doc_url(did: &str) -> String {
+    if let Some(host) = did.strip_prefix("did:web:") {
+        format!("https://{}/.well-known/did.json", host)
+    } else {
+        format!("https://plc.directory/{}", did)
+    }
+}
+
+/// Fetch a DID document and pull the handle out of `alsoKnownAs`
+async fn handle_from_did_doc(did: &str) -> Option<String> {
+    let resp = crate::outbound::get(&did_doc_url(did)).await.ok()?;
+    if !resp.status().is_success() {
+        return None;
+    }
+    let doc: serde_json::Value = resp.json().await.ok()?;
+    doc.get("alsoKnownAs")?
+        .as_array()?
+        .iter()
+        .filter_map(|v| v.as_str())
+        .find_map(|s| s.strip_prefix("at://"))
+        .map(|s| s.to_string())
+}
+
+/// Ask the public AppView to resolve a handle. Returns None when the
+/// handle doesn't resolve; Err only on transport failure.
+async fn resolve_handle_remote(handle: &str) -> Result<Option<String>, ()> {
+    let url = format!(
+        "https://public.api.bsky.app/xrpc/com.atproto.identity.resolveHandle?handle={}",
+        handle
+    );
+    let resp = crate::outbound::get(&url).await.map_err(|_| ())?;
+    if !resp.status().is_success() {
+        return Ok(None);
+    }
+    let resp_json: BTreeMap<String, String> = resp.json().await.map_err(|_| ())?;
+    Ok(resp_json.get("did").cloned())
+}
+
+/// Look up a fresh cache row by handle
+async fn cached_did(db: &SqlitePool, handle: &str) -> Option<String> {
+    sqlx::query_scalar(
+        "SELECT did FROM identity_cache WHERE handle = ? AND resolved_at > datetime('now', ?)",
+    )
+    .bind(handle)
+    .bind(format!("-{} seconds", cache_ttl_secs()))
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Look up a fresh cache row by DID
+async fn cached_handle(db: &SqlitePool, did: &str) -> Option<String> {
+    sqlx::query_scalar(
+        "SELECT handle FROM identity_cache WHERE did = ? AND resolved_at > datetime('now', ?)",
+    )
+    .bind(did)
+    .bind(format!("-{} seconds", cache_ttl_secs()))
+    .fetch_optional(db)
+    .await
+    .ok()
+    .flatten()
+}
+
+/// Record a verified resolution. Best-effort: a failed cache write only
+/// costs the next request a network round-trip.
+async fn store(db: &SqlitePool, did: &str, handle: &str) {
+    let _ = sqlx::query(
+        r#"
+        INSERT INTO identity_cache (did, handle, resolved_at)
+        VALUES (?, ?, datetime('now'))
+        ON CONFLICT(did)
+        DO UPDATE SET handle = excluded.handle, resolved_at = excluded.resolved_at
+        "#,
+    )
+    .bind(did)
+    .bind(handle)
+    .execute(db)
+    .await;
+}
+
+/// Resolve a handle to a DID, preferring the cache.
+///
+/// Fresh resolutions are verified against the DID document: when the
+/// document loads but doesn't list the handle, the resolution is
+/// rejected. A document that can't be fetched doesn't block resolution —
+/// a directory hiccup shouldn't 404 every profile page.
+pub(crate) async fn resolve_handle(db: &SqlitePool, handle: &str) -> Option<String> {
+    let handle = handle.trim().trim_start_matches('@').to_lowercase();
+
+    if let Some(did) = cached_did(db, &handle).await {
+        return Some(did);
+    }
+
+    let did = resolve_handle_remote(&handle).await.ok()??;
+
+    match handle_from_did_doc(&did).await {
+        Some(doc_handle) if !doc_handle.eq_ignore_ascii_case(&handle) => {
+            tracing::warn!(
+                "Identity: handle {} resolved to {} but its document claims {}",
+                handle, did, doc_handle
+            );
+            return None;
+        }
+        _ => {}
+    }
+
+    store(db, &did, &handle).await;
+    Some(did)
+}
+
+/// Resolve a DID to its current handle, preferring the cache.
+///
+/// The handle from the DID document is verified by resolving it forward
+/// again; a handle that doesn't point back at the same DID is dropped.
+pub(crate) async fn resolve_did(db: &SqlitePool, did: &str) -> Option<String> {
+    if let Some(handle) = cached_handle(db, did).await {
+        return Some(handle);
+    }
+
+    let handle = handle_from_did_doc(did).await?;
+
+    match resolve_handle_remote(&handle).await {
+        Ok(Some(resolved)) if resolved != did => {
+            tracing::warn!(
+                "Identity: document for {} claims handle {} but it resolves to {}",
+                did, handle, resolved
+            );
+            return None;
+        }
+        _ => {}
+    }
+
+    store(db, did, &handle).await;
+    Some(handle)
+}
@@ -0,0 +1,285 @@
+//! Local blob proxy serving emoji/avatar images at `/img/{did}/{cid}@{format}`.
+//!
+//! Read endpoints used to format URLs pointing straight at a third-party
+//! CDN, which was a single point of failure and leaked viewer IPs. This
+//! route fetches blobs from the owner's PDS via `com.atproto.sync.getBlob`,
+//! caches them on disk keyed by (did, cid), enforces the moderation
+//! blacklist, and only falls back to the external CDN when the PDS can't
+//! be reached. Blobs are content-addressed, so the cid doubles as a
+//! strong ETag and responses are immutable.
+//!
+//! `?size=64&fmt=webp` resizes and/or converts on the fly with the
+//! `image` crate. Rendered variants are cached next to the originals,
+//! bounded by rounding sizes to fixed buckets; animated GIFs (and
+//! sources the decoder can't handle, like animated WebP) pass through
+//! untouched.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+
+use crate::AppState;
+
+/// Formats this route will serve, mapped to their content types
+const IMAGE_FORMATS: &[(&str, &str)] = &[
+    ("png", "image/png"),
+    ("jpeg", "image/jpeg"),
+    ("jpg", "image/jpeg"),
+    ("webp", "image/webp"),
+    ("gif", "image/gif"),
+];
+
+/// Allowed rendered sizes. Resize requests round up to the nearest
+/// bucket so the variant cache stays bounded per blob.
+const SIZE_BUCKETS: &[u32] = &[32, 64, 128, 256, 512];
+
+#[derive(Debug, Deserialize)]
+pub struct ImgParams {
+    /// Bounding box for the longest edge, rounded up to a size bucket
+    pub size: Option<u32>,
+    /// Output format override (`png`, `jpeg`, `webp`)
+    pub fmt: Option<String>,
+}
+
+/// Map an output format name to its encoder and content type
+fn output_format(name: &str) -> Option<(image::ImageFormat, &'static str)> {
+    match name {
+        "png" => Some((image::ImageFormat::Png, "image/png")),
+        "jpeg" | "jpg" => Some((image::ImageFormat::Jpeg, "image/jpeg")),
+        "webp" => Some((image::ImageFormat::WebP, "image/webp")),
+        _ => None,
+    }
+}
+
+/// Round a requested size up to the nearest bucket (capped at the largest)
+fn size_bucket(requested: u32) -> u32 {
+    SIZE_BUCKETS
+        .iter()
+        .copied()
+        .find(|b| *b >= requested)
+        .unwrap_or(*SIZE_BUCKETS.last().unwrap())
+}
+
+/// Decode, resize, and re-encode a blob. Returns None when the source
+/// can't be decoded (e.g. animated WebP), which callers treat as
+/// passthrough of the original bytes.
+fn render_variant(bytes: &[u8], size: Option<u32>, fmt: image::ImageFormat) -> Option<Vec<u8>> {
+    let img = image::load_from_memory(bytes).ok()?;
+    let img = match size {
+        Some(s) => img.thumbnail(s, s),
+        None => img,
+    };
+    let mut out = std::io::Cursor::new(Vec::new());
+    // JPEG can't carry alpha; flatten before encoding
+    let result = if fmt == image::ImageFormat::Jpeg {
+        image::DynamicImage::ImageRgb8(img.to_rgb8()).write_to(&mut out, fmt)
+    } else {
+        img.write_to(&mut out, fmt)
+    };
+    result.ok()?;
+    Some(out.into_inner())
+}
+
+/// Directory for cached blobs (default: `img-cache` in the working dir)
+fn cache_dir() -> std::path::PathBuf {
+    std::env::var("ISTAT_IMG_CACHE_DIR")
+        .unwrap_or_else(|_| "img-cache".to_string())
+        .into()
+}
+
+/// External CDN used when the owner's PDS can't be reached
+fn cdn_base() -> String {
+    std::env::var("ISTAT_IMG_CDN_FALLBACK")
+        .unwrap_or_else(|_| "https://at.uwu.wang".to_string())
+}
+
+/// Resolve the PDS service endpoint for a DID from its DID document
+pub(crate) async fn resolve_pds(did: &str) -> Option<String> {
+    let url = if let Some(host) = did.strip_prefix("did:web:") {
+        format!("https://{}/.well-known/did.json", host)
+    } else {
+        format!("https://plc.directory/{}", did)
+    };
+    let resp = crate::outbound::get(&url).await.ok()?;
+    if !resp.status().is_success() {
+        return None;
+    }
+    let doc: serde_json::Value = resp.json().await.ok()?;
+    doc.get("service")?.as_array()?.iter().find_map(|svc| {
+        let is_pds = svc.get("id").and_then(|i| i.as_str()) == Some("#atproto_pds")
+            || svc.get("type").and_then(|t| t.as_str()) == Some("AtprotoPersonalDataServer");
+        if !is_pds {
+            return None;
+        }
+        svc.get("serviceEndpoint")?
+            .as_str()
+            .map(|s| s.trim_end_matches('/').to_string())
+    })
+}
+
+/// Fetch a blob from the owner's PDS, falling back to the external CDN
+pub(crate) async fn fetch_blob(did: &str, cid: &str, format: &str) -> Option<Vec<u8>> {
+    if let Some(pds) = resolve_pds(did).await {
+        let url = format!(
+            "{}/xrpc/com.atproto.sync.getBlob?did={}&cid={}",
+            pds, did, cid
+        );
+        if let Ok(resp) = crate::outbound::get(&url).await {
+            if resp.status().is_success() {
+                if let Ok(bytes) = resp.bytes().await {
+                    return Some(bytes.to_vec());
+                }
+            }
+        }
+    }
+
+    let url = format!(
+        "{}/{}/{}@{}",
+        cdn_base().trim_end_matches('/'),
+        did,
+        cid,
+        format
+    );
+    let resp = crate::outbound::get(&url).await.ok()?;
+    if !resp.status().is_success() {
+        return None;
+    }
+    resp.bytes().await.ok().map(|b| b.to_vec())
+}
+
+pub async fn handle_img(
+    State(state): State<AppState>,
+    Path((did, file)): Path<(String, String)>,
+    Query(params): Query<ImgParams>,
+    headers: HeaderMap,
+) -> Result<Response, StatusCode> {
+    let (cid, format) = file.rsplit_once('@').ok_or(StatusCode::BAD_REQUEST)?;
+    let content_type = IMAGE_FORMATS
+        .iter()
+        .find(|(f, _)| *f == format)
+        .map(|(_, ct)| *ct)
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    // Parse the variant request up front so the ETag covers it
+    let out_fmt = match params.fmt.as_deref() {
+        Some(name) => Some(output_format(name).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
+    let size = match params.size {
+        Some(0) => return Err(StatusCode::BAD_REQUEST),
+        Some(s) => Some(size_bucket(s)),
+        None => None,
+    };
+    // Animated GIFs pass through untouched; resizing would drop frames
+    let wants_variant = (size.is_some() || out_fmt.is_some()) && format != "gif";
+
+    // Path components come straight from the URL; keep them to the
+    // characters dids and cids actually use before touching the filesystem
+    let did_ok = did.starts_with("did:")
+        && did
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, ':' | '.' | '-' | '_' | '%'));
+    if !did_ok || cid.is_empty() || !cid.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Enforce the moderation blacklist regardless of content type
+    let blacklisted = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM effective_blacklisted_cids WHERE cid = ?)",
+    )
+    .bind(cid)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if blacklisted {
+        crate::metrics::record_blacklist_hit();
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let variant_name = out_fmt.map(|(_, ct)| ct.trim_start_matches("image/"));
+    let etag = if wants_variant {
+        format!(
+            "\"{}-{}.{}\"",
+            cid,
+            size.unwrap_or(0),
+            variant_name.unwrap_or(format)
+        )
+    } else {
+        format!("\"{}\"", cid)
+    };
+    if headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        == Some(etag.as_str())
+    {
+        return Ok(StatusCode::NOT_MODIFIED.into_response());
+    }
+
+    let dir = cache_dir();
+    let did_key = did.replace(':', "_");
+    let cache_path = dir.join(format!("{}-{}", did_key, cid));
+
+    let bytes = match tokio::fs::read(&cache_path).await {
+        Ok(bytes) => bytes,
+        Err(_) => {
+            let bytes = fetch_blob(&did, cid, format)
+                .await
+                .ok_or(StatusCode::BAD_GATEWAY)?;
+            let _ = tokio::fs::create_dir_all(&dir).await;
+            let _ = tokio::fs::write(&cache_path, &bytes).await;
+            bytes
+        }
+    };
+
+    let (bytes, content_type) = if wants_variant {
+        // Keep the original encoding unless fmt asks otherwise
+        let (fmt, out_ct) = match out_fmt {
+            Some(pair) => pair,
+            None => output_format(format).ok_or(StatusCode::BAD_REQUEST)?,
+        };
+        let variant_path = dir.join(format!(
+            "{}-{}-{}.{}",
+            did_key,
+            cid,
+            size.unwrap_or(0),
+            out_ct.trim_start_matches("image/")
+        ));
+        match tokio::fs::read(&variant_path).await {
+            Ok(rendered) => (rendered, out_ct),
+            Err(_) => {
+                let src = bytes.clone();
+                let rendered =
+                    tokio::task::spawn_blocking(move || render_variant(&src, size, fmt))
+                        .await
+                        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                match rendered {
+                    Some(rendered) => {
+                        let _ = tokio::fs::write(&variant_path, &rendered).await;
+                        (rendered, out_ct)
+                    }
+                    // Undecodable source (e.g. animated WebP): passthrough
+                    None => (bytes, content_type),
+                }
+            }
+        }
+    } else {
+        (bytes, content_type)
+    };
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, content_type.to_string()),
+            (header::ETAG, etag),
+            (
+                header::CACHE_CONTROL,
+                "public, max-age=31536000, immutable".to_string(),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
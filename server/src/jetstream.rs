@@ -1,7 +1,7 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use jacquard::types::value;
-use lexicons::vg_nat::istat::moji::emoji::Emoji;
+use lexicons::vg_nat::istat::moji::{emoji::Emoji, pack::Pack};
 
 use lexicons::{app_bsky::actor::profile::Profile, vg_nat::istat::status};
 use rocketman::{
@@ -14,11 +14,16 @@ use rocketman::{
 use serde::Deserialize;
 use serde_json::Value;
 use sqlx::SqlitePool;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::mpsc;
+use tracing::Instrument;
 
 /// Hydrates a profile from the network if it doesn't exist in the database.
 /// Returns the profile data (whether it was freshly fetched or already existed).
-async fn hydrate_profile(db: &SqlitePool, did: &str) -> Result<Option<serde_json::Value>> {
+pub(crate) async fn hydrate_profile(
+    db: &SqlitePool,
+    did: &str,
+) -> Result<Option<serde_json::Value>> {
     // Check if profile already exists
     let existing_profile: Option<String> = sqlx::query_scalar(
         "SELECT json_object('did', did, 'handle', handle, 'display_name', display_name, 'description', description, 'avatar_cid', avatar_cid, 'banner_cid', banner_cid, 'pronouns', pronouns, 'website', website, 'created_at', created_at) FROM profiles WHERE did = ?"
@@ -31,29 +36,161 @@ async fn hydrate_profile(db: &SqlitePool, did: &str) -> Result<Option<serde_json
         return Ok(serde_json::from_str(&profile_json).ok());
     }
 
-    eprintln!("Hydrating profile for {}", did);
+    // Deduplicate in-flight fetches: hydration is best-effort, so when
+    // another task is already fetching this DID we skip rather than issue
+    // a duplicate network round-trip racing on the same insert.
+    if !hydrating().lock().unwrap().insert(did.to_string()) {
+        return Ok(None);
+    }
+    let result = fetch_profile(db, did).await;
+    hydrating().lock().unwrap().remove(did);
 
-    // Fetch handle from PLC directory
-    let handle_url = format!("https://plc.directory/{}", did);
-    let handle = if let Ok(resp) = reqwest::get(&handle_url).await {
-        if resp.status().is_success() {
-            if let Ok(did_doc) = resp.json::<serde_json::Value>().await {
-                did_doc
-                    .get("alsoKnownAs")
-                    .and_then(|v| v.as_array())
-                    .and_then(|arr| arr.first())
-                    .and_then(|v| v.as_str())
-                    .and_then(|s| s.strip_prefix("at://"))
-                    .map(|s| s.to_string())
-            } else {
-                None
+    // A miss here would otherwise leave the DID unhydrated forever;
+    // queue it for the hydration worker to retry with backoff
+    if !matches!(result, Ok(Some(_))) {
+        enqueue_hydration(db, did).await;
+    }
+    result
+}
+
+/// Queue a DID for the hydration worker. Already-queued DIDs keep their
+/// attempt count and schedule.
+pub(crate) async fn enqueue_hydration(db: &SqlitePool, did: &str) {
+    let _ = sqlx::query("INSERT OR IGNORE INTO pending_hydration (did) VALUES (?)")
+        .bind(did)
+        .execute(db)
+        .await;
+}
+
+/// DIDs with a profile fetch currently in flight
+fn hydrating() -> &'static Mutex<std::collections::HashSet<String>> {
+    static HYDRATING: OnceLock<Mutex<std::collections::HashSet<String>>> = OnceLock::new();
+    HYDRATING.get_or_init(|| Mutex::new(std::collections::HashSet::new()))
+}
+
+/// Seconds between hydration worker passes (default: 30)
+fn hydration_interval_secs() -> u64 {
+    std::env::var("ISTAT_HYDRATION_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(30)
+}
+
+/// Days before a hydrated profile is queued for a refresh (default: 7)
+fn rehydrate_days() -> i64 {
+    std::env::var("ISTAT_REHYDRATE_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(7)
+}
+
+/// Attempts before the worker gives up on a DID
+const MAX_HYDRATION_ATTEMPTS: i64 = 8;
+/// Queued DIDs processed per worker pass
+const HYDRATION_BATCH: i64 = 25;
+
+/// Retry one queued DID. Success (or exhausted attempts) removes the
+/// row; failure reschedules it with exponential backoff.
+async fn process_pending_hydration(db: &SqlitePool, did: &str, attempts: i64) {
+    if !hydrating().lock().unwrap().insert(did.to_string()) {
+        return;
+    }
+    let result = fetch_profile(db, did).await;
+    hydrating().lock().unwrap().remove(did);
+
+    if matches!(result, Ok(Some(_))) {
+        let _ = sqlx::query("DELETE FROM pending_hydration WHERE did = ?")
+            .bind(did)
+            .execute(db)
+            .await;
+        return;
+    }
+
+    if attempts + 1 >= MAX_HYDRATION_ATTEMPTS {
+        tracing::warn!("Hydration: giving up on {} after {} attempts", did, attempts + 1);
+        let _ = sqlx::query("DELETE FROM pending_hydration WHERE did = ?")
+            .bind(did)
+            .execute(db)
+            .await;
+        return;
+    }
+
+    // 2min, 4min, 8min, ... capped at a day
+    let backoff_secs = (120i64 << attempts).min(86_400);
+    let _ = sqlx::query(
+        r#"
+        UPDATE pending_hydration
+        SET attempts = attempts + 1,
+            next_attempt_at = datetime('now', '+' || ? || ' seconds')
+        WHERE did = ?
+        "#,
+    )
+    .bind(backoff_secs)
+    .bind(did)
+    .execute(db)
+    .await;
+}
+
+/// Queue active profiles that haven't been refreshed in a while
+async fn enqueue_stale_profiles(db: &SqlitePool) {
+    let _ = sqlx::query(
+        r#"
+        INSERT OR IGNORE INTO pending_hydration (did)
+        SELECT did FROM profiles
+        WHERE account_status = 'active'
+          AND datetime(updated_at) < datetime('now', ?)
+        LIMIT 100
+        "#,
+    )
+    .bind(format!("-{} days", rehydrate_days()))
+    .execute(db)
+    .await;
+}
+
+/// Drain the hydration retry queue forever. Once an hour it also queues
+/// stale profiles so long-lived rows pick up handle and avatar changes.
+pub async fn run_hydration_worker(db: SqlitePool) {
+    let mut last_stale_pass = std::time::Instant::now();
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(hydration_interval_secs())).await;
+
+        if last_stale_pass.elapsed() > std::time::Duration::from_secs(3_600) {
+            enqueue_stale_profiles(&db).await;
+            last_stale_pass = std::time::Instant::now();
+        }
+
+        let due: Vec<(String, i64)> = match sqlx::query_as(
+            r#"
+            SELECT did, attempts FROM pending_hydration
+            WHERE datetime(next_attempt_at) <= datetime('now')
+            ORDER BY next_attempt_at ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(HYDRATION_BATCH)
+        .fetch_all(&db)
+        .await
+        {
+            Ok(rows) => rows,
+            Err(e) => {
+                tracing::warn!("Hydration: failed to read queue: {}", e);
+                continue;
             }
-        } else {
-            None
+        };
+
+        for (did, attempts) in due {
+            process_pending_hydration(&db, &did, attempts).await;
         }
-    } else {
-        None
-    };
+    }
+}
+
+/// Network half of [`hydrate_profile`]: resolve the handle, fetch the
+/// Bluesky profile record, and insert the row.
+async fn fetch_profile(db: &SqlitePool, did: &str) -> Result<Option<serde_json::Value>> {
+    tracing::debug!("Hydrating profile for {}", did);
+
+    // Resolve the current handle through the cached identity resolver
+    let handle = crate::identity::resolve_did(db, did).await;
 
     // Fetch profile from Bluesky API
     let profile_url = format!(
@@ -61,7 +198,7 @@ async fn hydrate_profile(db: &SqlitePool, did: &str) -> Result<Option<serde_json
         did
     );
 
-    if let Ok(resp) = reqwest::get(&profile_url).await {
+    if let Ok(resp) = crate::outbound::get(&profile_url).await {
         if resp.status().is_success() {
             if let Ok(profile_data) = resp.json::<serde_json::Value>().await {
                 if let Some(record) = profile_data.get("value") {
@@ -82,10 +219,21 @@ async fn hydrate_profile(db: &SqlitePool, did: &str) -> Result<Option<serde_json
                         .and_then(|v| v.get("$link"))
                         .and_then(|v| v.as_str());
 
+                    // Upsert so the hydration worker can also refresh
+                    // stale rows, not just fill in missing ones
                     sqlx::query(
                         r#"
                         INSERT INTO profiles (did, handle, display_name, description, avatar_cid, banner_cid, pronouns, website, created_at, updated_at, account_status, last_seen_at)
                         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 'active', ?)
+                        ON CONFLICT(did) DO UPDATE SET
+                            handle = excluded.handle,
+                            display_name = excluded.display_name,
+                            description = excluded.description,
+                            avatar_cid = excluded.avatar_cid,
+                            banner_cid = excluded.banner_cid,
+                            pronouns = excluded.pronouns,
+                            website = excluded.website,
+                            updated_at = excluded.updated_at
                         "#,
                     )
                     .bind(did)
@@ -102,7 +250,7 @@ async fn hydrate_profile(db: &SqlitePool, did: &str) -> Result<Option<serde_json
                     .execute(db)
                     .await?;
 
-                    eprintln!(
+                    tracing::info!(
                         "Hydrated profile for {} (@{})",
                         did,
                         handle.as_deref().unwrap_or("unknown")
@@ -135,253 +283,601 @@ struct StrongRef {
     cid: String,
 }
 
+/// Whether a DID has been taken down by local moderation; their records
+/// are dropped at ingest instead of indexed
+async fn actor_taken_down(conn: &mut sqlx::SqliteConnection, did: &str) -> Result<bool> {
+    let taken_down =
+        sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM actor_takedowns WHERE did = ?)")
+            .bind(did)
+            .fetch_one(conn)
+            .await?;
+    Ok(taken_down)
+}
+
 pub struct EmojiIngestor {
-    db: SqlitePool,
+    tx: mpsc::Sender<CommitJob>,
 }
 
 impl EmojiIngestor {
-    pub fn new(db: SqlitePool) -> Self {
-        Self { db }
+    pub fn new(tx: mpsc::Sender<CommitJob>) -> Self {
+        Self { tx }
     }
 }
 
 #[async_trait]
 impl LexiconIngestor for EmojiIngestor {
     async fn ingest(&self, event: Event<Value>) -> Result<()> {
+        health::record("emoji", event.time_us as i64);
         let commit = match event.commit {
             Some(c) => c,
             None => return Ok(()),
         };
 
-        let record = match commit.record {
-            Some(r) => value::from_json_value::<Emoji>(r)?,
-            None => return Ok(()),
-        };
+        self.tx
+            .send(CommitJob {
+                did: event.did,
+                rkey: commit.rkey,
+                operation: commit.operation,
+                record: commit.record,
+            })
+            .await
+            .map_err(|_| anyhow::anyhow!("emoji pipeline worker stopped"))?;
 
-        let rkey = &commit.rkey;
-        let operation = &commit.operation;
+        Ok(())
+    }
+}
 
-        match operation {
-            rocketman::types::event::Operation::Create
-            | rocketman::types::event::Operation::Update => {
-                let created_at = chrono::Utc::now().to_rfc3339();
-                let at_uri = format!("{}/vg.nat.istat.moji.emoji/{}", event.did, rkey);
+/// Index one emoji record into emojis/emoji_tags and the FTS index.
+/// Shared by the pipeline worker and the relay backfill; runs on a
+/// connection so callers can batch several records per transaction.
+/// Profile hydration is the caller's job — it can hit the network.
+pub(crate) async fn index_emoji(
+    conn: &mut sqlx::SqliteConnection,
+    did: &str,
+    rkey: &str,
+    mut raw: Value,
+) -> Result<()> {
+    if actor_taken_down(&mut *conn, did).await? {
+        return Ok(());
+    }
 
-                // Hydrate profile for this user if we don't have it
-                let _ = hydrate_profile(&self.db, &event.did).await;
+    compat::normalize_emoji(&mut raw);
+    let record = value::from_json_value::<Emoji>(raw)?;
 
-                let blob = record.emoji.blob();
-                let cid = blob.r#ref.as_str();
-                let mime_type = blob.mime_type.as_str();
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let at_uri = crate::collections::registry().emoji_at(&did, &rkey);
 
-                sqlx::query(
-                    r#"
-                    INSERT OR REPLACE INTO emojis (at, did, blob_cid, mime_type, emoji_name, alt_text, created_at)
-                    VALUES (?, ?, ?, ?, ?, ?, ?)
-                    "#,
-                )
+    let blob = record.emoji.blob();
+    let cid = blob.r#ref.as_str();
+    let mime_type = blob.mime_type.as_str();
+
+    let emoji_name = record.name.to_string();
+    let alt_text = record.alt_text.as_ref().map(|s| s.to_string());
+
+    // Only index categories from the curated set; anything else is dropped
+    let category = record
+        .category
+        .as_ref()
+        .map(|c| c.to_string())
+        .filter(|c| crate::xrpc::EMOJI_CATEGORIES.contains(&c.as_str()));
+
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO emojis (at, did, blob_cid, mime_type, emoji_name, alt_text, category, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&at_uri)
+    .bind(did)
+    .bind(cid)
+    .bind(mime_type)
+    .bind(&emoji_name)
+    .bind(&alt_text)
+    .bind(&category)
+    .bind(&created_at)
+    .execute(&mut *conn)
+    .await?;
+
+    // Replace the tag index for this emoji (normalized, deduped, capped)
+    sqlx::query("DELETE FROM emoji_tags WHERE emoji_at = ?")
+        .bind(&at_uri)
+        .execute(&mut *conn)
+        .await?;
+    let mut indexed_tags = Vec::new();
+    if let Some(tags) = &record.tags {
+        let mut seen = std::collections::HashSet::new();
+        for tag in tags.iter().take(8) {
+            let Some(tag) = crate::xrpc::normalize_emoji_tag(tag.as_ref()) else {
+                continue;
+            };
+            if !seen.insert(tag.clone()) {
+                continue;
+            }
+            sqlx::query("INSERT OR IGNORE INTO emoji_tags (emoji_at, tag) VALUES (?, ?)")
                 .bind(&at_uri)
-                .bind(&event.did)
-                .bind(cid)
-                .bind(mime_type)
-                .bind(&record.name.to_string())
-                .bind(&record.alt_text.map(|s| s.to_string()))
-                .bind(&created_at)
-                .execute(&self.db)
+                .bind(&tag)
+                .execute(&mut *conn)
                 .await?;
+            indexed_tags.push(tag);
+        }
+    }
 
-                println!(
-                    "Inserted/updated emoji: at={}, name={:?}, cid={:?}, mime={}",
-                    at_uri, record.name, cid, mime_type
-                );
-            }
-            rocketman::types::event::Operation::Delete => {
-                let at_uri = format!("{}/vg.nat.istat.moji.emoji/{}", event.did, rkey);
+    // Mirror the searchable text into the FTS index
+    update_emoji_fts(
+        conn,
+        &at_uri,
+        &emoji_name,
+        alt_text.as_deref(),
+        &indexed_tags.join(" "),
+    )
+    .await;
 
-                sqlx::query(
-                    r#"
-                    DELETE FROM emojis WHERE at = ?
-                    "#,
-                )
+    tracing::info!(
+        "Inserted/updated emoji: at={}, name={:?}, cid={:?}, mime={}",
+        at_uri, record.name, cid, mime_type
+    );
+
+    Ok(())
+}
+
+/// Apply one emoji commit on a pipeline connection
+async fn apply_emoji(conn: &mut sqlx::SqliteConnection, job: CommitJob) -> Result<()> {
+    match job.operation {
+        rocketman::types::event::Operation::Create
+        | rocketman::types::event::Operation::Update => {
+            let record = match job.record {
+                Some(r) => r,
+                None => return Ok(()),
+            };
+            index_emoji(conn, &job.did, &job.rkey, record).await?;
+        }
+        rocketman::types::event::Operation::Delete => {
+            let at_uri = crate::collections::registry().emoji_at(&job.did, &job.rkey);
+
+            sqlx::query(
+                r#"
+                DELETE FROM emojis WHERE at = ?
+                "#,
+            )
+            .bind(&at_uri)
+            .execute(&mut *conn)
+            .await?;
+
+            sqlx::query("DELETE FROM emoji_tags WHERE emoji_at = ?")
                 .bind(&at_uri)
-                .execute(&self.db)
+                .execute(&mut *conn)
                 .await?;
 
-                println!("Deleted emoji: at={}", at_uri);
-            }
+            delete_emoji_fts(conn, &at_uri).await;
+
+            tracing::info!("Deleted emoji: at={}", at_uri);
         }
+    }
+
+    Ok(())
+}
+
+/// Best-effort refresh of the emoji_fts row for one emoji. The virtual
+/// table only exists when the SQLite build ships FTS5, so failures are
+/// logged and swallowed — search falls back to LIKE scans without it.
+pub(crate) async fn update_emoji_fts(
+    conn: &mut sqlx::SqliteConnection,
+    at: &str,
+    name: &str,
+    alt_text: Option<&str>,
+    tags: &str,
+) {
+    let _ = sqlx::query("DELETE FROM emoji_fts WHERE at = ?")
+        .bind(at)
+        .execute(&mut *conn)
+        .await;
+    if let Err(e) =
+        sqlx::query("INSERT INTO emoji_fts (at, emoji_name, alt_text, tags) VALUES (?, ?, ?, ?)")
+            .bind(at)
+            .bind(name)
+            .bind(alt_text.unwrap_or(""))
+            .bind(tags)
+            .execute(&mut *conn)
+            .await
+    {
+        tracing::debug!("emoji FTS index unavailable: {}", e);
+    }
+}
+
+/// Best-effort removal of an emoji from the FTS index; see [`update_emoji_fts`].
+async fn delete_emoji_fts(conn: &mut sqlx::SqliteConnection, at: &str) {
+    let _ = sqlx::query("DELETE FROM emoji_fts WHERE at = ?")
+        .bind(at)
+        .execute(conn)
+        .await;
+}
+
+pub struct PackIngestor {
+    tx: mpsc::Sender<CommitJob>,
+}
+
+impl PackIngestor {
+    pub fn new(tx: mpsc::Sender<CommitJob>) -> Self {
+        Self { tx }
+    }
+}
+
+#[async_trait]
+impl LexiconIngestor for PackIngestor {
+    async fn ingest(&self, event: Event<Value>) -> Result<()> {
+        health::record("pack", event.time_us as i64);
+        let commit = match event.commit {
+            Some(c) => c,
+            None => return Ok(()),
+        };
+
+        self.tx
+            .send(CommitJob {
+                did: event.did,
+                rkey: commit.rkey,
+                operation: commit.operation,
+                record: commit.record,
+            })
+            .await
+            .map_err(|_| anyhow::anyhow!("pack pipeline worker stopped"))?;
 
         Ok(())
     }
 }
 
+/// Index one pack record into emoji_packs/emoji_pack_items. Shared by
+/// the pipeline worker and the optimistic index after createPack.
+/// Membership rows are replaced wholesale; only valid emoji at-uris are
+/// kept, deduped, in record order.
+pub(crate) async fn index_pack(
+    conn: &mut sqlx::SqliteConnection,
+    did: &str,
+    rkey: &str,
+    raw: Value,
+) -> Result<()> {
+    if actor_taken_down(&mut *conn, did).await? {
+        return Ok(());
+    }
+
+    let record = value::from_json_value::<Pack>(raw)?;
+
+    let registry = crate::collections::registry();
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let at_uri = registry.pack_at(did, rkey);
+
+    let name = record.name.to_string();
+    let description = record.description.as_ref().map(|s| s.to_string());
+
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO emoji_packs (at, did, rkey, name, description, created_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&at_uri)
+    .bind(did)
+    .bind(rkey)
+    .bind(&name)
+    .bind(&description)
+    .bind(&created_at)
+    .execute(&mut *conn)
+    .await?;
+
+    sqlx::query("DELETE FROM emoji_pack_items WHERE pack_at = ?")
+        .bind(&at_uri)
+        .execute(&mut *conn)
+        .await?;
+
+    let emoji_path = format!("/{}/", registry.emoji);
+    let mut position = 0i64;
+    let mut seen = std::collections::HashSet::new();
+    for uri in record.emojis.iter().take(100) {
+        let uri = uri.as_str();
+        if !uri.starts_with("at://") || !uri.contains(&emoji_path) {
+            continue;
+        }
+        if !seen.insert(uri.to_string()) {
+            continue;
+        }
+        sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO emoji_pack_items (pack_at, emoji_ref, position)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(&at_uri)
+        .bind(uri)
+        .bind(position)
+        .execute(&mut *conn)
+        .await?;
+        position += 1;
+    }
+
+    tracing::info!(
+        "Inserted/updated emoji pack: at={}, name={:?}, emojis={}",
+        at_uri, name, position
+    );
+
+    Ok(())
+}
+
+/// Apply one pack commit on a pipeline connection
+async fn apply_pack(conn: &mut sqlx::SqliteConnection, job: CommitJob) -> Result<()> {
+    match job.operation {
+        rocketman::types::event::Operation::Create
+        | rocketman::types::event::Operation::Update => {
+            let record = match job.record {
+                Some(r) => r,
+                None => return Ok(()),
+            };
+            index_pack(conn, &job.did, &job.rkey, record).await?;
+        }
+        rocketman::types::event::Operation::Delete => {
+            let at_uri = crate::collections::registry().pack_at(&job.did, &job.rkey);
+
+            sqlx::query("DELETE FROM emoji_packs WHERE at = ?")
+                .bind(&at_uri)
+                .execute(&mut *conn)
+                .await?;
+
+            sqlx::query("DELETE FROM emoji_pack_items WHERE pack_at = ?")
+                .bind(&at_uri)
+                .execute(&mut *conn)
+                .await?;
+
+            tracing::info!("Deleted emoji pack: at={}", at_uri);
+        }
+    }
+
+    Ok(())
+}
+
 pub struct StatusIngestor {
-    db: SqlitePool,
+    tx: mpsc::Sender<CommitJob>,
 }
 
 impl StatusIngestor {
-    pub fn new(db: SqlitePool) -> Self {
-        Self { db }
+    pub fn new(tx: mpsc::Sender<CommitJob>) -> Self {
+        Self { tx }
     }
 }
 
 #[async_trait]
 impl LexiconIngestor for StatusIngestor {
     async fn ingest(&self, event: Event<Value>) -> Result<()> {
+        health::record("status", event.time_us as i64);
         let commit = match event.commit {
             Some(c) => c,
             None => return Ok(()),
         };
 
-        let rkey = &commit.rkey;
-        let operation = &commit.operation;
+        self.tx
+            .send(CommitJob {
+                did: event.did,
+                rkey: commit.rkey,
+                operation: commit.operation,
+                record: commit.record,
+            })
+            .await
+            .map_err(|_| anyhow::anyhow!("status pipeline worker stopped"))?;
 
-        match operation {
-            rocketman::types::event::Operation::Create
-            | rocketman::types::event::Operation::Update => {
-                let record = value::from_json_value::<status::record::Record>(
-                    commit
-                        .record
-                        .ok_or_else(|| anyhow::anyhow!("Missing record"))?,
-                )?;
-                let at_uri = format!("{}/vg.nat.istat.status.record/{}", event.did, rkey);
+        Ok(())
+    }
+}
 
-                // Hydrate profile for this user if we don't have it
-                let _ = hydrate_profile(&self.db, &event.did).await;
+/// Index one status record into statuses/status_replies. Shared by the
+/// pipeline worker and the relay backfill; runs on a connection so
+/// callers can batch several records per transaction. Profile hydration
+/// is the caller's job — it can hit the network.
+pub(crate) async fn index_status(
+    conn: &mut sqlx::SqliteConnection,
+    did: &str,
+    rkey: &str,
+    mut raw: Value,
+) -> Result<()> {
+    if actor_taken_down(&mut *conn, did).await? {
+        return Ok(());
+    }
 
-                // Extract uri and cid from the emoji strongRef (which is a Data type)
-                // Deserialize Data as StrongRef
-                let emoji_ref: StrongRef = value::from_data(&record.emoji)?;
+    compat::normalize_status(&mut raw);
+    let record = value::from_json_value::<status::record::Record>(raw)?;
+    let at_uri = crate::collections::registry().status_at(&did, &rkey);
 
-                sqlx::query(
-                    r#"
-                    INSERT OR REPLACE INTO statuses (at, did, rkey, emoji_ref, emoji_ref_cid, title, description, expires, created_at)
-                    VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
-                    "#,
-                )
+    // Extract uri and cid from the emoji strongRef (which is a Data type)
+    // Deserialize Data as StrongRef
+    let emoji_ref: StrongRef = value::from_data(&record.emoji)?;
+
+    sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO statuses (at, did, rkey, emoji_ref, emoji_ref_cid, title, description, expires, timezone, reply_to, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&at_uri)
+    .bind(did)
+    .bind(rkey)
+    .bind(&emoji_ref.uri)
+    .bind(&emoji_ref.cid)
+    .bind(&record.title.as_ref().map(|s| s.as_ref()))
+    .bind(&record.description.as_ref().map(|s| s.as_ref()))
+    .bind(&record.expires.as_ref().map(|dt| dt.as_str()))
+    .bind(&record.timezone.as_ref().map(|s| s.as_ref()))
+    .bind(&record.reply_to.as_ref().map(|u| u.as_str()))
+    .bind(record.created_at.as_str())
+    .execute(&mut *conn)
+    .await?;
+
+    // Maintain the reply edge for fast per-status reply counts.
+    // An update can also remove replyTo, so delete the stale edge.
+    match record.reply_to.as_ref() {
+        Some(parent) => {
+            sqlx::query(
+                r#"
+                INSERT OR REPLACE INTO status_replies (at, parent_at, created_at)
+                VALUES (?, ?, ?)
+                "#,
+            )
+            .bind(&at_uri)
+            .bind(parent.as_str())
+            .bind(record.created_at.as_str())
+            .execute(&mut *conn)
+            .await?;
+        }
+        None => {
+            sqlx::query("DELETE FROM status_replies WHERE at = ?")
                 .bind(&at_uri)
-                .bind(&event.did)
-                .bind(rkey)
-                .bind(&emoji_ref.uri)
-                .bind(&emoji_ref.cid)
-                .bind(&record.title.as_ref().map(|s| s.as_ref()))
-                .bind(&record.description.as_ref().map(|s| s.as_ref()))
-                .bind(&record.expires.as_ref().map(|dt| dt.as_str()))
-                .bind(record.created_at.as_str())
-                .execute(&self.db)
+                .execute(&mut *conn)
                 .await?;
+        }
+    }
 
-                println!(
-                    "Inserted/updated status: at={}, emoji={}",
-                    at_uri, emoji_ref.uri
-                );
-            }
-            rocketman::types::event::Operation::Delete => {
-                let at_uri = format!("{}/vg.nat.istat.status.record/{}", event.did, rkey);
+    // Remember the author's most recent timezone on their profile
+    if let Some(tz) = record.timezone.as_ref() {
+        sqlx::query("UPDATE profiles SET timezone = ? WHERE did = ?")
+            .bind(tz.as_ref())
+            .bind(did)
+            .execute(&mut *conn)
+            .await?;
+    }
 
-                sqlx::query(
-                    r#"
-                    DELETE FROM statuses WHERE at = ?
-                    "#,
-                )
+    tracing::info!(
+        "Inserted/updated status: at={}, emoji={}",
+        at_uri, emoji_ref.uri
+    );
+
+    Ok(())
+}
+
+/// Apply one status commit on a pipeline connection
+async fn apply_status(conn: &mut sqlx::SqliteConnection, job: CommitJob) -> Result<()> {
+    match job.operation {
+        rocketman::types::event::Operation::Create
+        | rocketman::types::event::Operation::Update => {
+            let record = job.record.ok_or_else(|| anyhow::anyhow!("Missing record"))?;
+            index_status(conn, &job.did, &job.rkey, record).await?;
+        }
+        rocketman::types::event::Operation::Delete => {
+            let at_uri = crate::collections::registry().status_at(&job.did, &job.rkey);
+
+            sqlx::query(
+                r#"
+                DELETE FROM statuses WHERE at = ?
+                "#,
+            )
+            .bind(&at_uri)
+            .execute(&mut *conn)
+            .await?;
+
+            sqlx::query("DELETE FROM status_replies WHERE at = ?")
                 .bind(&at_uri)
-                .execute(&self.db)
+                .execute(&mut *conn)
                 .await?;
 
-                println!("Deleted status: at={}", at_uri);
-            }
+            tracing::info!("Deleted status: at={}", at_uri);
         }
-
-        Ok(())
     }
+
+    Ok(())
 }
 
 pub struct ProfileIngestor {
-    db: SqlitePool,
+    tx: mpsc::Sender<CommitJob>,
 }
 
 impl ProfileIngestor {
-    pub fn new(db: SqlitePool) -> Self {
-        Self { db }
+    pub fn new(tx: mpsc::Sender<CommitJob>) -> Self {
+        Self { tx }
     }
 }
 
 #[async_trait]
 impl LexiconIngestor for ProfileIngestor {
     async fn ingest(&self, event: Event<Value>) -> Result<()> {
+        health::record("profile", event.time_us as i64);
         let commit = match event.commit {
             Some(c) => c,
             None => return Ok(()),
         };
 
-        let operation = &commit.operation;
+        self.tx
+            .send(CommitJob {
+                did: event.did,
+                rkey: commit.rkey,
+                operation: commit.operation,
+                record: commit.record,
+            })
+            .await
+            .map_err(|_| anyhow::anyhow!("profile pipeline worker stopped"))?;
 
-        match operation {
-            rocketman::types::event::Operation::Create
-            | rocketman::types::event::Operation::Update => {
-                let record: Profile = value::from_json_value::<Profile>(
-                    commit
-                        .record
-                        .ok_or_else(|| anyhow::anyhow!("Missing record"))?,
-                )?;
+        Ok(())
+    }
+}
 
-                let updated_at = chrono::Utc::now().to_rfc3339();
+/// Apply one profile commit on a pipeline connection
+async fn apply_profile(conn: &mut sqlx::SqliteConnection, job: CommitJob) -> Result<()> {
+    match job.operation {
+        rocketman::types::event::Operation::Create
+        | rocketman::types::event::Operation::Update => {
+            let record: Profile = value::from_json_value::<Profile>(
+                job.record.ok_or_else(|| anyhow::anyhow!("Missing record"))?,
+            )?;
 
-                // Only update profiles that already exist in the database
-                let result = sqlx::query(
-                    r#"
-                    UPDATE profiles
-                    SET display_name = ?,
-                        description = ?,
-                        avatar_cid = ?,
-                        banner_cid = ?,
-                        pronouns = ?,
-                        website = ?,
-                        created_at = COALESCE(?, created_at),
-                        updated_at = ?,
-                        last_seen_at = ?
-                    WHERE did = ?
-                    "#,
-                )
-                .bind(record.display_name.as_ref().map(|s| s.as_ref()))
-                .bind(record.description.as_ref().map(|s| s.as_ref()))
-                .bind(record.avatar.as_ref().map(|b| b.blob().r#ref.as_str()))
-                .bind(record.banner.as_ref().map(|b| b.blob().r#ref.as_str()))
-                .bind(record.pronouns.as_ref().map(|s| s.as_ref()))
-                .bind(record.website.as_ref().map(|u| u.as_str()))
-                .bind(record.created_at.as_ref().map(|dt| dt.as_str()))
-                .bind(&updated_at)
-                .bind(&updated_at)
-                .bind(&event.did)
-                .execute(&self.db)
-                .await?;
+            let updated_at = chrono::Utc::now().to_rfc3339();
 
-                if result.rows_affected() > 0 {
-                    println!("Updated profile: did={}", event.did);
-                }
-            }
-            rocketman::types::event::Operation::Delete => {
-                // Mark as deleted instead of removing
-                let now = chrono::Utc::now().to_rfc3339();
-                sqlx::query(
-                    r#"
-                    UPDATE profiles
-                    SET account_status = 'deleted',
-                        account_status_updated_at = ?
-                    WHERE did = ?
-                    "#,
-                )
-                .bind(&now)
-                .bind(&event.did)
-                .execute(&self.db)
-                .await?;
+            // Only update profiles that already exist in the database
+            let result = sqlx::query(
+                r#"
+                UPDATE profiles
+                SET display_name = ?,
+                    description = ?,
+                    avatar_cid = ?,
+                    banner_cid = ?,
+                    pronouns = ?,
+                    website = ?,
+                    created_at = COALESCE(?, created_at),
+                    updated_at = ?,
+                    last_seen_at = ?
+                WHERE did = ?
+                "#,
+            )
+            .bind(record.display_name.as_ref().map(|s| s.as_ref()))
+            .bind(record.description.as_ref().map(|s| s.as_ref()))
+            .bind(record.avatar.as_ref().map(|b| b.blob().r#ref.as_str()))
+            .bind(record.banner.as_ref().map(|b| b.blob().r#ref.as_str()))
+            .bind(record.pronouns.as_ref().map(|s| s.as_ref()))
+            .bind(record.website.as_ref().map(|u| u.as_str()))
+            .bind(record.created_at.as_ref().map(|dt| dt.as_str()))
+            .bind(&updated_at)
+            .bind(&updated_at)
+            .bind(&job.did)
+            .execute(&mut *conn)
+            .await?;
 
-                println!("Marked profile as deleted: did={}", event.did);
+            if result.rows_affected() > 0 {
+                tracing::info!("Updated profile: did={}", job.did);
             }
         }
+        rocketman::types::event::Operation::Delete => {
+            // Mark as deleted instead of removing
+            let now = chrono::Utc::now().to_rfc3339();
+            sqlx::query(
+                r#"
+                UPDATE profiles
+                SET account_status = 'deleted',
+                    account_status_updated_at = ?
+                WHERE did = ?
+                "#,
+            )
+            .bind(&now)
+            .bind(&job.did)
+            .execute(&mut *conn)
+            .await?;
 
-        Ok(())
+            tracing::info!("Marked profile as deleted: did={}", job.did);
+        }
     }
+
+    Ok(())
 }
 
 pub struct IdentityIngestor {
@@ -397,6 +893,7 @@ impl IdentityIngestor {
 #[async_trait]
 impl LexiconIngestor for IdentityIngestor {
     async fn ingest(&self, event: Event<Value>) -> Result<()> {
+        health::record("identity", event.time_us as i64);
         let now = chrono::Utc::now().to_rfc3339();
 
         // Handle identity events (handle changes)
@@ -422,7 +919,7 @@ impl LexiconIngestor for IdentityIngestor {
                 .await?;
 
                 if result.rows_affected() > 0 {
-                    println!("Updated handle for did={}: {}", did, handle);
+                    tracing::info!("Updated handle for did={}: {}", did, handle);
                 }
             }
         }
@@ -444,6 +941,7 @@ impl AccountIngestor {
 #[async_trait]
 impl LexiconIngestor for AccountIngestor {
     async fn ingest(&self, event: Event<Value>) -> Result<()> {
+        health::record("account", event.time_us as i64);
         let now = chrono::Utc::now().to_rfc3339();
 
         // Handle account status events (active/inactive/deleted/suspended)
@@ -481,7 +979,7 @@ impl LexiconIngestor for AccountIngestor {
             .await?;
 
             if result.rows_affected() > 0 {
-                println!("Updated account status for did={}: {}", did, account_status);
+                tracing::info!("Updated account status for did={}: {}", did, account_status);
             }
         }
 
@@ -489,35 +987,357 @@ impl LexiconIngestor for AccountIngestor {
     }
 }
 
+/// One commit detached from its jetstream event so it can cross a
+/// pipeline channel
+pub struct CommitJob {
+    did: String,
+    rkey: String,
+    operation: rocketman::types::event::Operation,
+    record: Option<Value>,
+}
+
+/// How many jobs a pipeline channel buffers before ingestors block
+const PIPELINE_QUEUE: usize = 512;
+/// Upper bound on jobs applied per transaction
+const PIPELINE_BATCH: usize = 64;
+
+#[derive(Clone, Copy)]
+enum Collection {
+    Emoji,
+    Pack,
+    Status,
+    Profile,
+}
+
+/// Write pipeline for jetstream ingestion.
+///
+/// Ingestors only parse events and enqueue [`CommitJob`]s; one worker per
+/// collection drains its queue and applies each drained batch in a single
+/// transaction, so a burst in one collection neither stalls the others
+/// nor pays per-row commit overhead. The bounded channels push back on
+/// the jetstream handler when workers fall behind.
+struct IngestPipeline {
+    emoji_tx: mpsc::Sender<CommitJob>,
+    pack_tx: mpsc::Sender<CommitJob>,
+    status_tx: mpsc::Sender<CommitJob>,
+    profile_tx: mpsc::Sender<CommitJob>,
+}
+
+impl IngestPipeline {
+    /// Spawn one worker per collection. A worker exits when every
+    /// ingestor holding its sender is dropped, so supervisor restarts
+    /// don't leak tasks.
+    fn start(db: &SqlitePool) -> Self {
+        let (emoji_tx, emoji_rx) = mpsc::channel(PIPELINE_QUEUE);
+        let (pack_tx, pack_rx) = mpsc::channel(PIPELINE_QUEUE);
+        let (status_tx, status_rx) = mpsc::channel(PIPELINE_QUEUE);
+        let (profile_tx, profile_rx) = mpsc::channel(PIPELINE_QUEUE);
+        tokio::spawn(run_worker(db.clone(), emoji_rx, Collection::Emoji));
+        tokio::spawn(run_worker(db.clone(), pack_rx, Collection::Pack));
+        tokio::spawn(run_worker(db.clone(), status_rx, Collection::Status));
+        tokio::spawn(run_worker(db.clone(), profile_rx, Collection::Profile));
+        Self {
+            emoji_tx,
+            pack_tx,
+            status_tx,
+            profile_tx,
+        }
+    }
+}
+
+/// Drain one collection's queue forever, batching whatever is already
+/// queued behind each received job
+async fn run_worker(db: SqlitePool, mut rx: mpsc::Receiver<CommitJob>, collection: Collection) {
+    while let Some(first) = rx.recv().await {
+        let mut batch = vec![first];
+        while batch.len() < PIPELINE_BATCH {
+            match rx.try_recv() {
+                Ok(job) => batch.push(job),
+                Err(_) => break,
+            }
+        }
+
+        let len = batch.len();
+        if let Err(e) = apply_batch(&db, collection, batch).await {
+            tracing::error!("Pipeline: failed to apply batch of {}: {}", len, e);
+        }
+    }
+}
+
+/// Apply one batch inside a single transaction. Profiles the batch
+/// references are hydrated first, outside the transaction, since that
+/// can hit the network. A record that fails to index is logged and
+/// skipped so it doesn't drop the rest of the batch.
+async fn apply_batch(db: &SqlitePool, collection: Collection, batch: Vec<CommitJob>) -> Result<()> {
+    if matches!(
+        collection,
+        Collection::Emoji | Collection::Pack | Collection::Status
+    ) {
+        let mut seen = std::collections::HashSet::new();
+        for job in &batch {
+            let is_write = !matches!(job.operation, rocketman::types::event::Operation::Delete);
+            if is_write && seen.insert(job.did.clone()) {
+                let _ = hydrate_profile(db, &job.did).await;
+            }
+        }
+    }
+
+    let collection_name = match collection {
+        Collection::Emoji => "emoji",
+        Collection::Pack => "pack",
+        Collection::Status => "status",
+        Collection::Profile => "profile",
+    };
+
+    let started = std::time::Instant::now();
+    let mut tx = db.begin().await?;
+    let mut applied = 0u64;
+    let mut applied_statuses = Vec::new();
+    for job in batch {
+        let op = match job.operation {
+            rocketman::types::event::Operation::Create => "create",
+            rocketman::types::event::Operation::Update => "update",
+            rocketman::types::event::Operation::Delete => "delete",
+        };
+        let (did, rkey) = (job.did.clone(), job.rkey.clone());
+        let span = tracing::info_span!(
+            "apply_commit",
+            collection = collection_name,
+            operation = op,
+            did = %did,
+            rkey = %rkey,
+        );
+
+        let result = match collection {
+            Collection::Emoji => apply_emoji(&mut *tx, job).instrument(span).await,
+            Collection::Pack => apply_pack(&mut *tx, job).instrument(span).await,
+            Collection::Status => apply_status(&mut *tx, job).instrument(span).await,
+            Collection::Profile => apply_profile(&mut *tx, job).instrument(span).await,
+        };
+        match result {
+            Ok(()) => {
+                applied += 1;
+                if matches!(collection, Collection::Status) {
+                    applied_statuses.push((op, did, rkey));
+                }
+            }
+            Err(e) => tracing::warn!("Pipeline: failed to index record: {}", e),
+        }
+    }
+    tx.commit().await?;
+
+    crate::metrics::record_db_batch(started.elapsed().as_secs_f64());
+    crate::metrics::record_rows_ingested(collection_name, applied);
+
+    // Broadcast to live SSE consumers only once the batch is committed
+    for (op, did, rkey) in applied_statuses {
+        crate::events::publish(op, &did, &rkey);
+    }
+
+    Ok(())
+}
+
+/// Ingestion liveness and throughput counters, read by `/healthz`.
+///
+/// Updated lock-free from the ingest path. Events/sec comes from a
+/// one-minute tumbling window, and lag compares wall time against the
+/// event's `time_us` so a stalled or far-behind stream is visible.
+pub mod health {
+    use serde_json::json;
+    use std::sync::atomic::{AtomicBool, AtomicI64, AtomicU64, Ordering};
+
+    static CONNECTED: AtomicBool = AtomicBool::new(false);
+    static RECONNECTS: AtomicU64 = AtomicU64::new(0);
+    static EVENTS_TOTAL: AtomicU64 = AtomicU64::new(0);
+    static EMOJI_EVENTS: AtomicU64 = AtomicU64::new(0);
+    static PACK_EVENTS: AtomicU64 = AtomicU64::new(0);
+    static STATUS_EVENTS: AtomicU64 = AtomicU64::new(0);
+    static PROFILE_EVENTS: AtomicU64 = AtomicU64::new(0);
+    static IDENTITY_EVENTS: AtomicU64 = AtomicU64::new(0);
+    static ACCOUNT_EVENTS: AtomicU64 = AtomicU64::new(0);
+    static LAST_EVENT_MS: AtomicI64 = AtomicI64::new(0);
+    static LAG_US: AtomicI64 = AtomicI64::new(0);
+    static WINDOW_MINUTE: AtomicI64 = AtomicI64::new(0);
+    static WINDOW_COUNT: AtomicU64 = AtomicU64::new(0);
+    static PREV_WINDOW_COUNT: AtomicU64 = AtomicU64::new(0);
+
+    pub(super) fn set_connected(connected: bool) {
+        CONNECTED.store(connected, Ordering::Relaxed);
+        if !connected {
+            RECONNECTS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub(super) fn record(collection: &str, time_us: i64) {
+        EVENTS_TOTAL.fetch_add(1, Ordering::Relaxed);
+        let counter = match collection {
+            "emoji" => &EMOJI_EVENTS,
+            "pack" => &PACK_EVENTS,
+            "status" => &STATUS_EVENTS,
+            "profile" => &PROFILE_EVENTS,
+            "identity" => &IDENTITY_EVENTS,
+            _ => &ACCOUNT_EVENTS,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        LAST_EVENT_MS.store(now_ms, Ordering::Relaxed);
+        if time_us > 0 {
+            LAG_US.store((now_ms * 1000).saturating_sub(time_us), Ordering::Relaxed);
+        }
+
+        let minute = now_ms / 60_000;
+        if WINDOW_MINUTE.swap(minute, Ordering::Relaxed) != minute {
+            PREV_WINDOW_COUNT.store(WINDOW_COUNT.swap(0, Ordering::Relaxed), Ordering::Relaxed);
+        }
+        WINDOW_COUNT.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Snapshot for the health endpoint
+    pub fn snapshot() -> serde_json::Value {
+        let connected = CONNECTED.load(Ordering::Relaxed);
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let last_ms = LAST_EVENT_MS.load(Ordering::Relaxed);
+        let last_event_age_secs = if last_ms == 0 {
+            serde_json::Value::Null
+        } else {
+            json!((now_ms - last_ms) / 1000)
+        };
+
+        json!({
+            "status": if connected { "ok" } else { "degraded" },
+            "jetstream": {
+                "connected": connected,
+                "reconnects": RECONNECTS.load(Ordering::Relaxed),
+                "events_total": EVENTS_TOTAL.load(Ordering::Relaxed),
+                "events_per_sec": PREV_WINDOW_COUNT.load(Ordering::Relaxed) as f64 / 60.0,
+                "events_by_collection": {
+                    "emoji": EMOJI_EVENTS.load(Ordering::Relaxed),
+                    "pack": PACK_EVENTS.load(Ordering::Relaxed),
+                    "status": STATUS_EVENTS.load(Ordering::Relaxed),
+                    "profile": PROFILE_EVENTS.load(Ordering::Relaxed),
+                    "identity": IDENTITY_EVENTS.load(Ordering::Relaxed),
+                    "account": ACCOUNT_EVENTS.load(Ordering::Relaxed),
+                },
+                "last_event_age_secs": last_event_age_secs,
+                "lag_secs": LAG_US.load(Ordering::Relaxed) as f64 / 1_000_000.0,
+            }
+        })
+    }
+
+    /// Append jetstream series in the Prometheus exposition format
+    pub fn prometheus(out: &mut String) {
+        out.push_str(
+            "# HELP istat_jetstream_connected Whether the jetstream consumer is connected\n",
+        );
+        out.push_str("# TYPE istat_jetstream_connected gauge\n");
+        out.push_str(&format!(
+            "istat_jetstream_connected {}\n",
+            CONNECTED.load(Ordering::Relaxed) as u8
+        ));
+
+        out.push_str("# HELP istat_jetstream_reconnects_total Jetstream reconnect count\n");
+        out.push_str("# TYPE istat_jetstream_reconnects_total counter\n");
+        out.push_str(&format!(
+            "istat_jetstream_reconnects_total {}\n",
+            RECONNECTS.load(Ordering::Relaxed)
+        ));
+
+        out.push_str("# HELP istat_jetstream_lag_seconds Wall time behind the newest event\n");
+        out.push_str("# TYPE istat_jetstream_lag_seconds gauge\n");
+        out.push_str(&format!(
+            "istat_jetstream_lag_seconds {}\n",
+            LAG_US.load(Ordering::Relaxed) as f64 / 1_000_000.0
+        ));
+
+        out.push_str("# HELP istat_jetstream_events_total Events observed by collection\n");
+        out.push_str("# TYPE istat_jetstream_events_total counter\n");
+        for (collection, counter) in [
+            ("emoji", &EMOJI_EVENTS),
+            ("pack", &PACK_EVENTS),
+            ("status", &STATUS_EVENTS),
+            ("profile", &PROFILE_EVENTS),
+            ("identity", &IDENTITY_EVENTS),
+            ("account", &ACCOUNT_EVENTS),
+        ] {
+            out.push_str(&format!(
+                "istat_jetstream_events_total{{collection=\"{}\"}} {}\n",
+                collection,
+                counter.load(Ordering::Relaxed)
+            ));
+        }
+    }
+}
+
+/// Run jetstream ingestion forever, reconnecting with exponential
+/// backoff when the stream errors or ends. A connection that survives a
+/// minute resets the backoff.
+pub async fn supervise_jetstream(db: SqlitePool) {
+    let mut backoff_secs = 1u64;
+    loop {
+        let started = std::time::Instant::now();
+        health::set_connected(true);
+
+        match start_jetstream(db.clone()).await {
+            Ok(()) => tracing::warn!("Jetstream stream ended; reconnecting"),
+            Err(e) => tracing::error!("Jetstream error: {}", e),
+        }
+        health::set_connected(false);
+
+        if started.elapsed() > std::time::Duration::from_secs(60) {
+            backoff_secs = 1;
+        }
+        tracing::info!("Reconnecting to jetstream in {}s", backoff_secs);
+        tokio::time::sleep(std::time::Duration::from_secs(backoff_secs)).await;
+        backoff_secs = (backoff_secs * 2).min(60);
+    }
+}
+
 pub async fn start_jetstream(db: SqlitePool) -> Result<()> {
+    let registry = crate::collections::registry();
+    let extra = crate::collections::take_extra_ingestors();
+
+    let mut wanted = vec![
+        registry.profile.clone(),
+        registry.emoji.clone(),
+        registry.pack.clone(),
+        registry.status.clone(),
+    ];
+    wanted.extend(extra.iter().map(|(nsid, _)| nsid.clone()));
+
     let opts = JetstreamOptions::builder()
         .ws_url(rocketman::endpoints::JetstreamEndpoints::Public(
             rocketman::endpoints::JetstreamEndpointLocations::UsEast,
             1,
         ))
-        .wanted_collections(vec![
-            "app.bsky.actor.profile".to_string(),
-            "vg.nat.istat.moji.emoji".to_string(),
-            "vg.nat.istat.status.record".to_string(),
-        ])
+        .wanted_collections(wanted)
         .bound(8 * 8 * 8 * 8 * 8 * 8) // 262144
         .build();
 
     let jetstream = JetstreamConnection::new(opts);
 
+    let pipeline = IngestPipeline::start(&db);
+
     let mut ingestors: Ingestors = Ingestors::new();
     ingestors.commits.insert(
-        "vg.nat.istat.moji.emoji".to_string(),
-        Box::new(EmojiIngestor::new(db.clone())),
+        registry.emoji.clone(),
+        Box::new(EmojiIngestor::new(pipeline.emoji_tx)),
+    );
+    ingestors.commits.insert(
+        registry.pack.clone(),
+        Box::new(PackIngestor::new(pipeline.pack_tx)),
     );
     ingestors.commits.insert(
-        "vg.nat.istat.status.record".to_string(),
-        Box::new(StatusIngestor::new(db.clone())),
+        registry.status.clone(),
+        Box::new(StatusIngestor::new(pipeline.status_tx)),
     );
     ingestors.commits.insert(
-        "app.bsky.actor.profile".to_string(),
-        Box::new(ProfileIngestor::new(db.clone())),
+        registry.profile.clone(),
+        Box::new(ProfileIngestor::new(pipeline.profile_tx)),
     );
+    for (nsid, ingestor) in extra {
+        ingestors.commits.insert(nsid, ingestor);
+    }
     ingestors.identity = Some(Box::new(IdentityIngestor::new(db.clone())));
     ingestors.account = Some(Box::new(AccountIngestor::new(db)));
 
@@ -533,15 +1353,81 @@ pub async fn start_jetstream(db: SqlitePool) -> Result<()> {
                 handler::handle_message(message, &ingestors, reconnect_tx.clone(), c_cursor.clone())
                     .await
             {
-                eprintln!("Error processing message: {}", e);
+                tracing::error!("Error processing message: {}", e);
             }
         }
     });
 
     if let Err(e) = jetstream.connect(cursor.clone()).await {
-        eprintln!("Failed to connect to Jetstream: {}", e);
+        tracing::error!("Failed to connect to Jetstream: {}", e);
         return Err(anyhow::anyhow!("Jetstream connection failed"));
     }
 
     Ok(())
 }
+
+/// Versioned compatibility shims for older record shapes.
+///
+/// Records written before a lexicon rename still exist in repos and show up
+/// during backfill. Rather than failing deserialization (and silently
+/// dropping the record), we rewrite legacy JSON into the current shape and
+/// count how often each legacy version is seen.
+mod compat {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static STATUS_V0: AtomicU64 = AtomicU64::new(0);
+    static EMOJI_V0: AtomicU64 = AtomicU64::new(0);
+
+    fn bump(counter: &AtomicU64, what: &str) {
+        let total = counter.fetch_add(1, Ordering::Relaxed) + 1;
+        tracing::info!("Normalized legacy {} record (total: {})", what, total);
+    }
+
+    /// Rename a field in place if the current name is absent.
+    /// Returns true if a rename happened.
+    fn rename_field(
+        obj: &mut serde_json::Map<String, serde_json::Value>,
+        old: &str,
+        new: &str,
+    ) -> bool {
+        if obj.contains_key(new) {
+            return false;
+        }
+        match obj.remove(old) {
+            Some(value) => {
+                obj.insert(new.to_string(), value);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Normalize legacy status record shapes.
+    ///
+    /// v0 records predate two renames: `text` became `title` and
+    /// `expiresAt` became `expires`.
+    pub fn normalize_status(record: &mut serde_json::Value) {
+        let Some(obj) = record.as_object_mut() else {
+            return;
+        };
+
+        let mut legacy = false;
+        legacy |= rename_field(obj, "text", "title");
+        legacy |= rename_field(obj, "expiresAt", "expires");
+
+        if legacy {
+            bump(&STATUS_V0, "status");
+        }
+    }
+
+    /// Normalize legacy emoji record shapes (`alt` became `altText`).
+    pub fn normalize_emoji(record: &mut serde_json::Value) {
+        let Some(obj) = record.as_object_mut() else {
+            return;
+        };
+
+        if rename_field(obj, "alt", "altText") {
+            bump(&EMOJI_V0, "emoji");
+        }
+    }
+}
@@ -12,8 +12,8 @@ use lexicons::vg_nat::istat::{
     actor::get_profile::GetProfileRequest,
     moji::search_emoji::SearchEmojiRequest,
     status::{
-        get_status::GetStatusRequest, list_statuses::ListStatusesRequest,
-        list_user_statuses::ListUserStatusesRequest,
+        get_status::GetStatusRequest, list_replies::ListRepliesRequest,
+        list_statuses::ListStatusesRequest, list_user_statuses::ListUserStatusesRequest,
     },
 };
 use miette::{IntoDiagnostic, Result};
@@ -24,8 +24,18 @@ use tower::ServiceExt;
 use tower_http::cors::CorsLayer;
 use tower_http::services::ServeDir;
 
+mod backfill;
+mod collections;
+mod events;
+mod identity;
+mod img;
 mod jetstream;
+mod metrics;
 mod oatproxy;
+mod og;
+mod outbound;
+mod ratelimit;
+mod sweeper;
 mod xrpc;
 
 #[derive(Clone)]
@@ -50,6 +60,10 @@ struct ClientMetadata {
     dpop_bound_access_tokens: bool,
 }
 
+async fn handle_healthz() -> Json<serde_json::Value> {
+    Json(jetstream::health::snapshot())
+}
+
 async fn handle_client_metadata(State(state): State<AppState>) -> Json<ClientMetadata> {
     let base_url = &state.public_url;
     Json(ClientMetadata {
@@ -110,21 +124,62 @@ async fn init_db(db_url: &str) -> Result<SqlitePool> {
         .await
         .into_diagnostic()?;
 
+    // The emoji search index lives outside the migration chain because FTS5
+    // is an optional SQLite feature; without it, search falls back to LIKE.
+    match sqlx::query(
+        r#"
+        CREATE VIRTUAL TABLE IF NOT EXISTS emoji_fts
+        USING fts5(at UNINDEXED, emoji_name, alt_text, tags, tokenize = 'unicode61')
+        "#,
+    )
+    .execute(&pool)
+    .await
+    {
+        Ok(_) => {
+            // Backfill rows the jetstream ingestor hasn't touched yet
+            let _ = sqlx::query(
+                r#"
+                INSERT INTO emoji_fts (at, emoji_name, alt_text, tags)
+                SELECT e.at, COALESCE(e.emoji_name, ''), COALESCE(e.alt_text, ''),
+                       COALESCE((SELECT group_concat(t.tag, ' ')
+                                 FROM emoji_tags t WHERE t.emoji_at = e.at), '')
+                FROM emojis e
+                WHERE e.at NOT IN (SELECT at FROM emoji_fts)
+                "#,
+            )
+            .execute(&pool)
+            .await;
+        }
+        Err(e) => {
+            tracing::warn!("FTS5 unavailable; emoji search will use LIKE scans: {}", e);
+        }
+    }
+
     Ok(pool)
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::filter::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-                "simple_server=debug,jacquard_oauth_proxy=debug,info"
-                    .parse()
-                    .unwrap()
-            }),
-        )
-        .init();
+    // Initialize tracing. ISTAT_LOG_JSON=1 switches to newline-delimited
+    // JSON for log shippers; the filter is the usual RUST_LOG syntax.
+    let env_filter = || {
+        tracing_subscriber::filter::EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+            "simple_server=debug,jacquard_oauth_proxy=debug,info"
+                .parse()
+                .unwrap()
+        })
+    };
+    let log_json = std::env::var("ISTAT_LOG_JSON")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+    if log_json {
+        tracing_subscriber::fmt()
+            .with_env_filter(env_filter())
+            .json()
+            .init();
+    } else {
+        tracing_subscriber::fmt().with_env_filter(env_filter()).init();
+    }
 
     let db_url = std::env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:istat.db".to_string());
     let public_url =
@@ -136,11 +191,25 @@ async fn main() -> Result<()> {
 
     let pool = init_db(&db_url).await?;
 
+    // Supervisor reconnects with backoff if the stream drops or errors
     let jetstream_pool = pool.clone();
+    tokio::spawn(jetstream::supervise_jetstream(jetstream_pool));
+
+    // One-shot relay backfill of records that predate this instance;
+    // resumable, so re-running after an interrupt is cheap
+    if std::env::var("ISTAT_BACKFILL_ON_START").unwrap_or_default() == "true" {
+        tokio::spawn(backfill::run_backfill(pool.clone(), None));
+    }
+
+    // Periodically prune expired statuses and reconcile old rows
+    // against their PDS
+    tokio::spawn(sweeper::run_sweeper(pool.clone()));
+    tokio::spawn(jetstream::run_hydration_worker(pool.clone()));
+
+    // Periodically pull moderation signals from trusted peers
+    let peer_sync_pool = pool.clone();
     tokio::spawn(async move {
-        if let Err(e) = jetstream::start_jetstream(jetstream_pool).await {
-            eprintln!("Jetstream error: {}", e);
-        }
+        xrpc::federation::start_peer_sync(peer_sync_pool).await;
     });
 
     // Set up OAuth proxy
@@ -229,13 +298,61 @@ async fn main() -> Result<()> {
         }
     }
 
+    // Service clients for the client_credentials grant
+    // Format: "client_id:client_secret:did" entries separated by commas
+    if let Ok(service_clients) = std::env::var("ISTAT_SERVICE_CLIENTS") {
+        for entry in service_clients.split(',').filter(|s| !s.trim().is_empty()) {
+            let parts: Vec<&str> = entry.trim().splitn(3, ':').collect();
+            if parts.len() == 3 {
+                proxy_config =
+                    proxy_config.with_service_client(parts[0], parts[1], parts[2]);
+            } else {
+                tracing::warn!("ignoring malformed ISTAT_SERVICE_CLIENTS entry");
+            }
+        }
+    }
+
+    // Allowlists for getServiceAuth minting, comma-separated
+    if let Ok(auds) = std::env::var("ISTAT_SERVICE_AUTH_AUDS") {
+        proxy_config = proxy_config.with_service_auth_allowed_auds(
+            auds.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+        );
+    }
+
+    if let Ok(lxms) = std::env::var("ISTAT_SERVICE_AUTH_LXMS") {
+        proxy_config = proxy_config.with_service_auth_allowed_lxms(
+            lxms.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+        );
+    }
+
+    // Service DIDs eligible for direct atproto-proxy routing, comma-separated
+    if let Ok(dids) = std::env::var("ISTAT_ATPROTO_PROXY_DIDS") {
+        proxy_config = proxy_config.with_atproto_proxy_allowed_dids(
+            dids.split(',')
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+                .collect(),
+        );
+    }
+
     let oatproxy_server = jacquard_oatproxy::OAuthProxyServer::builder()
         .config(proxy_config)
         .session_store(oatproxy_store.clone())
         .key_store(oatproxy_store.clone())
-        .build()
+        .build_async()
+        .await
         .into_diagnostic()?;
 
+    // Sweeps expired refresh tokens and, when a retention policy is
+    // configured, idle upstream sessions
+    oatproxy_server.spawn_cleanup_worker();
+
     let token_manager = Arc::new(jacquard_oatproxy::TokenManager::new(public_url.clone()));
 
     let state = AppState {
@@ -250,14 +367,40 @@ async fn main() -> Result<()> {
             "/client-metadata.json",
             axum::routing::get(handle_client_metadata),
         )
-        .merge(ResolveHandleRequest::into_router(xrpc::handle_resolve))
-        .merge(GetProfileRequest::into_router(xrpc::handle_get_profile))
-        .merge(SearchEmojiRequest::into_router(xrpc::handle_search_emoji))
-        .merge(GetStatusRequest::into_router(xrpc::handle_get_status))
-        .merge(ListUserStatusesRequest::into_router(
-            xrpc::handle_list_user_statuses,
-        ))
-        .merge(ListStatusesRequest::into_router(xrpc::handle_list_statuses))
+        .route("/healthz", axum::routing::get(handle_healthz))
+        .route("/metrics", axum::routing::get(metrics::handle_metrics))
+        .route(
+            "/events/statuses",
+            axum::routing::get(events::handle_status_events),
+        )
+        .route(
+            "/og/{handle}/{rkey}",
+            axum::routing::get(og::handle_og_page),
+        )
+        .route(
+            "/og/{handle}/{rkey}/card.svg",
+            axum::routing::get(og::handle_og_card),
+        )
+        .route("/oembed", axum::routing::get(og::handle_oembed))
+        // Public read endpoints get per-IP token buckets; search is the
+        // most expensive, so it has its own (tighter) limit
+        .merge(
+            Router::new()
+                .merge(SearchEmojiRequest::into_router(xrpc::handle_search_emoji))
+                .route_layer(axum::middleware::from_fn(ratelimit::limit_search)),
+        )
+        .merge(
+            Router::new()
+                .merge(ResolveHandleRequest::into_router(xrpc::handle_resolve))
+                .merge(GetProfileRequest::into_router(xrpc::handle_get_profile))
+                .merge(GetStatusRequest::into_router(xrpc::handle_get_status))
+                .merge(ListUserStatusesRequest::into_router(
+                    xrpc::handle_list_user_statuses,
+                ))
+                .merge(ListStatusesRequest::into_router(xrpc::handle_list_statuses))
+                .merge(ListRepliesRequest::into_router(xrpc::handle_list_replies))
+                .route_layer(axum::middleware::from_fn(ratelimit::limit_list)),
+        )
         // Moderation endpoints
         .route(
             "/xrpc/vg.nat.istat.moderation.blacklistCid",
@@ -279,14 +422,149 @@ async fn main() -> Result<()> {
             "/xrpc/vg.nat.istat.moderation.isAdmin",
             axum::routing::get(xrpc::moderation::handle_is_admin),
         )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.addAdmin",
+            axum::routing::post(xrpc::moderation::handle_add_admin),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.removeAdmin",
+            axum::routing::post(xrpc::moderation::handle_remove_admin),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.listAdmins",
+            axum::routing::get(xrpc::moderation::handle_list_admins),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.createReport",
+            axum::routing::post(xrpc::moderation::handle_create_report),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.listReports",
+            axum::routing::get(xrpc::moderation::handle_list_reports),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.resolveReport",
+            axum::routing::post(xrpc::moderation::handle_resolve_report),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.takedownActor",
+            axum::routing::post(xrpc::moderation::handle_takedown_actor),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.reinstateActor",
+            axum::routing::post(xrpc::moderation::handle_reinstate_actor),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.bulkAction",
+            axum::routing::post(xrpc::moderation::handle_bulk_action),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.restoreEmoji",
+            axum::routing::post(xrpc::moderation::handle_restore_emoji),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.restoreStatus",
+            axum::routing::post(xrpc::moderation::handle_restore_status),
+        )
         .route(
             "/xrpc/vg.nat.istat.moji.deleteEmoji",
             axum::routing::post(xrpc::moderation::handle_delete_emoji),
         )
+        .route(
+            "/xrpc/vg.nat.istat.moji.setEmojiCategory",
+            axum::routing::post(xrpc::moderation::handle_set_emoji_category),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moji.getEmoji",
+            axum::routing::get(xrpc::emoji::handle_get_emoji),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moji.listUserEmoji",
+            axum::routing::get(xrpc::emoji::handle_list_user_emoji),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.backfill",
+            axum::routing::post(xrpc::moderation::handle_backfill),
+        )
         .route(
             "/xrpc/vg.nat.istat.status.deleteStatus",
             axum::routing::post(xrpc::moderation::handle_delete_status),
         )
+        .route(
+            "/xrpc/vg.nat.istat.status.getTimeline",
+            axum::routing::get(xrpc::timeline::handle_get_timeline),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.status.createStatus",
+            axum::routing::post(xrpc::publish::handle_create_status),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moji.uploadEmoji",
+            axum::routing::post(xrpc::publish::handle_upload_emoji),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moji.createPack",
+            axum::routing::post(xrpc::publish::handle_create_pack),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moji.getPack",
+            axum::routing::get(xrpc::pack::handle_get_pack),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moji.listPacks",
+            axum::routing::get(xrpc::pack::handle_list_packs),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.status.renewStatus",
+            axum::routing::post(xrpc::status::handle_renew_status),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.status.endStatus",
+            axum::routing::post(xrpc::status::handle_end_status),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.actor.exportMyData",
+            axum::routing::post(xrpc::export::handle_export_my_data),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.actor.getExport",
+            axum::routing::get(xrpc::export::handle_get_export),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.actor.downloadExport",
+            axum::routing::get(xrpc::export::handle_download_export),
+        )
+        // Local blob proxy with disk cache and CDN fallback
+        .route("/img/{did}/{file}", axum::routing::get(img::handle_img))
+        // WebFinger handle discovery
+        .route(
+            "/.well-known/webfinger",
+            axum::routing::get(xrpc::federation::handle_webfinger),
+        )
+        // Moderation signal sharing
+        .route(
+            "/.well-known/istat-blacklist.json",
+            axum::routing::get(xrpc::federation::handle_wellknown_blacklist),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.addPeer",
+            axum::routing::post(xrpc::federation::handle_add_peer),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.removePeer",
+            axum::routing::post(xrpc::federation::handle_remove_peer),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.setPeerEnabled",
+            axum::routing::post(xrpc::federation::handle_set_peer_enabled),
+        )
+        .route(
+            "/xrpc/vg.nat.istat.moderation.listPeers",
+            axum::routing::get(xrpc::federation::handle_list_peers),
+        )
+        // Latency/status accounting for every matched route, including
+        // the rate limiter's 429s
+        .layer(axum::middleware::from_fn(metrics::track_http))
         .with_state(state.clone());
 
     let dev_mode = std::env::var("DEV_MODE").unwrap_or_default() == "true";
@@ -296,11 +574,13 @@ async fn main() -> Result<()> {
         // Frontend disabled - only serve API and OAuth endpoints
         tracing::info!("Frontend disabled - serving only API and OAuth endpoints");
 
+        // Permissive CORS covers only the app's own XRPC routes; the
+        // oatproxy router applies its own per-endpoint CORS policy
         Router::new()
             .merge(xrpc_router)
+            .layer(CorsLayer::permissive())
             .with_state(state.clone())
             .fallback_service(oatproxy_server.router())
-            .layer(CorsLayer::permissive())
     } else if dev_mode {
         // In dev mode, proxy non-API requests to Vite dev server
         tracing::info!("Running in dev mode - proxying to Vite at localhost:3001");
@@ -352,9 +632,9 @@ async fn main() -> Result<()> {
 
         Router::new()
             .merge(xrpc_router)
+            .layer(CorsLayer::permissive())
             .with_state(state.clone())
             .fallback_service(oatproxy_server.router().fallback(vite_proxy))
-            .layer(CorsLayer::permissive())
     } else {
         // In prod mode, serve static files from dist directory (SPA mode)
         tracing::info!("Running in production mode - serving static files from dist/ (SPA mode)");
@@ -389,9 +669,9 @@ async fn main() -> Result<()> {
 
         Router::new()
             .merge(xrpc_router)
+            .layer(CorsLayer::permissive())
             .with_state(state.clone())
             .fallback_service(oatproxy_server.router().fallback(spa_fallback))
-            .layer(CorsLayer::permissive())
     };
 
     let listener = tokio::net::TcpListener::bind(&bind_addr)
@@ -402,6 +682,12 @@ async fn main() -> Result<()> {
         listener.local_addr().into_diagnostic()?
     );
 
-    axum::serve(listener, app).await.unwrap();
+    // Serve with connect info so the oatproxy can build X-Forwarded-For
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<std::net::SocketAddr>(),
+    )
+    .await
+    .unwrap();
     Ok(())
 }
@@ -0,0 +1,193 @@
+//! Prometheus metrics for the server, served at `/metrics`.
+//!
+//! Hand-rolled on atomics and a couple of small maps rather than a
+//! metrics crate: the set of series is fixed and tiny, and the health
+//! module already established the pattern. HTTP latency is recorded per
+//! matched route (never the raw path, to keep cardinality bounded), DB
+//! write timing comes from the ingest pipeline's batch transactions,
+//! and the jetstream series are rendered by the health module from the
+//! counters it already maintains. Set `ISTAT_METRICS_TOKEN` to require
+//! `Authorization: Bearer <token>` on scrapes.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use axum::{
+    extract::{MatchedPath, Request},
+    http::{HeaderMap, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+static BLACKLIST_HITS: AtomicU64 = AtomicU64::new(0);
+static ROWS_EMOJI: AtomicU64 = AtomicU64::new(0);
+static ROWS_STATUS: AtomicU64 = AtomicU64::new(0);
+static ROWS_PROFILE: AtomicU64 = AtomicU64::new(0);
+static DB_BATCH_COUNT: AtomicU64 = AtomicU64::new(0);
+static DB_BATCH_MICROS: AtomicU64 = AtomicU64::new(0);
+
+/// Request counts keyed by (route, status)
+fn http_counts() -> &'static Mutex<HashMap<(String, u16), u64>> {
+    static COUNTS: OnceLock<Mutex<HashMap<(String, u16), u64>>> = OnceLock::new();
+    COUNTS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Latency (count, total seconds) keyed by route
+fn http_durations() -> &'static Mutex<HashMap<String, (u64, f64)>> {
+    static DURATIONS: OnceLock<Mutex<HashMap<String, (u64, f64)>>> = OnceLock::new();
+    DURATIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Record one HTTP request against its matched route
+fn record_http(route: &str, status: u16, secs: f64) {
+    let mut counts = http_counts().lock().unwrap();
+    *counts.entry((route.to_string(), status)).or_insert(0) += 1;
+    drop(counts);
+
+    let mut durations = http_durations().lock().unwrap();
+    let entry = durations.entry(route.to_string()).or_insert((0, 0.0));
+    entry.0 += 1;
+    entry.1 += secs;
+}
+
+/// Record one ingest pipeline write transaction
+pub fn record_db_batch(secs: f64) {
+    DB_BATCH_COUNT.fetch_add(1, Ordering::Relaxed);
+    DB_BATCH_MICROS.fetch_add((secs * 1_000_000.0) as u64, Ordering::Relaxed);
+}
+
+/// Record rows successfully applied for a collection
+pub fn record_rows_ingested(collection: &str, rows: u64) {
+    let counter = match collection {
+        "emoji" => &ROWS_EMOJI,
+        "status" => &ROWS_STATUS,
+        _ => &ROWS_PROFILE,
+    };
+    counter.fetch_add(rows, Ordering::Relaxed);
+}
+
+/// Record a request blocked by the moderation blacklist
+pub fn record_blacklist_hit() {
+    BLACKLIST_HITS.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Middleware recording latency and status for every matched route
+pub async fn track_http(req: Request, next: Next) -> Response {
+    // Only the matched route template goes into labels; raw paths would
+    // make the series set unbounded
+    let route = req
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|p| p.as_str().to_string())
+        .unwrap_or_else(|| "unmatched".to_string());
+
+    let started = Instant::now();
+    let response = next.run(req).await;
+    record_http(
+        &route,
+        response.status().as_u16(),
+        started.elapsed().as_secs_f64(),
+    );
+    response
+}
+
+/// Escape a label value per the Prometheus exposition format
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Render everything in the text exposition format
+fn render() -> String {
+    let mut out = String::new();
+
+    out.push_str("# HELP istat_http_requests_total HTTP requests by route and status\n");
+    out.push_str("# TYPE istat_http_requests_total counter\n");
+    for ((route, status), count) in http_counts().lock().unwrap().iter() {
+        out.push_str(&format!(
+            "istat_http_requests_total{{route=\"{}\",status=\"{}\"}} {}\n",
+            escape_label(route),
+            status,
+            count
+        ));
+    }
+
+    out.push_str("# HELP istat_http_request_duration_seconds HTTP request latency by route\n");
+    out.push_str("# TYPE istat_http_request_duration_seconds summary\n");
+    for (route, (count, sum)) in http_durations().lock().unwrap().iter() {
+        let route = escape_label(route);
+        out.push_str(&format!(
+            "istat_http_request_duration_seconds_count{{route=\"{}\"}} {}\n",
+            route, count
+        ));
+        out.push_str(&format!(
+            "istat_http_request_duration_seconds_sum{{route=\"{}\"}} {}\n",
+            route, sum
+        ));
+    }
+
+    out.push_str("# HELP istat_db_batch_duration_seconds Ingest pipeline write transaction time\n");
+    out.push_str("# TYPE istat_db_batch_duration_seconds summary\n");
+    out.push_str(&format!(
+        "istat_db_batch_duration_seconds_count {}\n",
+        DB_BATCH_COUNT.load(Ordering::Relaxed)
+    ));
+    out.push_str(&format!(
+        "istat_db_batch_duration_seconds_sum {}\n",
+        DB_BATCH_MICROS.load(Ordering::Relaxed) as f64 / 1_000_000.0
+    ));
+
+    out.push_str("# HELP istat_rows_ingested_total Rows applied by the ingest pipeline\n");
+    out.push_str("# TYPE istat_rows_ingested_total counter\n");
+    for (collection, counter) in [
+        ("emoji", &ROWS_EMOJI),
+        ("status", &ROWS_STATUS),
+        ("profile", &ROWS_PROFILE),
+    ] {
+        out.push_str(&format!(
+            "istat_rows_ingested_total{{collection=\"{}\"}} {}\n",
+            collection,
+            counter.load(Ordering::Relaxed)
+        ));
+    }
+
+    out.push_str("# HELP istat_blacklist_hits_total Requests blocked by the moderation blacklist\n");
+    out.push_str("# TYPE istat_blacklist_hits_total counter\n");
+    out.push_str(&format!(
+        "istat_blacklist_hits_total {}\n",
+        BLACKLIST_HITS.load(Ordering::Relaxed)
+    ));
+
+    crate::jetstream::health::prometheus(&mut out);
+
+    out
+}
+
+pub async fn handle_metrics(headers: HeaderMap) -> Result<Response, StatusCode> {
+    if let Ok(token) = std::env::var("ISTAT_METRICS_TOKEN") {
+        if !token.is_empty() {
+            let authorized = headers
+                .get(header::AUTHORIZATION)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.strip_prefix("Bearer "))
+                == Some(token.as_str());
+            if !authorized {
+                return Err(StatusCode::UNAUTHORIZED);
+            }
+        }
+    }
+
+    Ok((
+        StatusCode::OK,
+        [(
+            header::CONTENT_TYPE,
+            "text/plain; version=0.0.4".to_string(),
+        )],
+        render(),
+    )
+        .into_response())
+}
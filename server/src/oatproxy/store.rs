@@ -2,8 +2,12 @@ use async_trait::async_trait;
 use jacquard_common::IntoStatic;
 use jacquard_oatproxy::{
     error::Result as OatResult,
+    migrate::{ExportEntry, ExportableStore},
     session::SessionId,
-    store::{DownstreamClientInfo, KeyStore, OAuthSessionStore, PARData, PendingAuth},
+    store::{
+        AccessTokenData, DownstreamClientInfo, GrantData, KeyStore, OAuthSessionStore, PARData,
+        PendingAuth, RefreshTokenData,
+    },
 };
 use p256::ecdsa::SigningKey;
 use rand::rngs::OsRng;
@@ -75,15 +79,18 @@ impl OAuthSessionStore for SqliteStore {
     async fn store_pending_auth(&self, code: &str, auth: PendingAuth) -> OatResult<()> {
         sqlx::query(
             r#"
-            INSERT INTO oatproxy_pending_auths (code, account_did, upstream_session_id, redirect_uri, state, expires_at)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO oatproxy_pending_auths (code, account_did, upstream_session_id, client_id, redirect_uri, state, auth_method, flow_id, expires_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(code)
         .bind(&auth.account_did)
         .bind(&auth.upstream_session_id)
+        .bind(&auth.client_id)
         .bind(&auth.redirect_uri)
         .bind(&auth.state)
+        .bind(&auth.auth_method)
+        .bind(&auth.flow_id)
         .bind(auth.expires_at.to_rfc3339())
         .execute(&self.db)
         .await
@@ -95,7 +102,7 @@ impl OAuthSessionStore for SqliteStore {
     async fn consume_pending_auth(&self, code: &str) -> OatResult<Option<PendingAuth>> {
         let row = sqlx::query(
             r#"
-            SELECT account_did, upstream_session_id, redirect_uri, state, expires_at
+            SELECT account_did, upstream_session_id, client_id, redirect_uri, state, auth_method, flow_id, expires_at
             FROM oatproxy_pending_auths
             WHERE code = ?
             "#,
@@ -123,6 +130,9 @@ impl OAuthSessionStore for SqliteStore {
                 .try_get("redirect_uri")
                 .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
             let state: Option<String> = row.try_get("state").ok();
+            let auth_method: String = row
+                .try_get("auth_method")
+                .unwrap_or_else(|_| "none".to_string());
             let expires_at: String = row
                 .try_get("expires_at")
                 .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
@@ -131,11 +141,17 @@ impl OAuthSessionStore for SqliteStore {
                 .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?
                 .with_timezone(&chrono::Utc);
 
+            let client_id: Option<String> = row.try_get("client_id").ok();
+            let flow_id: Option<String> = row.try_get("flow_id").ok();
+
             Ok(Some(PendingAuth {
                 account_did,
                 upstream_session_id,
+                client_id,
                 redirect_uri,
                 state,
+                auth_method,
+                flow_id,
                 expires_at,
             }))
         } else {
@@ -150,21 +166,31 @@ impl OAuthSessionStore for SqliteStore {
     ) -> OatResult<()> {
         sqlx::query(
             r#"
-            INSERT INTO oatproxy_downstream_clients (did, redirect_uri, state, response_type, scope, expires_at)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO oatproxy_downstream_clients (did, client_id, redirect_uri, state, response_type, response_mode, scope, auth_method, upstream_issuer, flow_id, expires_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(did) DO UPDATE SET
+                client_id = excluded.client_id,
                 redirect_uri = excluded.redirect_uri,
                 state = excluded.state,
                 response_type = excluded.response_type,
+                response_mode = excluded.response_mode,
                 scope = excluded.scope,
+                auth_method = excluded.auth_method,
+                upstream_issuer = excluded.upstream_issuer,
+                flow_id = excluded.flow_id,
                 expires_at = excluded.expires_at
             "#,
         )
         .bind(did)
+        .bind(&info.client_id)
         .bind(&info.redirect_uri)
         .bind(&info.state)
         .bind(&info.response_type)
+        .bind(&info.response_mode)
         .bind(&info.scope)
+        .bind(&info.auth_method)
+        .bind(&info.upstream_issuer)
+        .bind(&info.flow_id)
         .bind(info.expires_at.to_rfc3339())
         .execute(&self.db)
         .await
@@ -179,7 +205,7 @@ impl OAuthSessionStore for SqliteStore {
     ) -> OatResult<Option<DownstreamClientInfo>> {
         let row = sqlx::query(
             r#"
-            SELECT redirect_uri, state, response_type, scope, expires_at
+            SELECT client_id, redirect_uri, state, response_type, response_mode, scope, auth_method, upstream_issuer, flow_id, expires_at
             FROM oatproxy_downstream_clients
             WHERE did = ?
             "#,
@@ -197,6 +223,7 @@ impl OAuthSessionStore for SqliteStore {
                 .await
                 .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
 
+            let client_id: Option<String> = row.try_get("client_id").ok();
             let redirect_uri: String = row
                 .try_get("redirect_uri")
                 .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
@@ -204,7 +231,16 @@ impl OAuthSessionStore for SqliteStore {
             let response_type: String = row
                 .try_get("response_type")
                 .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            // Rows written before response_mode existed were delivered as fragments
+            let response_mode: String = row
+                .try_get("response_mode")
+                .unwrap_or_else(|_| "fragment".to_string());
             let scope: Option<String> = row.try_get("scope").ok();
+            let auth_method: String = row
+                .try_get("auth_method")
+                .unwrap_or_else(|_| "none".to_string());
+            let upstream_issuer: Option<String> = row.try_get("upstream_issuer").ok();
+            let flow_id: Option<String> = row.try_get("flow_id").ok();
             let expires_at: String = row
                 .try_get("expires_at")
                 .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
@@ -214,10 +250,15 @@ impl OAuthSessionStore for SqliteStore {
                 .with_timezone(&chrono::Utc);
 
             Ok(Some(DownstreamClientInfo {
+                client_id,
                 redirect_uri,
                 state,
                 response_type,
+                response_mode,
                 scope,
+                auth_method,
+                upstream_issuer,
+                flow_id,
                 expires_at,
             }))
         } else {
@@ -229,22 +270,26 @@ impl OAuthSessionStore for SqliteStore {
         sqlx::query(
             r#"
             INSERT INTO oatproxy_par_data (
-                request_uri, client_id, redirect_uri, response_type, state, scope,
-                code_challenge, code_challenge_method, login_hint, downstream_dpop_jkt, expires_at
+                request_uri, client_id, redirect_uri, response_type, response_mode, state, scope,
+                code_challenge, code_challenge_method, login_hint, downstream_dpop_jkt,
+                auth_method, flow_id, expires_at
             )
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(request_uri)
         .bind(&data.client_id)
         .bind(&data.redirect_uri)
         .bind(&data.response_type)
+        .bind(&data.response_mode)
         .bind(&data.state)
         .bind(&data.scope)
         .bind(&data.code_challenge)
         .bind(&data.code_challenge_method)
         .bind(&data.login_hint)
         .bind(&data.downstream_dpop_jkt)
+        .bind(&data.auth_method)
+        .bind(&data.flow_id)
         .bind(data.expires_at.to_rfc3339())
         .execute(&self.db)
         .await
@@ -254,12 +299,15 @@ impl OAuthSessionStore for SqliteStore {
     }
 
     async fn consume_par_data(&self, request_uri: &str) -> OatResult<Option<PARData>> {
+        // DELETE ... RETURNING makes the consume atomic: concurrent
+        // authorize calls racing on the same request_uri see at most one row
         let row = sqlx::query(
             r#"
-            SELECT client_id, redirect_uri, response_type, state, scope,
-                   code_challenge, code_challenge_method, login_hint, downstream_dpop_jkt, expires_at
-            FROM oatproxy_par_data
+            DELETE FROM oatproxy_par_data
             WHERE request_uri = ?
+            RETURNING client_id, redirect_uri, response_type, response_mode, state, scope,
+                      code_challenge, code_challenge_method, login_hint, downstream_dpop_jkt,
+                      auth_method, flow_id, expires_at
             "#,
         )
         .bind(request_uri)
@@ -268,13 +316,6 @@ impl OAuthSessionStore for SqliteStore {
         .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
 
         if let Some(row) = row {
-            // Delete the PAR data
-            sqlx::query("DELETE FROM oatproxy_par_data WHERE request_uri = ?")
-                .bind(request_uri)
-                .execute(&self.db)
-                .await
-                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
-
             let client_id: String = row
                 .try_get("client_id")
                 .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
@@ -284,6 +325,10 @@ impl OAuthSessionStore for SqliteStore {
             let response_type: String = row
                 .try_get("response_type")
                 .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            // Rows written before response_mode existed were delivered as fragments
+            let response_mode: String = row
+                .try_get("response_mode")
+                .unwrap_or_else(|_| "fragment".to_string());
             let state: Option<String> = row.try_get("state").ok();
             let scope: Option<String> = row.try_get("scope").ok();
             let code_challenge: Option<String> = row.try_get("code_challenge").ok();
@@ -292,6 +337,10 @@ impl OAuthSessionStore for SqliteStore {
             let downstream_dpop_jkt: String = row
                 .try_get("downstream_dpop_jkt")
                 .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let auth_method: String = row
+                .try_get("auth_method")
+                .unwrap_or_else(|_| "none".to_string());
+            let flow_id: Option<String> = row.try_get("flow_id").ok();
             let expires_at: String = row
                 .try_get("expires_at")
                 .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
@@ -304,12 +353,15 @@ impl OAuthSessionStore for SqliteStore {
                 client_id,
                 redirect_uri,
                 response_type,
+                response_mode,
                 state,
                 scope,
                 code_challenge,
                 code_challenge_method,
                 login_hint,
                 downstream_dpop_jkt,
+                auth_method,
+                flow_id,
                 expires_at,
             }))
         } else {
@@ -317,24 +369,52 @@ impl OAuthSessionStore for SqliteStore {
         }
     }
 
+    async fn count_active_par_requests(
+        &self,
+        client_id: &str,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> OatResult<u64> {
+        let count: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM oatproxy_par_data
+            WHERE client_id = ? AND datetime(expires_at) > datetime(?)
+            "#,
+        )
+        .bind(client_id)
+        .bind(now.to_rfc3339())
+        .fetch_one(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        Ok(count as u64)
+    }
+
     async fn store_refresh_token_mapping(
         &self,
         refresh_token: &str,
-        account_did: String,
-        session_id: String,
+        data: RefreshTokenData,
     ) -> OatResult<()> {
         sqlx::query(
             r#"
-            INSERT INTO oatproxy_refresh_tokens (refresh_token, account_did, session_id)
-            VALUES (?, ?, ?)
+            INSERT INTO oatproxy_refresh_tokens (refresh_token, account_did, session_id, client_id, absolute_expires_at, idle_expires_at, dpop_jkt)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
             ON CONFLICT(refresh_token) DO UPDATE SET
                 account_did = excluded.account_did,
-                session_id = excluded.session_id
+                session_id = excluded.session_id,
+                client_id = excluded.client_id,
+                absolute_expires_at = excluded.absolute_expires_at,
+                idle_expires_at = excluded.idle_expires_at,
+                dpop_jkt = excluded.dpop_jkt
             "#,
         )
         .bind(refresh_token)
-        .bind(&account_did)
-        .bind(&session_id)
+        .bind(&data.account_did)
+        .bind(&data.session_id)
+        .bind(&data.client_id)
+        .bind(data.absolute_expires_at.map(|t| t.to_rfc3339()))
+        .bind(data.idle_expires_at.map(|t| t.to_rfc3339()))
+        .bind(&data.dpop_jkt)
         .execute(&self.db)
         .await
         .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
@@ -345,10 +425,11 @@ impl OAuthSessionStore for SqliteStore {
     async fn get_refresh_token_mapping(
         &self,
         refresh_token: &str,
-    ) -> OatResult<Option<(String, String)>> {
+    ) -> OatResult<Option<RefreshTokenData>> {
         let row = sqlx::query(
             r#"
-            SELECT account_did, session_id
+            SELECT account_did, session_id, client_id, absolute_expires_at, idle_expires_at,
+                   dpop_jkt
             FROM oatproxy_refresh_tokens
             WHERE refresh_token = ?
             "#,
@@ -365,13 +446,324 @@ impl OAuthSessionStore for SqliteStore {
             let session_id: String = row
                 .try_get("session_id")
                 .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let client_id: Option<String> = row.try_get("client_id").ok();
+            let absolute_expires_at: Option<String> = row.try_get("absolute_expires_at").ok();
+            let idle_expires_at: Option<String> = row.try_get("idle_expires_at").ok();
+            let dpop_jkt: Option<String> = row.try_get("dpop_jkt").ok();
+
+            let absolute_expires_at = absolute_expires_at
+                .map(|t| {
+                    chrono::DateTime::parse_from_rfc3339(&t)
+                        .map(|t| t.with_timezone(&chrono::Utc))
+                        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))
+                })
+                .transpose()?;
+            let idle_expires_at = idle_expires_at
+                .map(|t| {
+                    chrono::DateTime::parse_from_rfc3339(&t)
+                        .map(|t| t.with_timezone(&chrono::Utc))
+                        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))
+                })
+                .transpose()?;
+
+            Ok(Some(RefreshTokenData {
+                account_did,
+                session_id,
+                client_id,
+                absolute_expires_at,
+                idle_expires_at,
+                dpop_jkt,
+            }))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn delete_refresh_token_mapping(&self, refresh_token: &str) -> OatResult<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM oatproxy_refresh_tokens
+            WHERE refresh_token = ?
+            "#,
+        )
+        .bind(refresh_token)
+        .execute(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete_expired_refresh_tokens(&self, now: chrono::DateTime<chrono::Utc>) -> OatResult<u64> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM oatproxy_refresh_tokens
+            WHERE (absolute_expires_at IS NOT NULL AND datetime(absolute_expires_at) < datetime(?))
+               OR (idle_expires_at IS NOT NULL AND datetime(idle_expires_at) < datetime(?))
+            "#,
+        )
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete_idle_sessions_before(
+        &self,
+        cutoff: chrono::DateTime<chrono::Utc>,
+    ) -> OatResult<Vec<(String, String)>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT did, session_id
+            FROM oatproxy_oauth_sessions
+            WHERE last_used_at IS NOT NULL AND datetime(last_used_at) < datetime(?)
+            "#,
+        )
+        .bind(cutoff.to_rfc3339())
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        let mut purged = Vec::with_capacity(rows.len());
+        for row in rows {
+            let did: String = row
+                .try_get("did")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let session_id: String = row
+                .try_get("session_id")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+            // Per-session state keyed by session_id goes with the session
+            sqlx::query("DELETE FROM oatproxy_session_dpop_keys WHERE session_id = ?")
+                .bind(&session_id)
+                .execute(&self.db)
+                .await
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            sqlx::query("DELETE FROM oatproxy_session_dpop_nonces WHERE session_id = ?")
+                .bind(&session_id)
+                .execute(&self.db)
+                .await
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            sqlx::query("DELETE FROM oatproxy_session_auth_methods WHERE session_id = ?")
+                .bind(&session_id)
+                .execute(&self.db)
+                .await
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            sqlx::query("DELETE FROM oatproxy_active_sessions WHERE did = ? AND session_id = ?")
+                .bind(&did)
+                .bind(&session_id)
+                .execute(&self.db)
+                .await
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            sqlx::query("DELETE FROM oatproxy_oauth_sessions WHERE did = ? AND session_id = ?")
+                .bind(&did)
+                .bind(&session_id)
+                .execute(&self.db)
+                .await
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+            purged.push((did, session_id));
+        }
+
+        Ok(purged)
+    }
+
+    async fn record_grant(
+        &self,
+        did: &str,
+        client_id: &str,
+        scope: Option<String>,
+    ) -> OatResult<()> {
+        let now = chrono::Utc::now().to_rfc3339();
+        sqlx::query(
+            r#"
+            INSERT INTO oatproxy_grants (did, client_id, scope, created_at, last_used_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(did, client_id) DO UPDATE SET
+                scope = COALESCE(excluded.scope, scope),
+                last_used_at = excluded.last_used_at
+            "#,
+        )
+        .bind(did)
+        .bind(client_id)
+        .bind(&scope)
+        .bind(&now)
+        .bind(&now)
+        .execute(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn list_grants(&self, did: &str) -> OatResult<Vec<GrantData>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT client_id, scope, created_at, last_used_at
+            FROM oatproxy_grants
+            WHERE did = ?
+            ORDER BY last_used_at DESC
+            "#,
+        )
+        .bind(did)
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        let mut grants = Vec::with_capacity(rows.len());
+        for row in rows {
+            let client_id: String = row
+                .try_get("client_id")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let scope: Option<String> = row
+                .try_get("scope")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let created_at: String = row
+                .try_get("created_at")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let last_used_at: String = row
+                .try_get("last_used_at")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+            let created_at = chrono::DateTime::parse_from_rfc3339(&created_at)
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?
+                .with_timezone(&chrono::Utc);
+            let last_used_at = chrono::DateTime::parse_from_rfc3339(&last_used_at)
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?
+                .with_timezone(&chrono::Utc);
+
+            grants.push(GrantData {
+                client_id,
+                scope,
+                created_at,
+                last_used_at,
+            });
+        }
+
+        Ok(grants)
+    }
+
+    async fn delete_grant(&self, did: &str, client_id: &str) -> OatResult<u64> {
+        sqlx::query(
+            r#"
+            DELETE FROM oatproxy_grants
+            WHERE did = ? AND client_id = ?
+            "#,
+        )
+        .bind(did)
+        .bind(client_id)
+        .execute(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"
+            DELETE FROM oatproxy_refresh_tokens
+            WHERE account_did = ? AND client_id = ?
+            "#,
+        )
+        .bind(did)
+        .bind(client_id)
+        .execute(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn store_access_token(&self, access_token: &str, data: AccessTokenData) -> OatResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO oatproxy_access_tokens (access_token, account_did, dpop_jkt, scope, issued_at, expires_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON CONFLICT(access_token) DO UPDATE SET
+                account_did = excluded.account_did,
+                dpop_jkt = excluded.dpop_jkt,
+                scope = excluded.scope,
+                issued_at = excluded.issued_at,
+                expires_at = excluded.expires_at
+            "#,
+        )
+        .bind(access_token)
+        .bind(&data.account_did)
+        .bind(&data.dpop_jkt)
+        .bind(&data.scope)
+        .bind(data.issued_at.to_rfc3339())
+        .bind(data.expires_at.to_rfc3339())
+        .execute(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        Ok(())
+    }
 
-            Ok(Some((account_did, session_id)))
+    async fn get_access_token(&self, access_token: &str) -> OatResult<Option<AccessTokenData>> {
+        let row = sqlx::query(
+            r#"
+            SELECT account_did, dpop_jkt, scope, issued_at, expires_at
+            FROM oatproxy_access_tokens
+            WHERE access_token = ?
+            "#,
+        )
+        .bind(access_token)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        if let Some(row) = row {
+            let account_did: String = row
+                .try_get("account_did")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let dpop_jkt: String = row
+                .try_get("dpop_jkt")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let scope: String = row
+                .try_get("scope")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let issued_at: String = row
+                .try_get("issued_at")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let expires_at: String = row
+                .try_get("expires_at")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+            let issued_at = chrono::DateTime::parse_from_rfc3339(&issued_at)
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?
+                .with_timezone(&chrono::Utc);
+            let expires_at = chrono::DateTime::parse_from_rfc3339(&expires_at)
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?
+                .with_timezone(&chrono::Utc);
+
+            Ok(Some(AccessTokenData {
+                account_did,
+                dpop_jkt,
+                scope,
+                issued_at,
+                expires_at,
+            }))
         } else {
             Ok(None)
         }
     }
 
+    async fn delete_access_token(&self, access_token: &str) -> OatResult<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM oatproxy_access_tokens
+            WHERE access_token = ?
+            "#,
+        )
+        .bind(access_token)
+        .execute(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
     async fn store_active_session(&self, did: &str, session_id: String) -> OatResult<()> {
         sqlx::query(
             r#"
@@ -412,6 +804,21 @@ impl OAuthSessionStore for SqliteStore {
         }
     }
 
+    async fn clear_active_session(&self, did: &str) -> OatResult<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM oatproxy_active_sessions
+            WHERE did = ?
+            "#,
+        )
+        .bind(did)
+        .execute(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
     async fn store_session_dpop_key(
         &self,
         session_id: &str,
@@ -473,6 +880,40 @@ impl OAuthSessionStore for SqliteStore {
         }
     }
 
+    async fn store_session_auth_method(
+        &self,
+        session_id: &str,
+        auth_method: String,
+    ) -> OatResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO oatproxy_session_auth_methods (session_id, auth_method)
+            VALUES (?, ?)
+            ON CONFLICT(session_id) DO UPDATE SET
+                auth_method = excluded.auth_method
+            "#,
+        )
+        .bind(session_id)
+        .bind(&auth_method)
+        .execute(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn get_session_auth_method(&self, session_id: &str) -> OatResult<Option<String>> {
+        let auth_method: Option<String> = sqlx::query_scalar(
+            "SELECT auth_method FROM oatproxy_session_auth_methods WHERE session_id = ?",
+        )
+        .bind(session_id)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        Ok(auth_method)
+    }
+
     async fn update_session_dpop_nonce(&self, session_id: &str, nonce: String) -> OatResult<()> {
         sqlx::query(
             r#"
@@ -533,15 +974,193 @@ impl OAuthSessionStore for SqliteStore {
     }
 }
 
+#[async_trait]
+impl ExportableStore for SqliteStore {
+    async fn export_entries(&self) -> OatResult<Vec<ExportEntry>> {
+        let mut entries = Vec::new();
+
+        // Upstream session data is the source of truth here (OAuthSession
+        // persistence is a no-op in this store, see update_session above)
+        let rows = sqlx::query("SELECT session_data FROM oatproxy_oauth_sessions")
+            .fetch_all(&self.db)
+            .await
+            .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+        for row in rows {
+            let data: String = row
+                .try_get("session_data")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            entries.push(ExportEntry::UpstreamSession { data });
+        }
+
+        let rows = sqlx::query("SELECT session_id, dpop_jkt, key_json FROM oatproxy_session_dpop_keys")
+            .fetch_all(&self.db)
+            .await
+            .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+        for row in rows {
+            let session_id: String = row
+                .try_get("session_id")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let dpop_jkt: String = row
+                .try_get("dpop_jkt")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let key_json: String = row
+                .try_get("key_json")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let key: jose_jwk::Jwk = serde_json::from_str(&key_json)
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            entries.push(ExportEntry::DpopKey {
+                session_id,
+                dpop_jkt,
+                key,
+            });
+        }
+
+        let rows = sqlx::query("SELECT session_id, nonce FROM oatproxy_session_dpop_nonces")
+            .fetch_all(&self.db)
+            .await
+            .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+        for row in rows {
+            let session_id: String = row
+                .try_get("session_id")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let nonce: String = row
+                .try_get("nonce")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            entries.push(ExportEntry::DpopNonce { session_id, nonce });
+        }
+
+        let rows = sqlx::query("SELECT session_id, auth_method FROM oatproxy_session_auth_methods")
+            .fetch_all(&self.db)
+            .await
+            .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+        for row in rows {
+            let session_id: String = row
+                .try_get("session_id")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let auth_method: String = row
+                .try_get("auth_method")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            entries.push(ExportEntry::AuthMethod {
+                session_id,
+                auth_method,
+            });
+        }
+
+        let rows = sqlx::query(
+            "SELECT refresh_token, account_did, session_id, client_id, absolute_expires_at, idle_expires_at, dpop_jkt FROM oatproxy_refresh_tokens",
+        )
+        .fetch_all(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+        for row in rows {
+            let refresh_token: String = row
+                .try_get("refresh_token")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let account_did: String = row
+                .try_get("account_did")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let session_id: String = row
+                .try_get("session_id")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let client_id: Option<String> = row.try_get("client_id").ok();
+            let absolute_expires_at: Option<String> = row.try_get("absolute_expires_at").ok();
+            let idle_expires_at: Option<String> = row.try_get("idle_expires_at").ok();
+            let absolute_expires_at = absolute_expires_at
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(&t).ok())
+                .map(|t| t.with_timezone(&chrono::Utc));
+            let idle_expires_at = idle_expires_at
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(&t).ok())
+                .map(|t| t.with_timezone(&chrono::Utc));
+            let dpop_jkt: Option<String> = row.try_get("dpop_jkt").ok();
+            entries.push(ExportEntry::RefreshToken {
+                refresh_token,
+                account_did,
+                session_id,
+                client_id,
+                absolute_expires_at,
+                idle_expires_at,
+                dpop_jkt,
+            });
+        }
+
+        let rows = sqlx::query("SELECT did, session_id FROM oatproxy_active_sessions")
+            .fetch_all(&self.db)
+            .await
+            .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+        for row in rows {
+            let did: String = row
+                .try_get("did")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let session_id: String = row
+                .try_get("session_id")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            entries.push(ExportEntry::ActiveSession { did, session_id });
+        }
+
+        Ok(entries)
+    }
+}
+
 #[async_trait]
 impl KeyStore for SqliteStore {
     async fn get_signing_key(&self) -> OatResult<SigningKey> {
         Ok(self.signing_key.clone())
     }
 
-    async fn get_dpop_key(&self, _thumbprint: &str) -> OatResult<Option<jose_jwk::Jwk>> {
-        // DPoP keys are stored per-session, look them up via get_session_dpop_key instead
-        Ok(None)
+    async fn get_dpop_key(&self, thumbprint: &str) -> OatResult<Option<jose_jwk::Jwk>> {
+        let row = sqlx::query(
+            r#"
+            SELECT key_json
+            FROM oatproxy_dpop_keys
+            WHERE thumbprint = ?
+            "#,
+        )
+        .bind(thumbprint)
+        .fetch_optional(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        if let Some(row) = row {
+            let key_json: String = row
+                .try_get("key_json")
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            let key: jose_jwk::Jwk = serde_json::from_str(&key_json)
+                .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+            Ok(Some(key))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn store_dpop_key(&self, thumbprint: &str, key: jose_jwk::Jwk) -> OatResult<()> {
+        let key_json = serde_json::to_string(&key)
+            .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO oatproxy_dpop_keys (thumbprint, key_json)
+            VALUES (?, ?)
+            ON CONFLICT(thumbprint) DO UPDATE SET
+                key_json = excluded.key_json
+            "#,
+        )
+        .bind(thumbprint)
+        .bind(&key_json)
+        .execute(&self.db)
+        .await
+        .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn delete_dpop_key(&self, thumbprint: &str) -> OatResult<()> {
+        sqlx::query("DELETE FROM oatproxy_dpop_keys WHERE thumbprint = ?")
+            .bind(thumbprint)
+            .execute(&self.db)
+            .await
+            .map_err(|e| jacquard_oatproxy::error::Error::StorageError(e.to_string()))?;
+
+        Ok(())
     }
 }
 
@@ -609,14 +1228,17 @@ impl jacquard_oauth::authstore::ClientAuthStore for SqliteStore {
 
             sqlx::query(
                 r#"
-                INSERT INTO oatproxy_oauth_sessions (did, session_id, session_data)
-                VALUES (?, ?, ?)
-                ON CONFLICT(did, session_id) DO UPDATE SET session_data = excluded.session_data
+                INSERT INTO oatproxy_oauth_sessions (did, session_id, session_data, last_used_at)
+                VALUES (?, ?, ?, ?)
+                ON CONFLICT(did, session_id) DO UPDATE SET
+                    session_data = excluded.session_data,
+                    last_used_at = excluded.last_used_at
                 "#,
             )
             .bind(&did_str)
             .bind(&session_id)
             .bind(&serialized)
+            .bind(chrono::Utc::now().to_rfc3339())
             .execute(&db)
             .await
             .map_err(|e| {
@@ -0,0 +1,340 @@
+//! OpenGraph cards and oEmbed for shared status links.
+//!
+//! A status URL pasted into another platform previously unfurled as
+//! nothing, since the frontend is a client-rendered SPA. `/og/{handle}/
+//! {rkey}` serves a crawler-friendly HTML page carrying OpenGraph and
+//! Twitter card tags plus an oEmbed discovery link, and redirects real
+//! browsers to the profile page. The preview image is an SVG card with
+//! the emoji (inlined as a data URI so the card is self-contained)
+//! composited next to the status title, rendered once and cached on
+//! disk keyed by (did, rkey). `/oembed` answers the standard oEmbed
+//! query for consumers that want structured data instead of meta tags.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{Html, IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+
+use crate::AppState;
+
+/// Directory for cached preview cards (default: `og-cache` in the working dir)
+fn cache_dir() -> std::path::PathBuf {
+    std::env::var("ISTAT_OG_CACHE_DIR")
+        .unwrap_or_else(|_| "og-cache".to_string())
+        .into()
+}
+
+fn public_url() -> String {
+    std::env::var("PUBLIC_URL").unwrap_or_else(|_| "http://localhost:3000".to_string())
+}
+
+/// Escape a string for embedding in HTML/XML text and attributes
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+struct StatusCard {
+    did: String,
+    handle: String,
+    display_name: Option<String>,
+    title: Option<String>,
+    description: Option<String>,
+    emoji_blob_cid: Option<String>,
+    emoji_mime: Option<String>,
+    emoji_name: Option<String>,
+}
+
+/// Load one status with its emoji and author, applying the usual
+/// soft-delete, expiry, takedown, and blacklist filters.
+async fn load_status(
+    state: &AppState,
+    handle: &str,
+    rkey: &str,
+) -> Result<StatusCard, StatusCode> {
+    let did = crate::identity::resolve_handle(&state.db, handle)
+        .await
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let row = sqlx::query(
+        r#"
+        SELECT s.title, s.description, p.handle, p.display_name,
+               e.blob_cid AS emoji_blob_cid, e.mime_type, e.emoji_name
+        FROM statuses s
+        LEFT JOIN profiles p ON s.did = p.did
+        LEFT JOIN emojis e ON s.emoji_ref = 'at://' || e.at
+        WHERE s.did = ? AND s.rkey = ?
+          AND s.deleted_at IS NULL
+          AND s.did NOT IN (SELECT did FROM actor_takedowns)
+          AND s.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active')
+          AND (e.blob_cid NOT IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'emoji_blob') OR e.blob_cid IS NULL)
+          AND (s.expires IS NULL OR datetime(s.expires) > datetime('now'))
+        "#,
+    )
+    .bind(&did)
+    .bind(rkey)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    Ok(StatusCard {
+        handle: row
+            .try_get::<Option<String>, _>("handle")
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| did.clone()),
+        display_name: row.try_get("display_name").ok().flatten(),
+        title: row
+            .try_get::<Option<String>, _>("title")
+            .ok()
+            .flatten()
+            .filter(|t| !t.is_empty()),
+        description: row
+            .try_get::<Option<String>, _>("description")
+            .ok()
+            .flatten()
+            .filter(|d| !d.is_empty()),
+        emoji_blob_cid: row.try_get("emoji_blob_cid").ok().flatten(),
+        emoji_mime: row.try_get("mime_type").ok().flatten(),
+        emoji_name: row.try_get("emoji_name").ok().flatten(),
+        did,
+    })
+}
+
+/// One-line summary used for og:title and the oEmbed title
+fn card_title(card: &StatusCard) -> String {
+    let who = card
+        .display_name
+        .clone()
+        .unwrap_or_else(|| card.handle.clone());
+    match (&card.emoji_name, &card.title) {
+        (Some(emoji), Some(title)) => format!("{} is {} {}", who, emoji, title),
+        (Some(emoji), None) => format!("{} is {}", who, emoji),
+        (None, Some(title)) => format!("{}: {}", who, title),
+        (None, None) => format!("{}'s status", who),
+    }
+}
+
+/// Render the SVG preview card: emoji on the left, title and handle on
+/// the right. SVG keeps rendering dependency-free and the embedded data
+/// URI keeps the card a single cacheable file.
+fn render_card(card: &StatusCard, emoji_data_uri: Option<&str>) -> String {
+    let title = card.title.as_deref().unwrap_or("");
+    let mut title_short: String = title.chars().take(60).collect();
+    if title.chars().count() > 60 {
+        title_short.push('…');
+    }
+    let emoji_name = card.emoji_name.as_deref().unwrap_or("");
+    let handle_line = format!("@{}", card.handle);
+
+    let emoji_block = match emoji_data_uri {
+        Some(uri) => format!(
+            r#"<image x="80" y="155" width="320" height="320" href="{}"/>"#,
+            uri
+        ),
+        None => String::new(),
+    };
+
+    format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="1200" height="630" viewBox="0 0 1200 630">
+  <rect width="1200" height="630" fill="#101014"/>
+  <rect x="0" y="614" width="1200" height="16" fill="#7c8aff"/>
+  {emoji_block}
+  <text x="460" y="280" font-family="sans-serif" font-size="56" font-weight="bold" fill="#f5f5f7">{title}</text>
+  <text x="460" y="350" font-family="sans-serif" font-size="40" fill="#9a9aa5">{emoji_name}</text>
+  <text x="460" y="430" font-family="sans-serif" font-size="34" fill="#7c8aff">{handle}</text>
+</svg>
+"#,
+        emoji_block = emoji_block,
+        title = escape(&title_short),
+        emoji_name = escape(emoji_name),
+        handle = escape(&handle_line),
+    )
+}
+
+/// Build (and disk-cache) the preview card for a status
+async fn card_svg(state: &AppState, handle: &str, rkey: &str) -> Result<Vec<u8>, StatusCode> {
+    let card = load_status(state, handle, rkey).await?;
+
+    let dir = cache_dir();
+    let did_key = card.did.replace(':', "_");
+    let cache_path = dir.join(format!("{}-{}.svg", did_key, rkey));
+
+    if let Ok(bytes) = tokio::fs::read(&cache_path).await {
+        return Ok(bytes);
+    }
+
+    // Inline the emoji as a data URI; a card without one still renders
+    let emoji_data_uri = match (&card.emoji_blob_cid, &card.emoji_mime) {
+        (Some(cid), mime) => {
+            let mime = mime.as_deref().unwrap_or("image/png");
+            let ext = mime.strip_prefix("image/").unwrap_or("png");
+            match crate::img::fetch_blob(&card.did, cid, ext).await {
+                Some(bytes) => {
+                    use base64::Engine;
+                    let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+                    Some(format!("data:{};base64,{}", mime, encoded))
+                }
+                None => None,
+            }
+        }
+        _ => None,
+    };
+
+    let svg = render_card(&card, emoji_data_uri.as_deref());
+    let _ = tokio::fs::create_dir_all(&dir).await;
+    let _ = tokio::fs::write(&cache_path, svg.as_bytes()).await;
+
+    Ok(svg.into_bytes())
+}
+
+pub async fn handle_og_page(
+    State(state): State<AppState>,
+    Path((handle, rkey)): Path<(String, String)>,
+) -> Result<Html<String>, StatusCode> {
+    let card = load_status(&state, &handle, &rkey).await?;
+
+    let base = public_url();
+    let base = base.trim_end_matches('/');
+    let page_url = format!("{}/{}", base, card.handle);
+    let image_url = format!("{}/og/{}/{}/card.svg", base, card.handle, rkey);
+    let oembed_url = format!(
+        "{}/oembed?url={}/og/{}/{}",
+        base, base, card.handle, rkey
+    );
+
+    let title = card_title(&card);
+    let description = card.description.clone().unwrap_or_default();
+
+    let html = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<meta property="og:type" content="website">
+<meta property="og:site_name" content="istat">
+<meta property="og:title" content="{title}">
+<meta property="og:description" content="{description}">
+<meta property="og:url" content="{page_url}">
+<meta property="og:image" content="{image_url}">
+<meta property="og:image:width" content="1200">
+<meta property="og:image:height" content="630">
+<meta name="twitter:card" content="summary_large_image">
+<meta name="twitter:title" content="{title}">
+<meta name="twitter:description" content="{description}">
+<meta name="twitter:image" content="{image_url}">
+<link rel="alternate" type="application/json+oembed" href="{oembed_url}" title="{title}">
+<meta http-equiv="refresh" content="0; url={page_url}">
+</head>
+<body>
+<p>Redirecting to <a href="{page_url}">{page_url}</a>…</p>
+</body>
+</html>
+"#,
+        title = escape(&title),
+        description = escape(&description),
+        page_url = escape(&page_url),
+        image_url = escape(&image_url),
+        oembed_url = escape(&oembed_url),
+    );
+
+    Ok(Html(html))
+}
+
+pub async fn handle_og_card(
+    State(state): State<AppState>,
+    Path((handle, rkey)): Path<(String, String)>,
+) -> Result<Response, StatusCode> {
+    let bytes = card_svg(&state, &handle, &rkey).await?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "image/svg+xml".to_string()),
+            (
+                header::CACHE_CONTROL,
+                "public, max-age=3600".to_string(),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OembedParams {
+    pub url: String,
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OembedResponse {
+    pub version: &'static str,
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub title: String,
+    pub author_name: String,
+    pub author_url: String,
+    pub provider_name: &'static str,
+    pub provider_url: String,
+    pub thumbnail_url: String,
+    pub thumbnail_width: u32,
+    pub thumbnail_height: u32,
+}
+
+pub async fn handle_oembed(
+    State(state): State<AppState>,
+    Query(params): Query<OembedParams>,
+) -> Result<Json<OembedResponse>, StatusCode> {
+    // Only JSON is offered; oEmbed says unsupported formats are 501
+    if let Some(format) = params.format.as_deref() {
+        if format != "json" {
+            return Err(StatusCode::NOT_IMPLEMENTED);
+        }
+    }
+
+    // Accept both the /og page URL and the profile URL with a rkey path
+    let path = params
+        .url
+        .split("://")
+        .nth(1)
+        .and_then(|rest| rest.split_once('/'))
+        .map(|(_, path)| path)
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    let mut segments = path.trim_end_matches('/').split('/');
+    let (handle, rkey) = match (segments.next(), segments.next(), segments.next()) {
+        (Some("og"), Some(handle), Some(rkey)) => (handle, rkey),
+        (Some(handle), Some(rkey), None) => (handle, rkey),
+        _ => return Err(StatusCode::NOT_FOUND),
+    };
+
+    let card = load_status(&state, handle, rkey).await?;
+
+    let base = public_url();
+    let base = base.trim_end_matches('/');
+
+    Ok(Json(OembedResponse {
+        version: "1.0",
+        kind: "link",
+        title: card_title(&card),
+        author_name: card
+            .display_name
+            .clone()
+            .unwrap_or_else(|| card.handle.clone()),
+        author_url: format!("{}/{}", base, card.handle),
+        provider_name: "istat",
+        provider_url: base.to_string(),
+        thumbnail_url: format!("{}/og/{}/{}/card.svg", base, card.handle, rkey),
+        thumbnail_width: 1200,
+        thumbnail_height: 630,
+    }))
+}
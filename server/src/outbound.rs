@@ -0,0 +1,96 @@
+//! Shared outbound HTTP client with per-destination timeouts and retries.
+//!
+//! Hydration and resolution calls previously used `reqwest::get` with no
+//! timeout, so a hung upstream (e.g. plc.directory) could stall the
+//! ingestor. All plain GETs to upstream services should go through
+//! [`get`] instead, which applies the destination's timeout/retry policy
+//! and counts timeouts for observability.
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Timeout/retry settings for one class of upstream host.
+#[derive(Debug, Clone, Copy)]
+struct DestinationPolicy {
+    timeout: Duration,
+    retries: u32,
+}
+
+fn env_u64(var: &str, default: u64) -> u64 {
+    std::env::var(var)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Resolve the policy for a URL from its host class.
+///
+/// Defaults come from `ISTAT_OUTBOUND_TIMEOUT_SECS` / `ISTAT_OUTBOUND_RETRIES`
+/// (10 seconds, 2 retries); plc.directory and the public AppView can be tuned
+/// independently via `ISTAT_PLC_*` and `ISTAT_APPVIEW_*`.
+fn policy_for(url: &str) -> DestinationPolicy {
+    let default_timeout = env_u64("ISTAT_OUTBOUND_TIMEOUT_SECS", 10);
+    let default_retries = env_u64("ISTAT_OUTBOUND_RETRIES", 2);
+
+    let (timeout_var, retries_var) = if url.contains("plc.directory") {
+        ("ISTAT_PLC_TIMEOUT_SECS", "ISTAT_PLC_RETRIES")
+    } else if url.contains("public.api.bsky.app") {
+        ("ISTAT_APPVIEW_TIMEOUT_SECS", "ISTAT_APPVIEW_RETRIES")
+    } else {
+        ("ISTAT_OUTBOUND_TIMEOUT_SECS", "ISTAT_OUTBOUND_RETRIES")
+    };
+
+    DestinationPolicy {
+        timeout: Duration::from_secs(env_u64(timeout_var, default_timeout)),
+        retries: env_u64(retries_var, default_retries) as u32,
+    }
+}
+
+/// Running count of outbound request timeouts, reported in the log line
+/// emitted on each occurrence.
+static TIMEOUT_COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// The shared outbound client. Connection pooling lives here; per-request
+/// timeouts are applied in [`get`].
+pub fn client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(5))
+            .build()
+            .expect("failed to build outbound HTTP client")
+    })
+}
+
+/// GET a URL with the destination's timeout and retry policy.
+///
+/// Retries only on timeouts and connection errors, never on HTTP error
+/// statuses, so callers keep their existing status handling.
+pub async fn get(url: &str) -> reqwest::Result<reqwest::Response> {
+    let policy = policy_for(url);
+    let mut attempt = 0u32;
+
+    loop {
+        let result = client().get(url).timeout(policy.timeout).send().await;
+        match result {
+            Ok(resp) => return Ok(resp),
+            Err(e) => {
+                if e.is_timeout() {
+                    let total = TIMEOUT_COUNT.fetch_add(1, Ordering::Relaxed) + 1;
+                    tracing::warn!(
+                        "Outbound request to {} timed out after {:?} (total timeouts: {})",
+                        url, policy.timeout, total
+                    );
+                }
+
+                if attempt >= policy.retries || !(e.is_timeout() || e.is_connect()) {
+                    return Err(e);
+                }
+
+                attempt += 1;
+                tokio::time::sleep(Duration::from_millis(250 * attempt as u64)).await;
+            }
+        }
+    }
+}
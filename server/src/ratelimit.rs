@@ -0,0 +1,155 @@
+//! Per-IP rate limiting for public read endpoints.
+//!
+//! The read API had no throttling, so a single client could hammer
+//! search (the most expensive queries) or the list endpoints freely.
+//! This module is a pair of axum middleware layers backed by in-memory
+//! token buckets keyed by client IP, with separate limits for search
+//! and list/lookup traffic. Limits are tunable via
+//! `ISTAT_RATE_SEARCH_PER_MIN` and `ISTAT_RATE_LIST_PER_MIN` (requests
+//! per minute, 0 disables the limiter). Responses carry the draft
+//! standard `RateLimit-*` headers; a throttled request gets a 429 with
+//! `Retry-After`.
+//!
+//! The client IP comes from `X-Forwarded-For` / `X-Real-IP`, so this
+//! assumes the usual reverse-proxy deployment; direct connections all
+//! share one bucket.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use axum::{
+    extract::Request,
+    http::{HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Class {
+    Search,
+    List,
+}
+
+/// Requests per minute for a class; 0 disables the limiter
+fn limit_per_min(class: Class) -> u64 {
+    let (var, default) = match class {
+        Class::Search => ("ISTAT_RATE_SEARCH_PER_MIN", 60),
+        Class::List => ("ISTAT_RATE_LIST_PER_MIN", 120),
+    };
+    std::env::var(var)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Buckets idle longer than this are pruned once the map grows large
+const IDLE_SECS: u64 = 120;
+const PRUNE_THRESHOLD: usize = 10_000;
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+fn buckets() -> &'static Mutex<HashMap<(Class, String), Bucket>> {
+    static BUCKETS: OnceLock<Mutex<HashMap<(Class, String), Bucket>>> = OnceLock::new();
+    BUCKETS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Best-effort client IP. First hop of X-Forwarded-For, then X-Real-IP.
+fn client_ip(headers: &HeaderMap) -> String {
+    headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .or_else(|| {
+            headers
+                .get("x-real-ip")
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Take one token from the (class, ip) bucket. Returns the remaining
+/// token count and seconds until the bucket refills on success, or
+/// seconds until the next token on rejection.
+fn take_token(class: Class, ip: String, limit: u64) -> Result<(u64, u64), u64> {
+    let capacity = limit as f64;
+    let rate = capacity / 60.0;
+    let now = Instant::now();
+
+    let mut map = buckets().lock().unwrap();
+    if map.len() > PRUNE_THRESHOLD {
+        map.retain(|_, b| now.duration_since(b.updated).as_secs() < IDLE_SECS);
+    }
+
+    let bucket = map.entry((class, ip)).or_insert(Bucket {
+        tokens: capacity,
+        updated: now,
+    });
+    let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+    bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity);
+    bucket.updated = now;
+
+    if bucket.tokens >= 1.0 {
+        bucket.tokens -= 1.0;
+        let reset = ((capacity - bucket.tokens) / rate).ceil() as u64;
+        Ok((bucket.tokens as u64, reset))
+    } else {
+        Err(((1.0 - bucket.tokens) / rate).ceil() as u64)
+    }
+}
+
+async fn limit(class: Class, req: Request, next: Next) -> Response {
+    let limit = limit_per_min(class);
+    if limit == 0 {
+        return next.run(req).await;
+    }
+
+    let ip = client_ip(req.headers());
+    match take_token(class, ip, limit) {
+        Ok((remaining, reset)) => {
+            let mut response = next.run(req).await;
+            let headers = response.headers_mut();
+            if let Ok(v) = limit.to_string().parse() {
+                headers.insert("RateLimit-Limit", v);
+            }
+            if let Ok(v) = remaining.to_string().parse() {
+                headers.insert("RateLimit-Remaining", v);
+            }
+            if let Ok(v) = reset.to_string().parse() {
+                headers.insert("RateLimit-Reset", v);
+            }
+            response
+        }
+        Err(retry_after) => {
+            let mut response = StatusCode::TOO_MANY_REQUESTS.into_response();
+            let headers = response.headers_mut();
+            if let Ok(v) = limit.to_string().parse() {
+                headers.insert("RateLimit-Limit", v);
+            }
+            if let Ok(v) = "0".parse() {
+                headers.insert("RateLimit-Remaining", v);
+            }
+            if let Ok(v) = retry_after.to_string().parse() {
+                headers.insert("RateLimit-Reset", v.clone());
+                headers.insert("Retry-After", v);
+            }
+            response
+        }
+    }
+}
+
+/// Layer for search endpoints (the most expensive queries)
+pub async fn limit_search(req: Request, next: Next) -> Response {
+    limit(Class::Search, req, next).await
+}
+
+/// Layer for list and lookup endpoints
+pub async fn limit_list(req: Request, next: Next) -> Response {
+    limit(Class::List, req, next).await
+}
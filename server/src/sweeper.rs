@@ -0,0 +1,156 @@
+//! Periodic status cleanup.
+//!
+//! Read endpoints filter expired and soft-deleted statuses at query time,
+//! but the rows themselves linger forever, and a record deleted upstream
+//! while this instance was offline never produces a jetstream delete
+//! event. The sweeper handles both: it prunes rows whose expiry or soft
+//! delete is past the retention window, and reconciles old rows against
+//! the owner's PDS via `com.atproto.repo.getRecord`, dropping rows the
+//! PDS no longer knows about.
+
+use sqlx::SqlitePool;
+
+/// Seconds between sweeps (default: hourly)
+fn sweep_interval_secs() -> u64 {
+    std::env::var("ISTAT_SWEEP_INTERVAL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(3_600)
+}
+
+/// Days an expired or soft-deleted status is kept before pruning
+fn retention_days() -> i64 {
+    std::env::var("ISTAT_SWEEP_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(30)
+}
+
+/// Minimum age in days before a row is reconciled against the PDS
+fn reconcile_days() -> i64 {
+    std::env::var("ISTAT_SWEEP_RECONCILE_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(7)
+}
+
+/// Rows reconciled per sweep, to bound the network work
+const RECONCILE_BATCH: i64 = 50;
+
+/// Delete one status row and its reply edges
+async fn delete_status_row(db: &SqlitePool, at: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM statuses WHERE at = ?")
+        .bind(at)
+        .execute(db)
+        .await?;
+    sqlx::query("DELETE FROM status_replies WHERE at = ? OR parent_at = 'at://' || ?")
+        .bind(at)
+        .bind(at)
+        .execute(db)
+        .await?;
+    Ok(())
+}
+
+/// Prune statuses whose expiry or soft delete is older than the
+/// retention window. Returns the number of rows removed.
+async fn prune_statuses(db: &SqlitePool) -> Result<u64, sqlx::Error> {
+    let cutoff = format!("-{} days", retention_days());
+
+    let stale: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT at FROM statuses
+        WHERE (expires IS NOT NULL AND datetime(expires) < datetime('now', ?))
+           OR (deleted_at IS NOT NULL AND datetime(deleted_at) < datetime('now', ?))
+        "#,
+    )
+    .bind(&cutoff)
+    .bind(&cutoff)
+    .fetch_all(db)
+    .await?;
+
+    for at in &stale {
+        delete_status_row(db, at).await?;
+    }
+    Ok(stale.len() as u64)
+}
+
+/// Check whether the PDS still has a record. Returns None when the
+/// answer is inconclusive (PDS unreachable, unexpected response).
+async fn record_exists_upstream(did: &str, rkey: &str) -> Option<bool> {
+    let pds = crate::img::resolve_pds(did).await?;
+    let url = format!(
+        "{}/xrpc/com.atproto.repo.getRecord?repo={}&collection={}&rkey={}",
+        pds, did, crate::collections::registry().status, rkey
+    );
+    let resp = crate::outbound::get(&url).await.ok()?;
+
+    if resp.status().is_success() {
+        return Some(true);
+    }
+    if resp.status() == reqwest::StatusCode::BAD_REQUEST {
+        // getRecord answers 400 RecordNotFound for deleted records; only
+        // treat that specific error as a definitive miss
+        let body: serde_json::Value = resp.json().await.ok()?;
+        if body.get("error").and_then(|e| e.as_str()) == Some("RecordNotFound") {
+            return Some(false);
+        }
+    }
+    None
+}
+
+/// Reconcile the least-recently-checked old rows against their PDS,
+/// deleting rows the PDS no longer has. Returns the number deleted.
+async fn reconcile_statuses(db: &SqlitePool) -> Result<u64, sqlx::Error> {
+    let rows: Vec<(String, String, String)> = sqlx::query_as(
+        r#"
+        SELECT at, did, rkey FROM statuses
+        WHERE deleted_at IS NULL
+          AND datetime(created_at) < datetime('now', ?)
+          AND (last_checked_at IS NULL OR datetime(last_checked_at) < datetime('now', '-1 day'))
+        ORDER BY last_checked_at ASC
+        LIMIT ?
+        "#,
+    )
+    .bind(format!("-{} days", reconcile_days()))
+    .bind(RECONCILE_BATCH)
+    .fetch_all(db)
+    .await?;
+
+    let mut removed = 0u64;
+    for (at, did, rkey) in &rows {
+        match record_exists_upstream(did, rkey).await {
+            Some(true) => {
+                sqlx::query("UPDATE statuses SET last_checked_at = datetime('now') WHERE at = ?")
+                    .bind(at)
+                    .execute(db)
+                    .await?;
+            }
+            Some(false) => {
+                delete_status_row(db, at).await?;
+                removed += 1;
+                tracing::info!("Sweeper: removed status deleted upstream: at={}", at);
+            }
+            // Inconclusive: leave last_checked_at alone so the row is
+            // retried next sweep
+            None => {}
+        }
+    }
+    Ok(removed)
+}
+
+/// Run the sweeper forever on its interval
+pub async fn run_sweeper(db: SqlitePool) {
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(sweep_interval_secs())).await;
+
+        match prune_statuses(&db).await {
+            Ok(0) => {}
+            Ok(n) => tracing::info!("Sweeper: pruned {} expired/deleted statuses", n),
+            Err(e) => tracing::warn!("Sweeper: prune failed: {}", e),
+        }
+
+        if let Err(e) = reconcile_statuses(&db).await {
+            tracing::warn!("Sweeper: reconcile failed: {}", e);
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Read-your-writes consistency tokens.
+//!
+//! Write-through endpoints update the local index and then return an opaque
+//! token naming the write. List endpoints accept the token back via the
+//! `istat-consistency-token` header and wait briefly until the referenced
+//! write is visible in the index before querying, so a client that just
+//! wrote never sees a list that predates its own write (e.g. when the
+//! authoritative copy arrives via jetstream slightly later).
+
+use axum::http::HeaderMap;
+use sqlx::SqlitePool;
+use std::time::Duration;
+
+/// Header the frontend sends tokens back on
+pub const CONSISTENCY_TOKEN_HEADER: &str = "istat-consistency-token";
+
+/// How long a list request will wait for a pending write before giving up
+const MAX_WAIT: Duration = Duration::from_secs(2);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Tokens older than this are assumed long-visible and skipped entirely
+const MAX_TOKEN_AGE_MS: i64 = 30_000;
+
+/// Issue a token for a status write. `expires` is the expiry value the
+/// write set (None if the write removed the expiry).
+pub fn issue_token(at_uri: &str, expires: Option<&str>) -> String {
+    use base64::Engine;
+
+    let payload = format!(
+        "{}|{}|{}",
+        at_uri,
+        expires.unwrap_or("-"),
+        chrono::Utc::now().timestamp_millis()
+    );
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(payload.as_bytes())
+}
+
+/// If the request carries a consistency token, wait until the write it
+/// names is visible in the local index. Malformed or stale tokens are
+/// ignored — consistency is best-effort, never a reason to fail a read.
+pub async fn await_token_visible(db: &SqlitePool, headers: &HeaderMap) {
+    use base64::Engine;
+
+    let Some(token) = headers
+        .get(CONSISTENCY_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+    else {
+        return;
+    };
+
+    let Ok(decoded) = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(token) else {
+        return;
+    };
+    let Ok(payload) = String::from_utf8(decoded) else {
+        return;
+    };
+
+    let mut parts = payload.rsplitn(3, '|');
+    let (Some(issued_ms), Some(expires), Some(at_uri)) =
+        (parts.next(), parts.next(), parts.next())
+    else {
+        return;
+    };
+
+    let Ok(issued_ms) = issued_ms.parse::<i64>() else {
+        return;
+    };
+    if chrono::Utc::now().timestamp_millis() - issued_ms > MAX_TOKEN_AGE_MS {
+        return;
+    }
+
+    let expected_expires = if expires == "-" { None } else { Some(expires) };
+
+    let deadline = tokio::time::Instant::now() + MAX_WAIT;
+    loop {
+        let row: Option<Option<String>> =
+            sqlx::query_scalar("SELECT expires FROM statuses WHERE at = ? AND deleted_at IS NULL")
+                .bind(at_uri)
+                .fetch_optional(db)
+                .await
+                .ok()
+                .flatten();
+
+        if let Some(current_expires) = row {
+            if current_expires.as_deref() == expected_expires {
+                return;
+            }
+        }
+
+        if tokio::time::Instant::now() >= deadline {
+            tracing::warn!(
+                "consistency token for {} not visible after {:?}, serving anyway",
+                at_uri, MAX_WAIT
+            );
+            return;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
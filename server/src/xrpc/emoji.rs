@@ -0,0 +1,291 @@
+//! Emoji detail and browsing endpoints: `moji.getEmoji` returns a single
+//! emoji with usage stats, `moji.listUserEmoji` pages through everything
+//! a creator has published. Both apply the same blacklist, soft-delete,
+//! and takedown filters as search.
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+
+use super::img_url;
+use crate::AppState;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEmojiParams {
+    /// Full AT-URI of the emoji record
+    pub uri: String,
+    /// Admin-only: include emoji from non-active accounts
+    pub include_inactive: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmojiDetailView {
+    pub uri: String,
+    pub name: String,
+    pub alt_text: Option<String>,
+    pub category: Option<String>,
+    pub tags: Vec<String>,
+    pub url: String,
+    pub blob_cid: String,
+    pub created_by: String,
+    pub created_by_handle: Option<String>,
+    pub created_at: String,
+    /// Canonical emoji sharing this blob, when this record is a duplicate
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duplicate_of: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmojiUserView {
+    pub did: String,
+    pub handle: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetEmojiResponse {
+    pub emoji: EmojiDetailView,
+    /// Number of non-deleted statuses referencing this emoji
+    pub status_count: i64,
+    /// Most recent distinct users of the emoji
+    pub recent_users: Vec<EmojiUserView>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListUserEmojiParams {
+    /// Creator handle or DID
+    pub actor: String,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    /// Admin-only: include emoji from non-active accounts
+    pub include_inactive: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListUserEmojiResponse {
+    pub emojis: Vec<EmojiDetailView>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+fn mime_ext(mime_type: Option<&str>) -> &'static str {
+    match mime_type {
+        Some("image/png") => "png",
+        Some("image/jpeg") | Some("image/jpg") => "jpeg",
+        Some("image/webp") => "webp",
+        Some("image/gif") => "gif",
+        _ => "jpeg",
+    }
+}
+
+pub(crate) fn view_from_row(row: &sqlx::sqlite::SqliteRow) -> Option<EmojiDetailView> {
+    let at: String = row.try_get("at").ok()?;
+    let canonical_at: Option<String> = row.try_get("canonical_at").ok().flatten();
+    let did: String = row.try_get("did").ok()?;
+    let blob_cid: String = row.try_get("blob_cid").ok()?;
+    let mime_type: Option<String> = row.try_get("mime_type").ok().flatten();
+    let tags: Option<String> = row.try_get("tags").ok().flatten();
+
+    Some(EmojiDetailView {
+        uri: format!("at://{}", at),
+        name: row
+            .try_get::<Option<String>, _>("emoji_name")
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "changeme".to_string()),
+        alt_text: row.try_get("alt_text").ok().flatten(),
+        category: row.try_get("category").ok().flatten(),
+        tags: tags
+            .map(|t| t.split(' ').map(|s| s.to_string()).collect())
+            .unwrap_or_default(),
+        url: img_url(&did, &blob_cid, mime_ext(mime_type.as_deref())),
+        blob_cid,
+        created_by: did,
+        created_by_handle: row.try_get("handle").ok().flatten(),
+        created_at: row.try_get("created_at").ok()?,
+        duplicate_of: canonical_at
+            .filter(|c| *c != at)
+            .map(|c| format!("at://{}", c)),
+    })
+}
+
+pub async fn handle_get_emoji(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<GetEmojiParams>,
+) -> Result<Json<GetEmojiResponse>, StatusCode> {
+    let include_inactive = super::include_inactive_override(
+        &state,
+        &headers,
+        params.include_inactive.unwrap_or(false),
+    )
+    .await?;
+    let at = params
+        .uri
+        .strip_prefix("at://")
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    let row = sqlx::query(
+        r#"
+        SELECT e.at, e.did, e.blob_cid, e.mime_type, e.emoji_name, e.alt_text,
+               COALESCE(e.curated_category, e.category) AS category,
+               (SELECT group_concat(t.tag, ' ') FROM emoji_tags t WHERE t.emoji_at = e.at) AS tags,
+               p.handle, e.created_at,
+               (SELECT e2.at FROM emojis e2
+                WHERE e2.blob_cid = e.blob_cid AND e2.deleted_at IS NULL
+                ORDER BY (SELECT COUNT(*) FROM statuses su WHERE su.emoji_ref = 'at://' || e2.at) DESC,
+                         e2.created_at ASC, e2.at ASC
+                LIMIT 1) AS canonical_at
+        FROM emojis e
+        LEFT JOIN profiles p ON e.did = p.did
+        WHERE e.at = ?
+          AND e.deleted_at IS NULL
+          AND e.did NOT IN (SELECT did FROM actor_takedowns)
+          AND (? OR e.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active'))
+          AND e.blob_cid NOT IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'emoji_blob')
+        "#,
+    )
+    .bind(at)
+    .bind(include_inactive)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let row = row.ok_or(StatusCode::NOT_FOUND)?;
+    let emoji = view_from_row(&row).ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let status_count: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*) FROM statuses s
+        WHERE s.emoji_ref = 'at://' || ?
+          AND s.deleted_at IS NULL
+          AND s.did NOT IN (SELECT did FROM actor_takedowns)
+        "#,
+    )
+    .bind(at)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let user_rows = sqlx::query(
+        r#"
+        SELECT s.did, p.handle, MAX(s.created_at) AS last_used
+        FROM statuses s
+        LEFT JOIN profiles p ON s.did = p.did
+        WHERE s.emoji_ref = 'at://' || ?
+          AND s.deleted_at IS NULL
+          AND s.did NOT IN (SELECT did FROM actor_takedowns)
+        GROUP BY s.did
+        ORDER BY last_used DESC
+        LIMIT 10
+        "#,
+    )
+    .bind(at)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let recent_users = user_rows
+        .iter()
+        .filter_map(|row| {
+            Some(EmojiUserView {
+                did: row.try_get("did").ok()?,
+                handle: row.try_get("handle").ok().flatten(),
+            })
+        })
+        .collect();
+
+    Ok(Json(GetEmojiResponse {
+        emoji,
+        status_count,
+        recent_users,
+    }))
+}
+
+pub async fn handle_list_user_emoji(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<ListUserEmojiParams>,
+) -> Result<Json<ListUserEmojiResponse>, StatusCode> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    let include_inactive = super::include_inactive_override(
+        &state,
+        &headers,
+        params.include_inactive.unwrap_or(false),
+    )
+    .await?;
+
+    let did = if params.actor.starts_with("did:") {
+        params.actor.clone()
+    } else {
+        crate::identity::resolve_handle(&state.db, &params.actor)
+            .await
+            .ok_or(StatusCode::NOT_FOUND)?
+    };
+
+    // Cursor is "{created_at}|{at}" from the last row of the previous page
+    let (cursor_created_at, cursor_at) = match params.cursor.as_deref() {
+        Some(cursor) => {
+            let (created_at, at) = cursor.split_once('|').ok_or(StatusCode::BAD_REQUEST)?;
+            (Some(created_at.to_string()), Some(at.to_string()))
+        }
+        None => (None, None),
+    };
+
+    let rows = sqlx::query(
+        r#"
+        SELECT e.at, e.did, e.blob_cid, e.mime_type, e.emoji_name, e.alt_text,
+               COALESCE(e.curated_category, e.category) AS category,
+               (SELECT group_concat(t.tag, ' ') FROM emoji_tags t WHERE t.emoji_at = e.at) AS tags,
+               p.handle, e.created_at,
+               (SELECT e2.at FROM emojis e2
+                WHERE e2.blob_cid = e.blob_cid AND e2.deleted_at IS NULL
+                ORDER BY (SELECT COUNT(*) FROM statuses su WHERE su.emoji_ref = 'at://' || e2.at) DESC,
+                         e2.created_at ASC, e2.at ASC
+                LIMIT 1) AS canonical_at
+        FROM emojis e
+        LEFT JOIN profiles p ON e.did = p.did
+        WHERE e.did = ?
+          AND e.deleted_at IS NULL
+          AND e.did NOT IN (SELECT did FROM actor_takedowns)
+          AND (? OR e.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active'))
+          AND e.blob_cid NOT IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'emoji_blob')
+          AND (? IS NULL OR e.created_at < ? OR (e.created_at = ? AND e.at > ?))
+        ORDER BY e.created_at DESC, e.at
+        LIMIT ?
+        "#,
+    )
+    .bind(&did)
+    .bind(include_inactive)
+    .bind(&cursor_created_at)
+    .bind(&cursor_created_at)
+    .bind(&cursor_created_at)
+    .bind(&cursor_at)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let emojis: Vec<EmojiDetailView> = rows.iter().filter_map(view_from_row).collect();
+
+    // Only hand out a cursor when the page was full; a short page is the end
+    let cursor = if rows.len() as i64 == limit {
+        rows.last().and_then(|row| {
+            let created_at: String = row.try_get("created_at").ok()?;
+            let at: String = row.try_get("at").ok()?;
+            Some(format!("{}|{}", created_at, at))
+        })
+    } else {
+        None
+    };
+
+    Ok(Json(ListUserEmojiResponse { emojis, cursor }))
+}
@@ -0,0 +1,365 @@
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+
+use crate::AppState;
+
+use super::moderation::extract_authenticated_did;
+
+fn export_dir() -> String {
+    std::env::var("ISTAT_EXPORT_DIR").unwrap_or_else(|_| "./exports".to_string())
+}
+
+// Request/Response types
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportMyDataResponse {
+    pub job_id: String,
+    pub status: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetExportParams {
+    pub job_id: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetExportResponse {
+    pub job_id: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadExportParams {
+    pub job_id: String,
+    pub token: String,
+}
+
+/// Collect rows from a table for a DID as JSON objects, using the listed columns.
+async fn collect_rows(
+    db: &sqlx::SqlitePool,
+    table: &str,
+    columns: &[&str],
+    did: &str,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    let sql = format!("SELECT {} FROM {} WHERE did = ?", columns.join(", "), table);
+    let rows = sqlx::query(&sql).bind(did).fetch_all(db).await?;
+
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let mut obj = serde_json::Map::new();
+            for col in columns {
+                let value: Option<String> = row.try_get(*col).ok().flatten();
+                obj.insert(
+                    col.to_string(),
+                    value
+                        .map(serde_json::Value::String)
+                        .unwrap_or(serde_json::Value::Null),
+                );
+            }
+            serde_json::Value::Object(obj)
+        })
+        .collect())
+}
+
+/// Build the export archive for a DID: everything we've indexed, plus the
+/// emoji image bytes (base64) so the archive is self-contained.
+async fn build_archive(db: &sqlx::SqlitePool, did: &str) -> anyhow::Result<serde_json::Value> {
+    use base64::Engine;
+
+    let profile = collect_rows(
+        db,
+        "profiles",
+        &[
+            "did",
+            "handle",
+            "display_name",
+            "description",
+            "avatar_cid",
+            "banner_cid",
+            "pronouns",
+            "website",
+            "timezone",
+            "created_at",
+            "updated_at",
+            "account_status",
+        ],
+        did,
+    )
+    .await?;
+
+    let statuses = collect_rows(
+        db,
+        "statuses",
+        &[
+            "at",
+            "did",
+            "rkey",
+            "emoji_ref",
+            "emoji_ref_cid",
+            "title",
+            "description",
+            "expires",
+            "timezone",
+            "created_at",
+            "deleted_at",
+        ],
+        did,
+    )
+    .await?;
+
+    let mut packs = collect_rows(
+        db,
+        "emoji_packs",
+        &[
+            "at",
+            "did",
+            "rkey",
+            "name",
+            "description",
+            "created_at",
+            "deleted_at",
+        ],
+        did,
+    )
+    .await?;
+
+    // Attach each pack's membership so the archive is self-describing
+    for pack in &mut packs {
+        let Some(at) = pack.get("at").and_then(|v| v.as_str()).map(|s| s.to_string()) else {
+            continue;
+        };
+        let members: Vec<String> = sqlx::query_scalar(
+            "SELECT emoji_ref FROM emoji_pack_items WHERE pack_at = ? ORDER BY position",
+        )
+        .bind(&at)
+        .fetch_all(db)
+        .await?;
+        if let Some(obj) = pack.as_object_mut() {
+            obj.insert("emojis".to_string(), serde_json::json!(members));
+        }
+    }
+
+    let mut emojis = collect_rows(
+        db,
+        "emojis",
+        &[
+            "at",
+            "did",
+            "blob_cid",
+            "mime_type",
+            "emoji_name",
+            "alt_text",
+            "created_at",
+            "deleted_at",
+        ],
+        did,
+    )
+    .await?;
+
+    // Fetch the referenced emoji images so the archive is complete on its own
+    for emoji in &mut emojis {
+        let blob_cid = emoji.get("blob_cid").and_then(|v| v.as_str());
+        let mime_type = emoji.get("mime_type").and_then(|v| v.as_str());
+
+        if let (Some(cid), Some(mime)) = (blob_cid, mime_type) {
+            let mime_ext = match mime {
+                "image/png" => "png",
+                "image/jpeg" => "jpeg",
+                "image/jpg" => "jpeg",
+                "image/webp" => "webp",
+                "image/gif" => "gif",
+                _ => "jpeg",
+            };
+            if let Some(bytes) = crate::img::fetch_blob(&did, cid, mime_ext).await {
+                let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+                if let Some(obj) = emoji.as_object_mut() {
+                    obj.insert(
+                        "image_base64".to_string(),
+                        serde_json::Value::String(encoded),
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(serde_json::json!({
+        "did": did,
+        "exported_at": chrono::Utc::now().to_rfc3339(),
+        "profile": profile,
+        "statuses": statuses,
+        "emojis": emojis,
+        "packs": packs,
+    }))
+}
+
+/// Run an export job to completion, updating its row as it goes.
+async fn run_export_job(state: AppState, job_id: String, did: String) {
+    let _ = sqlx::query("UPDATE export_jobs SET status = 'processing' WHERE id = ?")
+        .bind(&job_id)
+        .execute(&state.db)
+        .await;
+
+    let result = async {
+        let archive = build_archive(&state.db, &did).await?;
+
+        let dir = export_dir();
+        tokio::fs::create_dir_all(&dir).await?;
+        let file_path = format!("{}/{}.json", dir, job_id);
+        tokio::fs::write(&file_path, serde_json::to_vec_pretty(&archive)?).await?;
+
+        Ok::<String, anyhow::Error>(file_path)
+    }
+    .await;
+
+    match result {
+        Ok(file_path) => {
+            let _ = sqlx::query(
+                "UPDATE export_jobs SET status = 'complete', file_path = ?, completed_at = datetime('now') WHERE id = ?"
+            )
+            .bind(&file_path)
+            .bind(&job_id)
+            .execute(&state.db)
+            .await;
+            tracing::info!("Export job {} complete for {}", job_id, did);
+        }
+        Err(e) => {
+            let _ = sqlx::query(
+                "UPDATE export_jobs SET status = 'failed', error = ?, completed_at = datetime('now') WHERE id = ?"
+            )
+            .bind(e.to_string())
+            .bind(&job_id)
+            .execute(&state.db)
+            .await;
+            tracing::warn!("Export job {} failed for {}: {}", job_id, did, e);
+        }
+    }
+}
+
+// Endpoint handlers
+
+pub async fn handle_export_my_data(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ExportMyDataResponse>, StatusCode> {
+    let did = extract_authenticated_did(&headers, &state).await?;
+
+    // Both come from the proxy crate's CSPRNG-backed token type; the
+    // download token is the only credential guarding the archive
+    let job_id = jacquard_oatproxy::SecureToken::with_entropy(16).into_string();
+    let download_token = jacquard_oatproxy::SecureToken::new().into_string();
+
+    sqlx::query("INSERT INTO export_jobs (id, did, download_token) VALUES (?, ?, ?)")
+        .bind(&job_id)
+        .bind(&did)
+        .bind(&download_token)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Generate the archive in the background; the client polls getExport
+    let job_state = state.clone();
+    let job_id_clone = job_id.clone();
+    tokio::spawn(async move {
+        run_export_job(job_state, job_id_clone, did).await;
+    });
+
+    Ok(Json(ExportMyDataResponse {
+        job_id,
+        status: "pending".to_string(),
+    }))
+}
+
+pub async fn handle_get_export(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<GetExportParams>,
+) -> Result<Json<GetExportResponse>, StatusCode> {
+    let did = extract_authenticated_did(&headers, &state).await?;
+
+    let row = sqlx::query(
+        "SELECT status, download_token, error FROM export_jobs WHERE id = ? AND did = ?",
+    )
+    .bind(&params.job_id)
+    .bind(&did)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let row = row.ok_or(StatusCode::NOT_FOUND)?;
+
+    let status: String = row
+        .try_get("status")
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let download_token: String = row
+        .try_get("download_token")
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let error: Option<String> = row.try_get("error").ok().flatten();
+
+    let download_url = if status == "complete" {
+        Some(format!(
+            "{}/xrpc/vg.nat.istat.actor.downloadExport?jobId={}&token={}",
+            state.public_url, params.job_id, download_token
+        ))
+    } else {
+        None
+    };
+
+    Ok(Json(GetExportResponse {
+        job_id: params.job_id,
+        status,
+        download_url,
+        error,
+    }))
+}
+
+pub async fn handle_download_export(
+    State(state): State<AppState>,
+    Query(params): Query<DownloadExportParams>,
+) -> Result<Response, StatusCode> {
+    // The download token in the link is the only credential needed here, so
+    // the archive can be fetched from a plain browser download
+    let row = sqlx::query(
+        "SELECT file_path FROM export_jobs WHERE id = ? AND download_token = ? AND status = 'complete'"
+    )
+    .bind(&params.job_id)
+    .bind(&params.token)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let row = row.ok_or(StatusCode::NOT_FOUND)?;
+
+    let file_path: Option<String> = row.try_get("file_path").ok().flatten();
+    let file_path = file_path.ok_or(StatusCode::NOT_FOUND)?;
+
+    let contents = tokio::fs::read(&file_path)
+        .await
+        .map_err(|_| StatusCode::NOT_FOUND)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"istat-export-{}.json\"", params.job_id),
+            ),
+        ],
+        contents,
+    )
+        .into_response())
+}
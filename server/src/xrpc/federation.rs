@@ -0,0 +1,482 @@
+use axum::{
+    Json,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+
+use crate::AppState;
+
+use super::moderation::{log_audit_action, require_admin};
+
+/// Hash a CID for publication so peers can match their own content without us
+/// distributing a directory of the content itself.
+fn hash_cid(cid: &str) -> String {
+    use sha2::{Digest, Sha256};
+    format!("{:x}", Sha256::digest(cid.as_bytes()))
+}
+
+// Published blacklist document
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishedBlacklistEntry {
+    pub hash: String,
+    pub content_type: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishedBlacklist {
+    pub version: u32,
+    pub generated_at: String,
+    pub entries: Vec<PublishedBlacklistEntry>,
+}
+
+// Request/Response types
+
+#[derive(Debug, Deserialize)]
+pub struct AddPeerRequest {
+    pub url: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RemovePeerRequest {
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetPeerEnabledRequest {
+    pub url: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PeerActionResponse {
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerView {
+    pub url: String,
+    pub name: Option<String>,
+    pub enabled: bool,
+    pub added_by: String,
+    pub last_synced_at: Option<String>,
+    pub entry_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListPeersResponse {
+    pub peers: Vec<PeerView>,
+}
+
+// Endpoint handlers
+
+/// Serve our own hashed blacklist at the well-known endpoint.
+/// Opt-in via ISTAT_PUBLISH_BLACKLIST=true.
+pub async fn handle_wellknown_blacklist(
+    State(state): State<AppState>,
+) -> Result<Json<PublishedBlacklist>, StatusCode> {
+    if std::env::var("ISTAT_PUBLISH_BLACKLIST").unwrap_or_default() != "true" {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let rows = sqlx::query("SELECT cid, content_type, reason FROM blacklisted_cids")
+        .fetch_all(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let entries: Vec<PublishedBlacklistEntry> = rows
+        .iter()
+        .filter_map(|row| {
+            let cid: String = row.try_get("cid").ok()?;
+            Some(PublishedBlacklistEntry {
+                hash: hash_cid(&cid),
+                content_type: row.try_get("content_type").ok()?,
+                reason: row.try_get("reason").ok(),
+            })
+        })
+        .collect();
+
+    Ok(Json(PublishedBlacklist {
+        version: 1,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        entries,
+    }))
+}
+
+pub async fn handle_add_peer(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<AddPeerRequest>,
+) -> Result<Json<PeerActionResponse>, StatusCode> {
+    let moderator_did = require_admin(&headers, &state).await?;
+
+    let url = req.url.trim_end_matches('/').to_string();
+    if !url.starts_with("https://") && !url.starts_with("http://") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    sqlx::query("INSERT OR IGNORE INTO moderation_peers (url, name, added_by) VALUES (?, ?, ?)")
+        .bind(&url)
+        .bind(&req.name)
+        .bind(&moderator_did)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    log_audit_action(
+        &state,
+        &moderator_did,
+        "add_moderation_peer",
+        "peer",
+        &url,
+        None,
+        None,
+    )
+    .await?;
+
+    // Pull the peer's list right away instead of waiting for the next cycle
+    let db = state.db.clone();
+    tokio::spawn(async move {
+        if let Err(e) = sync_peer(&db, &url).await {
+            tracing::warn!("Initial sync of moderation peer {} failed: {}", url, e);
+        }
+    });
+
+    Ok(Json(PeerActionResponse { success: true }))
+}
+
+pub async fn handle_remove_peer(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<RemovePeerRequest>,
+) -> Result<Json<PeerActionResponse>, StatusCode> {
+    let moderator_did = require_admin(&headers, &state).await?;
+
+    let url = req.url.trim_end_matches('/').to_string();
+
+    let result = sqlx::query("DELETE FROM moderation_peers WHERE url = ?")
+        .bind(&url)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    log_audit_action(
+        &state,
+        &moderator_did,
+        "remove_moderation_peer",
+        "peer",
+        &url,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(Json(PeerActionResponse { success: true }))
+}
+
+pub async fn handle_set_peer_enabled(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<SetPeerEnabledRequest>,
+) -> Result<Json<PeerActionResponse>, StatusCode> {
+    let moderator_did = require_admin(&headers, &state).await?;
+
+    let url = req.url.trim_end_matches('/').to_string();
+
+    let result = sqlx::query("UPDATE moderation_peers SET enabled = ? WHERE url = ?")
+        .bind(req.enabled)
+        .bind(&url)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    log_audit_action(
+        &state,
+        &moderator_did,
+        if req.enabled {
+            "enable_moderation_peer"
+        } else {
+            "disable_moderation_peer"
+        },
+        "peer",
+        &url,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(Json(PeerActionResponse { success: true }))
+}
+
+pub async fn handle_list_peers(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ListPeersResponse>, StatusCode> {
+    let _ = require_admin(&headers, &state).await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT p.url, p.name, p.enabled, p.added_by, p.last_synced_at,
+               (SELECT COUNT(*) FROM peer_blacklist_hashes h WHERE h.source_url = p.url) as entry_count
+        FROM moderation_peers p
+        ORDER BY p.created_at DESC
+        "#,
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let peers: Vec<PeerView> = rows
+        .iter()
+        .filter_map(|row| {
+            Some(PeerView {
+                url: row.try_get("url").ok()?,
+                name: row.try_get("name").ok().flatten(),
+                enabled: row.try_get("enabled").ok()?,
+                added_by: row.try_get("added_by").ok()?,
+                last_synced_at: row.try_get("last_synced_at").ok().flatten(),
+                entry_count: row.try_get("entry_count").ok()?,
+            })
+        })
+        .collect();
+
+    Ok(Json(ListPeersResponse { peers }))
+}
+
+// Peer syncing
+
+/// Fetch a single peer's published blacklist and merge it into our tables.
+async fn sync_peer(db: &SqlitePool, peer_url: &str) -> anyhow::Result<()> {
+    let doc_url = format!("{}/.well-known/istat-blacklist.json", peer_url);
+    let resp = crate::outbound::get(&doc_url).await?;
+    if !resp.status().is_success() {
+        anyhow::bail!("peer returned {}", resp.status());
+    }
+    let doc: PublishedBlacklist = resp.json().await?;
+
+    // Replace this peer's hash set wholesale; the peer's document is canonical
+    sqlx::query("DELETE FROM peer_blacklist_hashes WHERE source_url = ?")
+        .bind(peer_url)
+        .execute(db)
+        .await?;
+
+    for entry in &doc.entries {
+        sqlx::query(
+            "INSERT OR IGNORE INTO peer_blacklist_hashes (hash, content_type, reason, source_url) VALUES (?, ?, ?, ?)"
+        )
+        .bind(&entry.hash)
+        .bind(&entry.content_type)
+        .bind(&entry.reason)
+        .bind(peer_url)
+        .execute(db)
+        .await?;
+    }
+
+    // Materialize matches against CIDs we've actually indexed, so the
+    // filtering queries can stay as plain joins
+    sqlx::query("DELETE FROM peer_blacklisted_cids WHERE source_url = ?")
+        .bind(peer_url)
+        .execute(db)
+        .await?;
+
+    let local_cids: Vec<(String, String)> = {
+        let mut cids = Vec::new();
+
+        let emoji_rows =
+            sqlx::query("SELECT DISTINCT blob_cid FROM emojis WHERE blob_cid IS NOT NULL")
+                .fetch_all(db)
+                .await?;
+        for row in &emoji_rows {
+            if let Ok(cid) = row.try_get::<String, _>("blob_cid") {
+                cids.push((cid, "emoji_blob".to_string()));
+            }
+        }
+
+        let profile_rows = sqlx::query(
+            "SELECT avatar_cid, banner_cid FROM profiles WHERE avatar_cid IS NOT NULL OR banner_cid IS NOT NULL"
+        )
+        .fetch_all(db)
+        .await?;
+        for row in &profile_rows {
+            if let Ok(Some(cid)) = row.try_get::<Option<String>, _>("avatar_cid") {
+                cids.push((cid, "avatar".to_string()));
+            }
+            if let Ok(Some(cid)) = row.try_get::<Option<String>, _>("banner_cid") {
+                cids.push((cid, "banner".to_string()));
+            }
+        }
+
+        cids
+    };
+
+    let mut matched = 0;
+    for (cid, content_type) in &local_cids {
+        let hash = hash_cid(cid);
+        let hit = sqlx::query(
+            "SELECT reason FROM peer_blacklist_hashes WHERE hash = ? AND content_type = ? AND source_url = ?"
+        )
+        .bind(&hash)
+        .bind(content_type)
+        .bind(peer_url)
+        .fetch_optional(db)
+        .await?;
+
+        if let Some(row) = hit {
+            let reason: Option<String> = row.try_get("reason").ok().flatten();
+            sqlx::query(
+                "INSERT OR IGNORE INTO peer_blacklisted_cids (cid, content_type, reason, source_url) VALUES (?, ?, ?, ?)"
+            )
+            .bind(cid)
+            .bind(content_type)
+            .bind(&reason)
+            .bind(peer_url)
+            .execute(db)
+            .await?;
+            matched += 1;
+        }
+    }
+
+    sqlx::query("UPDATE moderation_peers SET last_synced_at = datetime('now') WHERE url = ?")
+        .bind(peer_url)
+        .execute(db)
+        .await?;
+
+    tracing::info!(
+        "Synced moderation peer {}: {} entries, {} local matches",
+        peer_url,
+        doc.entries.len(),
+        matched
+    );
+
+    Ok(())
+}
+
+/// Periodically re-sync all enabled moderation peers.
+pub async fn start_peer_sync(db: SqlitePool) {
+    let interval_secs = std::env::var("ISTAT_PEER_SYNC_INTERVAL_SECS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(3600u64);
+
+    loop {
+        let peers: Vec<String> =
+            match sqlx::query_scalar("SELECT url FROM moderation_peers WHERE enabled = 1")
+                .fetch_all(&db)
+                .await
+            {
+                Ok(peers) => peers,
+                Err(e) => {
+                    tracing::warn!("Failed to list moderation peers: {}", e);
+                    Vec::new()
+                }
+            };
+
+        for peer_url in peers {
+            if let Err(e) = sync_peer(&db, &peer_url).await {
+                tracing::warn!("Failed to sync moderation peer {}: {}", peer_url, e);
+            }
+        }
+
+        tokio::time::sleep(std::time::Duration::from_secs(interval_secs)).await;
+    }
+}
+
+// WebFinger (RFC 7033) handle discovery
+
+#[derive(Debug, Deserialize)]
+pub struct WebFingerQuery {
+    pub resource: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebFingerLink {
+    pub rel: String,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub link_type: Option<String>,
+    pub href: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WebFingerDocument {
+    pub subject: String,
+    pub aliases: Vec<String>,
+    pub links: Vec<WebFingerLink>,
+}
+
+/// Resolve `acct:` WebFinger queries to the account's DID and istat profile
+/// URL, so fediverse tooling can discover istat users. Backed by the local
+/// profiles table with a resolver fallback for accounts we haven't indexed.
+pub async fn handle_webfinger(
+    State(state): State<AppState>,
+    axum::extract::Query(query): axum::extract::Query<WebFingerQuery>,
+) -> Result<impl axum::response::IntoResponse, StatusCode> {
+    let resource = query.resource.trim();
+    let acct = resource
+        .strip_prefix("acct:")
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    // Fediverse-style user@domain maps onto the ATProto handle user.domain;
+    // a bare handle is accepted as-is
+    let handle = match acct.split_once('@') {
+        Some((user, domain)) if !user.contains('.') => format!("{}.{}", user, domain),
+        Some((user, _domain)) => user.to_string(),
+        None => acct.to_string(),
+    };
+
+    // Local index first, resolver fallback for unindexed accounts
+    let did: Option<String> = sqlx::query_scalar("SELECT did FROM profiles WHERE handle = ?")
+        .bind(&handle)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let did = match did {
+        Some(did) => did,
+        None => crate::identity::resolve_handle(&state.db, &handle)
+            .await
+            .ok_or(StatusCode::NOT_FOUND)?,
+    };
+
+    let base_url = state.public_url.trim_end_matches('/');
+    let profile_url = format!("{}/{}", base_url, handle);
+
+    let document = WebFingerDocument {
+        subject: format!("acct:{}", acct),
+        aliases: vec![format!("at://{}", did), profile_url.clone()],
+        links: vec![
+            WebFingerLink {
+                rel: "self".to_string(),
+                link_type: Some("application/json".to_string()),
+                href: format!(
+                    "{}/xrpc/vg.nat.istat.actor.getProfile?actor={}",
+                    base_url, did
+                ),
+            },
+            WebFingerLink {
+                rel: "http://webfinger.net/rel/profile-page".to_string(),
+                link_type: Some("text/html".to_string()),
+                href: profile_url,
+            },
+        ],
+    };
+
+    Ok((
+        [("content-type", "application/jrd+json")],
+        Json(document),
+    ))
+}
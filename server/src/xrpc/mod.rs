@@ -9,37 +9,126 @@ use lexicons::vg_nat::istat::{
     moji::search_emoji::{SearchEmojiOutput, SearchEmojiRequest},
     status::{
         get_status::{GetStatusOutput, GetStatusRequest},
+        list_replies::{ListRepliesOutput, ListRepliesRequest},
         list_statuses::{ListStatusesOutput, ListStatusesRequest},
         list_user_statuses::{ListUserStatusesOutput, ListUserStatusesRequest},
     },
 };
 use sqlx::Row;
-use std::{collections::BTreeMap, str::FromStr};
+use std::str::FromStr;
 
 use crate::AppState;
 
+pub mod consistency;
+pub mod emoji;
+pub mod export;
+pub mod federation;
 pub mod moderation;
+pub mod pack;
+pub mod publish;
+pub mod status;
+pub mod timeline;
+
+/// Curated picker categories. Record values outside this set are dropped at
+/// ingest, and the admin curation endpoint rejects anything else.
+pub const EMOJI_CATEGORIES: &[&str] = &[
+    "smileys",
+    "people",
+    "animals",
+    "food",
+    "activities",
+    "travel",
+    "objects",
+    "symbols",
+    "flags",
+    "memes",
+];
+
+/// Build the public URL for a blob image. Defaults to the local blob
+/// proxy route (see `crate::img`); set `ISTAT_IMG_BASE` to an external
+/// CDN base to serve from there instead.
+pub fn img_url(did: &str, cid: &str, format: &str) -> String {
+    let base = std::env::var("ISTAT_IMG_BASE").unwrap_or_else(|_| "/img".to_string());
+    format!("{}/{}/{}@{}", base.trim_end_matches('/'), did, cid, format)
+}
+
+/// Served in place of avatars and banners whose CID has been blacklisted
+/// by moderation (locally or via a peer), so clients render a neutral
+/// image instead of the original blob.
+pub const BLOCKED_IMAGE_URL: &str = "/blocked-image.svg";
+
+/// Normalize a free-form emoji tag for indexing: trimmed, lowercased, inner
+/// whitespace collapsed to `-`. Returns None for tags that normalize to
+/// nothing or exceed the lexicon length limit.
+pub fn normalize_emoji_tag(tag: &str) -> Option<String> {
+    let normalized = tag
+        .trim()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-");
+    if normalized.is_empty() || normalized.len() > 64 {
+        None
+    } else {
+        Some(normalized)
+    }
+}
+
+/// Build an FTS5 MATCH expression from user input: each alphanumeric
+/// term quoted and prefix-matched (so "par" finds "party_parrot"), terms
+/// combined with implicit AND. Returns None when the query has no
+/// indexable terms, which sends the search down the LIKE path.
+pub(crate) fn fts5_match_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .take(8)
+        .map(|t| format!("\"{}\"*", t))
+        .collect();
+    if terms.is_empty() {
+        None
+    } else {
+        Some(terms.join(" "))
+    }
+}
+
+/// Admin override for the account-status read filter. Non-active
+/// accounts (suspended, deactivated, deleted upstream) are hidden from
+/// every read endpoint; an admin can pass `includeInactive=true` to see
+/// them anyway. Non-admins asking for the override get 403.
+pub(crate) async fn include_inactive_override(
+    state: &AppState,
+    headers: &axum::http::HeaderMap,
+    requested: bool,
+) -> Result<bool, StatusCode> {
+    if !requested {
+        return Ok(false);
+    }
+    let did = moderation::extract_authenticated_did(headers, state).await?;
+    if moderation::is_admin(&did, state).await? {
+        Ok(true)
+    } else {
+        Err(StatusCode::FORBIDDEN)
+    }
+}
+
+/// True when a raw `includeInactive` query value asks for the override
+pub(crate) fn inactive_requested(params: &std::collections::HashMap<String, String>) -> bool {
+    params
+        .get("includeInactive")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false)
+}
 
 pub async fn handle_resolve(
+    State(state): State<AppState>,
     ExtractXrpc(req): ExtractXrpc<ResolveHandleRequest>,
 ) -> Result<Json<ResolveHandleOutput<'static>>, StatusCode> {
     let handle = req.handle;
-    let url = format!(
-        "https://public.api.bsky.app/xrpc/com.atproto.identity.resolveHandle?handle={}",
-        handle
-    );
-    let resp = reqwest::get(&url)
+    let did_str = crate::identity::resolve_handle(&state.db, handle.as_str())
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    if !resp.status().is_success() {
-        return Err(StatusCode::NOT_FOUND);
-    }
-    let resp_json: BTreeMap<String, String> = resp
-        .json()
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let did_str = resp_json.get("did").ok_or(StatusCode::NOT_FOUND)?;
-    let did = Did::from_str(did_str).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let did = Did::from_str(&did_str).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     let output = ResolveHandleOutput {
         did,
         extra_data: None,
@@ -50,45 +139,39 @@ pub async fn handle_resolve(
 
 pub async fn handle_get_status(
     State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(raw_params): axum::extract::Query<
+        std::collections::HashMap<String, String>,
+    >,
     ExtractXrpc(req): ExtractXrpc<GetStatusRequest>,
 ) -> Result<Json<GetStatusOutput<'static>>, StatusCode> {
     let handle = req.handle;
     let rkey = req.rkey;
+    let include_inactive =
+        include_inactive_override(&state, &headers, inactive_requested(&raw_params)).await?;
 
-    let url = format!(
-        "https://public.api.bsky.app/xrpc/com.atproto.identity.resolveHandle?handle={}",
-        handle
-    );
-    let resp = reqwest::get(&url)
+    let did = crate::identity::resolve_handle(&state.db, handle.as_str())
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    if !resp.status().is_success() {
-        return Err(StatusCode::NOT_FOUND);
-    }
-    let resp_json: BTreeMap<String, String> = resp
-        .json()
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let did = resp_json
-        .get("did")
-        .ok_or(StatusCode::NOT_FOUND)?
-        .to_string();
+        .ok_or(StatusCode::NOT_FOUND)?;
 
-    let at_uri = format!("{}/vg.nat.istat.status.record/{}", did, rkey);
+    let at_uri = crate::collections::registry().status_at(&did, &rkey);
 
     let row = sqlx::query(
         r#"
-        SELECT s.at, s.emoji_ref, s.emoji_ref_cid, s.title, s.description, s.expires, s.created_at,
+        SELECT s.at, s.emoji_ref, s.emoji_ref_cid, s.title, s.description, s.expires, s.timezone, s.created_at,
                e.mime_type, e.blob_cid
         FROM statuses s
         LEFT JOIN emojis e ON s.emoji_ref = 'at://' || e.at
         WHERE s.at = ?
           AND s.deleted_at IS NULL
+          AND s.did NOT IN (SELECT did FROM actor_takedowns)
+          AND (? OR s.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active'))
           AND (e.deleted_at IS NULL OR e.at IS NULL)
-          AND (e.blob_cid NOT IN (SELECT cid FROM blacklisted_cids WHERE content_type = 'emoji_blob') OR e.blob_cid IS NULL)
+          AND (e.blob_cid NOT IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'emoji_blob') OR e.blob_cid IS NULL)
         "#,
     )
     .bind(&at_uri)
+    .bind(include_inactive)
     .fetch_optional(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -102,6 +185,7 @@ pub async fn handle_get_status(
     let title: Option<String> = row.try_get("title").ok();
     let description: Option<String> = row.try_get("description").ok();
     let expires: Option<String> = row.try_get("expires").ok();
+    let timezone: Option<String> = row.try_get("timezone").ok().flatten();
     let created_at: String = row
         .try_get("created_at")
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -122,16 +206,14 @@ pub async fn handle_get_status(
         .split('/')
         .last()
         .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
-    let emoji_url = format!(
-        "https://at.uwu.wang/{}/{}@{}",
-        did, emoji_blob_cid, mime_ext
-    );
+    let emoji_url = img_url(&did, emoji_blob_cid, mime_ext);
 
     let output = GetStatusOutput {
         emoji_url: emoji_url.into(),
         title: title.map(|t| t.into()),
         description: description.map(|d| d.into()),
         expires: expires.map(|e| jacquard_common::types::string::Datetime::raw_str(e)),
+        timezone: timezone.map(Into::into),
         created_at: jacquard_common::types::string::Datetime::raw_str(created_at),
         extra_data: None,
     };
@@ -141,43 +223,38 @@ pub async fn handle_get_status(
 
 pub async fn handle_get_profile(
     State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(raw_params): axum::extract::Query<
+        std::collections::HashMap<String, String>,
+    >,
     ExtractXrpc(req): ExtractXrpc<GetProfileRequest>,
 ) -> Result<Json<GetProfileOutput<'static>>, StatusCode> {
     let actor = req.actor;
+    let include_inactive =
+        include_inactive_override(&state, &headers, inactive_requested(&raw_params)).await?;
 
     // resolve to DID if it's a handle
     let did = if actor.as_str().starts_with("did:") {
         actor.to_string()
     } else {
-        let url = format!(
-            "https://public.api.bsky.app/xrpc/com.atproto.identity.resolveHandle?handle={}",
-            actor
-        );
-        let resp = reqwest::get(&url)
-            .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        if !resp.status().is_success() {
-            return Err(StatusCode::NOT_FOUND);
-        }
-        let resp_json: BTreeMap<String, String> = resp
-            .json()
+        crate::identity::resolve_handle(&state.db, actor.as_str())
             .await
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        resp_json
-            .get("did")
             .ok_or(StatusCode::NOT_FOUND)?
-            .to_string()
     };
 
     let row = sqlx::query(
         r#"
         SELECT did, handle, display_name, description, avatar_cid, banner_cid,
-               pronouns, website, created_at
+               pronouns, website, timezone, created_at,
+               COALESCE(avatar_cid IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'avatar'), 0) AS avatar_blacklisted,
+               COALESCE(banner_cid IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'banner'), 0) AS banner_blacklisted
         FROM profiles
         WHERE did = ?
+          AND (? OR account_status = 'active')
         "#,
     )
     .bind(&did)
+    .bind(include_inactive)
     .fetch_optional(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -195,10 +272,22 @@ pub async fn handle_get_profile(
     let banner_cid: Option<String> = row.try_get("banner_cid").ok().flatten();
     let pronouns: Option<String> = row.try_get("pronouns").ok().flatten();
     let website: Option<String> = row.try_get("website").ok().flatten();
+    let timezone: Option<String> = row.try_get("timezone").ok().flatten();
     let created_at: Option<String> = row.try_get("created_at").ok().flatten();
 
-    let avatar = avatar_cid.map(|cid| format!("https://at.uwu.wang/{}/{}@webp", did, cid));
-    let banner = banner_cid.map(|cid| format!("https://at.uwu.wang/{}/{}@webp", did, cid));
+    let avatar_blacklisted: bool = row.try_get("avatar_blacklisted").unwrap_or(false);
+    let banner_blacklisted: bool = row.try_get("banner_blacklisted").unwrap_or(false);
+
+    let avatar = if avatar_blacklisted {
+        Some(BLOCKED_IMAGE_URL.to_string())
+    } else {
+        avatar_cid.map(|cid| img_url(&did, &cid, "webp"))
+    };
+    let banner = if banner_blacklisted {
+        Some(BLOCKED_IMAGE_URL.to_string())
+    } else {
+        banner_cid.map(|cid| img_url(&did, &cid, "webp"))
+    };
 
     let output = GetProfileOutput {
         did: DidType::from_str(&did).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
@@ -208,6 +297,7 @@ pub async fn handle_get_profile(
         avatar: avatar.map(Into::into),
         banner: banner.map(Into::into),
         pronouns: pronouns.map(Into::into),
+        timezone: timezone.map(Into::into),
         website: website.map(Into::into),
         created_at: created_at
             .filter(|s| !s.is_empty() && s.contains('T'))
@@ -220,37 +310,182 @@ pub async fn handle_get_profile(
 
 pub async fn handle_search_emoji(
     State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(raw_params): axum::extract::Query<
+        std::collections::HashMap<String, String>,
+    >,
     ExtractXrpc(req): ExtractXrpc<SearchEmojiRequest>,
 ) -> Result<Json<SearchEmojiOutput<'static>>, StatusCode> {
     let query = req.query;
     let limit = req.limit.unwrap_or(20).min(100) as i64;
+    let include_inactive =
+        include_inactive_override(&state, &headers, inactive_requested(&raw_params)).await?;
 
-    // Use LIKE for simple case-insensitive search
-    // SQLite FTS would be better for production, but this works for now
-    let search_pattern = format!("%{}%", query);
+    // The curated override wins over the record category when filtering
+    let category = req.category.as_ref().map(|c| c.to_string());
+    if let Some(ref category) = category {
+        if !EMOJI_CATEGORIES.contains(&category.as_str()) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+    // Tags are stored normalized, so normalize the filter the same way
+    let tag = match req.tag.as_ref() {
+        Some(t) => Some(normalize_emoji_tag(t).ok_or(StatusCode::BAD_REQUEST)?),
+        None => None,
+    };
 
-    let rows = sqlx::query(
-        r#"
-        SELECT e.at, e.did, e.blob_cid, e.mime_type, e.emoji_name, e.alt_text,
-               p.handle
-        FROM emojis e
-        LEFT JOIN profiles p ON e.did = p.did
-        WHERE (e.emoji_name LIKE ? COLLATE NOCASE
-           OR e.alt_text LIKE ? COLLATE NOCASE)
-          AND e.deleted_at IS NULL
-          AND e.blob_cid NOT IN (SELECT cid FROM blacklisted_cids WHERE content_type = 'emoji_blob')
-        ORDER BY e.created_at DESC
-        LIMIT ?
-        "#,
-    )
-    .bind(&search_pattern)
-    .bind(&search_pattern)
-    .bind(limit)
-    .fetch_all(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    // Keyset cursor carrying the rank, created_at, and row key of the last
+    // item on the previous page; ordering is rank, then created_at DESC,
+    // then the row key so pages stay stable under concurrent inserts
+    let (cursor_rank, cursor_created_at, cursor_at) = match req.cursor.as_deref() {
+        Some(cursor) => {
+            let mut parts = cursor.splitn(3, '|');
+            let rank: i64 = parts
+                .next()
+                .and_then(|r| r.parse().ok())
+                .ok_or(StatusCode::BAD_REQUEST)?;
+            let created_at = parts.next().ok_or(StatusCode::BAD_REQUEST)?.to_string();
+            let at = parts.next().ok_or(StatusCode::BAD_REQUEST)?.to_string();
+            (Some(rank), Some(created_at), Some(at))
+        }
+        None => (None, None, None),
+    };
+
+    // FTS5 first: every term prefix-matched so partial names hit, ranked
+    // by how often the emoji is actually used in statuses (rank is the
+    // negated usage count, so the shared keyset-cursor shape still
+    // applies). The virtual table is created best-effort at startup, so
+    // a failed query here — no FTS5 in this SQLite build — falls back to
+    // the LIKE scan below. Re-uploads of the same blob collapse to one
+    // canonical row per blob CID: the most-used copy, oldest on a tie.
+    let fts_rows = match fts5_match_query(&query) {
+        Some(match_expr) => {
+            let result = sqlx::query(
+                r#"
+                SELECT * FROM (
+                    SELECT e.at, e.did, e.blob_cid, e.mime_type, e.emoji_name, e.alt_text,
+                           COALESCE(e.curated_category, e.category) AS category,
+                           (SELECT group_concat(t.tag, ' ') FROM emoji_tags t WHERE t.emoji_at = e.at) AS tags,
+                           (SELECT group_concat(pi.pack_at, ' ') FROM emoji_pack_items pi
+                            JOIN emoji_packs pk ON pk.at = pi.pack_at AND pk.deleted_at IS NULL
+                            WHERE pi.emoji_ref = 'at://' || e.at) AS packs,
+                           p.handle, e.created_at,
+                           -(SELECT COUNT(*) FROM statuses s WHERE s.emoji_ref = 'at://' || e.at) AS rank
+                    FROM emoji_fts(?) f
+                    JOIN emojis e ON e.at = f.at
+                    LEFT JOIN profiles p ON e.did = p.did
+                    WHERE (? IS NULL OR COALESCE(e.curated_category, e.category) = ?)
+                      AND (? IS NULL OR e.at IN (SELECT emoji_at FROM emoji_tags WHERE tag = ?))
+                      AND e.deleted_at IS NULL
+                      AND e.did NOT IN (SELECT did FROM actor_takedowns)
+                      AND (? OR e.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active'))
+                      AND e.blob_cid NOT IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'emoji_blob')
+                      AND e.at = (SELECT e2.at FROM emojis e2
+                           WHERE e2.blob_cid = e.blob_cid AND e2.deleted_at IS NULL
+                           ORDER BY (SELECT COUNT(*) FROM statuses su WHERE su.emoji_ref = 'at://' || e2.at) DESC,
+                                    e2.created_at ASC, e2.at ASC
+                           LIMIT 1)
+                )
+                WHERE (? IS NULL OR rank > ?
+                   OR (rank = ? AND (created_at < ? OR (created_at = ? AND at > ?))))
+                ORDER BY rank, created_at DESC, at
+                LIMIT ?
+                "#,
+            )
+            .bind(&match_expr)
+            .bind(&category)
+            .bind(&category)
+            .bind(&tag)
+            .bind(&tag)
+            .bind(include_inactive)
+            .bind(cursor_rank)
+            .bind(cursor_rank)
+            .bind(cursor_rank)
+            .bind(&cursor_created_at)
+            .bind(&cursor_created_at)
+            .bind(&cursor_at)
+            .bind(limit)
+            .fetch_all(&state.db)
+            .await;
+            match result {
+                Ok(rows) => Some(rows),
+                Err(e) => {
+                    tracing::debug!("emoji FTS query failed, falling back to LIKE: {}", e);
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
+    let rows = if let Some(rows) = fts_rows {
+        rows
+    } else {
+        // LIKE fallback: case-insensitive substring scan with tiered
+        // ranks (name, alt text, tag)
+        let search_pattern = format!("%{}%", query);
+
+        sqlx::query(
+            r#"
+            SELECT * FROM (
+                SELECT e.at, e.did, e.blob_cid, e.mime_type, e.emoji_name, e.alt_text,
+                       COALESCE(e.curated_category, e.category) AS category,
+                       (SELECT group_concat(t.tag, ' ') FROM emoji_tags t WHERE t.emoji_at = e.at) AS tags,
+                       (SELECT group_concat(pi.pack_at, ' ') FROM emoji_pack_items pi
+                        JOIN emoji_packs pk ON pk.at = pi.pack_at AND pk.deleted_at IS NULL
+                        WHERE pi.emoji_ref = 'at://' || e.at) AS packs,
+                       p.handle, e.created_at,
+                       CASE
+                           WHEN e.emoji_name LIKE ? COLLATE NOCASE THEN 0
+                           WHEN e.alt_text LIKE ? COLLATE NOCASE THEN 1
+                           ELSE 2
+                       END AS rank
+                FROM emojis e
+                LEFT JOIN profiles p ON e.did = p.did
+                WHERE (e.emoji_name LIKE ? COLLATE NOCASE
+                   OR e.alt_text LIKE ? COLLATE NOCASE
+                   OR e.at IN (SELECT emoji_at FROM emoji_tags WHERE tag LIKE ? COLLATE NOCASE))
+                  AND (? IS NULL OR COALESCE(e.curated_category, e.category) = ?)
+                  AND (? IS NULL OR e.at IN (SELECT emoji_at FROM emoji_tags WHERE tag = ?))
+                  AND e.deleted_at IS NULL
+                  AND e.did NOT IN (SELECT did FROM actor_takedowns)
+                  AND (? OR e.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active'))
+                  AND e.blob_cid NOT IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'emoji_blob')
+                  AND e.at = (SELECT e2.at FROM emojis e2
+                       WHERE e2.blob_cid = e.blob_cid AND e2.deleted_at IS NULL
+                       ORDER BY (SELECT COUNT(*) FROM statuses su WHERE su.emoji_ref = 'at://' || e2.at) DESC,
+                                e2.created_at ASC, e2.at ASC
+                       LIMIT 1)
+            )
+            WHERE (? IS NULL OR rank > ?
+               OR (rank = ? AND (created_at < ? OR (created_at = ? AND at > ?))))
+            ORDER BY rank, created_at DESC, at
+            LIMIT ?
+            "#,
+        )
+        .bind(&search_pattern)
+        .bind(&search_pattern)
+        .bind(&search_pattern)
+        .bind(&search_pattern)
+        .bind(&search_pattern)
+        .bind(&category)
+        .bind(&category)
+        .bind(&tag)
+        .bind(&tag)
+        .bind(include_inactive)
+        .bind(cursor_rank)
+        .bind(cursor_rank)
+        .bind(cursor_rank)
+        .bind(&cursor_created_at)
+        .bind(&cursor_created_at)
+        .bind(&cursor_at)
+        .bind(limit)
+        .fetch_all(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    };
 
-    eprintln!("search_emoji query='{}' found {} rows", query, rows.len());
+    tracing::debug!("search_emoji query='{}' found {} rows", query, rows.len());
 
     use jacquard_common::types::string::{AtUri, Did as DidType, Handle};
     use lexicons::vg_nat::istat::moji::search_emoji::EmojiView;
@@ -265,9 +500,12 @@ pub async fn handle_search_emoji(
             let mime_type: Option<String> = row.try_get("mime_type").ok().flatten();
             let emoji_name: Option<String> = row.try_get("emoji_name").ok().flatten();
             let alt_text: Option<String> = row.try_get("alt_text").ok().flatten();
+            let category: Option<String> = row.try_get("category").ok().flatten();
+            let tags: Option<String> = row.try_get("tags").ok().flatten();
+            let packs: Option<String> = row.try_get("packs").ok().flatten();
             let handle: Option<String> = row.try_get("handle").ok().flatten();
 
-            eprintln!(
+            tracing::debug!(
                 "processing emoji: uri={}, name={:?}, alt={:?}",
                 at_uri, emoji_name, alt_text
             );
@@ -284,24 +522,44 @@ pub async fn handle_search_emoji(
                 })
                 .unwrap_or("jpeg");
 
-            let url = format!("https://at.uwu.wang/{}/{}@{}", did, blob_cid, mime_ext);
+            let url = img_url(&did, &blob_cid, mime_ext);
 
             let result = EmojiView::new()
                 .uri(AtUri::from_str(&at_uri).ok()?)
                 .name(emoji_name.unwrap_or_else(|| "changeme".to_string()))
                 .maybe_alt_text(alt_text.map(Into::into))
+                .maybe_category(category.map(Into::into))
+                .maybe_tags(tags.map(|t| t.split(' ').map(|s| s.to_string().into()).collect()))
+                .maybe_packs(packs.map(|p| {
+                    p.split(' ')
+                        .filter_map(|at| AtUri::from_str(&format!("at://{}", at)).ok())
+                        .collect()
+                }))
                 .url(url)
                 .created_by(DidType::from_str(&did).ok()?)
                 .maybe_created_by_handle(handle.and_then(|h| Handle::from_str(&h).ok()))
                 .blob_cid(blob_cid)
                 .build();
 
-            eprintln!("successfully built emoji view");
+            tracing::debug!("successfully built emoji view");
             Some(result)
         })
         .collect();
 
+    // Only hand out a cursor when the page was full; a short page is the end
+    let cursor = if rows.len() == limit as usize {
+        rows.last().and_then(|row| {
+            let rank: i64 = row.try_get("rank").ok()?;
+            let created_at: String = row.try_get("created_at").ok()?;
+            let at: String = row.try_get("at").ok()?;
+            Some(format!("{}|{}|{}", rank, created_at, at))
+        })
+    } else {
+        None
+    };
+
     let output = SearchEmojiOutput {
+        cursor: cursor.map(Into::into),
         emojis,
         extra_data: None,
     };
@@ -311,48 +569,79 @@ pub async fn handle_search_emoji(
 
 pub async fn handle_list_user_statuses(
     State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(raw_params): axum::extract::Query<
+        std::collections::HashMap<String, String>,
+    >,
     ExtractXrpc(req): ExtractXrpc<ListUserStatusesRequest>,
 ) -> Result<Json<ListUserStatusesOutput<'static>>, StatusCode> {
     let handle = req.handle;
     let limit = req.limit.unwrap_or(50).min(100) as i64;
+    let include_expired = req.include_expired.unwrap_or(false);
+    let include_inactive =
+        include_inactive_override(&state, &headers, inactive_requested(&raw_params)).await?;
+    let from = req.from.map(|d| d.as_str().to_string());
+    let until = req.until.map(|d| d.as_str().to_string());
 
-    let url = format!(
-        "https://public.api.bsky.app/xrpc/com.atproto.identity.resolveHandle?handle={}",
-        handle
-    );
-    let resp = reqwest::get(&url)
+    // Honor any read-your-writes token before querying
+    consistency::await_token_visible(&state.db, &headers).await;
+
+    let did = crate::identity::resolve_handle(&state.db, handle.as_str())
         .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    if !resp.status().is_success() {
-        return Err(StatusCode::NOT_FOUND);
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // Expired statuses are history, not public state: only the owner or
+    // an admin may page through them
+    if include_expired {
+        let caller = moderation::extract_authenticated_did(&headers, &state).await?;
+        if caller != did && !moderation::is_admin(&caller, &state).await? {
+            return Err(StatusCode::FORBIDDEN);
+        }
     }
-    let resp_json: BTreeMap<String, String> = resp
-        .json()
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let did = resp_json
-        .get("did")
-        .ok_or(StatusCode::NOT_FOUND)?
-        .to_string();
+
+    // Cursor is "{created_at}|{rkey}" from the last row of the previous page
+    let (cursor_created_at, cursor_rkey) = match req.cursor.as_deref() {
+        Some(cursor) => {
+            let (created_at, rkey) = cursor.split_once('|').ok_or(StatusCode::BAD_REQUEST)?;
+            (Some(created_at.to_string()), Some(rkey.to_string()))
+        }
+        None => (None, None),
+    };
 
     let rows = sqlx::query(
         r#"
-        SELECT s.rkey, s.emoji_ref, s.title, s.description, s.expires, s.created_at,
+        SELECT s.rkey, s.emoji_ref, s.title, s.description, s.expires, s.timezone, s.created_at,
                p.handle, p.display_name, p.avatar_cid,
+               COALESCE(p.avatar_cid IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'avatar'), 0) AS avatar_blacklisted,
                e.blob_cid as emoji_blob_cid, e.mime_type, e.emoji_name, e.alt_text, e.did as emoji_did
         FROM statuses s
         LEFT JOIN profiles p ON s.did = p.did
         LEFT JOIN emojis e ON s.emoji_ref = 'at://' || e.at
         WHERE s.did = ?
           AND s.deleted_at IS NULL
+          AND s.did NOT IN (SELECT did FROM actor_takedowns)
+          AND (? OR s.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active'))
           AND (e.deleted_at IS NULL OR e.at IS NULL)
-          AND (e.blob_cid NOT IN (SELECT cid FROM blacklisted_cids WHERE content_type = 'emoji_blob') OR e.blob_cid IS NULL)
-          AND (s.expires IS NULL OR datetime(s.expires) > datetime('now'))
-        ORDER BY s.created_at DESC
+          AND (e.blob_cid NOT IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'emoji_blob') OR e.blob_cid IS NULL)
+          AND (? OR s.expires IS NULL OR datetime(s.expires) > datetime('now'))
+          AND (? IS NULL OR datetime(s.created_at) >= datetime(?))
+          AND (? IS NULL OR datetime(s.created_at) <= datetime(?))
+          AND (? IS NULL OR s.created_at < ? OR (s.created_at = ? AND s.rkey < ?))
+        ORDER BY s.created_at DESC, s.rkey DESC
         LIMIT ?
         "#,
     )
     .bind(&did)
+    .bind(include_inactive)
+    .bind(include_expired)
+    .bind(&from)
+    .bind(&from)
+    .bind(&until)
+    .bind(&until)
+    .bind(&cursor_created_at)
+    .bind(&cursor_created_at)
+    .bind(&cursor_created_at)
+    .bind(&cursor_rkey)
     .bind(limit)
     .fetch_all(&state.db)
     .await
@@ -377,6 +666,7 @@ pub async fn handle_list_user_statuses(
                 .ok()
                 .and_then(|s: String| if s.is_empty() { None } else { Some(s) });
             let expires: Option<String> = row.try_get("expires").ok();
+            let timezone: Option<String> = row.try_get("timezone").ok().flatten();
             let created_at: String = row.try_get("created_at").ok()?;
             let handle: Option<String> = row.try_get("handle").ok().flatten();
             let display_name: Option<String> = row.try_get("display_name").ok().flatten();
@@ -399,32 +689,22 @@ pub async fn handle_list_user_statuses(
 
             let emoji_url = if let Some(blob_cid) = emoji_blob_cid {
                 if let Some(emoji_owner_did) = emoji_did {
-                    format!(
-                        "https://at.uwu.wang/{}/{}@{}",
-                        emoji_owner_did, blob_cid, mime_ext
-                    )
+                    img_url(&emoji_owner_did, &blob_cid, mime_ext)
                 } else {
                     // fallback: try to extract DID from emoji_ref
                     emoji_ref
                         .strip_prefix("at://")
                         .and_then(|s| s.split('/').next())
-                        .map(|emoji_owner| {
-                            format!(
-                                "https://at.uwu.wang/{}/{}@{}",
-                                emoji_owner, blob_cid, mime_ext
-                            )
-                        })
-                        .unwrap_or_else(|| {
-                            format!("https://at.uwu.wang/{}/{}@{}", did, blob_cid, mime_ext)
-                        })
+                        .map(|emoji_owner| img_url(emoji_owner, &blob_cid, mime_ext))
+                        .unwrap_or_else(|| img_url(&did, &blob_cid, mime_ext))
                 }
             } else {
                 emoji_ref
                     .split('/')
                     .last()
-                    .map(|cid| format!("https://at.uwu.wang/{}/{}@{}", did, cid, mime_ext))
+                    .map(|cid| img_url(&did, cid, mime_ext))
                     .unwrap_or_else(|| {
-                        eprintln!(
+                        tracing::warn!(
                             "Warning: emoji not found for user status {}, emoji_ref: {}",
                             rkey, emoji_ref
                         );
@@ -432,13 +712,17 @@ pub async fn handle_list_user_statuses(
                     })
             };
 
-            let avatar_url =
-                avatar_cid.map(|cid| format!("https://at.uwu.wang/{}/{}@webp", did, cid));
+            let avatar_blacklisted: bool = row.try_get("avatar_blacklisted").unwrap_or(false);
+            let avatar_url = if avatar_blacklisted {
+                Some(BLOCKED_IMAGE_URL.to_string())
+            } else {
+                avatar_cid.map(|cid| img_url(&did, &cid, "webp"))
+            };
 
             // Validate datetime format before passing to raw_str to avoid panics
             // Skip statuses with invalid datetimes
             if created_at.is_empty() || !created_at.contains('T') {
-                eprintln!("Invalid created_at datetime for status: {}", created_at);
+                tracing::warn!("Invalid created_at datetime for status: {}", created_at);
                 return None;
             }
 
@@ -458,15 +742,27 @@ pub async fn handle_list_user_statuses(
                             .filter(|e| !e.is_empty() && e.contains('T'))
                             .map(|e| Datetime::raw_str(e)),
                     )
+                    .maybe_timezone(timezone.map(Into::into))
                     .created_at(Datetime::raw_str(created_at))
                     .build(),
             )
         })
         .collect();
 
+    // Only hand out a cursor when the page was full; a short page is the end
+    let next_cursor = if rows.len() == limit as usize {
+        rows.last().and_then(|row| {
+            let created_at: String = row.try_get("created_at").ok()?;
+            let rkey: String = row.try_get("rkey").ok()?;
+            Some(format!("{}|{}", created_at, rkey))
+        })
+    } else {
+        None
+    };
+
     let output = ListUserStatusesOutput {
         statuses,
-        cursor: None,
+        cursor: next_cursor.map(Into::into),
         extra_data: None,
     };
 
@@ -475,32 +771,46 @@ pub async fn handle_list_user_statuses(
 
 pub async fn handle_list_statuses(
     State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(raw_params): axum::extract::Query<
+        std::collections::HashMap<String, String>,
+    >,
     ExtractXrpc(req): ExtractXrpc<ListStatusesRequest>,
 ) -> Result<Json<ListStatusesOutput<'static>>, StatusCode> {
     let limit = req.limit.unwrap_or(50).min(100) as i64;
+    let include_inactive =
+        include_inactive_override(&state, &headers, inactive_requested(&raw_params)).await?;
+
+    // Honor any read-your-writes token before querying
+    consistency::await_token_visible(&state.db, &headers).await;
 
     let rows = sqlx::query(
         r#"
-        SELECT s.did, s.rkey, s.emoji_ref, s.title, s.description, s.expires, s.created_at,
+        SELECT s.did, s.rkey, s.emoji_ref, s.title, s.description, s.expires, s.timezone, s.reply_to, s.created_at,
                p.handle, p.display_name, p.avatar_cid,
-               e.blob_cid as emoji_blob_cid, e.mime_type, e.emoji_name, e.alt_text, e.did as emoji_did
+               COALESCE(p.avatar_cid IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'avatar'), 0) AS avatar_blacklisted,
+               e.blob_cid as emoji_blob_cid, e.mime_type, e.emoji_name, e.alt_text, e.did as emoji_did,
+               (SELECT COUNT(*) FROM status_replies r WHERE r.parent_at = s.at) as reply_count
         FROM statuses s
         LEFT JOIN profiles p ON s.did = p.did
         LEFT JOIN emojis e ON s.emoji_ref = 'at://' || e.at
         WHERE s.deleted_at IS NULL
+          AND s.did NOT IN (SELECT did FROM actor_takedowns)
+          AND (? OR s.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active'))
           AND (e.deleted_at IS NULL OR e.at IS NULL)
-          AND (e.blob_cid NOT IN (SELECT cid FROM blacklisted_cids WHERE content_type = 'emoji_blob') OR e.blob_cid IS NULL)
+          AND (e.blob_cid NOT IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'emoji_blob') OR e.blob_cid IS NULL)
           AND (s.expires IS NULL OR datetime(s.expires) > datetime('now'))
         ORDER BY s.created_at DESC
         LIMIT ?
         "#,
     )
+    .bind(include_inactive)
     .bind(limit)
     .fetch_all(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-    use jacquard_common::types::string::{Datetime, Did, Handle};
+    use jacquard_common::types::string::{AtUri, Datetime, Did, Handle};
     use lexicons::vg_nat::istat::status::list_statuses::StatusView;
 
     let statuses: Vec<_> = rows
@@ -519,6 +829,9 @@ pub async fn handle_list_statuses(
                 .ok()
                 .and_then(|s: String| if s.is_empty() { None } else { Some(s) });
             let expires: Option<String> = row.try_get("expires").ok();
+            let timezone: Option<String> = row.try_get("timezone").ok().flatten();
+            let reply_to: Option<String> = row.try_get("reply_to").ok().flatten();
+            let reply_count: i64 = row.try_get("reply_count").unwrap_or(0);
             let created_at: String = row.try_get("created_at").ok()?;
             let handle: Option<String> = row.try_get("handle").ok().flatten();
             let display_name: Option<String> = row.try_get("display_name").ok().flatten();
@@ -546,24 +859,14 @@ pub async fn handle_list_statuses(
             // Otherwise try to extract from the emoji_ref AT-URI
             let emoji_url = if let Some(ref blob_cid) = emoji_blob_cid {
                 if let Some(emoji_owner_did) = emoji_did {
-                    format!(
-                        "https://at.uwu.wang/{}/{}@{}",
-                        emoji_owner_did, blob_cid, mime_ext
-                    )
+                    img_url(&emoji_owner_did, &blob_cid, mime_ext)
                 } else {
                     // fallback: try to extract DID from emoji_ref
                     emoji_ref
                         .strip_prefix("at://")
                         .and_then(|s| s.split('/').next())
-                        .map(|emoji_owner| {
-                            format!(
-                                "https://at.uwu.wang/{}/{}@{}",
-                                emoji_owner, blob_cid, mime_ext
-                            )
-                        })
-                        .unwrap_or_else(|| {
-                            format!("https://at.uwu.wang/{}/{}@{}", did, blob_cid, mime_ext)
-                        })
+                        .map(|emoji_owner| img_url(emoji_owner, &blob_cid, mime_ext))
+                        .unwrap_or_else(|| img_url(&did, &blob_cid, mime_ext))
                 }
             } else {
                 // Fallback: try to extract CID from emoji_ref AT-URI (last segment)
@@ -571,9 +874,9 @@ pub async fn handle_list_statuses(
                 emoji_ref
                     .split('/')
                     .last()
-                    .map(|cid| format!("https://at.uwu.wang/{}/{}@{}", did, cid, mime_ext))
+                    .map(|cid| img_url(&did, cid, mime_ext))
                     .unwrap_or_else(|| {
-                        eprintln!(
+                        tracing::warn!(
                             "Warning: emoji not found for status {}, emoji_ref: {}",
                             rkey, emoji_ref
                         );
@@ -581,15 +884,19 @@ pub async fn handle_list_statuses(
                     })
             };
 
-            let avatar_url =
-                avatar_cid.map(|cid| format!("https://at.uwu.wang/{}/{}@webp", did, cid));
+            let avatar_blacklisted: bool = row.try_get("avatar_blacklisted").unwrap_or(false);
+            let avatar_url = if avatar_blacklisted {
+                Some(BLOCKED_IMAGE_URL.to_string())
+            } else {
+                avatar_cid.map(|cid| img_url(&did, &cid, "webp"))
+            };
 
             let handle_str = handle.unwrap_or(did.clone());
 
             // Validate datetime format before passing to raw_str to avoid panics
             // Skip statuses with invalid datetimes
             if created_at.is_empty() || !created_at.contains('T') {
-                eprintln!("Invalid created_at datetime for status: {}", created_at);
+                tracing::warn!("Invalid created_at datetime for status: {}", created_at);
                 return None;
             }
 
@@ -612,6 +919,9 @@ pub async fn handle_list_statuses(
                             .filter(|e| !e.is_empty() && e.contains('T'))
                             .map(|e| Datetime::raw_str(e)),
                     )
+                    .maybe_timezone(timezone.map(Into::into))
+                    .maybe_reply_to(reply_to.and_then(|u| AtUri::from_str(&u).ok()))
+                    .reply_count(reply_count)
                     .created_at(Datetime::raw_str(created_at))
                     .build(),
             )
@@ -626,3 +936,163 @@ pub async fn handle_list_statuses(
 
     Ok(Json(output))
 }
+
+pub async fn handle_list_replies(
+    State(state): State<AppState>,
+    headers: axum::http::HeaderMap,
+    axum::extract::Query(raw_params): axum::extract::Query<
+        std::collections::HashMap<String, String>,
+    >,
+    ExtractXrpc(req): ExtractXrpc<ListRepliesRequest>,
+) -> Result<Json<ListRepliesOutput<'static>>, StatusCode> {
+    let uri = req.uri.to_string();
+    let limit = req.limit.unwrap_or(50).min(100) as i64;
+    let include_inactive =
+        include_inactive_override(&state, &headers, inactive_requested(&raw_params)).await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT s.did, s.rkey, s.emoji_ref, s.title, s.description, s.expires, s.timezone, s.reply_to, s.created_at,
+               p.handle, p.display_name, p.avatar_cid,
+               COALESCE(p.avatar_cid IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'avatar'), 0) AS avatar_blacklisted,
+               e.blob_cid as emoji_blob_cid, e.mime_type, e.emoji_name, e.alt_text, e.did as emoji_did,
+               (SELECT COUNT(*) FROM status_replies r WHERE r.parent_at = s.at) as reply_count
+        FROM statuses s
+        LEFT JOIN profiles p ON s.did = p.did
+        LEFT JOIN emojis e ON s.emoji_ref = 'at://' || e.at
+        WHERE s.reply_to = ?
+          AND s.deleted_at IS NULL
+          AND s.did NOT IN (SELECT did FROM actor_takedowns)
+          AND (? OR s.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active'))
+          AND (e.deleted_at IS NULL OR e.at IS NULL)
+          AND (e.blob_cid NOT IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'emoji_blob') OR e.blob_cid IS NULL)
+        ORDER BY s.created_at ASC
+        LIMIT ?
+        "#,
+    )
+    .bind(&uri)
+    .bind(include_inactive)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    use jacquard_common::types::string::{AtUri, Datetime, Did, Handle};
+    use lexicons::vg_nat::istat::status::list_statuses::StatusView;
+
+    let replies: Vec<_> = rows
+        .iter()
+        .filter_map(|row| {
+            let did: String = row.try_get("did").ok()?;
+            let rkey: String = row.try_get("rkey").ok()?;
+            let emoji_ref: String = row.try_get("emoji_ref").ok()?;
+            let emoji_blob_cid: Option<String> = row.try_get("emoji_blob_cid").ok().flatten();
+            let title: Option<String> = row
+                .try_get("title")
+                .ok()
+                .and_then(|s: String| if s.is_empty() { None } else { Some(s) });
+            let description: Option<String> = row
+                .try_get("description")
+                .ok()
+                .and_then(|s: String| if s.is_empty() { None } else { Some(s) });
+            let expires: Option<String> = row.try_get("expires").ok();
+            let timezone: Option<String> = row.try_get("timezone").ok().flatten();
+            let reply_to: Option<String> = row.try_get("reply_to").ok().flatten();
+            let reply_count: i64 = row.try_get("reply_count").unwrap_or(0);
+            let created_at: String = row.try_get("created_at").ok()?;
+            let handle: Option<String> = row.try_get("handle").ok().flatten();
+            let display_name: Option<String> = row.try_get("display_name").ok().flatten();
+            let avatar_cid: Option<String> = row.try_get("avatar_cid").ok().flatten();
+            let emoji_name: Option<String> = row.try_get("emoji_name").ok().flatten();
+            let alt_text: Option<String> = row.try_get("alt_text").ok().flatten();
+            let emoji_did: Option<String> = row.try_get("emoji_did").ok().flatten();
+            let mime: Option<String> = row.try_get("mime_type").ok().flatten();
+
+            let mime_ext = mime
+                .as_deref()
+                .and_then(|m| match m {
+                    "image/png" => Some("png"),
+                    "image/jpeg" => Some("jpeg"),
+                    "image/jpg" => Some("jpeg"),
+                    "image/webp" => Some("webp"),
+                    "image/gif" => Some("gif"),
+                    _ => Some("jpeg"),
+                })
+                .unwrap_or("jpeg");
+
+            let emoji_url = if let Some(ref blob_cid) = emoji_blob_cid {
+                if let Some(emoji_owner_did) = emoji_did {
+                    img_url(&emoji_owner_did, &blob_cid, mime_ext)
+                } else {
+                    emoji_ref
+                        .strip_prefix("at://")
+                        .and_then(|s| s.split('/').next())
+                        .map(|emoji_owner| img_url(emoji_owner, &blob_cid, mime_ext))
+                        .unwrap_or_else(|| img_url(&did, &blob_cid, mime_ext))
+                }
+            } else {
+                emoji_ref
+                    .split('/')
+                    .last()
+                    .map(|cid| img_url(&did, cid, mime_ext))
+                    .unwrap_or_else(|| {
+                        tracing::warn!(
+                            "Warning: emoji not found for reply {}, emoji_ref: {}",
+                            rkey, emoji_ref
+                        );
+                        String::new()
+                    })
+            };
+
+            let avatar_blacklisted: bool = row.try_get("avatar_blacklisted").unwrap_or(false);
+            let avatar_url = if avatar_blacklisted {
+                Some(BLOCKED_IMAGE_URL.to_string())
+            } else {
+                avatar_cid.map(|cid| img_url(&did, &cid, "webp"))
+            };
+
+            let handle_str = handle.unwrap_or(did.clone());
+
+            // Validate datetime format before passing to raw_str to avoid panics
+            // Skip statuses with invalid datetimes
+            if created_at.is_empty() || !created_at.contains('T') {
+                tracing::warn!("Invalid created_at datetime for reply: {}", created_at);
+                return None;
+            }
+
+            Some(
+                StatusView::new()
+                    .did(Did::from_str(&did).ok()?)
+                    .handle(Handle::from_str(&handle_str).ok()?)
+                    .maybe_display_name(display_name.map(Into::into))
+                    .maybe_avatar_url(avatar_url.map(Into::into))
+                    .rkey(rkey)
+                    .emoji_url(emoji_url)
+                    .maybe_emoji_name(emoji_name.map(Into::into))
+                    .maybe_emoji_alt(alt_text.map(Into::into))
+                    .maybe_emoji_blob_cid(emoji_blob_cid.map(Into::into))
+                    .maybe_emoji_ref(Some(emoji_ref.into()))
+                    .maybe_title(title.map(Into::into))
+                    .maybe_description(description.map(Into::into))
+                    .maybe_expires(
+                        expires
+                            .filter(|e| !e.is_empty() && e.contains('T'))
+                            .map(|e| Datetime::raw_str(e)),
+                    )
+                    .maybe_timezone(timezone.map(Into::into))
+                    .maybe_reply_to(reply_to.and_then(|u| AtUri::from_str(&u).ok()))
+                    .reply_count(reply_count)
+                    .created_at(Datetime::raw_str(created_at))
+                    .build(),
+            )
+        })
+        .collect();
+
+    let output = ListRepliesOutput {
+        replies,
+        cursor: None,
+        extra_data: None,
+    };
+
+    Ok(Json(output))
+}
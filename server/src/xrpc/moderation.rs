@@ -11,7 +11,7 @@ use std::{env, str::FromStr};
 use crate::AppState;
 
 /// Extract DID from Authorization header by validating JWT
-async fn extract_authenticated_did(
+pub(crate) async fn extract_authenticated_did(
     headers: &HeaderMap,
     state: &AppState,
 ) -> Result<String, StatusCode> {
@@ -35,7 +35,7 @@ async fn extract_authenticated_did(
         .validate_downstream_jwt(token, key_store_ref)
         .await
         .map_err(|e| {
-            eprintln!("Failed to validate downstream JWT: {:?}", e);
+            tracing::warn!("Failed to validate downstream JWT: {:?}", e);
             StatusCode::UNAUTHORIZED
         })?;
 
@@ -43,7 +43,7 @@ async fn extract_authenticated_did(
 }
 
 /// Check if a DID is an admin
-async fn is_admin(did: &str, state: &AppState) -> Result<bool, StatusCode> {
+pub(crate) async fn is_admin(did: &str, state: &AppState) -> Result<bool, StatusCode> {
     // First check if this DID matches any initial admin from env var
     // ADMIN_DID can be a single DID or comma-separated list: "did:web:abc,did:web:xyz"
     if let Ok(admin_dids_str) = env::var("ADMIN_DID") {
@@ -78,7 +78,7 @@ async fn is_admin(did: &str, state: &AppState) -> Result<bool, StatusCode> {
 }
 
 /// Require that the authenticated user is an admin
-async fn require_admin(headers: &HeaderMap, state: &AppState) -> Result<String, StatusCode> {
+pub(crate) async fn require_admin(headers: &HeaderMap, state: &AppState) -> Result<String, StatusCode> {
     let did = extract_authenticated_did(headers, state).await?;
 
     if !is_admin(&did, state).await? {
@@ -89,7 +89,7 @@ async fn require_admin(headers: &HeaderMap, state: &AppState) -> Result<String,
 }
 
 /// Log a moderation action to the audit log
-async fn log_audit_action(
+pub(crate) async fn log_audit_action(
     state: &AppState,
     moderator_did: &str,
     action: &str,
@@ -114,7 +114,7 @@ async fn log_audit_action(
     .execute(&state.db)
     .await
     .map_err(|e| {
-        eprintln!("Failed to log audit action: {:?}", e);
+        tracing::warn!("Failed to log audit action: {:?}", e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
 
@@ -190,6 +190,213 @@ pub struct DeleteStatusResponse {
     pub success: bool,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkActionRequest {
+    /// Soft-delete every emoji and status authored by this DID
+    pub did: Option<String>,
+    /// AT-URIs of emoji or status records to soft-delete
+    #[serde(default)]
+    pub uris: Vec<String>,
+    /// Blob CIDs to blacklist as emoji_blob
+    #[serde(default)]
+    pub cids: Vec<String>,
+    pub reason: String,
+    pub reason_details: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkActionItem {
+    pub subject: String,
+    pub action: String,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BulkActionResponse {
+    pub deleted_emojis: u64,
+    pub deleted_statuses: u64,
+    pub blacklisted_cids: u64,
+    pub items: Vec<BulkActionItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RestoreEmojiRequest {
+    pub uri: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RestoreEmojiResponse {
+    pub success: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RestoreStatusRequest {
+    pub uri: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RestoreStatusResponse {
+    pub success: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BackfillRequest {
+    /// Limit the run to one repo; omit to walk every known DID
+    pub did: Option<String>,
+    /// Clear checkpoints first so completed repos are re-walked
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BackfillResponse {
+    pub success: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SetEmojiCategoryRequest {
+    pub uri: String,
+    pub category: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SetEmojiCategoryResponse {
+    pub success: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddAdminRequest {
+    pub did: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AddAdminResponse {
+    pub success: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RemoveAdminRequest {
+    pub did: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RemoveAdminResponse {
+    pub success: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminView {
+    pub did: String,
+    pub granted_by: Option<String>,
+    pub granted_at: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListAdminsResponse {
+    pub admins: Vec<AdminView>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TakedownActorRequest {
+    pub did: String,
+    pub reason: String,
+    pub reason_details: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TakedownActorResponse {
+    pub success: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReinstateActorRequest {
+    pub did: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReinstateActorResponse {
+    pub success: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateReportRequest {
+    /// AT-URI of the reported record, or a bare blob CID
+    pub subject: String,
+    pub reason: String,
+    pub reason_details: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreateReportResponse {
+    pub success: bool,
+    pub id: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ListReportsParams {
+    /// Filter by lifecycle state; "all" lists everything (default: open)
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportView {
+    pub id: i64,
+    pub subject: String,
+    pub reason: String,
+    pub reason_details: Option<String>,
+    pub reporter_did: String,
+    pub status: String,
+    pub resolved_by: Option<String>,
+    pub resolution_notes: Option<String>,
+    pub created_at: String,
+    pub resolved_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListReportsResponse {
+    pub reports: Vec<ReportView>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ResolveReportRequest {
+    pub id: i64,
+    /// New lifecycle state: acknowledged, resolved, or dismissed
+    pub status: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ResolveReportResponse {
+    pub success: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListAuditLogParams {
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+    /// Only entries by this moderator
+    pub moderator_did: Option<String>,
+    /// Only entries with this action (e.g. "blacklist_cid")
+    pub action: Option<String>,
+    /// Only entries against this target type (e.g. "emoji_blob")
+    pub target_type: Option<String>,
+    /// Inclusive lower bound on created_at (any SQLite datetime string)
+    pub since: Option<String>,
+    /// Inclusive upper bound on created_at
+    pub until: Option<String>,
+}
+
 // Endpoint handlers
 
 pub async fn handle_blacklist_cid(
@@ -342,7 +549,7 @@ pub async fn handle_is_admin(
         Ok(did) => did,
         Err(e) => {
             // Not authenticated or invalid token -> not an admin
-            eprintln!("Failed to extract DID from auth token (status: {:?})", e);
+            tracing::warn!("Failed to extract DID from auth token (status: {:?})", e);
             return Ok(Json(IsAdminResponse { is_admin: false }));
         }
     };
@@ -382,7 +589,7 @@ pub async fn handle_delete_emoji(
     }
 
     // Soft delete the emoji
-    let at_uri_without_prefix = format!("{}/vg.nat.istat.moji.emoji/{}", emoji_did, rkey);
+    let at_uri_without_prefix = crate::collections::registry().emoji_at(&emoji_did, &rkey);
     let result = sqlx::query(
         "UPDATE emojis SET deleted_at = datetime('now'), deleted_by = ? WHERE at = ? AND deleted_at IS NULL"
     )
@@ -402,16 +609,235 @@ pub async fn handle_delete_emoji(
     Ok(Json(DeleteEmojiResponse { success: true }))
 }
 
-pub async fn handle_delete_status(
+/// Batched moderation for spam cleanup: soft-delete everything by a DID,
+/// soft-delete a list of AT-URIs, and/or blacklist a list of blob CIDs,
+/// all applied in one transaction with a single summarized audit entry.
+pub async fn handle_bulk_action(
     State(state): State<AppState>,
     headers: HeaderMap,
-    Json(req): Json<DeleteStatusRequest>,
-) -> Result<Json<DeleteStatusResponse>, StatusCode> {
+    Json(req): Json<BulkActionRequest>,
+) -> Result<Json<BulkActionResponse>, StatusCode> {
+    let moderator_did = require_admin(&headers, &state).await?;
+
+    let valid_reasons = ["nudity", "gore", "harassment", "spam", "copyright", "other"];
+    if !valid_reasons.contains(&req.reason.as_str()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if req.did.is_none() && req.uris.is_empty() && req.cids.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut deleted_emojis = 0u64;
+    let mut deleted_statuses = 0u64;
+    let mut blacklisted_cids = 0u64;
+    let mut items = Vec::new();
+
+    if let Some(ref did) = req.did {
+        deleted_emojis += sqlx::query(
+            "UPDATE emojis SET deleted_at = datetime('now'), deleted_by = ? WHERE did = ? AND deleted_at IS NULL"
+        )
+        .bind(&moderator_did)
+        .bind(did)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .rows_affected();
+
+        deleted_statuses += sqlx::query(
+            "UPDATE statuses SET deleted_at = datetime('now'), deleted_by = ? WHERE did = ? AND deleted_at IS NULL"
+        )
+        .bind(&moderator_did)
+        .bind(did)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .rows_affected();
+    }
+
+    for uri in &req.uris {
+        let at_uri_without_prefix = uri.strip_prefix("at://").unwrap_or(uri);
+        let registry = crate::collections::registry();
+        let (table, action) = if at_uri_without_prefix.contains(&format!("/{}/", registry.emoji)) {
+            ("emojis", "delete_emoji")
+        } else if at_uri_without_prefix.contains(&format!("/{}/", registry.status)) {
+            ("statuses", "delete_status")
+        } else {
+            items.push(BulkActionItem {
+                subject: uri.clone(),
+                action: "delete".to_string(),
+                success: false,
+                error: Some("unrecognized collection".to_string()),
+            });
+            continue;
+        };
+
+        let affected = sqlx::query(&format!(
+            "UPDATE {} SET deleted_at = datetime('now'), deleted_by = ? WHERE at = ? AND deleted_at IS NULL",
+            table
+        ))
+        .bind(&moderator_did)
+        .bind(at_uri_without_prefix)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .rows_affected();
+
+        if affected > 0 {
+            if table == "emojis" {
+                deleted_emojis += affected;
+            } else {
+                deleted_statuses += affected;
+            }
+        }
+        items.push(BulkActionItem {
+            subject: uri.clone(),
+            action: action.to_string(),
+            success: affected > 0,
+            error: (affected == 0).then(|| "not found or already deleted".to_string()),
+        });
+    }
+
+    for cid in &req.cids {
+        let affected = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO blacklisted_cids
+                (cid, reason, reason_details, content_type, moderator_did)
+            VALUES (?, ?, ?, 'emoji_blob', ?)
+            "#,
+        )
+        .bind(cid)
+        .bind(&req.reason)
+        .bind(&req.reason_details)
+        .bind(&moderator_did)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .rows_affected();
+
+        blacklisted_cids += affected;
+        items.push(BulkActionItem {
+            subject: cid.clone(),
+            action: "blacklist_cid".to_string(),
+            success: affected > 0,
+            error: (affected == 0).then(|| "already blacklisted".to_string()),
+        });
+    }
+
+    // One summarized audit entry inside the same transaction
+    let summary = format!(
+        "deleted_emojis={}, deleted_statuses={}, blacklisted_cids={}",
+        deleted_emojis, deleted_statuses, blacklisted_cids
+    );
+    sqlx::query(
+        r#"
+        INSERT INTO moderation_audit_log
+            (moderator_did, action, target_type, target_id, reason, reason_details)
+        VALUES (?, 'bulk_action', 'bulk', ?, ?, ?)
+        "#,
+    )
+    .bind(&moderator_did)
+    .bind(req.did.as_deref().unwrap_or("list"))
+    .bind(&req.reason)
+    .bind(&summary)
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(BulkActionResponse {
+        deleted_emojis,
+        deleted_statuses,
+        blacklisted_cids,
+        items,
+    }))
+}
+
+/// Undo a soft delete. Owner or admin only; refuses with 409 when the
+/// emoji's blob has been blacklisted since the delete, so a restore
+/// can't resurface blocked content.
+pub async fn handle_restore_emoji(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<RestoreEmojiRequest>,
+) -> Result<Json<RestoreEmojiResponse>, StatusCode> {
+    let did = extract_authenticated_did(&headers, &state).await?;
+    let is_admin_user = is_admin(&did, &state).await?;
+
+    let uri_parts: Vec<&str> = req
+        .uri
+        .strip_prefix("at://")
+        .unwrap_or(&req.uri)
+        .split('/')
+        .collect();
+    if uri_parts.len() < 3 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let emoji_did = uri_parts[0];
+    let rkey = uri_parts[2];
+
+    if did != emoji_did && !is_admin_user {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let at_uri_without_prefix = crate::collections::registry().emoji_at(&emoji_did, &rkey);
+
+    // Re-validate the blob before resurrecting the row
+    let blacklisted = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM emojis e
+            JOIN effective_blacklisted_cids b
+              ON b.cid = e.blob_cid AND b.content_type = 'emoji_blob'
+            WHERE e.at = ?
+        )
+        "#,
+    )
+    .bind(&at_uri_without_prefix)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if blacklisted {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let result = sqlx::query(
+        "UPDATE emojis SET deleted_at = NULL, deleted_by = NULL WHERE at = ? AND deleted_at IS NOT NULL"
+    )
+    .bind(&at_uri_without_prefix)
+    .execute(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    log_audit_action(&state, &did, "restore_emoji", "emoji", &req.uri, None, None).await?;
+
+    Ok(Json(RestoreEmojiResponse { success: true }))
+}
+
+/// Undo a soft delete on a status. Owner or admin only; refuses with 409
+/// when the referenced emoji's blob has been blacklisted since.
+pub async fn handle_restore_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<RestoreStatusRequest>,
+) -> Result<Json<RestoreStatusResponse>, StatusCode> {
     let did = extract_authenticated_did(&headers, &state).await?;
     let is_admin_user = is_admin(&did, &state).await?;
 
-    // Parse AT-URI to get DID and rkey
-    // Format: at://did:plc:xyz/vg.nat.istat.status.record/rkey
     let uri_parts: Vec<&str> = req
         .uri
         .strip_prefix("at://")
@@ -423,20 +849,37 @@ pub async fn handle_delete_status(
     }
 
     let status_did = uri_parts[0];
-    let _collection = uri_parts[1];
     let rkey = uri_parts[2];
 
-    // Check if user owns this status or is an admin
     if did != status_did && !is_admin_user {
         return Err(StatusCode::FORBIDDEN);
     }
 
-    // Soft delete the status
-    let at_uri_without_prefix = format!("{}/vg.nat.istat.status.record/{}", status_did, rkey);
+    let at_uri_without_prefix = crate::collections::registry().status_at(&status_did, &rkey);
+
+    let blacklisted = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM statuses s
+            JOIN emojis e ON s.emoji_ref = 'at://' || e.at
+            JOIN effective_blacklisted_cids b
+              ON b.cid = e.blob_cid AND b.content_type = 'emoji_blob'
+            WHERE s.at = ?
+        )
+        "#,
+    )
+    .bind(&at_uri_without_prefix)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if blacklisted {
+        return Err(StatusCode::CONFLICT);
+    }
+
     let result = sqlx::query(
-        "UPDATE statuses SET deleted_at = datetime('now'), deleted_by = ? WHERE at = ? AND deleted_at IS NULL"
+        "UPDATE statuses SET deleted_at = NULL, deleted_by = NULL WHERE at = ? AND deleted_at IS NOT NULL"
     )
-    .bind(&did)
     .bind(&at_uri_without_prefix)
     .execute(&state.db)
     .await
@@ -446,11 +889,10 @@ pub async fn handle_delete_status(
         return Err(StatusCode::NOT_FOUND);
     }
 
-    // Log audit action
     log_audit_action(
         &state,
         &did,
-        "delete_status",
+        "restore_status",
         "status",
         &req.uri,
         None,
@@ -458,39 +900,219 @@ pub async fn handle_delete_status(
     )
     .await?;
 
-    Ok(Json(DeleteStatusResponse { success: true }))
+    Ok(Json(RestoreStatusResponse { success: true }))
 }
 
-use lexicons::vg_nat::istat::moderation::list_audit_log::{AuditLogEntry, ListAuditLogOutput};
-
-pub async fn handle_list_audit_log(
+pub async fn handle_set_emoji_category(
     State(state): State<AppState>,
     headers: HeaderMap,
-) -> Result<Json<ListAuditLogOutput<'static>>, StatusCode> {
-    let _ = require_admin(&headers, &state).await?;
+    Json(req): Json<SetEmojiCategoryRequest>,
+) -> Result<Json<SetEmojiCategoryResponse>, StatusCode> {
+    let moderator_did = require_admin(&headers, &state).await?;
 
-    let rows = sqlx::query(
-        r#"
-        SELECT
-            l.id,
-            l.moderator_did,
-            l.action,
-            l.target_type,
-            l.target_id,
-            l.reason,
-            l.reason_details,
-            l.created_at,
-            p.handle as moderator_handle
-        FROM moderation_audit_log l
-        LEFT JOIN profiles p ON l.moderator_did = p.did
-        ORDER BY l.created_at DESC
-        LIMIT 100
+    // Curated overrides are restricted to the picker's category set
+    if let Some(ref category) = req.category {
+        if !crate::xrpc::EMOJI_CATEGORIES.contains(&category.as_str()) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    // Stored `at` values carry no at:// prefix
+    let at_uri_without_prefix = req.uri.strip_prefix("at://").unwrap_or(&req.uri);
+
+    let result =
+        sqlx::query("UPDATE emojis SET curated_category = ? WHERE at = ? AND deleted_at IS NULL")
+            .bind(&req.category)
+            .bind(at_uri_without_prefix)
+            .execute(&state.db)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    log_audit_action(
+        &state,
+        &moderator_did,
+        "set_emoji_category",
+        "emoji",
+        &req.uri,
+        None,
+        req.category.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(SetEmojiCategoryResponse { success: true }))
+}
+
+pub async fn handle_backfill(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<BackfillRequest>,
+) -> Result<Json<BackfillResponse>, StatusCode> {
+    let moderator_did = require_admin(&headers, &state).await?;
+
+    if req.force {
+        crate::backfill::reset_checkpoints(&state.db, req.did.as_deref())
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    // The walk can take a while, so it runs in the background; progress
+    // is checkpointed and logged as it goes
+    let dids = req.did.clone().map(|did| vec![did]);
+    tokio::spawn(crate::backfill::run_backfill(state.db.clone(), dids));
+
+    log_audit_action(
+        &state,
+        &moderator_did,
+        "backfill",
+        "repo",
+        req.did.as_deref().unwrap_or("all"),
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(Json(BackfillResponse { success: true }))
+}
+
+pub async fn handle_delete_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<DeleteStatusRequest>,
+) -> Result<Json<DeleteStatusResponse>, StatusCode> {
+    let did = extract_authenticated_did(&headers, &state).await?;
+    let is_admin_user = is_admin(&did, &state).await?;
+
+    // Parse AT-URI to get DID and rkey
+    // Format: at://did:plc:xyz/vg.nat.istat.status.record/rkey
+    let uri_parts: Vec<&str> = req
+        .uri
+        .strip_prefix("at://")
+        .unwrap_or(&req.uri)
+        .split('/')
+        .collect();
+    if uri_parts.len() < 3 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let status_did = uri_parts[0];
+    let _collection = uri_parts[1];
+    let rkey = uri_parts[2];
+
+    // Check if user owns this status or is an admin
+    if did != status_did && !is_admin_user {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    // Soft delete the status
+    let at_uri_without_prefix = crate::collections::registry().status_at(&status_did, &rkey);
+    let result = sqlx::query(
+        "UPDATE statuses SET deleted_at = datetime('now'), deleted_by = ? WHERE at = ? AND deleted_at IS NULL"
+    )
+    .bind(&did)
+    .bind(&at_uri_without_prefix)
+    .execute(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    // Log audit action
+    log_audit_action(
+        &state,
+        &did,
+        "delete_status",
+        "status",
+        &req.uri,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(Json(DeleteStatusResponse { success: true }))
+}
+
+use lexicons::vg_nat::istat::moderation::list_audit_log::{AuditLogEntry, ListAuditLogOutput};
+
+pub async fn handle_list_audit_log(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    axum::extract::Query(params): axum::extract::Query<ListAuditLogParams>,
+) -> Result<Json<ListAuditLogOutput<'static>>, StatusCode> {
+    let _ = require_admin(&headers, &state).await?;
+
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+
+    // Cursor is "{created_at}|{id}" from the last row of the previous
+    // page; created_at alone isn't unique enough to resume on
+    let (cursor_created_at, cursor_id) = match params.cursor.as_deref() {
+        Some(cursor) => {
+            let (created_at, id) = cursor.split_once('|').ok_or(StatusCode::BAD_REQUEST)?;
+            let id: i64 = id.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+            (Some(created_at.to_string()), id)
+        }
+        None => (None, 0),
+    };
+
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            l.id,
+            l.moderator_did,
+            l.action,
+            l.target_type,
+            l.target_id,
+            l.reason,
+            l.reason_details,
+            l.created_at,
+            p.handle as moderator_handle
+        FROM moderation_audit_log l
+        LEFT JOIN profiles p ON l.moderator_did = p.did
+        WHERE (? IS NULL OR l.created_at < ? OR (l.created_at = ? AND l.id < ?))
+          AND (? IS NULL OR l.moderator_did = ?)
+          AND (? IS NULL OR l.action = ?)
+          AND (? IS NULL OR l.target_type = ?)
+          AND (? IS NULL OR datetime(l.created_at) >= datetime(?))
+          AND (? IS NULL OR datetime(l.created_at) <= datetime(?))
+        ORDER BY l.created_at DESC, l.id DESC
+        LIMIT ?
         "#,
     )
+    .bind(&cursor_created_at)
+    .bind(&cursor_created_at)
+    .bind(&cursor_created_at)
+    .bind(cursor_id)
+    .bind(&params.moderator_did)
+    .bind(&params.moderator_did)
+    .bind(&params.action)
+    .bind(&params.action)
+    .bind(&params.target_type)
+    .bind(&params.target_type)
+    .bind(&params.since)
+    .bind(&params.since)
+    .bind(&params.until)
+    .bind(&params.until)
+    .bind(limit)
     .fetch_all(&state.db)
     .await
     .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    // Only hand back a cursor when the page filled up
+    let next_cursor = if rows.len() as i64 == limit {
+        rows.last().and_then(|row| {
+            let created_at: String = row.try_get("created_at").ok()?;
+            let id: i64 = row.try_get("id").ok()?;
+            Some(format!("{}|{}", created_at, id))
+        })
+    } else {
+        None
+    };
+
     use jacquard_common::types::string::{Datetime, Did, Handle};
 
     let entries: Vec<_> = rows
@@ -526,9 +1148,403 @@ pub async fn handle_list_audit_log(
 
     let output = ListAuditLogOutput {
         entries,
-        cursor: None,
+        cursor: next_cursor.map(Into::into),
         extra_data: None,
     };
 
     Ok(Json(output))
 }
+
+pub async fn handle_add_admin(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<AddAdminRequest>,
+) -> Result<Json<AddAdminResponse>, StatusCode> {
+    let moderator_did = require_admin(&headers, &state).await?;
+
+    if !req.did.starts_with("did:") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Check if already an admin
+    let exists = sqlx::query_scalar::<_, bool>("SELECT EXISTS(SELECT 1 FROM admins WHERE did = ?)")
+        .bind(&req.did)
+        .fetch_one(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if exists {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    sqlx::query("INSERT INTO admins (did, granted_by, notes) VALUES (?, ?, ?)")
+        .bind(&req.did)
+        .bind(&moderator_did)
+        .bind(&req.notes)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Log audit action
+    log_audit_action(
+        &state,
+        &moderator_did,
+        "add_admin",
+        "admin",
+        &req.did,
+        None,
+        req.notes.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(AddAdminResponse { success: true }))
+}
+
+pub async fn handle_remove_admin(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<RemoveAdminRequest>,
+) -> Result<Json<RemoveAdminResponse>, StatusCode> {
+    let moderator_did = require_admin(&headers, &state).await?;
+
+    // An admin can't revoke themselves; that's how instances get locked out
+    if req.did == moderator_did {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Admins bootstrapped via ADMIN_DID would be re-granted on their next
+    // request, so removing them here would only be confusing
+    if let Ok(admin_dids_str) = env::var("ADMIN_DID") {
+        if admin_dids_str
+            .split(',')
+            .map(|s| s.trim())
+            .any(|s| s == req.did)
+        {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    let result = sqlx::query("DELETE FROM admins WHERE did = ?")
+        .bind(&req.did)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    // Log audit action
+    log_audit_action(
+        &state,
+        &moderator_did,
+        "remove_admin",
+        "admin",
+        &req.did,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(Json(RemoveAdminResponse { success: true }))
+}
+
+pub async fn handle_list_admins(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ListAdminsResponse>, StatusCode> {
+    let _ = require_admin(&headers, &state).await?;
+
+    let rows = sqlx::query(
+        r#"
+        SELECT did, granted_by, granted_at, notes
+        FROM admins
+        ORDER BY granted_at ASC
+        "#,
+    )
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let admins: Vec<AdminView> = rows
+        .iter()
+        .filter_map(|row| {
+            Some(AdminView {
+                did: row.try_get("did").ok()?,
+                granted_by: row.try_get("granted_by").ok().flatten(),
+                granted_at: row.try_get("granted_at").ok()?,
+                notes: row.try_get("notes").ok().flatten(),
+            })
+        })
+        .collect();
+
+    Ok(Json(ListAdminsResponse { admins }))
+}
+
+pub async fn handle_create_report(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<CreateReportRequest>,
+) -> Result<Json<CreateReportResponse>, StatusCode> {
+    // Any authenticated user can report, not just admins
+    let reporter_did = extract_authenticated_did(&headers, &state).await?;
+
+    let valid_reasons = ["nudity", "gore", "harassment", "spam", "copyright", "other"];
+    if !valid_reasons.contains(&req.reason.as_str()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let subject = req.subject.trim();
+    if subject.is_empty() || subject.len() > 512 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    if let Some(details) = &req.reason_details {
+        if details.len() > 5120 {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    // One open report per (reporter, subject) keeps repeat submissions
+    // from flooding the queue
+    let already_open = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS(
+            SELECT 1 FROM reports
+            WHERE reporter_did = ? AND subject = ? AND status = 'open'
+        )
+        "#,
+    )
+    .bind(&reporter_did)
+    .bind(subject)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if already_open {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO reports (subject, reason, reason_details, reporter_did)
+        VALUES (?, ?, ?, ?)
+        "#,
+    )
+    .bind(subject)
+    .bind(&req.reason)
+    .bind(&req.reason_details)
+    .bind(&reporter_did)
+    .execute(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(Json(CreateReportResponse {
+        success: true,
+        id: result.last_insert_rowid(),
+    }))
+}
+
+pub async fn handle_list_reports(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    axum::extract::Query(params): axum::extract::Query<ListReportsParams>,
+) -> Result<Json<ListReportsResponse>, StatusCode> {
+    let _ = require_admin(&headers, &state).await?;
+
+    // Default to the triage queue; pass status=all for the full history
+    let status = params.status.unwrap_or_else(|| "open".to_string());
+    let valid_statuses = ["open", "acknowledged", "resolved", "dismissed", "all"];
+    if !valid_statuses.contains(&status.as_str()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let rows = sqlx::query(
+        r#"
+        SELECT id, subject, reason, reason_details, reporter_did, status,
+               resolved_by, resolution_notes, created_at, resolved_at
+        FROM reports
+        WHERE (? = 'all' OR status = ?)
+        ORDER BY created_at DESC
+        LIMIT 100
+        "#,
+    )
+    .bind(&status)
+    .bind(&status)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let reports: Vec<ReportView> = rows
+        .iter()
+        .filter_map(|row| {
+            Some(ReportView {
+                id: row.try_get("id").ok()?,
+                subject: row.try_get("subject").ok()?,
+                reason: row.try_get("reason").ok()?,
+                reason_details: row.try_get("reason_details").ok().flatten(),
+                reporter_did: row.try_get("reporter_did").ok()?,
+                status: row.try_get("status").ok()?,
+                resolved_by: row.try_get("resolved_by").ok().flatten(),
+                resolution_notes: row.try_get("resolution_notes").ok().flatten(),
+                created_at: row.try_get("created_at").ok()?,
+                resolved_at: row.try_get("resolved_at").ok().flatten(),
+            })
+        })
+        .collect();
+
+    Ok(Json(ListReportsResponse { reports }))
+}
+
+pub async fn handle_resolve_report(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<ResolveReportRequest>,
+) -> Result<Json<ResolveReportResponse>, StatusCode> {
+    let moderator_did = require_admin(&headers, &state).await?;
+
+    // "acknowledged" parks a report in the triaged queue; the other two
+    // close it out
+    let valid_statuses = ["acknowledged", "resolved", "dismissed"];
+    if !valid_statuses.contains(&req.status.as_str()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let subject: Option<String> = sqlx::query_scalar("SELECT subject FROM reports WHERE id = ?")
+        .bind(req.id)
+        .fetch_optional(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let subject = subject.ok_or(StatusCode::NOT_FOUND)?;
+
+    let closing = req.status != "acknowledged";
+    sqlx::query(
+        r#"
+        UPDATE reports
+        SET status = ?,
+            resolved_by = ?,
+            resolution_notes = ?,
+            resolved_at = CASE WHEN ? THEN CURRENT_TIMESTAMP ELSE NULL END
+        WHERE id = ?
+        "#,
+    )
+    .bind(&req.status)
+    .bind(&moderator_did)
+    .bind(&req.notes)
+    .bind(closing)
+    .bind(req.id)
+    .execute(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Log audit action against the reported subject so report handling
+    // shows up next to the moderation it triggered
+    log_audit_action(
+        &state,
+        &moderator_did,
+        &format!("report_{}", req.status),
+        "report",
+        &subject,
+        None,
+        req.notes.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(ResolveReportResponse { success: true }))
+}
+
+pub async fn handle_takedown_actor(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<TakedownActorRequest>,
+) -> Result<Json<TakedownActorResponse>, StatusCode> {
+    let moderator_did = require_admin(&headers, &state).await?;
+
+    if !req.did.starts_with("did:") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let valid_reasons = ["nudity", "gore", "harassment", "spam", "copyright", "other"];
+    if !valid_reasons.contains(&req.reason.as_str()) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Admins can't take each other down through this endpoint; revoke
+    // admin rights first
+    if is_admin(&req.did, &state).await? {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let exists = sqlx::query_scalar::<_, bool>(
+        "SELECT EXISTS(SELECT 1 FROM actor_takedowns WHERE did = ?)",
+    )
+    .bind(&req.did)
+    .fetch_one(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if exists {
+        return Err(StatusCode::CONFLICT);
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO actor_takedowns (did, reason, reason_details, moderator_did)
+        VALUES (?, ?, ?, ?)
+        "#,
+    )
+    .bind(&req.did)
+    .bind(&req.reason)
+    .bind(&req.reason_details)
+    .bind(&moderator_did)
+    .execute(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Log audit action
+    log_audit_action(
+        &state,
+        &moderator_did,
+        "takedown_actor",
+        "actor",
+        &req.did,
+        Some(&req.reason),
+        req.reason_details.as_deref(),
+    )
+    .await?;
+
+    Ok(Json(TakedownActorResponse { success: true }))
+}
+
+pub async fn handle_reinstate_actor(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<ReinstateActorRequest>,
+) -> Result<Json<ReinstateActorResponse>, StatusCode> {
+    let moderator_did = require_admin(&headers, &state).await?;
+
+    let result = sqlx::query("DELETE FROM actor_takedowns WHERE did = ?")
+        .bind(&req.did)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if result.rows_affected() == 0 {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    // Log audit action
+    log_audit_action(
+        &state,
+        &moderator_did,
+        "reinstate_actor",
+        "actor",
+        &req.did,
+        None,
+        None,
+    )
+    .await?;
+
+    Ok(Json(ReinstateActorResponse { success: true }))
+}
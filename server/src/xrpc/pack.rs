@@ -0,0 +1,237 @@
+//! Emoji pack browsing endpoints: `moji.getPack` returns one pack with
+//! its member emoji, `moji.listPacks` pages through packs site-wide or
+//! for one creator. Packs are created through the proxy-write endpoint
+//! in publish.rs; these apply the usual soft-delete, takedown, and
+//! account-status filters, and members additionally pass the blacklist.
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+
+use super::emoji::EmojiDetailView;
+use crate::AppState;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPackParams {
+    /// Full AT-URI of the pack record
+    pub uri: String,
+    /// Admin-only: include packs from non-active accounts
+    pub include_inactive: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackView {
+    pub uri: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub created_by: String,
+    pub created_by_handle: Option<String>,
+    pub created_at: String,
+    /// Number of member emoji still visible after filtering
+    pub emoji_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPackResponse {
+    pub pack: PackView,
+    /// Member emoji in the pack's display order
+    pub emojis: Vec<EmojiDetailView>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListPacksParams {
+    /// Creator handle or DID; omit to list packs from everyone
+    pub actor: Option<String>,
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+    /// Admin-only: include packs from non-active accounts
+    pub include_inactive: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListPacksResponse {
+    pub packs: Vec<PackView>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+fn pack_from_row(row: &sqlx::sqlite::SqliteRow) -> Option<PackView> {
+    let at: String = row.try_get("at").ok()?;
+
+    Some(PackView {
+        uri: format!("at://{}", at),
+        name: row.try_get("name").ok()?,
+        description: row.try_get("description").ok().flatten(),
+        created_by: row.try_get("did").ok()?,
+        created_by_handle: row.try_get("handle").ok().flatten(),
+        created_at: row.try_get("created_at").ok()?,
+        emoji_count: row.try_get("emoji_count").unwrap_or(0),
+    })
+}
+
+/// Shared SELECT column list; emoji_count only counts members that
+/// survive the soft-delete, takedown, and blacklist filters
+const PACK_COLUMNS: &str = r#"
+    pk.at, pk.did, pk.name, pk.description, pk.created_at, p.handle,
+    (SELECT COUNT(*) FROM emoji_pack_items pi
+     JOIN emojis e ON pi.emoji_ref = 'at://' || e.at
+     WHERE pi.pack_at = pk.at
+       AND e.deleted_at IS NULL
+       AND e.did NOT IN (SELECT did FROM actor_takedowns)
+       AND e.blob_cid NOT IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'emoji_blob')
+    ) AS emoji_count
+"#;
+
+pub async fn handle_get_pack(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<GetPackParams>,
+) -> Result<Json<GetPackResponse>, StatusCode> {
+    let include_inactive = super::include_inactive_override(
+        &state,
+        &headers,
+        params.include_inactive.unwrap_or(false),
+    )
+    .await?;
+    let at = params
+        .uri
+        .strip_prefix("at://")
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    let row = sqlx::query(&format!(
+        r#"
+        SELECT {PACK_COLUMNS}
+        FROM emoji_packs pk
+        LEFT JOIN profiles p ON pk.did = p.did
+        WHERE pk.at = ?
+          AND pk.deleted_at IS NULL
+          AND pk.did NOT IN (SELECT did FROM actor_takedowns)
+          AND (? OR pk.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active'))
+        "#,
+    ))
+    .bind(at)
+    .bind(include_inactive)
+    .fetch_optional(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let row = row.ok_or(StatusCode::NOT_FOUND)?;
+    let pack = pack_from_row(&row).ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let emoji_rows = sqlx::query(
+        r#"
+        SELECT e.at, e.did, e.blob_cid, e.mime_type, e.emoji_name, e.alt_text,
+               COALESCE(e.curated_category, e.category) AS category,
+               (SELECT group_concat(t.tag, ' ') FROM emoji_tags t WHERE t.emoji_at = e.at) AS tags,
+               p.handle, e.created_at,
+               (SELECT e2.at FROM emojis e2
+                WHERE e2.blob_cid = e.blob_cid AND e2.deleted_at IS NULL
+                ORDER BY (SELECT COUNT(*) FROM statuses su WHERE su.emoji_ref = 'at://' || e2.at) DESC,
+                         e2.created_at ASC, e2.at ASC
+                LIMIT 1) AS canonical_at
+        FROM emoji_pack_items pi
+        JOIN emojis e ON pi.emoji_ref = 'at://' || e.at
+        LEFT JOIN profiles p ON e.did = p.did
+        WHERE pi.pack_at = ?
+          AND e.deleted_at IS NULL
+          AND e.did NOT IN (SELECT did FROM actor_takedowns)
+          AND (? OR e.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active'))
+          AND e.blob_cid NOT IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'emoji_blob')
+        ORDER BY pi.position
+        "#,
+    )
+    .bind(at)
+    .bind(include_inactive)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let emojis = emoji_rows
+        .iter()
+        .filter_map(super::emoji::view_from_row)
+        .collect();
+
+    Ok(Json(GetPackResponse { pack, emojis }))
+}
+
+pub async fn handle_list_packs(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<ListPacksParams>,
+) -> Result<Json<ListPacksResponse>, StatusCode> {
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+    let include_inactive = super::include_inactive_override(
+        &state,
+        &headers,
+        params.include_inactive.unwrap_or(false),
+    )
+    .await?;
+
+    let did = match params.actor.as_deref() {
+        Some(actor) if actor.starts_with("did:") => Some(actor.to_string()),
+        Some(actor) => Some(
+            crate::identity::resolve_handle(&state.db, actor)
+                .await
+                .ok_or(StatusCode::NOT_FOUND)?,
+        ),
+        None => None,
+    };
+
+    // Cursor is "{created_at}|{at}" from the last row of the previous page
+    let (cursor_created_at, cursor_at) = match params.cursor.as_deref() {
+        Some(cursor) => {
+            let (created_at, at) = cursor.split_once('|').ok_or(StatusCode::BAD_REQUEST)?;
+            (Some(created_at.to_string()), Some(at.to_string()))
+        }
+        None => (None, None),
+    };
+
+    let rows = sqlx::query(&format!(
+        r#"
+        SELECT {PACK_COLUMNS}
+        FROM emoji_packs pk
+        LEFT JOIN profiles p ON pk.did = p.did
+        WHERE (? IS NULL OR pk.did = ?)
+          AND pk.deleted_at IS NULL
+          AND pk.did NOT IN (SELECT did FROM actor_takedowns)
+          AND (? OR pk.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active'))
+          AND (? IS NULL OR pk.created_at < ? OR (pk.created_at = ? AND pk.at > ?))
+        ORDER BY pk.created_at DESC, pk.at
+        LIMIT ?
+        "#,
+    ))
+    .bind(&did)
+    .bind(&did)
+    .bind(include_inactive)
+    .bind(&cursor_created_at)
+    .bind(&cursor_created_at)
+    .bind(&cursor_created_at)
+    .bind(&cursor_at)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let packs: Vec<PackView> = rows.iter().filter_map(pack_from_row).collect();
+
+    // Only hand out a cursor when the page was full; a short page is the end
+    let cursor = if rows.len() as i64 == limit {
+        rows.last().and_then(|row| {
+            let created_at: String = row.try_get("created_at").ok()?;
+            let at: String = row.try_get("at").ok()?;
+            Some(format!("{}|{}", created_at, at))
+        })
+    } else {
+        None
+    };
+
+    Ok(Json(ListPacksResponse { packs, cursor }))
+}
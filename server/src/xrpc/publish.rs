@@ -0,0 +1,587 @@
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+};
+use jacquard_oatproxy::store::OAuthSessionStore;
+use jacquard_oauth::authstore::ClientAuthStore;
+use serde::{Deserialize, Serialize};
+
+use crate::AppState;
+
+use super::moderation::extract_authenticated_did;
+
+// Request/Response types
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct StatusEmojiRef {
+    pub uri: String,
+    pub cid: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateStatusRequest {
+    /// StrongRef to the emoji record representing the status
+    pub emoji: StatusEmojiRef,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub expires: Option<String>,
+    pub reply_to: Option<String>,
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateStatusResponse {
+    pub uri: String,
+    pub cid: String,
+    /// Opaque read-your-writes token; pass back to list endpoints via the
+    /// `istat-consistency-token` header
+    pub consistency_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadEmojiParams {
+    pub name: String,
+    pub alt_text: Option<String>,
+    pub category: Option<String>,
+    /// Comma-separated tag list
+    pub tags: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadEmojiResponse {
+    pub uri: String,
+    pub cid: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePackRequest {
+    pub name: String,
+    pub description: Option<String>,
+    /// AT-URIs of emoji records, in display order
+    pub emojis: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatePackResponse {
+    pub uri: String,
+    pub cid: String,
+}
+
+/// Body of an authenticated write forwarded to the user's PDS
+#[derive(Clone, Copy)]
+enum WriteBody<'a> {
+    Json(&'a serde_json::Value),
+    Blob { bytes: &'a [u8], mime: &'a str },
+}
+
+/// POST an XRPC call to the user's PDS using the stored upstream session,
+/// retrying once on a DPoP nonce challenge (mirrors put_status_expiry in
+/// status.rs). Returns the parsed response body.
+async fn upstream_write(
+    state: &AppState,
+    did: &str,
+    nsid: &str,
+    body: WriteBody<'_>,
+) -> Result<serde_json::Value, StatusCode> {
+    // Look up the upstream session for this user
+    let session_id = state
+        .key_store
+        .get_active_session(did)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let did_typed = jacquard_common::types::did::Did::new_owned(did)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let upstream_session =
+        ClientAuthStore::get_session(state.key_store.as_ref(), &did_typed, &session_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let host_url = upstream_session.host_url.as_str().trim_end_matches('/');
+    let url = format!("{}/xrpc/{}", host_url, nsid);
+
+    // DPoP key and stored nonce for the upstream request
+    let dpop_key = state
+        .key_store
+        .get_session_dpop_key(&session_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map(|(_jkt, key)| key)
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut dpop_nonce = state
+        .key_store
+        .get_session_dpop_nonce(&session_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Retry loop for DPoP nonce handling (mirrors the XRPC proxy)
+    let mut retry_count = 0;
+    let max_retries = 1;
+
+    loop {
+        let dpop_proof = state
+            .token_manager
+            .create_upstream_dpop_proof(
+                "POST",
+                &url,
+                Some(upstream_session.token_set.access_token.as_ref()),
+                dpop_nonce.as_deref(),
+                &dpop_key,
+            )
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        let client = reqwest::Client::new();
+        let request = client
+            .post(&url)
+            .header(
+                "Authorization",
+                format!("DPoP {}", upstream_session.token_set.access_token),
+            )
+            .header("DPoP", dpop_proof);
+        let request = match body {
+            WriteBody::Json(value) => request.json(value),
+            WriteBody::Blob { bytes, mime } => {
+                request.header("Content-Type", mime).body(bytes.to_vec())
+            }
+        };
+
+        let response = request
+            .send()
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        // Check for DPoP nonce requirement (can be 400 or 401)
+        if (response.status() == 400 || response.status() == 401) && retry_count < max_retries {
+            if let Some(new_nonce) = response.headers().get("DPoP-Nonce") {
+                if let Ok(nonce_str) = new_nonce.to_str() {
+                    dpop_nonce = Some(nonce_str.to_string());
+                    let _ = state
+                        .key_store
+                        .update_session_dpop_nonce(&session_id, nonce_str.to_string())
+                        .await;
+                    retry_count += 1;
+                    continue;
+                }
+            }
+        }
+
+        // Store any refreshed nonce for the next request
+        if let Some(new_nonce) = response.headers().get("DPoP-Nonce") {
+            if let Ok(nonce_str) = new_nonce.to_str() {
+                let _ = state
+                    .key_store
+                    .update_session_dpop_nonce(&session_id, nonce_str.to_string())
+                    .await;
+            }
+        }
+
+        if !response.status().is_success() {
+            tracing::warn!("{} failed for {}: {}", nsid, did, response.status());
+            return Err(StatusCode::BAD_GATEWAY);
+        }
+
+        return response
+            .json()
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    }
+}
+
+/// Pull the rkey out of an at-uri returned by createRecord
+fn rkey_from_uri(uri: &str) -> Result<String, StatusCode> {
+    uri.rsplit('/')
+        .next()
+        .filter(|r| !r.is_empty())
+        .map(|r| r.to_string())
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+// Endpoint handlers
+
+pub async fn handle_create_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<CreateStatusRequest>,
+) -> Result<Json<CreateStatusResponse>, StatusCode> {
+    let did = extract_authenticated_did(&headers, &state).await?;
+
+    let registry = crate::collections::registry();
+    if !req.emoji.uri.starts_with("at://")
+        || !req.emoji.uri.contains(&format!("/{}/", registry.emoji))
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Some(ref expires) = req.expires {
+        if expires.is_empty() || !expires.contains('T') {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+    if let Some(ref reply_to) = req.reply_to {
+        if !reply_to.starts_with("at://") {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+    if req.title.as_ref().is_some_and(|t| t.len() > 2560)
+        || req.description.as_ref().is_some_and(|d| d.len() > 20480)
+        || req.timezone.as_ref().is_some_and(|t| t.len() > 64)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let mut record = serde_json::json!({
+        "$type": registry.status,
+        "emoji": { "uri": req.emoji.uri, "cid": req.emoji.cid },
+        "createdAt": created_at,
+    });
+    let obj = record.as_object_mut().unwrap();
+    if let Some(ref title) = req.title {
+        obj.insert("title".to_string(), serde_json::json!(title));
+    }
+    if let Some(ref description) = req.description {
+        obj.insert("description".to_string(), serde_json::json!(description));
+    }
+    if let Some(ref expires) = req.expires {
+        obj.insert("expires".to_string(), serde_json::json!(expires));
+    }
+    if let Some(ref reply_to) = req.reply_to {
+        obj.insert("replyTo".to_string(), serde_json::json!(reply_to));
+    }
+    if let Some(ref timezone) = req.timezone {
+        obj.insert("timezone".to_string(), serde_json::json!(timezone));
+    }
+
+    // Let the PDS assign the TID rkey; the response carries uri and cid
+    let create_body = serde_json::json!({
+        "repo": did,
+        "collection": registry.status,
+        "record": record,
+    });
+    let created = upstream_write(
+        &state,
+        &did,
+        "com.atproto.repo.createRecord",
+        WriteBody::Json(&create_body),
+    )
+    .await?;
+
+    let uri = created
+        .get("uri")
+        .and_then(|u| u.as_str())
+        .ok_or(StatusCode::BAD_GATEWAY)?
+        .to_string();
+    let cid = created
+        .get("cid")
+        .and_then(|c| c.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let rkey = rkey_from_uri(&uri)?;
+
+    // Optimistically index so reads reflect the new status immediately,
+    // without waiting for the jetstream event to arrive (mirrors the
+    // StatusIngestor insert)
+    let at_uri = crate::collections::registry().status_at(&did, &rkey);
+    let _ = sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO statuses (at, did, rkey, emoji_ref, emoji_ref_cid, title, description, expires, timezone, reply_to, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&at_uri)
+    .bind(&did)
+    .bind(&rkey)
+    .bind(&req.emoji.uri)
+    .bind(&req.emoji.cid)
+    .bind(&req.title)
+    .bind(&req.description)
+    .bind(&req.expires)
+    .bind(&req.timezone)
+    .bind(&req.reply_to)
+    .bind(&created_at)
+    .execute(&state.db)
+    .await;
+
+    if let Some(ref parent) = req.reply_to {
+        let _ = sqlx::query(
+            "INSERT OR REPLACE INTO status_replies (at, parent_at, created_at) VALUES (?, ?, ?)",
+        )
+        .bind(&at_uri)
+        .bind(parent)
+        .bind(&created_at)
+        .execute(&state.db)
+        .await;
+    }
+
+    let consistency_token = super::consistency::issue_token(&at_uri, req.expires.as_deref());
+
+    Ok(Json(CreateStatusResponse {
+        uri,
+        cid,
+        consistency_token,
+    }))
+}
+
+pub async fn handle_upload_emoji(
+    State(state): State<AppState>,
+    Query(params): Query<UploadEmojiParams>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<UploadEmojiResponse>, StatusCode> {
+    let did = extract_authenticated_did(&headers, &state).await?;
+
+    let name = params.name.trim().to_string();
+    if name.is_empty() || name.len() > 64 || name.contains(char::is_whitespace) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let alt_text = params
+        .alt_text
+        .as_deref()
+        .map(|a| a.trim())
+        .filter(|a| !a.is_empty())
+        .map(|a| a.to_string());
+    if alt_text.as_ref().is_some_and(|a| a.len() > 5120) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Some(ref category) = params.category {
+        if !crate::xrpc::EMOJI_CATEGORIES.contains(&category.as_str()) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+    // Tags are stored normalized, so normalize before publishing too
+    let mut tags: Vec<String> = Vec::new();
+    if let Some(ref raw) = params.tags {
+        let mut seen = std::collections::HashSet::new();
+        for tag in raw.split(',') {
+            let Some(tag) = crate::xrpc::normalize_emoji_tag(tag) else {
+                continue;
+            };
+            if seen.insert(tag.clone()) {
+                tags.push(tag);
+            }
+        }
+        tags.truncate(8);
+    }
+
+    let mime = headers
+        .get("content-type")
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or_default()
+        .to_string();
+    if !mime.starts_with("image/") {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if body.is_empty() {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if body.len() > 1_000_000 {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    let uploaded = upstream_write(
+        &state,
+        &did,
+        "com.atproto.repo.uploadBlob",
+        WriteBody::Blob {
+            bytes: &body,
+            mime: &mime,
+        },
+    )
+    .await?;
+    let blob = uploaded
+        .get("blob")
+        .cloned()
+        .ok_or(StatusCode::BAD_GATEWAY)?;
+    let blob_cid = blob
+        .get("ref")
+        .and_then(|r| r.get("$link"))
+        .and_then(|c| c.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let registry = crate::collections::registry();
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let mut record = serde_json::json!({
+        "$type": registry.emoji,
+        "emoji": blob,
+        "name": name,
+        "createdAt": created_at,
+    });
+    let obj = record.as_object_mut().unwrap();
+    if let Some(ref alt_text) = alt_text {
+        obj.insert("altText".to_string(), serde_json::json!(alt_text));
+    }
+    if let Some(ref category) = params.category {
+        obj.insert("category".to_string(), serde_json::json!(category));
+    }
+    if !tags.is_empty() {
+        obj.insert("tags".to_string(), serde_json::json!(tags));
+    }
+
+    let create_body = serde_json::json!({
+        "repo": did,
+        "collection": registry.emoji,
+        "record": record,
+    });
+    let created = upstream_write(
+        &state,
+        &did,
+        "com.atproto.repo.createRecord",
+        WriteBody::Json(&create_body),
+    )
+    .await?;
+
+    let uri = created
+        .get("uri")
+        .and_then(|u| u.as_str())
+        .ok_or(StatusCode::BAD_GATEWAY)?
+        .to_string();
+    let cid = created
+        .get("cid")
+        .and_then(|c| c.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let rkey = rkey_from_uri(&uri)?;
+
+    // Optimistically index so the emoji is searchable immediately,
+    // without waiting for the jetstream event (mirrors the EmojiIngestor)
+    let at_uri = crate::collections::registry().emoji_at(&did, &rkey);
+    let _ = sqlx::query(
+        r#"
+        INSERT OR REPLACE INTO emojis (at, did, blob_cid, mime_type, emoji_name, alt_text, category, created_at)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(&at_uri)
+    .bind(&did)
+    .bind(&blob_cid)
+    .bind(&mime)
+    .bind(&name)
+    .bind(&alt_text)
+    .bind(&params.category)
+    .bind(&created_at)
+    .execute(&state.db)
+    .await;
+
+    let _ = sqlx::query("DELETE FROM emoji_tags WHERE emoji_at = ?")
+        .bind(&at_uri)
+        .execute(&state.db)
+        .await;
+    for tag in &tags {
+        let _ = sqlx::query("INSERT OR IGNORE INTO emoji_tags (emoji_at, tag) VALUES (?, ?)")
+            .bind(&at_uri)
+            .bind(tag)
+            .execute(&state.db)
+            .await;
+    }
+    if let Ok(mut conn) = state.db.acquire().await {
+        crate::jetstream::update_emoji_fts(
+            &mut conn,
+            &at_uri,
+            &name,
+            alt_text.as_deref(),
+            &tags.join(" "),
+        )
+        .await;
+    }
+
+    Ok(Json(UploadEmojiResponse { uri, cid }))
+}
+
+pub async fn handle_create_pack(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<CreatePackRequest>,
+) -> Result<Json<CreatePackResponse>, StatusCode> {
+    let did = extract_authenticated_did(&headers, &state).await?;
+
+    let name = req.name.trim().to_string();
+    if name.is_empty() || name.len() > 128 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let description = req
+        .description
+        .as_deref()
+        .map(|d| d.trim())
+        .filter(|d| !d.is_empty())
+        .map(|d| d.to_string());
+    if description.as_ref().is_some_and(|d| d.len() > 5120) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let registry = crate::collections::registry();
+    let emoji_path = format!("/{}/", registry.emoji);
+    let mut emojis: Vec<String> = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for uri in &req.emojis {
+        if !uri.starts_with("at://") || !uri.contains(&emoji_path) {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        if seen.insert(uri.clone()) {
+            emojis.push(uri.clone());
+        }
+    }
+    if emojis.is_empty() || emojis.len() > 100 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let mut record = serde_json::json!({
+        "$type": registry.pack,
+        "name": name,
+        "emojis": emojis,
+        "createdAt": created_at,
+    });
+    if let Some(ref description) = description {
+        record
+            .as_object_mut()
+            .unwrap()
+            .insert("description".to_string(), serde_json::json!(description));
+    }
+
+    // Let the PDS assign the TID rkey; the response carries uri and cid
+    let create_body = serde_json::json!({
+        "repo": did,
+        "collection": registry.pack,
+        "record": record,
+    });
+    let created = upstream_write(
+        &state,
+        &did,
+        "com.atproto.repo.createRecord",
+        WriteBody::Json(&create_body),
+    )
+    .await?;
+
+    let uri = created
+        .get("uri")
+        .and_then(|u| u.as_str())
+        .ok_or(StatusCode::BAD_GATEWAY)?
+        .to_string();
+    let cid = created
+        .get("cid")
+        .and_then(|c| c.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let rkey = rkey_from_uri(&uri)?;
+
+    // Optimistically index so the pack is listable immediately, without
+    // waiting for the jetstream event (mirrors the PackIngestor)
+    if let Ok(mut conn) = state.db.acquire().await {
+        let _ = crate::jetstream::index_pack(&mut conn, &did, &rkey, record).await;
+    }
+
+    Ok(Json(CreatePackResponse { uri, cid }))
+}
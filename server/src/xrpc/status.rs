@@ -0,0 +1,274 @@
+use axum::{
+    Json,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+};
+use jacquard_oatproxy::store::OAuthSessionStore;
+use jacquard_oauth::authstore::ClientAuthStore;
+use serde::{Deserialize, Serialize};
+
+use crate::AppState;
+
+use super::moderation::extract_authenticated_did;
+
+// Request/Response types
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenewStatusRequest {
+    pub rkey: String,
+    /// New expiration timestamp; omit to remove the expiry entirely
+    pub expires: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RenewStatusResponse {
+    pub success: bool,
+    /// Opaque read-your-writes token; pass back to list endpoints via the
+    /// `istat-consistency-token` header
+    pub consistency_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EndStatusRequest {
+    pub rkey: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndStatusResponse {
+    pub success: bool,
+    /// Opaque read-your-writes token; pass back to list endpoints via the
+    /// `istat-consistency-token` header
+    pub consistency_token: String,
+}
+
+/// Rewrite the `expires` field of the user's status record on their PDS via
+/// putRecord, using the stored upstream session for authentication.
+async fn put_status_expiry(
+    state: &AppState,
+    did: &str,
+    rkey: &str,
+    expires: Option<&str>,
+) -> Result<(), StatusCode> {
+    // Look up the upstream session for this user
+    let session_id = state
+        .key_store
+        .get_active_session(did)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let did_typed = jacquard_common::types::did::Did::new_owned(did)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let upstream_session =
+        ClientAuthStore::get_session(state.key_store.as_ref(), &did_typed, &session_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let host_url = upstream_session.host_url.as_str().trim_end_matches('/');
+
+    // Fetch the current record so we don't lose any fields on rewrite
+    let get_url = format!(
+        "{}/xrpc/com.atproto.repo.getRecord?repo={}&collection={}&rkey={}",
+        host_url, did, crate::collections::registry().status, rkey
+    );
+    let resp = crate::outbound::get(&get_url)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if !resp.status().is_success() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let record_resp: serde_json::Value = resp
+        .json()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let swap_cid = record_resp
+        .get("cid")
+        .and_then(|c| c.as_str())
+        .map(|c| c.to_string());
+    let mut record = record_resp
+        .get("value")
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // Update the expires field in place
+    if let Some(obj) = record.as_object_mut() {
+        match expires {
+            Some(e) => {
+                obj.insert("expires".to_string(), serde_json::json!(e));
+            }
+            None => {
+                obj.remove("expires");
+            }
+        }
+    } else {
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let put_url = format!("{}/xrpc/com.atproto.repo.putRecord", host_url);
+    let body = serde_json::json!({
+        "repo": did,
+        "collection": crate::collections::registry().status,
+        "rkey": rkey,
+        "record": record,
+        "swapRecord": swap_cid,
+    });
+
+    // DPoP key and stored nonce for the upstream request
+    let dpop_key = state
+        .key_store
+        .get_session_dpop_key(&session_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map(|(_jkt, key)| key)
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut dpop_nonce = state
+        .key_store
+        .get_session_dpop_nonce(&session_id)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Retry loop for DPoP nonce handling (mirrors the XRPC proxy)
+    let mut retry_count = 0;
+    let max_retries = 1;
+
+    loop {
+        let dpop_proof = state
+            .token_manager
+            .create_upstream_dpop_proof(
+                "POST",
+                &put_url,
+                Some(upstream_session.token_set.access_token.as_ref()),
+                dpop_nonce.as_deref(),
+                &dpop_key,
+            )
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        let client = reqwest::Client::new();
+        let response = client
+            .post(&put_url)
+            .header(
+                "Authorization",
+                format!("DPoP {}", upstream_session.token_set.access_token),
+            )
+            .header("DPoP", dpop_proof)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+        // Check for DPoP nonce requirement (can be 400 or 401)
+        if (response.status() == 400 || response.status() == 401) && retry_count < max_retries {
+            if let Some(new_nonce) = response.headers().get("DPoP-Nonce") {
+                if let Ok(nonce_str) = new_nonce.to_str() {
+                    dpop_nonce = Some(nonce_str.to_string());
+                    let _ = state
+                        .key_store
+                        .update_session_dpop_nonce(&session_id, nonce_str.to_string())
+                        .await;
+                    retry_count += 1;
+                    continue;
+                }
+            }
+        }
+
+        // Store any refreshed nonce for the next request
+        if let Some(new_nonce) = response.headers().get("DPoP-Nonce") {
+            if let Ok(nonce_str) = new_nonce.to_str() {
+                let _ = state
+                    .key_store
+                    .update_session_dpop_nonce(&session_id, nonce_str.to_string())
+                    .await;
+            }
+        }
+
+        if !response.status().is_success() {
+            tracing::warn!(
+                "putRecord failed for {}/{}: {}",
+                did,
+                rkey,
+                response.status()
+            );
+            return Err(StatusCode::BAD_GATEWAY);
+        }
+
+        return Ok(());
+    }
+}
+
+/// Update the local index so reads reflect the new expiry immediately,
+/// without waiting for the jetstream event to arrive.
+async fn update_local_expiry(
+    state: &AppState,
+    did: &str,
+    rkey: &str,
+    expires: Option<&str>,
+) -> Result<(), StatusCode> {
+    let at_uri = crate::collections::registry().status_at(&did, &rkey);
+
+    sqlx::query("UPDATE statuses SET expires = ? WHERE at = ? AND deleted_at IS NULL")
+        .bind(expires)
+        .bind(&at_uri)
+        .execute(&state.db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(())
+}
+
+// Endpoint handlers
+
+pub async fn handle_renew_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<RenewStatusRequest>,
+) -> Result<Json<RenewStatusResponse>, StatusCode> {
+    let did = extract_authenticated_did(&headers, &state).await?;
+
+    // Validate datetime format if a new expiry was provided
+    if let Some(ref expires) = req.expires {
+        if expires.is_empty() || !expires.contains('T') {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    put_status_expiry(&state, &did, &req.rkey, req.expires.as_deref()).await?;
+    update_local_expiry(&state, &did, &req.rkey, req.expires.as_deref()).await?;
+
+    let at_uri = crate::collections::registry().status_at(&did, &req.rkey);
+    let consistency_token = super::consistency::issue_token(&at_uri, req.expires.as_deref());
+
+    Ok(Json(RenewStatusResponse {
+        success: true,
+        consistency_token,
+    }))
+}
+
+pub async fn handle_end_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(req): Json<EndStatusRequest>,
+) -> Result<Json<EndStatusResponse>, StatusCode> {
+    let did = extract_authenticated_did(&headers, &state).await?;
+
+    // End the status by setting its expiry to now, keeping the record intact
+    let now = chrono::Utc::now().to_rfc3339();
+
+    put_status_expiry(&state, &did, &req.rkey, Some(&now)).await?;
+    update_local_expiry(&state, &did, &req.rkey, Some(&now)).await?;
+
+    let at_uri = crate::collections::registry().status_at(&did, &req.rkey);
+    let consistency_token = super::consistency::issue_token(&at_uri, Some(&now));
+
+    Ok(Json(EndStatusResponse {
+        success: true,
+        consistency_token,
+    }))
+}
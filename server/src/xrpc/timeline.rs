@@ -0,0 +1,321 @@
+//! Follow-graph aware timeline.
+//!
+//! `listStatuses` is a global firehose; `getTimeline` narrows it to
+//! statuses from accounts the authenticated user follows. The follow
+//! list comes from the public AppView (`app.bsky.graph.getFollows`) and
+//! is cached in `follow_cache` on a TTL so the timeline query stays a
+//! local join. A stale cache is served as-is when the AppView is
+//! unreachable.
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+
+use super::moderation::extract_authenticated_did;
+use super::{BLOCKED_IMAGE_URL, img_url};
+use crate::AppState;
+
+/// Seconds a cached follow set stays fresh (default: 15 minutes)
+fn follows_ttl_secs() -> u64 {
+    std::env::var("ISTAT_FOLLOWS_TTL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(900)
+}
+
+/// Pages of 100 fetched per refresh, bounding work on huge follow graphs
+const MAX_FOLLOW_PAGES: usize = 25;
+
+#[derive(Debug, Deserialize)]
+pub struct GetTimelineParams {
+    pub limit: Option<i64>,
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GetTimelineResponse {
+    pub statuses: Vec<lexicons::vg_nat::istat::status::list_statuses::StatusView<'static>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+/// Fetch the full follow list from the AppView. Returns Err on any
+/// transport or parse failure so the caller can fall back to the cache.
+async fn fetch_follows(did: &str) -> Result<Vec<String>, ()> {
+    let mut follows = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    for _ in 0..MAX_FOLLOW_PAGES {
+        let mut url = format!(
+            "https://public.api.bsky.app/xrpc/app.bsky.graph.getFollows?actor={}&limit=100",
+            did
+        );
+        if let Some(ref c) = cursor {
+            url.push_str(&format!("&cursor={}", c));
+        }
+
+        let resp = crate::outbound::get(&url).await.map_err(|_| ())?;
+        if !resp.status().is_success() {
+            return Err(());
+        }
+        let body: serde_json::Value = resp.json().await.map_err(|_| ())?;
+
+        if let Some(items) = body.get("follows").and_then(|f| f.as_array()) {
+            for item in items {
+                if let Some(subject) = item.get("did").and_then(|d| d.as_str()) {
+                    follows.push(subject.to_string());
+                }
+            }
+        }
+
+        cursor = body
+            .get("cursor")
+            .and_then(|c| c.as_str())
+            .map(|c| c.to_string());
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    Ok(follows)
+}
+
+/// Refresh the cached follow set if it's past its TTL. Failures are
+/// swallowed when a (possibly stale) cache already exists.
+pub(crate) async fn ensure_follows_fresh(db: &SqlitePool, did: &str) -> Result<(), StatusCode> {
+    let fresh: Option<i64> = sqlx::query_scalar(
+        "SELECT 1 FROM follow_cache_meta WHERE did = ? AND refreshed_at > datetime('now', ?)",
+    )
+    .bind(did)
+    .bind(format!("-{} seconds", follows_ttl_secs()))
+    .fetch_optional(db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    if fresh.is_some() {
+        return Ok(());
+    }
+
+    let has_cache: Option<i64> = sqlx::query_scalar("SELECT 1 FROM follow_cache_meta WHERE did = ?")
+        .bind(did)
+        .fetch_optional(db)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let follows = match fetch_follows(did).await {
+        Ok(f) => f,
+        // AppView unreachable: serve the stale cache if there is one
+        Err(()) if has_cache.is_some() => return Ok(()),
+        Err(()) => return Err(StatusCode::BAD_GATEWAY),
+    };
+
+    let mut tx = db
+        .begin()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    sqlx::query("DELETE FROM follow_cache WHERE did = ?")
+        .bind(did)
+        .execute(&mut *tx)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    for subject in &follows {
+        sqlx::query("INSERT OR IGNORE INTO follow_cache (did, subject_did) VALUES (?, ?)")
+            .bind(did)
+            .bind(subject)
+            .execute(&mut *tx)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO follow_cache_meta (did, refreshed_at) VALUES (?, datetime('now'))
+        ON CONFLICT(did) DO UPDATE SET refreshed_at = datetime('now')
+        "#,
+    )
+    .bind(did)
+    .execute(&mut *tx)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    tx.commit()
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Ok(())
+}
+
+pub async fn handle_get_timeline(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<GetTimelineParams>,
+) -> Result<Json<GetTimelineResponse>, StatusCode> {
+    let did = extract_authenticated_did(&headers, &state).await?;
+    let limit = params.limit.unwrap_or(50).clamp(1, 100);
+
+    ensure_follows_fresh(&state.db, &did).await?;
+
+    // Cursor is "{created_at}|{at}" from the last row of the previous page
+    let (cursor_created_at, cursor_at) = match params.cursor.as_deref() {
+        Some(cursor) => {
+            let (created_at, at) = cursor.split_once('|').ok_or(StatusCode::BAD_REQUEST)?;
+            (Some(created_at.to_string()), Some(at.to_string()))
+        }
+        None => (None, None),
+    };
+
+    let rows = sqlx::query(
+        r#"
+        SELECT s.at, s.did, s.rkey, s.emoji_ref, s.title, s.description, s.expires, s.timezone, s.reply_to, s.created_at,
+               p.handle, p.display_name, p.avatar_cid,
+               COALESCE(p.avatar_cid IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'avatar'), 0) AS avatar_blacklisted,
+               e.blob_cid as emoji_blob_cid, e.mime_type, e.emoji_name, e.alt_text, e.did as emoji_did,
+               (SELECT COUNT(*) FROM status_replies r WHERE r.parent_at = s.at) as reply_count
+        FROM statuses s
+        LEFT JOIN profiles p ON s.did = p.did
+        LEFT JOIN emojis e ON s.emoji_ref = 'at://' || e.at
+        WHERE s.did IN (SELECT subject_did FROM follow_cache WHERE did = ?)
+          AND s.deleted_at IS NULL
+          AND s.did NOT IN (SELECT did FROM actor_takedowns)
+          AND s.did NOT IN (SELECT did FROM profiles WHERE account_status != 'active')
+          AND (e.deleted_at IS NULL OR e.at IS NULL)
+          AND (e.blob_cid NOT IN (SELECT cid FROM effective_blacklisted_cids WHERE content_type = 'emoji_blob') OR e.blob_cid IS NULL)
+          AND (s.expires IS NULL OR datetime(s.expires) > datetime('now'))
+          AND (? IS NULL OR s.created_at < ? OR (s.created_at = ? AND s.at < ?))
+        ORDER BY s.created_at DESC, s.at DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(&did)
+    .bind(&cursor_created_at)
+    .bind(&cursor_created_at)
+    .bind(&cursor_created_at)
+    .bind(&cursor_at)
+    .bind(limit)
+    .fetch_all(&state.db)
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    use jacquard_common::types::string::{AtUri, Datetime, Did, Handle};
+    use lexicons::vg_nat::istat::status::list_statuses::StatusView;
+    use std::str::FromStr;
+
+    let statuses: Vec<_> = rows
+        .iter()
+        .filter_map(|row| {
+            let did: String = row.try_get("did").ok()?;
+            let rkey: String = row.try_get("rkey").ok()?;
+            let emoji_ref: String = row.try_get("emoji_ref").ok()?;
+            let emoji_blob_cid: Option<String> = row.try_get("emoji_blob_cid").ok().flatten();
+            let title: Option<String> = row
+                .try_get("title")
+                .ok()
+                .and_then(|s: String| if s.is_empty() { None } else { Some(s) });
+            let description: Option<String> = row
+                .try_get("description")
+                .ok()
+                .and_then(|s: String| if s.is_empty() { None } else { Some(s) });
+            let expires: Option<String> = row.try_get("expires").ok();
+            let timezone: Option<String> = row.try_get("timezone").ok().flatten();
+            let reply_to: Option<String> = row.try_get("reply_to").ok().flatten();
+            let reply_count: i64 = row.try_get("reply_count").unwrap_or(0);
+            let created_at: String = row.try_get("created_at").ok()?;
+            let handle: Option<String> = row.try_get("handle").ok().flatten();
+            let display_name: Option<String> = row.try_get("display_name").ok().flatten();
+            let avatar_cid: Option<String> = row.try_get("avatar_cid").ok().flatten();
+            let emoji_name: Option<String> = row.try_get("emoji_name").ok().flatten();
+            let alt_text: Option<String> = row.try_get("alt_text").ok().flatten();
+            let emoji_did: Option<String> = row.try_get("emoji_did").ok().flatten();
+
+            let mime: Option<String> = row.try_get("mime_type").ok().flatten();
+
+            let mime_ext = mime
+                .as_deref()
+                .and_then(|m| match m {
+                    "image/png" => Some("png"),
+                    "image/jpeg" => Some("jpeg"),
+                    "image/jpg" => Some("jpeg"),
+                    "image/webp" => Some("webp"),
+                    "image/gif" => Some("gif"),
+                    _ => Some("jpeg"),
+                })
+                .unwrap_or("jpeg");
+
+            let emoji_url = if let Some(ref blob_cid) = emoji_blob_cid {
+                if let Some(emoji_owner_did) = emoji_did {
+                    img_url(&emoji_owner_did, blob_cid, mime_ext)
+                } else {
+                    emoji_ref
+                        .strip_prefix("at://")
+                        .and_then(|s| s.split('/').next())
+                        .map(|emoji_owner| img_url(emoji_owner, blob_cid, mime_ext))
+                        .unwrap_or_else(|| img_url(&did, blob_cid, mime_ext))
+                }
+            } else {
+                emoji_ref
+                    .split('/')
+                    .last()
+                    .map(|cid| img_url(&did, cid, mime_ext))
+                    .unwrap_or_default()
+            };
+
+            let avatar_blacklisted: bool = row.try_get("avatar_blacklisted").unwrap_or(false);
+            let avatar_url = if avatar_blacklisted {
+                Some(BLOCKED_IMAGE_URL.to_string())
+            } else {
+                avatar_cid.map(|cid| img_url(&did, &cid, "webp"))
+            };
+
+            let handle_str = handle.unwrap_or(did.clone());
+
+            if created_at.is_empty() || !created_at.contains('T') {
+                return None;
+            }
+
+            Some(
+                StatusView::new()
+                    .did(Did::from_str(&did).ok()?)
+                    .handle(Handle::from_str(&handle_str).ok()?)
+                    .maybe_display_name(display_name.map(Into::into))
+                    .maybe_avatar_url(avatar_url.map(Into::into))
+                    .rkey(rkey)
+                    .emoji_url(emoji_url)
+                    .maybe_emoji_name(emoji_name.map(Into::into))
+                    .maybe_emoji_alt(alt_text.map(Into::into))
+                    .maybe_emoji_blob_cid(emoji_blob_cid.map(Into::into))
+                    .maybe_emoji_ref(Some(emoji_ref.into()))
+                    .maybe_title(title.map(Into::into))
+                    .maybe_description(description.map(Into::into))
+                    .maybe_expires(
+                        expires
+                            .filter(|e| !e.is_empty() && e.contains('T'))
+                            .map(Datetime::raw_str),
+                    )
+                    .maybe_timezone(timezone.map(Into::into))
+                    .maybe_reply_to(reply_to.and_then(|u| AtUri::from_str(&u).ok()))
+                    .reply_count(reply_count)
+                    .created_at(Datetime::raw_str(created_at))
+                    .build(),
+            )
+        })
+        .collect();
+
+    // Only hand out a cursor when the page was full; a short page is the end
+    let cursor = if rows.len() as i64 == limit {
+        rows.last().and_then(|row| {
+            let created_at: String = row.try_get("created_at").ok()?;
+            let at: String = row.try_get("at").ok()?;
+            Some(format!("{}|{}", created_at, at))
+        })
+    } else {
+        None
+    };
+
+    Ok(Json(GetTimelineResponse { statuses, cursor }))
+}